@@ -1,7 +1,7 @@
 use heck::ToUpperCamelCase;
 use proc_macro2::Ident;
 use quote::{format_ident, quote};
-use std::path::Path;
+use std::{collections::HashMap, path::Path};
 use regex::Regex;
 
 fn main() {
@@ -11,30 +11,123 @@ fn main() {
 
     println!("Generating Rust code from proto files in {:?}", proto_dir);
 
-    let mut versions = vec![];
+    let mut version_dirs = vec![];
     for entry in std::fs::read_dir(&proto_dir).expect("Failed to read proto directory") {
         let entry = entry.expect("Failed to read directory entry");
         let path = entry.path();
         if path.is_dir() {
-            let version = path.file_name().expect("Failed to get directory name").to_str().expect("Failed to convert OsStr to str");
-            versions.push(version.to_string());
-            let version = version.trim_start_matches("api_").replace('_', ".");
-            println!("Generating code for ESPHome API version {}", version);
-            generate_code_for_version(&version, &path);
+            version_dirs.push(path);
         }
     }
 
+    let min_versions = collect_min_versions(&version_dirs);
+    let mut versions = vec![];
+    for path in &version_dirs {
+        let version = path.file_name().expect("Failed to get directory name").to_str().expect("Failed to convert OsStr to str");
+        versions.push(version.to_string());
+        let version = version.trim_start_matches("api_").replace('_', ".");
+        println!("Generating code for ESPHome API version {}", version);
+        generate_code_for_version(&version, path, &min_versions);
+    }
+
     generate_proto_api_file(&proto_dir, versions);
 }
 
+// Collects the oldest API version in which each message id is available, messages can be renamed between versions.
+// Messages available in the oldest known version are reported as 1.0, as they predate all known versions.
+fn collect_min_versions(version_dirs: &[std::path::PathBuf]) -> HashMap<u16, (u32, u32)> {
+    let mut versions: Vec<((u32, u32), &std::path::PathBuf)> = version_dirs
+        .iter()
+        .map(|path| {
+            let name = path.file_name().expect("Failed to get directory name").to_str().expect("Failed to convert OsStr to str");
+            let (major, minor) = name.trim_start_matches("api_").split_once('_').expect("Version should be in format api_X_Y");
+            ((major.parse().expect("Major version should be a number"), minor.parse().expect("Minor version should be a number")), path)
+        })
+        .collect();
+    versions.sort();
+    let mut min_versions = HashMap::new();
+    for (index, (version, path)) in versions.iter().enumerate() {
+        let version = if index == 0 { (1, 0) } else { *version };
+        let proto_file = path.join("api.proto").to_string_lossy().to_string();
+        for (_, message_id, _) in parse_message_types(&proto_file) {
+            min_versions.entry(message_id).or_insert(version);
+        }
+    }
+    min_versions
+}
+
+// Parses the message names and ids from the proto file.
+// Prost is not able to extract custom MessageOptions, so we need to do that ourselves to get to the message id.
+fn parse_message_types(proto_file: &str) -> Vec<(Ident, u16, String)> {
+    let content = std::fs::read_to_string(proto_file).expect("Failed to read proto file");
+    let re = Regex::new(r"message ([A-Za-z]+) \{[^}]*option ?\(id\) ?= ?([\d]+);").unwrap();
+
+    re
+        .captures_iter(&content)
+        .map(|m| {
+            let message_name = m.get(1).unwrap().as_str().to_string();
+            let message_id: u16 = m
+                .get(2)
+                .unwrap()
+                .as_str()
+                .parse()
+                .expect("Failed to parse message id");
+            (
+                format_ident!("{}", message_name.to_upper_camel_case()),
+                message_id,
+                message_name,
+            )
+        })
+        .collect()
+}
+
+// Fields shown in the `Display` output of a message, as (type, name), when the message has them.
+const KEY_FIELDS: [(&str, &str); 3] = [("fixed32", "key"), ("string", "object_id"), ("uint64", "address")];
+
+// Parses the key fields of each message from the proto file, keyed by message name.
+fn parse_key_fields(proto_file: &str) -> HashMap<String, Vec<String>> {
+    let content = std::fs::read_to_string(proto_file).expect("Failed to read proto file");
+    let message_re = Regex::new(r"(?m)^message ([A-Za-z]+) \{").unwrap();
+    let field_re = Regex::new(r"^\s*(?:optional )?([a-z0-9]+) ([a-z_]+) = \d+").unwrap();
+
+    message_re
+        .captures_iter(&content)
+        .map(|m| {
+            let message_name = m.get(1).unwrap().as_str().to_upper_camel_case();
+            // Only top level fields of the message, nested messages and enums are skipped
+            let mut depth = 0;
+            let mut fields = vec![];
+            for line in content[m.get(0).unwrap().end()..].lines() {
+                if depth == 0 {
+                    if let Some(field) = field_re.captures(line) {
+                        fields.push((field.get(1).unwrap().as_str(), field.get(2).unwrap().as_str()));
+                    }
+                }
+                depth += line.matches('{').count() as i32;
+                depth -= line.matches('}').count() as i32;
+                if depth < 0 {
+                    break;
+                }
+            }
+            let key_fields = KEY_FIELDS
+                .iter()
+                .filter(|key_field| fields.contains(key_field))
+                .map(|(_, name)| name.to_string())
+                .collect();
+            (message_name, key_fields)
+        })
+        .collect()
+}
+
 // Generates Rust code for a specific ESPHome API version from the proto files in the given path.
-fn generate_code_for_version(version: &str, path: &Path) {
+fn generate_code_for_version(version: &str, path: &Path, min_versions: &HashMap<u16, (u32, u32)>) {
     let proto_file = path.join("api.proto").to_string_lossy().to_string();
-    let service_generator = Box::new(ServiceGenerator::new(version, &proto_file));
+    let service_generator = Box::new(ServiceGenerator::new(version, &proto_file, min_versions));
     let mut config = prost_build::Config::new();
     config.default_package_filename("mod");
     config.service_generator(service_generator);
     config.out_dir(path);
+    config.file_descriptor_set_path(path.join("descriptor.bin"));
     config.compile_protos(&[&proto_file], &[path]).unwrap();
 }
 
@@ -85,6 +178,23 @@ pub use {version}::*;
 "));
     }
 
+    // Expose all versions side by side
+    content.push_str("
+// If feature \"api-versions\" is specified, include all versions as separate modules
+#[cfg(feature = \"api-versions\")]
+pub mod versions {
+");
+    for version in &versions {
+        let module = version.trim_start_matches("api_");
+        let display_version = module.replace('_', ".");
+        content.push_str(&format!("    /// Messages of ESPHome API version {display_version}.
+    pub mod v{module} {{
+        include!(\"{version}/mod.rs\");
+    }}
+"));
+    }
+    content.push_str("}\n");
+
     std::fs::write(api_file_path, content).expect("Failed to write api.rs file");
 }
 
@@ -102,35 +212,31 @@ fn version_to_feature_name(version: &str) -> String {
 
 struct ServiceGenerator {
     version: String,
-    types: Vec<(Ident, u16)>,
+    types: Vec<(Ident, u16, (u32, u32))>,
+    proto_names: HashMap<String, String>,
+    key_fields: HashMap<String, Vec<String>>,
 }
 
 impl ServiceGenerator {
-    pub fn new(version: &str, proto_file: &str) -> Self {
-        // Prost is not able to extract custom MessageOptions, so we need to do that ourselves to get to the message id.
-        let content = std::fs::read_to_string(proto_file).expect("Failed to read proto file");
-        let re = Regex::new(r"message ([A-Za-z]+) \{[^}]*option ?\(id\) ?= ?([\d]+);").unwrap();
-
-        let types = re
-            .captures_iter(&content)
-            .map(|m| {
-                let message_name = m.get(1).unwrap().as_str().to_string();
-                let message_id: u16 = m
-                    .get(2)
-                    .unwrap()
-                    .as_str()
-                    .parse()
-                    .expect("Failed to parse message id");
-                (
-                    format_ident!("{}", message_name.to_upper_camel_case()),
-                    message_id,
-                )
+    pub fn new(version: &str, proto_file: &str, min_versions: &HashMap<u16, (u32, u32)>) -> Self {
+        let message_types = parse_message_types(proto_file);
+        let proto_names = message_types
+            .iter()
+            .map(|(message_name, _, proto_name)| (message_name.to_string(), proto_name.clone()))
+            .collect();
+        let types = message_types
+            .into_iter()
+            .map(|(message_name, message_id, _)| {
+                let min_version = *min_versions.get(&message_id).expect("Message should have a minimum version");
+                (message_name, message_id, min_version)
             })
             .collect();
 
         ServiceGenerator {
             version: version.to_string(),
             types,
+            proto_names,
+            key_fields: parse_key_fields(proto_file),
         }
     }
 }
@@ -147,34 +253,199 @@ impl prost_build::ServiceGenerator for ServiceGenerator {
         let variants = self
             .types
             .iter()
-            .map(|(message_name, _)| message_name)
+            .map(|(message_name, _, _)| message_name)
             .collect::<Vec<_>>();
         let variant_to_typeid = self
             .types
             .iter()
-            .map(|(message_name, message_id)| quote! { #message_name(_) => #message_id })
+            .map(|(message_name, message_id, _)| quote! { #message_name(_) => #message_id })
             .collect::<Vec<_>>();
         let typeid_to_variant = self
             .types
             .iter()
-            .map(|(message_name, message_id)| quote! { #message_id => #message_name::decode(payload).map(#enum_name::#message_name) })
+            .map(|(message_name, message_id, _)| quote! { #message_id => #message_name::decode(payload).map(#enum_name::#message_name) })
+            .collect::<Vec<_>>();
+        let variant_to_min_version = self
+            .types
+            .iter()
+            .map(|(message_name, _, _)| quote! { #message_name(_) => <#message_name as MessageType>::MIN_API_VERSION })
+            .collect::<Vec<_>>();
+        let variant_to_name = self
+            .types
+            .iter()
+            .map(|(message_name, _, _)| {
+                let name = message_name.to_string();
+                quote! { #message_name(_) => #name }
+            })
+            .collect::<Vec<_>>();
+        let message_types = self
+            .types
+            .iter()
+            .map(|(message_name, message_id, _)| {
+                let name = message_name.to_string();
+                quote! { (#message_id, #name) }
+            })
+            .collect::<Vec<_>>();
+        let typeid_to_name = self
+            .types
+            .iter()
+            .map(|(message_name, message_id, _)| {
+                let name = message_name.to_string();
+                quote! { #message_id => Some(#name) }
+            })
+            .collect::<Vec<_>>();
+        let descriptor_path = format!("/src/proto/api_{}/descriptor.bin", self.version.replace('.', "_"));
+        let variant_to_dynamic = self
+            .types
+            .iter()
+            .map(|(message_name, _, _)| {
+                let proto_name = &self.proto_names[&message_name.to_string()];
+                quote! { #message_name(msg) => (#proto_name, msg.encode_to_vec()) }
+            })
+            .collect::<Vec<_>>();
+        let variant_to_display = self
+            .types
+            .iter()
+            .map(|(message_name, _, _)| {
+                let name = message_name.to_string();
+                let key_fields = self.key_fields.get(&name).map(Vec::as_slice).unwrap_or_default();
+                if key_fields.is_empty() {
+                    return quote! { #message_name(_) => f.write_str(#name) };
+                }
+                let format = key_fields
+                    .iter()
+                    .map(|field| match field.as_str() {
+                        "object_id" => format!("{field}=\"{{}}\""),
+                        "address" => format!("{field}={{:012X}}"),
+                        _ => format!("{field}={{}}"),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let format = format!("{name}({format})");
+                let fields = key_fields.iter().map(|field| format_ident!("{}", field));
+                quote! { #message_name(msg) => write!(f, #format, #(msg.#fields),*) }
+            })
             .collect::<Vec<_>>();
         out.push_str(
             quote! {
                 pub const API_VERSION: (u32, u32) = (#major, #minor);
 
+                /// Message type ids used on the wire with the name of the message type, for all messages of this API version.
+                pub const MESSAGE_TYPES: &[(u16, &str)] = &[#(#message_types),*];
+
                 #[derive(Clone, Debug, PartialEq)]
                 pub enum #enum_name {
-                   #(#variants(#variants)),*
+                   #(#variants(#variants),)*
+                   /// Message with a type id unknown to this API version, e.g. sent by newer firmware.
+                   Unknown {
+                       /// Message type id used on the wire.
+                       type_id: u16,
+                       /// Encoded protobuf payload of the message.
+                       payload: Vec<u8>,
+                   },
+                }
+                /// Trait implemented by all messages that can be wrapped in an [`EspHomeMessage`].
+                pub trait MessageType: Into<#enum_name> + TryFrom<#enum_name, Error = #enum_name> {
+                    /// Message type id used on the wire.
+                    const TYPE_ID: u16;
+                    /// Oldest API version in which the message is available, as (major, minor).
+                    const MIN_API_VERSION: (u32, u32);
+                    /// Gets a reference to the message, if the wrapped message is of this type.
+                    fn from_message(msg: &#enum_name) -> Option<&Self>;
                 }
                 impl #enum_name {
+                    /// Gets the message type id used on the wire.
                     #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
-                    const fn get_message_type(&self) -> u16 {
+                    pub const fn get_message_type(&self) -> u16 {
                         match self {
                             #(Self::#variant_to_typeid,)*
+                            Self::Unknown { type_id, .. } => *type_id,
+                        }
+                    }
+                    /// Gets the oldest API version in which the message is available, as (major, minor).
+                    ///
+                    /// Messages available in all known API versions, and unknown messages, report version 1.0.
+                    #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
+                    pub const fn min_api_version(&self) -> (u32, u32) {
+                        match self {
+                            #(Self::#variant_to_min_version,)*
+                            Self::Unknown { .. } => (1, 0),
+                        }
+                    }
+                    /// Gets the name of the message type.
+                    #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
+                    pub const fn message_name(&self) -> &'static str {
+                        match self {
+                            #(Self::#variant_to_name,)*
+                            Self::Unknown { .. } => "Unknown",
+                        }
+                    }
+                    /// Gets the name of the message type with the given type id, if the type id is known.
+                    #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
+                    pub const fn message_name_of_type(type_id: u16) -> Option<&'static str> {
+                        match type_id {
+                            #(#typeid_to_name,)*
+                            _ => None,
+                        }
+                    }
+                    /// Decodes the protobuf payload of a frame with the given message type id, borrowing the payload.
+                    ///
+                    /// Payloads of unknown message types are returned as [`Self::Unknown`].
+                    ///
+                    /// # Errors
+                    ///
+                    /// Will return an error if the payload can't be decoded as the message type.
+                    #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
+                    pub fn decode_frame(type_id: u16, payload: &[u8]) -> Result<Self, String> {
+                        use prost::Message as _;
+
+                        match type_id {
+                            #(#typeid_to_variant,)*
+                            _ => Ok(Self::Unknown { type_id, payload: payload.to_vec() }),
+                        }.map_err(|e| format!("Failed to decode message: {e}"))
+                    }
+                    /// Converts the message to a dynamic message, giving access to its fields by name.
+                    ///
+                    /// Returns `None` for unknown messages, as their descriptor isn't available.
+                    #[cfg(feature = "reflect")]
+                    #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
+                    pub fn to_dynamic(&self) -> Option<prost_reflect::DynamicMessage> {
+                        use prost::Message as _;
+
+                        let (name, payload) = match self {
+                            #(Self::#variant_to_dynamic,)*
+                            Self::Unknown { .. } => return None,
+                        };
+                        let descriptor = descriptor_pool().get_message_by_name(name)?;
+                        prost_reflect::DynamicMessage::decode(descriptor, payload.as_slice()).ok()
+                    }
+                }
+                /// Concise representation of the message, with the message name and the key fields
+                /// identifying the entity or device. Use [`Debug`] for the full message.
+                impl std::fmt::Display for #enum_name {
+                    #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
+                    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        match self {
+                            #(Self::#variant_to_display,)*
+                            Self::Unknown { type_id, payload } => write!(f, "Unknown(type_id={type_id}, {} bytes)", payload.len()),
                         }
                     }
                 }
+                /// Gets the descriptors of all messages of this API version.
+                ///
+                /// # Panics
+                ///
+                /// Will panic if the descriptor set shipped with the crate can't be decoded.
+                #[cfg(feature = "reflect")]
+                pub fn descriptor_pool() -> &'static prost_reflect::DescriptorPool {
+                    static POOL: std::sync::OnceLock<prost_reflect::DescriptorPool> = std::sync::OnceLock::new();
+                    POOL.get_or_init(|| {
+                        prost_reflect::DescriptorPool::decode(
+                            include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), #descriptor_path)).as_slice(),
+                        )
+                        .expect("Descriptor set should be valid")
+                    })
+                }
                 impl From<#enum_name> for Vec<u8> {
                     #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
                     fn from(val: #enum_name) -> Self {
@@ -183,6 +454,7 @@ impl prost_build::ServiceGenerator for ServiceGenerator {
                         let type_id = val.get_message_type();
                         let payload = match val {
                             #(#enum_name::#variants(d) => d.encode_to_vec(),)*
+                            #enum_name::Unknown { payload, .. } => payload,
                         };
                         let payload_len = u16::try_from(payload.len()).expect("Payload length exceeds u16::MAX");
                         [
@@ -194,19 +466,19 @@ impl prost_build::ServiceGenerator for ServiceGenerator {
                 }
                 impl TryFrom<Vec<u8>> for #enum_name {
                     type Error = String;
-                    #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
                     fn try_from(msg: Vec<u8>) -> Result<Self, Self::Error> {
-                        use prost::Message as _;
+                        Self::try_from(msg.as_slice())
+                    }
+                }
+                impl TryFrom<&[u8]> for #enum_name {
+                    type Error = String;
+                    fn try_from(msg: &[u8]) -> Result<Self, Self::Error> {
                         if msg.len() < 4 {
                             return Err("Message too short".to_owned());
                         }
                         let type_id = u16::from_be_bytes([msg[0], msg[1]]);
                         // let size = u16::from_be_bytes([msg[2], msg[3]]);
-                        let payload = &msg[4..];
-                        match type_id {
-                            #(#typeid_to_variant,)*
-                            _ => return Err(format!("Unknown message type: {type_id}")),
-                        }.map_err(|e| format!("Failed to decode message: {e}"))
+                        Self::decode_frame(type_id, &msg[4..])
                     }
                 }
             }
@@ -217,13 +489,32 @@ impl prost_build::ServiceGenerator for ServiceGenerator {
         let conversions = self
             .types
             .iter()
-            .map(|(message_name, _)| {
+            .map(|(message_name, message_id, (min_major, min_minor))| {
                 quote! {
                     impl From<#message_name> for #enum_name {
                         fn from(msg: #message_name) -> Self {
                             Self::#message_name(msg)
                         }
                     }
+                    impl TryFrom<#enum_name> for #message_name {
+                        type Error = #enum_name;
+                        fn try_from(msg: #enum_name) -> Result<Self, Self::Error> {
+                            match msg {
+                                #enum_name::#message_name(msg) => Ok(msg),
+                                other => Err(other),
+                            }
+                        }
+                    }
+                    impl MessageType for #message_name {
+                        const TYPE_ID: u16 = #message_id;
+                        const MIN_API_VERSION: (u32, u32) = (#min_major, #min_minor);
+                        fn from_message(msg: &#enum_name) -> Option<&Self> {
+                            match msg {
+                                #enum_name::#message_name(msg) => Some(msg),
+                                _ => None,
+                            }
+                        }
+                    }
                 }
             })
             .collect::<Vec<_>>();
@@ -20,11 +20,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== ESPHome Device Discovery ===");
     println!("Searching for devices on the local network...\n");
 
-    // Create a discovery client
-    let mut discovered_devices = std::collections::HashSet::new();
-
-    // Start discovery
-    let mut result_stream = discovery::Client::default().discover()?;
+    // Start discovery, yielding each device once, unless its addresses or attributes change
+    let mut result_stream = discovery::Client::default().discover_unique()?;
 
     println!("Listening for device announcements (press Ctrl+C to stop)...\n");
 
@@ -32,35 +29,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     loop {
         match result_stream.next().await {
             Ok(device) => {
-                // Check if we've seen this device before
-                let device_id = format!(
-                    "{}:{}",
-                    device.hostname(),
-                    device
-                        .socket_address()
-                        .map(|s| s.to_string())
-                        .unwrap_or_default()
-                );
-
-                if discovered_devices.insert(device_id) {
-                    println!("=== New Device Discovered ===");
-                    println!("Hostname: {}", device.hostname());
+                println!("=== Device Discovered ===");
+                println!("Hostname: {}", device.hostname());
 
-                    if let Some(addr) = device.socket_address() {
-                        println!("Address: {}", addr);
-                    }
+                if let Some(addr) = device.socket_address() {
+                    println!("Address: {}", addr);
+                }
 
-                    println!(
-                        "Encrypted: {}",
-                        if device.has_encryption() { "Yes" } else { "No" }
-                    );
+                println!(
+                    "Encrypted: {}",
+                    if device.has_encryption() { "Yes" } else { "No" }
+                );
 
-                    println!("Attributes:");
-                    for (key, value) in device.attributes() {
-                        println!("  {} = {}", key, value);
-                    }
-                    println!();
+                println!("Attributes:");
+                for (key, value) in device.attributes() {
+                    println!("  {} = {}", key, value);
                 }
+                println!();
             }
             Err(e) => {
                 eprintln!("Discovery error: {}", e);
@@ -37,7 +37,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     if let Some(key) = api_key {
         builder = builder.key(key);
     }
-    let mut client = builder.connect().await?;
+    let client = builder.connect().await?;
 
     println!("Connected successfully!\n");
 
@@ -37,7 +37,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     // Build and connect with API key (enables Noise protocol encryption)
-    let mut client = EspHomeClient::builder()
+    let client = EspHomeClient::builder()
         .address(address)
         .key(api_key)
         .connect()
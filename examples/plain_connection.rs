@@ -31,7 +31,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Connecting to ESPHome device at {}", address);
 
     // Build and connect to the ESPHome device
-    let mut client = EspHomeClient::builder()
+    let client = EspHomeClient::builder()
         .address(address)
         .connect()
         .await
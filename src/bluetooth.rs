@@ -0,0 +1,14 @@
+mod address;
+mod advertisement;
+#[cfg(feature = "bthome")]
+/// Decoding of BTHome v2 sensor data, only available with the "bthome" feature.
+pub mod bthome;
+
+#[allow(
+    clippy::module_name_repetitions,
+    reason = "A plain Address would be ambiguous with the address of the ESPHome device"
+)]
+pub use address::BluetoothAddress;
+pub use advertisement::{
+    AdStructure, Advertisement, Eddystone, IBeacon, ServiceUuid, parse_ad_structures,
+};
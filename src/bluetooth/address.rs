@@ -0,0 +1,140 @@
+use std::{fmt, str::FromStr};
+
+use crate::error::BluetoothError;
+
+/// Bluetooth device address, stored in the lower 48 bits of a `u64` like in the proxy messages.
+///
+/// # Usage:
+/// ```rust
+/// use esphome_client::bluetooth::BluetoothAddress;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let address: BluetoothAddress = "A4:C1:38:12:34:56".parse()?;
+/// assert_eq!(u64::from(address), 0xA4C1_3812_3456);
+/// assert_eq!(BluetoothAddress::from(0xA4C1_3812_3456).to_string(), "A4:C1:38:12:34:56");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BluetoothAddress(u64);
+
+impl BluetoothAddress {
+    /// Creates an address from its six bytes, most significant first.
+    #[must_use]
+    pub const fn from_bytes(bytes: [u8; 6]) -> Self {
+        let [b0, b1, b2, b3, b4, b5] = bytes;
+        Self(u64::from_be_bytes([0, 0, b0, b1, b2, b3, b4, b5]))
+    }
+
+    /// Returns the six bytes of the address, most significant first.
+    #[must_use]
+    pub const fn to_bytes(self) -> [u8; 6] {
+        let [_, _, b0, b1, b2, b3, b4, b5] = self.0.to_be_bytes();
+        [b0, b1, b2, b3, b4, b5]
+    }
+}
+
+/// Only the lower 48 bits are used, the upper bits are discarded.
+impl From<u64> for BluetoothAddress {
+    fn from(address: u64) -> Self {
+        Self(address & 0xFFFF_FFFF_FFFF)
+    }
+}
+
+impl From<BluetoothAddress> for u64 {
+    fn from(address: BluetoothAddress) -> Self {
+        address.0
+    }
+}
+
+/// Formats the address as upper case hex bytes separated by colons, e.g. `AA:BB:CC:DD:EE:FF`.
+impl fmt::Display for BluetoothAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [b0, b1, b2, b3, b4, b5] = self.to_bytes();
+        write!(f, "{b0:02X}:{b1:02X}:{b2:02X}:{b3:02X}:{b4:02X}:{b5:02X}")
+    }
+}
+
+/// Parses six hex bytes separated by colons or dashes, in either case.
+impl FromStr for BluetoothAddress {
+    type Err = BluetoothError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || BluetoothError::InvalidAddress {
+            address: s.to_owned(),
+        };
+        let separator = if s.contains('-') { '-' } else { ':' };
+        let mut bytes = [0; 6];
+        let mut parts = s.split(separator);
+        for byte in &mut bytes {
+            let part = parts.next().ok_or_else(invalid)?;
+            if part.len() != 2 {
+                return Err(invalid());
+            }
+            *byte = u8::from_str_radix(part, 16).map_err(|_e| invalid())?;
+        }
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(Self::from_bytes(bytes))
+    }
+}
+
+#[cfg(feature = "macaddr")]
+impl From<macaddr::MacAddr6> for BluetoothAddress {
+    fn from(address: macaddr::MacAddr6) -> Self {
+        Self::from_bytes(address.into_array())
+    }
+}
+
+#[cfg(feature = "macaddr")]
+impl From<BluetoothAddress> for macaddr::MacAddr6 {
+    fn from(address: BluetoothAddress) -> Self {
+        Self::from(address.to_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_round_trip() {
+        let address = BluetoothAddress::from(0xA4C1_3812_3456);
+        assert_eq!(address.to_string(), "A4:C1:38:12:34:56");
+        assert_eq!(
+            "a4-c1-38-12-34-56".parse::<BluetoothAddress>().unwrap(),
+            address
+        );
+        assert_eq!(
+            u64::from(BluetoothAddress::from(u64::MAX)),
+            0xFFFF_FFFF_FFFF
+        );
+    }
+
+    #[test]
+    fn test_address_parse_errors() {
+        for invalid in [
+            "",
+            "AA:BB:CC:DD:EE",
+            "AA:BB:CC:DD:EE:FF:00",
+            "AA:BB:CC:DD:EE:F",
+            "AA:BB:CC:DD:EE:GG",
+        ] {
+            let error = invalid.parse::<BluetoothAddress>().unwrap_err();
+            assert!(
+                matches!(error, BluetoothError::InvalidAddress { .. }),
+                "Unexpected error for {invalid}: {error}"
+            );
+        }
+    }
+
+    #[cfg(feature = "macaddr")]
+    #[test]
+    fn test_address_macaddr() {
+        let mac = macaddr::MacAddr6::new(0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF);
+        let address = BluetoothAddress::from(mac);
+        assert_eq!(u64::from(address), 0xAABB_CCDD_EEFF);
+        assert_eq!(macaddr::MacAddr6::from(address), mac);
+    }
+}
@@ -0,0 +1,580 @@
+use std::{fmt, time::Duration};
+
+use crate::error::AdvertisementError;
+#[cfg(feature = "bthome")]
+use crate::error::BtHomeError;
+
+const AD_FLAGS: u8 = 0x01;
+const AD_INCOMPLETE_UUIDS_16: u8 = 0x02;
+const AD_COMPLETE_UUIDS_16: u8 = 0x03;
+const AD_INCOMPLETE_UUIDS_32: u8 = 0x04;
+const AD_COMPLETE_UUIDS_32: u8 = 0x05;
+const AD_INCOMPLETE_UUIDS_128: u8 = 0x06;
+const AD_COMPLETE_UUIDS_128: u8 = 0x07;
+const AD_SHORTENED_LOCAL_NAME: u8 = 0x08;
+const AD_COMPLETE_LOCAL_NAME: u8 = 0x09;
+const AD_TX_POWER_LEVEL: u8 = 0x0a;
+const AD_SERVICE_DATA_16: u8 = 0x16;
+const AD_SERVICE_DATA_32: u8 = 0x20;
+const AD_SERVICE_DATA_128: u8 = 0x21;
+const AD_MANUFACTURER_DATA: u8 = 0xff;
+
+/// Bluetooth base UUID, `00000000-0000-1000-8000-00805F9B34FB`, used to expand short UUIDs.
+const BASE_UUID: u128 = 0x0000_0000_0000_1000_8000_0080_5f9b_34fb;
+
+const APPLE_COMPANY_ID: u16 = 0x004c;
+const EDDYSTONE_UUID: u16 = 0xfeaa;
+
+/// UUID of a service, in the shortest form it was advertised with.
+#[allow(
+    variant_size_differences,
+    reason = "Full UUIDs are kept inline, so the type stays Copy"
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ServiceUuid {
+    /// 16-bit UUID, relative to the Bluetooth base UUID.
+    Uuid16(u16),
+    /// 32-bit UUID, relative to the Bluetooth base UUID.
+    Uuid32(u32),
+    /// Full 128-bit UUID.
+    Uuid128(u128),
+}
+
+impl ServiceUuid {
+    /// Returns the full 128-bit UUID.
+    #[must_use]
+    pub fn as_u128(self) -> u128 {
+        match self {
+            Self::Uuid16(uuid) => BASE_UUID | (u128::from(uuid) << 96),
+            Self::Uuid32(uuid) => BASE_UUID | (u128::from(uuid) << 96),
+            Self::Uuid128(uuid) => uuid,
+        }
+    }
+}
+
+/// Formats the full 128-bit UUID, e.g. `0000180f-0000-1000-8000-00805f9b34fb`.
+impl fmt::Display for ServiceUuid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let uuid = self.as_u128();
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            uuid >> 96,
+            (uuid >> 80) & 0xffff,
+            (uuid >> 64) & 0xffff,
+            (uuid >> 48) & 0xffff,
+            uuid & 0xffff_ffff_ffff
+        )
+    }
+}
+
+/// Single AD structure of an advertisement payload.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdStructure {
+    /// Discoverability and capability flags.
+    Flags(u8),
+    /// List of service UUIDs.
+    ServiceUuids {
+        /// Advertised service UUIDs.
+        uuids: Vec<ServiceUuid>,
+        /// Whether the list contains all services of the device.
+        complete: bool,
+    },
+    /// Local name of the device.
+    LocalName {
+        /// Name of the device, invalid UTF-8 is replaced.
+        name: String,
+        /// Whether the name is complete, or shortened to fit the advertisement.
+        complete: bool,
+    },
+    /// Transmitted power level in dBm.
+    TxPowerLevel(i8),
+    /// Data associated with a service.
+    ServiceData {
+        /// UUID of the service.
+        uuid: ServiceUuid,
+        /// Service specific data.
+        data: Vec<u8>,
+    },
+    /// Manufacturer specific data.
+    ManufacturerData {
+        /// Bluetooth SIG assigned company identifier.
+        company_id: u16,
+        /// Manufacturer specific data.
+        data: Vec<u8>,
+    },
+    /// AD structure of a type that isn't decoded.
+    Other {
+        /// AD type of the structure.
+        ad_type: u8,
+        /// Raw data of the structure.
+        data: Vec<u8>,
+    },
+}
+
+impl AdStructure {
+    fn parse(ad_type: u8, data: &[u8]) -> Result<Self, AdvertisementError> {
+        let invalid = |reason: &str| AdvertisementError::InvalidStructure {
+            ad_type,
+            reason: reason.to_owned(),
+        };
+        Ok(match ad_type {
+            AD_FLAGS => Self::Flags(*data.first().ok_or_else(|| invalid("missing flags"))?),
+            AD_INCOMPLETE_UUIDS_16 | AD_COMPLETE_UUIDS_16 => Self::ServiceUuids {
+                uuids: uuid_list(data, 2).ok_or_else(|| invalid("length isn't a multiple of 2"))?,
+                complete: ad_type == AD_COMPLETE_UUIDS_16,
+            },
+            AD_INCOMPLETE_UUIDS_32 | AD_COMPLETE_UUIDS_32 => Self::ServiceUuids {
+                uuids: uuid_list(data, 4).ok_or_else(|| invalid("length isn't a multiple of 4"))?,
+                complete: ad_type == AD_COMPLETE_UUIDS_32,
+            },
+            AD_INCOMPLETE_UUIDS_128 | AD_COMPLETE_UUIDS_128 => Self::ServiceUuids {
+                uuids: uuid_list(data, 16)
+                    .ok_or_else(|| invalid("length isn't a multiple of 16"))?,
+                complete: ad_type == AD_COMPLETE_UUIDS_128,
+            },
+            AD_SHORTENED_LOCAL_NAME | AD_COMPLETE_LOCAL_NAME => Self::LocalName {
+                name: String::from_utf8_lossy(data).into_owned(),
+                complete: ad_type == AD_COMPLETE_LOCAL_NAME,
+            },
+            AD_TX_POWER_LEVEL => {
+                let [power] = data else {
+                    return Err(invalid("expected a single byte"));
+                };
+                Self::TxPowerLevel(i8::from_le_bytes([*power]))
+            }
+            AD_SERVICE_DATA_16 | AD_SERVICE_DATA_32 | AD_SERVICE_DATA_128 => {
+                let size = match ad_type {
+                    AD_SERVICE_DATA_16 => 2,
+                    AD_SERVICE_DATA_32 => 4,
+                    _ => 16,
+                };
+                if data.len() < size {
+                    return Err(invalid("missing service UUID"));
+                }
+                let (uuid, data) = data.split_at(size);
+                Self::ServiceData {
+                    uuid: parse_uuid(uuid).ok_or_else(|| invalid("invalid service UUID"))?,
+                    data: data.to_vec(),
+                }
+            }
+            AD_MANUFACTURER_DATA => {
+                let Some((company_id, data)) = data.split_first_chunk::<2>() else {
+                    return Err(invalid("missing company identifier"));
+                };
+                Self::ManufacturerData {
+                    company_id: u16::from_le_bytes(*company_id),
+                    data: data.to_vec(),
+                }
+            }
+            _ => Self::Other {
+                ad_type,
+                data: data.to_vec(),
+            },
+        })
+    }
+}
+
+/// Parses a little endian UUID of 2, 4 or 16 bytes.
+fn parse_uuid(data: &[u8]) -> Option<ServiceUuid> {
+    Some(match data.len() {
+        2 => ServiceUuid::Uuid16(u16::from_le_bytes(data.try_into().ok()?)),
+        4 => ServiceUuid::Uuid32(u32::from_le_bytes(data.try_into().ok()?)),
+        16 => ServiceUuid::Uuid128(u128::from_le_bytes(data.try_into().ok()?)),
+        _ => return None,
+    })
+}
+
+fn uuid_list(data: &[u8], size: usize) -> Option<Vec<ServiceUuid>> {
+    if data.len() % size != 0 {
+        return None;
+    }
+    data.chunks_exact(size).map(parse_uuid).collect()
+}
+
+/// Splits an advertisement payload into its AD structures.
+///
+/// Parsing stops at the first zero length structure, which marks the start of padding.
+///
+/// # Errors
+///
+/// Will return an error if a structure exceeds the payload, or its data doesn't match its type.
+pub fn parse_ad_structures(payload: &[u8]) -> Result<Vec<AdStructure>, AdvertisementError> {
+    let mut structures = Vec::new();
+    let mut offset = 0;
+    while let Some(&length) = payload.get(offset) {
+        if length == 0 {
+            break;
+        }
+        let length = usize::from(length);
+        let Some([ad_type, data @ ..]) = payload.get(offset + 1..=offset + length) else {
+            return Err(AdvertisementError::Truncated { offset, length });
+        };
+        structures.push(AdStructure::parse(*ad_type, data)?);
+        offset += length + 1;
+    }
+    Ok(structures)
+}
+
+/// Decoded advertisement payload, e.g. the data of a `BluetoothLERawAdvertisement`.
+///
+/// # Usage:
+/// ```rust
+/// use esphome_client::bluetooth::Advertisement;
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let advertisement = Advertisement::parse(&[0x02, 0x01, 0x06, 0x05, 0x09, b'L', b'a', b'm', b'p'])?;
+/// assert_eq!(advertisement.flags(), Some(0x06));
+/// assert_eq!(advertisement.local_name(), Some("Lamp"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Advertisement {
+    structures: Vec<AdStructure>,
+}
+
+impl Advertisement {
+    /// Parses an advertisement payload.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the payload is malformed, see [`parse_ad_structures`].
+    pub fn parse(payload: &[u8]) -> Result<Self, AdvertisementError> {
+        Ok(Self {
+            structures: parse_ad_structures(payload)?,
+        })
+    }
+
+    /// Returns the AD structures of the advertisement, in payload order.
+    #[must_use]
+    pub fn structures(&self) -> &[AdStructure] {
+        &self.structures
+    }
+
+    /// Returns the advertised flags.
+    #[must_use]
+    pub fn flags(&self) -> Option<u8> {
+        self.structures
+            .iter()
+            .find_map(|structure| match structure {
+                AdStructure::Flags(flags) => Some(*flags),
+                _ => None,
+            })
+    }
+
+    /// Returns the local name, preferring the complete name over a shortened one.
+    #[must_use]
+    pub fn local_name(&self) -> Option<&str> {
+        let mut shortened = None;
+        for structure in &self.structures {
+            if let AdStructure::LocalName { name, complete } = structure {
+                if *complete {
+                    return Some(name);
+                }
+                shortened = shortened.or(Some(name.as_str()));
+            }
+        }
+        shortened
+    }
+
+    /// Returns the transmitted power level in dBm.
+    #[must_use]
+    pub fn tx_power_level(&self) -> Option<i8> {
+        self.structures
+            .iter()
+            .find_map(|structure| match structure {
+                AdStructure::TxPowerLevel(power) => Some(*power),
+                _ => None,
+            })
+    }
+
+    /// Returns all advertised service UUIDs.
+    pub fn service_uuids(&self) -> impl Iterator<Item = ServiceUuid> + '_ {
+        self.structures
+            .iter()
+            .filter_map(|structure| match structure {
+                AdStructure::ServiceUuids { uuids, .. } => Some(uuids),
+                _ => None,
+            })
+            .flatten()
+            .copied()
+    }
+
+    /// Returns the data of the given service, matching short UUIDs by their full form.
+    #[must_use]
+    pub fn service_data(&self, uuid: ServiceUuid) -> Option<&[u8]> {
+        self.structures
+            .iter()
+            .find_map(|structure| match structure {
+                AdStructure::ServiceData { uuid: other, data }
+                    if other.as_u128() == uuid.as_u128() =>
+                {
+                    Some(data.as_slice())
+                }
+                _ => None,
+            })
+    }
+
+    /// Returns the manufacturer data of the given company.
+    #[must_use]
+    pub fn manufacturer_data(&self, company_id: u16) -> Option<&[u8]> {
+        self.structures
+            .iter()
+            .find_map(|structure| match structure {
+                AdStructure::ManufacturerData {
+                    company_id: other,
+                    data,
+                } if *other == company_id => Some(data.as_slice()),
+                _ => None,
+            })
+    }
+
+    /// Decodes the advertisement as an iBeacon, if it is one.
+    #[must_use]
+    pub fn ibeacon(&self) -> Option<IBeacon> {
+        let [0x02, 0x15, rest @ ..] = self.manufacturer_data(APPLE_COMPANY_ID)? else {
+            return None;
+        };
+        let (uuid, rest) = rest.split_first_chunk::<16>()?;
+        let [major_high, major_low, minor_high, minor_low, power] = rest else {
+            return None;
+        };
+        Some(IBeacon {
+            uuid: u128::from_be_bytes(*uuid),
+            major: u16::from_be_bytes([*major_high, *major_low]),
+            minor: u16::from_be_bytes([*minor_high, *minor_low]),
+            measured_power: i8::from_be_bytes([*power]),
+        })
+    }
+
+    /// Decodes the BTHome service data of the advertisement, if it has any.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the service data can't be decoded, see [`Packet::decode`](super::bthome::Packet::decode).
+    #[cfg(feature = "bthome")]
+    pub fn bthome(&self) -> Result<Option<super::bthome::Packet>, BtHomeError> {
+        self.service_data(ServiceUuid::Uuid16(super::bthome::BTHOME_UUID))
+            .map(super::bthome::Packet::decode)
+            .transpose()
+    }
+
+    /// Decodes the advertisement as an Eddystone frame, if it is one.
+    #[must_use]
+    pub fn eddystone(&self) -> Option<Eddystone> {
+        Eddystone::parse(self.service_data(ServiceUuid::Uuid16(EDDYSTONE_UUID))?)
+    }
+}
+
+/// Apple iBeacon frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IBeacon {
+    /// Proximity UUID.
+    pub uuid: u128,
+    /// Major value.
+    pub major: u16,
+    /// Minor value.
+    pub minor: u16,
+    /// Calibrated signal strength at 1 meter, in dBm.
+    pub measured_power: i8,
+}
+
+/// Google Eddystone frame.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Eddystone {
+    /// Unique beacon identifier.
+    Uid {
+        /// Calibrated transmission power at 0 meters, in dBm.
+        tx_power: i8,
+        /// Namespace of the identifier.
+        namespace: [u8; 10],
+        /// Instance within the namespace.
+        instance: [u8; 6],
+    },
+    /// Compressed URL.
+    Url {
+        /// Calibrated transmission power at 0 meters, in dBm.
+        tx_power: i8,
+        /// Expanded URL.
+        url: String,
+    },
+    /// Unencrypted telemetry.
+    Tlm {
+        /// Battery voltage in millivolts, 0 if not supported.
+        battery_mv: u16,
+        /// Beacon temperature in degrees Celsius, `None` if not supported.
+        temperature: Option<f32>,
+        /// Number of advertisements sent since power-up.
+        advertisement_count: u32,
+        /// Time since power-up.
+        uptime: Duration,
+    },
+    /// Ephemeral identifier.
+    Eid {
+        /// Calibrated transmission power at 0 meters, in dBm.
+        tx_power: i8,
+        /// Current ephemeral identifier.
+        eid: [u8; 8],
+    },
+}
+
+impl Eddystone {
+    fn parse(data: &[u8]) -> Option<Self> {
+        match data {
+            [0x00, tx_power, rest @ ..] => {
+                let (namespace, rest) = rest.split_first_chunk::<10>()?;
+                let (instance, _) = rest.split_first_chunk::<6>()?;
+                Some(Self::Uid {
+                    tx_power: i8::from_be_bytes([*tx_power]),
+                    namespace: *namespace,
+                    instance: *instance,
+                })
+            }
+            [0x10, tx_power, scheme, encoded @ ..] => {
+                let mut url = String::from(*URL_SCHEMES.get(usize::from(*scheme))?);
+                for &byte in encoded {
+                    match URL_EXPANSIONS.get(usize::from(byte)) {
+                        Some(expansion) => url.push_str(expansion),
+                        None => url.push(char::from(byte)),
+                    }
+                }
+                Some(Self::Url {
+                    tx_power: i8::from_be_bytes([*tx_power]),
+                    url,
+                })
+            }
+            [0x20, 0x00, rest @ ..] => {
+                let (battery_mv, rest) = rest.split_first_chunk::<2>()?;
+                let (temperature, rest) = rest.split_first_chunk::<2>()?;
+                let (count, rest) = rest.split_first_chunk::<4>()?;
+                let (uptime, _) = rest.split_first_chunk::<4>()?;
+                Some(Self::Tlm {
+                    battery_mv: u16::from_be_bytes(*battery_mv),
+                    // Signed 8.8 fixed point, 0x8000 when not supported.
+                    temperature: (*temperature != [0x80, 0x00])
+                        .then(|| f32::from(i16::from_be_bytes(*temperature)) / 256.0),
+                    advertisement_count: u32::from_be_bytes(*count),
+                    // Counted in 0.1 second steps.
+                    uptime: Duration::from_millis(u64::from(u32::from_be_bytes(*uptime)) * 100),
+                })
+            }
+            [0x30, tx_power, rest @ ..] => Some(Self::Eid {
+                tx_power: i8::from_be_bytes([*tx_power]),
+                eid: *rest.first_chunk::<8>()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+const URL_SCHEMES: [&str; 4] = ["http://www.", "https://www.", "http://", "https://"];
+const URL_EXPANSIONS: [&str; 14] = [
+    ".com/", ".org/", ".edu/", ".net/", ".info/", ".biz/", ".gov/", ".com", ".org", ".edu", ".net",
+    ".info", ".biz", ".gov",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ad_structures() {
+        let payload = [
+            0x02, 0x01, 0x06, // flags
+            0x05, 0x03, 0x0f, 0x18, 0x0a, 0x18, // complete 16-bit UUIDs
+            0x04, 0x08, b'L', b'a', b'm', // shortened name
+            0x02, 0x0a, 0xf4, // tx power
+            0x05, 0x16, 0x0f, 0x18, 0x64, 0x00, // battery service data
+            0x05, 0xff, 0x59, 0x00, 0x01, 0x02, // manufacturer data
+            0x00, 0x00, // padding
+        ];
+        let advertisement = Advertisement::parse(&payload).unwrap();
+        assert_eq!(advertisement.structures().len(), 6);
+        assert_eq!(advertisement.flags(), Some(0x06));
+        assert_eq!(advertisement.local_name(), Some("Lam"));
+        assert_eq!(advertisement.tx_power_level(), Some(-12));
+        assert_eq!(
+            advertisement.service_uuids().collect::<Vec<_>>(),
+            vec![ServiceUuid::Uuid16(0x180f), ServiceUuid::Uuid16(0x180a)]
+        );
+        assert_eq!(
+            advertisement.service_data(ServiceUuid::Uuid128(ServiceUuid::Uuid16(0x180f).as_u128())),
+            Some([0x64, 0x00].as_slice())
+        );
+        assert_eq!(
+            advertisement.manufacturer_data(0x0059),
+            Some([0x01, 0x02].as_slice())
+        );
+        assert!(advertisement.ibeacon().is_none(), "Not an iBeacon");
+    }
+
+    #[test]
+    fn test_parse_ad_structures_errors() {
+        assert!(matches!(
+            parse_ad_structures(&[0x02, 0x01, 0x06, 0x05, 0x09, b'a']).unwrap_err(),
+            AdvertisementError::Truncated {
+                offset: 3,
+                length: 5
+            }
+        ));
+        assert!(matches!(
+            parse_ad_structures(&[0x04, 0x03, 0x0f, 0x18, 0x0a]).unwrap_err(),
+            AdvertisementError::InvalidStructure { ad_type: 0x03, .. }
+        ));
+    }
+
+    #[test]
+    fn test_service_uuid_display() {
+        assert_eq!(
+            ServiceUuid::Uuid16(0x180f).to_string(),
+            "0000180f-0000-1000-8000-00805f9b34fb"
+        );
+    }
+
+    #[test]
+    fn test_ibeacon() {
+        let mut payload = vec![0x1a, 0xff, 0x4c, 0x00, 0x02, 0x15];
+        payload.extend_from_slice(&0xe2c5_6db5_dffb_48d2_b060_d0f5_a710_96e0_u128.to_be_bytes());
+        payload.extend_from_slice(&[0x00, 0x01, 0x00, 0x02, 0xc5]);
+        let beacon = Advertisement::parse(&payload).unwrap().ibeacon().unwrap();
+        assert_eq!(
+            beacon,
+            IBeacon {
+                uuid: 0xe2c5_6db5_dffb_48d2_b060_d0f5_a710_96e0,
+                major: 1,
+                minor: 2,
+                measured_power: -59,
+            }
+        );
+    }
+
+    #[test]
+    fn test_eddystone() {
+        let url = [
+            0x0e, 0x16, 0xaa, 0xfe, 0x10, 0xeb, 0x03, b'e', b's', b'p', b'h', b'o', b'm', b'e',
+            0x07,
+        ];
+        assert_eq!(
+            Advertisement::parse(&url).unwrap().eddystone(),
+            Some(Eddystone::Url {
+                tx_power: -21,
+                url: "https://esphome.com".to_owned()
+            })
+        );
+
+        let tlm = [
+            0x11, 0x16, 0xaa, 0xfe, 0x20, 0x00, 0x0b, 0xb8, 0x17, 0x80, 0x00, 0x00, 0x00, 0x0a,
+            0x00, 0x00, 0x00, 0x64,
+        ];
+        assert_eq!(
+            Advertisement::parse(&tlm).unwrap().eddystone(),
+            Some(Eddystone::Tlm {
+                battery_mv: 3000,
+                temperature: Some(23.5),
+                advertisement_count: 10,
+                uptime: Duration::from_secs(10),
+            })
+        );
+    }
+}
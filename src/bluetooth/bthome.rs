@@ -0,0 +1,549 @@
+use crate::error::BtHomeError;
+
+/// 16-bit service UUID of the BTHome service data.
+pub const BTHOME_UUID: u16 = 0xfcd2;
+
+const FLAG_ENCRYPTED: u8 = 0x01;
+const FLAG_TRIGGER_BASED: u8 = 0x04;
+const VERSION_SHIFT: u8 = 5;
+
+/// Kind of a numeric measurement, in the unit reported by [`SensorKind::unit`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SensorKind {
+    /// Acceleration in m/s².
+    Acceleration,
+    /// Battery level in %.
+    Battery,
+    /// Channel number.
+    Channel,
+    /// Conductivity in µS/cm.
+    Conductivity,
+    /// Carbon dioxide in ppm.
+    Co2,
+    /// Counter value.
+    Count,
+    /// Current in A.
+    Current,
+    /// Dew point in °C.
+    Dewpoint,
+    /// Direction in °.
+    Direction,
+    /// Distance in m.
+    Distance,
+    /// Duration in s.
+    Duration,
+    /// Energy in kWh.
+    Energy,
+    /// Gas volume in m³.
+    Gas,
+    /// Gyroscope in °/s.
+    Gyroscope,
+    /// Relative humidity in %.
+    Humidity,
+    /// Illuminance in lx.
+    Illuminance,
+    /// Mass in kg.
+    Mass,
+    /// Mass in lb.
+    MassLb,
+    /// Moisture in %.
+    Moisture,
+    /// Particulate matter up to 10 µm in µg/m³.
+    Pm10,
+    /// Particulate matter up to 2.5 µm in µg/m³.
+    Pm25,
+    /// Power in W.
+    Power,
+    /// Precipitation in mm.
+    Precipitation,
+    /// Pressure in hPa.
+    Pressure,
+    /// Rotation in °.
+    Rotation,
+    /// Speed in m/s.
+    Speed,
+    /// Temperature in °C.
+    Temperature,
+    /// Timestamp in seconds since the unix epoch.
+    Timestamp,
+    /// Total volatile organic compounds in µg/m³.
+    Tvoc,
+    /// UV index.
+    UvIndex,
+    /// Voltage in V.
+    Voltage,
+    /// Volume in L.
+    Volume,
+    /// Volume flow rate in m³/h.
+    VolumeFlowRate,
+    /// Stored volume in L.
+    VolumeStorage,
+    /// Water in L.
+    Water,
+}
+
+impl SensorKind {
+    /// Returns the unit of the measurement, empty for unitless values.
+    #[must_use]
+    pub const fn unit(self) -> &'static str {
+        match self {
+            Self::Acceleration => "m/s²",
+            Self::Battery | Self::Humidity | Self::Moisture => "%",
+            Self::Channel | Self::Count | Self::UvIndex => "",
+            Self::Conductivity => "µS/cm",
+            Self::Co2 => "ppm",
+            Self::Current => "A",
+            Self::Dewpoint | Self::Temperature => "°C",
+            Self::Direction | Self::Rotation => "°",
+            Self::Distance => "m",
+            Self::Duration | Self::Timestamp => "s",
+            Self::Energy => "kWh",
+            Self::Gas => "m³",
+            Self::Gyroscope => "°/s",
+            Self::Illuminance => "lx",
+            Self::Mass => "kg",
+            Self::MassLb => "lb",
+            Self::Pm10 | Self::Pm25 | Self::Tvoc => "µg/m³",
+            Self::Power => "W",
+            Self::Precipitation => "mm",
+            Self::Pressure => "hPa",
+            Self::Speed => "m/s",
+            Self::Voltage => "V",
+            Self::Volume | Self::VolumeStorage | Self::Water => "L",
+            Self::VolumeFlowRate => "m³/h",
+        }
+    }
+}
+
+/// Kind of a binary measurement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BinaryKind {
+    /// Battery is low.
+    Battery,
+    /// Battery is charging.
+    BatteryCharging,
+    /// Carbon monoxide detected.
+    CarbonMonoxide,
+    /// Cold.
+    Cold,
+    /// Connected.
+    Connectivity,
+    /// Door is open.
+    Door,
+    /// Garage door is open.
+    GarageDoor,
+    /// Gas detected.
+    Gas,
+    /// Generic on/off state.
+    Generic,
+    /// Hot.
+    Heat,
+    /// Light detected.
+    Light,
+    /// Unlocked.
+    Lock,
+    /// Wet.
+    Moisture,
+    /// Motion detected.
+    Motion,
+    /// Moving.
+    Moving,
+    /// Occupied.
+    Occupancy,
+    /// Open.
+    Opening,
+    /// Plugged in.
+    Plug,
+    /// Power detected.
+    Power,
+    /// Present.
+    Presence,
+    /// Problem detected.
+    Problem,
+    /// Running.
+    Running,
+    /// Unsafe.
+    Safety,
+    /// Smoke detected.
+    Smoke,
+    /// Sound detected.
+    Sound,
+    /// Tampering detected.
+    Tamper,
+    /// Vibration detected.
+    Vibration,
+    /// Window is open.
+    Window,
+}
+
+/// Event of a button.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ButtonEvent {
+    /// No event.
+    None,
+    /// Single press.
+    Press,
+    /// Double press.
+    DoublePress,
+    /// Triple press.
+    TriplePress,
+    /// Long press.
+    LongPress,
+    /// Long double press.
+    LongDoublePress,
+    /// Long triple press.
+    LongTriplePress,
+    /// Button is held down.
+    HoldPress,
+}
+
+/// Single measurement of a BTHome packet.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Measurement {
+    /// Packet id, used to detect duplicate packets.
+    PacketId(u8),
+    /// Numeric measurement.
+    Sensor {
+        /// Kind of the measurement.
+        kind: SensorKind,
+        /// Value in the unit of the kind.
+        value: f64,
+    },
+    /// Binary measurement.
+    Binary {
+        /// Kind of the measurement.
+        kind: BinaryKind,
+        /// Whether the state is on, e.g. open or detected.
+        value: bool,
+    },
+    /// Button event.
+    Button(ButtonEvent),
+    /// Dimmer rotation, in steps. Negative steps rotate left, positive steps rotate right.
+    Dimmer(i16),
+    /// Text value.
+    Text(String),
+    /// Raw value.
+    Raw(Vec<u8>),
+    /// Object that isn't decoded, like device information.
+    Other {
+        /// BTHome object id.
+        object_id: u8,
+        /// Raw data of the object.
+        data: Vec<u8>,
+    },
+}
+
+/// Decoded BTHome v2 service data.
+///
+/// # Usage:
+/// ```rust
+/// use esphome_client::bluetooth::bthome::{Packet, SensorKind};
+///
+/// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let packet = Packet::decode(&[0x40, 0x02, 0xca, 0x09])?;
+/// assert_eq!(packet.sensor(SensorKind::Temperature), Some(25.06));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Packet {
+    /// Whether the device sends packets on events only, instead of at a regular interval.
+    pub trigger_based: bool,
+    /// Measurements in the order of the packet.
+    pub measurements: Vec<Measurement>,
+}
+
+impl Packet {
+    /// Decodes the service data of the BTHome service, see [`BTHOME_UUID`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the data is encrypted, of another BTHome version, or malformed.
+    pub fn decode(data: &[u8]) -> Result<Self, BtHomeError> {
+        let Some((&info, mut objects)) = data.split_first() else {
+            return Err(BtHomeError::Truncated { object_id: None });
+        };
+        let version = info >> VERSION_SHIFT;
+        if version != 2 {
+            return Err(BtHomeError::UnsupportedVersion { version });
+        }
+        if info & FLAG_ENCRYPTED != 0 {
+            return Err(BtHomeError::Encrypted);
+        }
+        let mut measurements = Vec::new();
+        while let Some((&object_id, rest)) = objects.split_first() {
+            let truncated = BtHomeError::Truncated {
+                object_id: Some(object_id),
+            };
+            let length = match object_id {
+                0x53 | 0x54 => usize::from(*rest.first().ok_or(truncated)?) + 1,
+                _ => object_length(object_id).ok_or(BtHomeError::UnknownObject { object_id })?,
+            };
+            if rest.len() < length {
+                return Err(BtHomeError::Truncated {
+                    object_id: Some(object_id),
+                });
+            }
+            let (value, rest) = rest.split_at(length);
+            measurements.push(decode_object(object_id, value));
+            objects = rest;
+        }
+        Ok(Self {
+            trigger_based: info & FLAG_TRIGGER_BASED != 0,
+            measurements,
+        })
+    }
+
+    /// Returns the first value of the given sensor kind.
+    #[must_use]
+    pub fn sensor(&self, kind: SensorKind) -> Option<f64> {
+        self.measurements
+            .iter()
+            .find_map(|measurement| match measurement {
+                Measurement::Sensor { kind: other, value } if *other == kind => Some(*value),
+                _ => None,
+            })
+    }
+
+    /// Returns the first state of the given binary kind.
+    #[must_use]
+    pub fn binary(&self, kind: BinaryKind) -> Option<bool> {
+        self.measurements
+            .iter()
+            .find_map(|measurement| match measurement {
+                Measurement::Binary { kind: other, value } if *other == kind => Some(*value),
+                _ => None,
+            })
+    }
+}
+
+/// Numeric object: kind, length in bytes, whether the value is signed, and the divisor to apply.
+struct SensorObject(SensorKind, usize, bool, f64);
+
+/// Returns the format of the numeric objects.
+const fn sensor_object(object_id: u8) -> Option<SensorObject> {
+    use SensorKind::{
+        Acceleration, Battery, Channel, Co2, Conductivity, Count, Current, Dewpoint, Direction,
+        Distance, Duration, Energy, Gas, Gyroscope, Humidity, Illuminance, Mass, MassLb, Moisture,
+        Pm10, Pm25, Power, Precipitation, Pressure, Rotation, Speed, Temperature, Timestamp, Tvoc,
+        UvIndex, Voltage, Volume, VolumeFlowRate, VolumeStorage, Water,
+    };
+    Some(match object_id {
+        0x01 => SensorObject(Battery, 1, false, 1.0),
+        0x02 => SensorObject(Temperature, 2, true, 100.0),
+        0x03 => SensorObject(Humidity, 2, false, 100.0),
+        0x04 => SensorObject(Pressure, 3, false, 100.0),
+        0x05 => SensorObject(Illuminance, 3, false, 100.0),
+        0x06 => SensorObject(Mass, 2, false, 100.0),
+        0x07 => SensorObject(MassLb, 2, false, 100.0),
+        0x08 => SensorObject(Dewpoint, 2, true, 100.0),
+        0x09 => SensorObject(Count, 1, false, 1.0),
+        0x0a => SensorObject(Energy, 3, false, 1000.0),
+        0x0b => SensorObject(Power, 3, false, 100.0),
+        0x0c => SensorObject(Voltage, 2, false, 1000.0),
+        0x0d => SensorObject(Pm25, 2, false, 1.0),
+        0x0e => SensorObject(Pm10, 2, false, 1.0),
+        0x12 => SensorObject(Co2, 2, false, 1.0),
+        0x13 => SensorObject(Tvoc, 2, false, 1.0),
+        0x14 => SensorObject(Moisture, 2, false, 100.0),
+        0x2e => SensorObject(Humidity, 1, false, 1.0),
+        0x2f => SensorObject(Moisture, 1, false, 1.0),
+        0x3d => SensorObject(Count, 2, false, 1.0),
+        0x3e => SensorObject(Count, 4, false, 1.0),
+        0x3f => SensorObject(Rotation, 2, true, 10.0),
+        0x40 => SensorObject(Distance, 2, false, 1000.0),
+        0x41 => SensorObject(Distance, 2, false, 10.0),
+        0x42 => SensorObject(Duration, 3, false, 1000.0),
+        0x43 => SensorObject(Current, 2, false, 1000.0),
+        0x44 => SensorObject(Speed, 2, false, 100.0),
+        0x45 => SensorObject(Temperature, 2, true, 10.0),
+        0x46 => SensorObject(UvIndex, 1, false, 10.0),
+        0x47 => SensorObject(Volume, 2, false, 10.0),
+        0x48 => SensorObject(Volume, 2, false, 1000.0),
+        0x49 => SensorObject(VolumeFlowRate, 2, false, 1000.0),
+        0x4a => SensorObject(Voltage, 2, false, 10.0),
+        0x4b => SensorObject(Gas, 3, false, 1000.0),
+        0x4c => SensorObject(Gas, 4, false, 1000.0),
+        0x4d => SensorObject(Energy, 4, false, 1000.0),
+        0x4e => SensorObject(Volume, 4, false, 1000.0),
+        0x4f => SensorObject(Water, 4, false, 1000.0),
+        0x50 => SensorObject(Timestamp, 4, false, 1.0),
+        0x51 => SensorObject(Acceleration, 2, false, 1000.0),
+        0x52 => SensorObject(Gyroscope, 2, false, 1000.0),
+        0x55 => SensorObject(VolumeStorage, 4, false, 1000.0),
+        0x56 => SensorObject(Conductivity, 2, false, 1.0),
+        0x57 => SensorObject(Temperature, 1, true, 1.0),
+        0x58 => SensorObject(Temperature, 1, true, 1.0 / 0.35),
+        0x59 => SensorObject(Count, 1, true, 1.0),
+        0x5a => SensorObject(Count, 2, true, 1.0),
+        0x5b => SensorObject(Count, 4, true, 1.0),
+        0x5c => SensorObject(Power, 4, true, 100.0),
+        0x5d => SensorObject(Current, 2, true, 1000.0),
+        0x5e => SensorObject(Direction, 2, false, 100.0),
+        0x5f => SensorObject(Precipitation, 2, false, 10.0),
+        0x60 => SensorObject(Channel, 1, false, 1.0),
+        _ => return None,
+    })
+}
+
+/// Returns the kind of the binary objects.
+const fn binary_object(object_id: u8) -> Option<BinaryKind> {
+    Some(match object_id {
+        0x0f => BinaryKind::Generic,
+        0x10 => BinaryKind::Power,
+        0x11 => BinaryKind::Opening,
+        0x15 => BinaryKind::Battery,
+        0x16 => BinaryKind::BatteryCharging,
+        0x17 => BinaryKind::CarbonMonoxide,
+        0x18 => BinaryKind::Cold,
+        0x19 => BinaryKind::Connectivity,
+        0x1a => BinaryKind::Door,
+        0x1b => BinaryKind::GarageDoor,
+        0x1c => BinaryKind::Gas,
+        0x1d => BinaryKind::Heat,
+        0x1e => BinaryKind::Light,
+        0x1f => BinaryKind::Lock,
+        0x20 => BinaryKind::Moisture,
+        0x21 => BinaryKind::Motion,
+        0x22 => BinaryKind::Moving,
+        0x23 => BinaryKind::Occupancy,
+        0x24 => BinaryKind::Plug,
+        0x25 => BinaryKind::Presence,
+        0x26 => BinaryKind::Problem,
+        0x27 => BinaryKind::Running,
+        0x28 => BinaryKind::Safety,
+        0x29 => BinaryKind::Smoke,
+        0x2a => BinaryKind::Sound,
+        0x2b => BinaryKind::Tamper,
+        0x2c => BinaryKind::Vibration,
+        0x2d => BinaryKind::Window,
+        _ => return None,
+    })
+}
+
+/// Returns the length of the fixed size objects.
+const fn object_length(object_id: u8) -> Option<usize> {
+    if let Some(SensorObject(_, length, _, _)) = sensor_object(object_id) {
+        return Some(length);
+    }
+    match object_id {
+        0x00 | 0x3a => Some(1),
+        0x3c | 0xf0 => Some(2),
+        0xf2 => Some(3),
+        0xf1 => Some(4),
+        _ if binary_object(object_id).is_some() => Some(1),
+        _ => None,
+    }
+}
+
+/// Decodes an object, of which the data has the length of the object.
+fn decode_object(object_id: u8, data: &[u8]) -> Measurement {
+    if let Some(SensorObject(kind, _, signed, divisor)) = sensor_object(object_id) {
+        return Measurement::Sensor {
+            kind,
+            value: integer(data, signed) / divisor,
+        };
+    }
+    if let Some(kind) = binary_object(object_id) {
+        return Measurement::Binary {
+            kind,
+            value: data.first().is_some_and(|&value| value != 0),
+        };
+    }
+    match (object_id, data) {
+        (0x00, &[id]) => Measurement::PacketId(id),
+        (0x3a, &[event]) => Measurement::Button(match event {
+            0x01 => ButtonEvent::Press,
+            0x02 => ButtonEvent::DoublePress,
+            0x03 => ButtonEvent::TriplePress,
+            0x04 => ButtonEvent::LongPress,
+            0x05 => ButtonEvent::LongDoublePress,
+            0x06 => ButtonEvent::LongTriplePress,
+            0x80 => ButtonEvent::HoldPress,
+            _ => ButtonEvent::None,
+        }),
+        (0x3c, &[event, steps]) => Measurement::Dimmer(match event {
+            0x01 => -i16::from(steps),
+            0x02 => i16::from(steps),
+            _ => 0,
+        }),
+        (0x53, [_, text @ ..]) => Measurement::Text(String::from_utf8_lossy(text).into_owned()),
+        (0x54, [_, raw @ ..]) => Measurement::Raw(raw.to_vec()),
+        _ => Measurement::Other {
+            object_id,
+            data: data.to_vec(),
+        },
+    }
+}
+
+/// Decodes a little endian integer of up to 4 bytes.
+fn integer(data: &[u8], signed: bool) -> f64 {
+    let mut bytes = [0; 4];
+    for (byte, value) in bytes.iter_mut().zip(data) {
+        *byte = *value;
+    }
+    let value = u32::from_le_bytes(bytes);
+    if !signed {
+        return f64::from(value);
+    }
+    // Sign extend from the length of the data
+    let shift = 32 - 8 * u32::try_from(data.len().min(4)).unwrap_or(4);
+    f64::from(i32::from_le_bytes((value << shift).to_le_bytes()) >> shift)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_packet() {
+        let packet = Packet::decode(&[
+            0x44, // v2, trigger based
+            0x00, 0x07, // packet id
+            0x01, 0x61, // battery
+            0x02, 0x30, 0xf8, // temperature
+            0x03, 0xbf, 0x13, // humidity
+            0x21, 0x01, // motion
+            0x3a, 0x02, // button
+            0x3c, 0x01, 0x03, // dimmer
+            0x53, 0x02, b'h', b'i', // text
+        ])
+        .unwrap();
+        assert!(packet.trigger_based);
+        assert_eq!(packet.measurements.first(), Some(&Measurement::PacketId(7)));
+        assert_eq!(packet.sensor(SensorKind::Battery), Some(97.0));
+        assert_eq!(packet.sensor(SensorKind::Temperature), Some(-20.0));
+        assert_eq!(packet.sensor(SensorKind::Humidity), Some(50.55));
+        assert_eq!(packet.binary(BinaryKind::Motion), Some(true));
+        assert_eq!(
+            &packet.measurements[5..],
+            &[
+                Measurement::Button(ButtonEvent::DoublePress),
+                Measurement::Dimmer(-3),
+                Measurement::Text("hi".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert!(matches!(
+            Packet::decode(&[0x41, 0x01, 0x61]).unwrap_err(),
+            BtHomeError::Encrypted
+        ));
+        assert!(matches!(
+            Packet::decode(&[0x20, 0x01, 0x61]).unwrap_err(),
+            BtHomeError::UnsupportedVersion { version: 1 }
+        ));
+        assert!(matches!(
+            Packet::decode(&[0x40, 0x02, 0x30]).unwrap_err(),
+            BtHomeError::Truncated {
+                object_id: Some(0x02)
+            }
+        ));
+        assert!(matches!(
+            Packet::decode(&[0x40, 0xee, 0x00]).unwrap_err(),
+            BtHomeError::UnknownObject { object_id: 0xee }
+        ));
+    }
+}
@@ -0,0 +1,354 @@
+use std::collections::HashMap;
+#[cfg(feature = "stream")]
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+#[cfg(feature = "stream")]
+use futures_core::Stream;
+#[cfg(feature = "stream")]
+use tokio::{task::JoinHandle, time::interval};
+
+#[cfg(feature = "image")]
+use crate::error::ProtocolError;
+#[cfg(feature = "stream")]
+use crate::proto::CameraImageRequest;
+use crate::proto::CameraImageResponse;
+#[cfg(any(feature = "image", feature = "stream"))]
+use crate::{EspHomeClient, error::ClientError, proto::EspHomeMessage};
+
+/// A complete camera image, reassembled from one or more `CameraImageResponse` chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    /// Key of the camera entity that produced the image.
+    pub key: u32,
+    /// Encoded image data, ESPHome cameras produce JPEG images.
+    pub data: Vec<u8>,
+}
+
+/// Reassembles the chunked `CameraImageResponse` messages into complete frames.
+///
+/// ESPHome splits camera images over multiple messages, the last chunk of an image has the `done` flag set.
+/// Chunks are tracked per camera key, so images of multiple cameras can be interleaved.
+#[derive(Debug, Default)]
+pub struct FrameAssembler {
+    buffers: HashMap<u32, Vec<u8>>,
+}
+
+impl FrameAssembler {
+    /// Creates an empty assembler.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a chunk to the frame of its camera.
+    ///
+    /// Returns the complete frame when the chunk is the last one of an image.
+    pub fn push(&mut self, chunk: &CameraImageResponse) -> Option<Frame> {
+        let buffer = self.buffers.entry(chunk.key).or_default();
+        buffer.extend_from_slice(&chunk.data);
+        if !chunk.done {
+            return None;
+        }
+        let data = self.buffers.remove(&chunk.key).unwrap_or_default();
+        Some(Frame {
+            key: chunk.key,
+            data,
+        })
+    }
+
+    /// Discards all partially received frames.
+    pub fn clear(&mut self) {
+        self.buffers.clear();
+    }
+}
+
+/// A decoded camera image, only available with the `image` feature.
+#[cfg(feature = "image")]
+#[derive(Debug, Clone)]
+pub struct DecodedFrame {
+    /// Key of the camera entity that produced the image.
+    pub key: u32,
+    /// Width of the image in pixels.
+    pub width: u32,
+    /// Height of the image in pixels.
+    pub height: u32,
+    /// The decoded image.
+    pub image: image::DynamicImage,
+}
+
+#[cfg(feature = "image")]
+impl Frame {
+    /// Decodes the JPEG data of the frame, only available with the `image` feature.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the data is not a valid JPEG image.
+    pub fn decode(&self) -> Result<DecodedFrame, ClientError> {
+        use image::GenericImageView as _;
+
+        let image = image::load_from_memory_with_format(&self.data, image::ImageFormat::Jpeg)
+            .map_err(|e| ProtocolError::ValidationFailed {
+                reason: format!("Failed to decode camera frame: {e}"),
+            })?;
+        let (width, height) = image.dimensions();
+        Ok(DecodedFrame {
+            key: self.key,
+            width,
+            height,
+            image,
+        })
+    }
+}
+
+/// Stream of decoded camera frames read from a client, only available with the `image` feature.
+///
+/// Other messages read from the client are discarded, so this is meant for connections dedicated to
+/// camera streaming. Images need to be requested by sending a `CameraImageRequest`.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, camera::DecodedFrames, types::CameraImageRequest};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// client.try_write(CameraImageRequest { single: false, stream: true }).await?;
+/// let mut frames = DecodedFrames::new(&client);
+/// loop {
+///     let frame = frames.next().await?;
+///     println!("Frame of {}x{} from camera {}", frame.width, frame.height, frame.key);
+/// }
+/// # }
+/// ```
+#[cfg(feature = "image")]
+#[derive(Debug)]
+pub struct DecodedFrames<'a> {
+    client: &'a EspHomeClient,
+    assembler: FrameAssembler,
+}
+
+#[cfg(feature = "image")]
+impl<'a> DecodedFrames<'a> {
+    /// Creates a stream of decoded frames read from the given client.
+    #[must_use]
+    pub fn new(client: &'a EspHomeClient) -> Self {
+        Self {
+            client,
+            assembler: FrameAssembler::new(),
+        }
+    }
+
+    /// Waits for the next complete frame and decodes it.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if reading from the client fails, or if the frame is not a valid JPEG image.
+    pub async fn next(&mut self) -> Result<DecodedFrame, ClientError> {
+        loop {
+            if let EspHomeMessage::CameraImageResponse(chunk) = self.client.try_read().await? {
+                if let Some(frame) = self.assembler.push(&chunk) {
+                    return frame.decode();
+                }
+            }
+        }
+    }
+}
+
+/// Pending read of the next message from the client.
+#[cfg(feature = "stream")]
+type PendingRead<'a> =
+    Pin<Box<dyn Future<Output = Result<EspHomeMessage, ClientError>> + Send + 'a>>;
+
+/// Continuous stream of images of a camera, only available with the `stream` feature.
+///
+/// ESPHome stops streaming a few seconds after the last stream request, so the request is repeated
+/// in the background (every 4 seconds by default) for as long as the stream exists.
+/// Yields the (JPEG) data of every complete image. The stream ends when reading from the client fails,
+/// the error is available through [`CameraStream::take_error`].
+///
+/// Other messages read from the client are discarded, so this is meant for connections dedicated to
+/// camera streaming.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, camera::CameraStream};
+/// use futures_util::StreamExt as _;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// let mut images = CameraStream::new(&client, 1234);
+/// while let Some(image) = images.next().await {
+///     println!("Received image of {} bytes", image.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "stream")]
+#[allow(
+    clippy::module_name_repetitions,
+    reason = "Stream alone is ambiguous with the futures trait"
+)]
+pub struct CameraStream<'a> {
+    client: &'a EspHomeClient,
+    key: u32,
+    assembler: FrameAssembler,
+    refresh: JoinHandle<()>,
+    pending: Option<PendingRead<'a>>,
+    error: Option<ClientError>,
+}
+
+#[cfg(feature = "stream")]
+impl fmt::Debug for CameraStream<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CameraStream")
+            .field("key", &self.key)
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<'a> CameraStream<'a> {
+    /// Default interval at which the stream request is repeated.
+    pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_secs(4);
+
+    /// Starts streaming the camera with the given key.
+    ///
+    /// Needs to be called within a tokio runtime, which runs the task repeating the stream request.
+    #[must_use]
+    pub fn new(client: &'a EspHomeClient, key: u32) -> Self {
+        Self::with_refresh_interval(client, key, Self::DEFAULT_REFRESH_INTERVAL)
+    }
+
+    /// Starts streaming the camera with the given key, repeating the stream request at the given interval.
+    ///
+    /// Needs to be called within a tokio runtime, which runs the task repeating the stream request.
+    #[must_use]
+    pub fn with_refresh_interval(
+        client: &'a EspHomeClient,
+        key: u32,
+        refresh_interval: Duration,
+    ) -> Self {
+        let writer = client.write_stream();
+        let refresh = tokio::spawn(async move {
+            let mut ticks = interval(refresh_interval);
+            loop {
+                ticks.tick().await;
+                let request = CameraImageRequest {
+                    single: false,
+                    stream: true,
+                };
+                if let Err(e) = writer.try_write(request).await {
+                    tracing::warn!("Failed to request camera stream: {e}");
+                    return;
+                }
+            }
+        });
+        Self {
+            client,
+            key,
+            assembler: FrameAssembler::new(),
+            refresh,
+            pending: None,
+            error: None,
+        }
+    }
+
+    /// Takes the error which ended the stream.
+    pub const fn take_error(&mut self) -> Option<ClientError> {
+        self.error.take()
+    }
+}
+
+#[cfg(feature = "stream")]
+impl Stream for CameraStream<'_> {
+    type Item = Vec<u8>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.error.is_some() {
+            return Poll::Ready(None);
+        }
+        loop {
+            let client = self.client;
+            let pending = self
+                .pending
+                .get_or_insert_with(|| Box::pin(client.try_read()));
+            let result = match pending.as_mut().poll(cx) {
+                Poll::Ready(result) => result,
+                Poll::Pending => return Poll::Pending,
+            };
+            self.pending = None;
+            match result {
+                Ok(EspHomeMessage::CameraImageResponse(chunk)) if chunk.key == self.key => {
+                    if let Some(frame) = self.assembler.push(&chunk) {
+                        return Poll::Ready(Some(frame.data));
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    self.refresh.abort();
+                    self.error = Some(e);
+                    return Poll::Ready(None);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "stream")]
+impl Drop for CameraStream<'_> {
+    fn drop(&mut self) {
+        self.refresh.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(key: u32, data: &[u8], done: bool) -> CameraImageResponse {
+        CameraImageResponse {
+            key,
+            data: data.to_vec(),
+            done,
+            #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+            device_id: 0,
+        }
+    }
+
+    #[test]
+    fn test_assembler_reassembles_chunks() {
+        let mut assembler = FrameAssembler::new();
+        assert!(assembler.push(&chunk(1, &[1, 2], false)).is_none());
+        assert!(assembler.push(&chunk(1, &[3], false)).is_none());
+        let frame = assembler.push(&chunk(1, &[4], true)).unwrap();
+        assert_eq!(frame.key, 1);
+        assert_eq!(frame.data, vec![1, 2, 3, 4]);
+        assert!(assembler.buffers.is_empty());
+    }
+
+    #[test]
+    fn test_assembler_interleaved_cameras() {
+        let mut assembler = FrameAssembler::new();
+        assert!(assembler.push(&chunk(1, &[1], false)).is_none());
+        assert!(assembler.push(&chunk(2, &[9], false)).is_none());
+        let first = assembler.push(&chunk(1, &[2], true)).unwrap();
+        assert_eq!(first.data, vec![1, 2]);
+        let second = assembler.push(&chunk(2, &[8], true)).unwrap();
+        assert_eq!(second.data, vec![9, 8]);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_decode_invalid_frame() {
+        let frame = Frame {
+            key: 1,
+            data: vec![0, 1, 2],
+        };
+        frame.decode().unwrap_err();
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Link type used for the captured frames, `LINKTYPE_USER0` is reserved for private use.
+///
+/// In Wireshark the frames can be decoded by mapping `User 0 (DLT=147)` to a custom dissector.
+pub const LINK_TYPE: u16 = 147;
+
+const SECTION_HEADER_BLOCK: u32 = 0x0A0D_0D0A;
+const INTERFACE_DESCRIPTION_BLOCK: u32 = 0x0000_0001;
+const ENHANCED_PACKET_BLOCK: u32 = 0x0000_0006;
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+
+/// Direction of a captured frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Frame sent by the client to the device.
+    ToDevice,
+    /// Frame received by the client from the device.
+    FromDevice,
+}
+
+impl Direction {
+    const fn as_byte(self) -> u8 {
+        match self {
+            Self::ToDevice => 0,
+            Self::FromDevice => 1,
+        }
+    }
+}
+
+/// Capture sink which writes the decoded API frames to a pcapng file.
+///
+/// Frames are recorded after decryption, so noise encrypted sessions can be analyzed as well.
+/// Each packet uses the [`LINK_TYPE`] link type and contains:
+/// - 1 byte direction, `0` for frames sent to the device, `1` for frames received from the device
+/// - 2 bytes message type id (big endian)
+/// - the protobuf encoded message
+///
+/// The capture is shared by all clones, so a single file can be used for multiple connections.
+/// Failing to write a frame is logged, but doesn't affect the connection.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, capture::Capture};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = EspHomeClient::builder()
+///     .address("192.168.0.2:6053")
+///     .capture(Capture::create("esphome.pcapng")?)
+///     .connect()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Capture {
+    sink: Arc<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl fmt::Debug for Capture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Capture").finish_non_exhaustive()
+    }
+}
+
+impl Capture {
+    /// Creates (or truncates) a pcapng file at the given path.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the file can't be created or the headers can't be written.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+
+    /// Creates a capture writing to the given writer, the pcapng headers are written immediately.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the headers can't be written.
+    pub fn new<W: Write + Send + 'static>(mut writer: W) -> io::Result<Self> {
+        write_block(&mut writer, SECTION_HEADER_BLOCK, |body| {
+            body.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+            body.extend_from_slice(&1u16.to_le_bytes()); // major version
+            body.extend_from_slice(&0u16.to_le_bytes()); // minor version
+            body.extend_from_slice(&(-1i64).to_le_bytes()); // unspecified section length
+        })?;
+        write_block(&mut writer, INTERFACE_DESCRIPTION_BLOCK, |body| {
+            body.extend_from_slice(&LINK_TYPE.to_le_bytes());
+            body.extend_from_slice(&0u16.to_le_bytes()); // reserved
+            body.extend_from_slice(&0u32.to_le_bytes()); // no snap length
+        })?;
+        writer.flush()?;
+        Ok(Self {
+            sink: Arc::new(Mutex::new(Box::new(writer))),
+        })
+    }
+
+    /// Records a frame, the frame consists of the 2 byte type id, 2 byte length and the message.
+    pub(crate) fn record(&self, direction: Direction, frame: &[u8]) {
+        let result = match (frame.get(..2), frame.get(4..)) {
+            (Some(type_id), Some(message)) => self.try_record(
+                direction,
+                u16::from_be_bytes([type_id[0], type_id[1]]),
+                message,
+            ),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frame is too short",
+            )),
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to write frame to capture: {e}");
+        }
+    }
+
+    /// Records a message with the given type id.
+    pub(crate) fn record_message(&self, direction: Direction, type_id: u16, message: &[u8]) {
+        if let Err(e) = self.try_record(direction, type_id, message) {
+            tracing::warn!("Failed to write frame to capture: {e}");
+        }
+    }
+
+    fn try_record(&self, direction: Direction, type_id: u16, message: &[u8]) -> io::Result<()> {
+        let mut packet = Vec::with_capacity(message.len() + 3);
+        packet.push(direction.as_byte());
+        packet.extend_from_slice(&type_id.to_be_bytes());
+        packet.extend_from_slice(message);
+
+        let length = u32::try_from(packet.len())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| u64::try_from(d.as_micros()).unwrap_or(u64::MAX))
+            .unwrap_or_default();
+        let ts_high = u32::try_from(timestamp >> 32).unwrap_or_default();
+        let ts_low = u32::try_from(timestamp & u64::from(u32::MAX)).unwrap_or_default();
+
+        let mut sink = self
+            .sink
+            .lock()
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        write_block(&mut *sink, ENHANCED_PACKET_BLOCK, |body| {
+            body.extend_from_slice(&0u32.to_le_bytes()); // interface id
+            body.extend_from_slice(&ts_high.to_le_bytes());
+            body.extend_from_slice(&ts_low.to_le_bytes());
+            body.extend_from_slice(&length.to_le_bytes()); // captured length
+            body.extend_from_slice(&length.to_le_bytes()); // original length
+            body.extend_from_slice(&packet);
+        })?;
+        sink.flush()
+    }
+}
+
+/// Writes a pcapng block, padding the body to 32 bits.
+fn write_block<W: Write + ?Sized>(
+    writer: &mut W,
+    block_type: u32,
+    fill: impl FnOnce(&mut Vec<u8>),
+) -> io::Result<()> {
+    let mut body = Vec::new();
+    fill(&mut body);
+    body.resize(body.len().next_multiple_of(4), 0);
+    let total_length = u32::try_from(body.len() + 12)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+
+    let mut block = Vec::with_capacity(body.len() + 12);
+    block.extend_from_slice(&block_type.to_le_bytes());
+    block.extend_from_slice(&total_length.to_le_bytes());
+    block.extend_from_slice(&body);
+    block.extend_from_slice(&total_length.to_le_bytes());
+    writer.write_all(&block)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0
+                .lock()
+                .map_err(|e| io::Error::other(e.to_string()))?
+                .extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn u32_at(data: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_capture_headers() {
+        let buffer = SharedBuffer::default();
+        Capture::new(buffer.clone()).unwrap();
+        let data = buffer.0.lock().unwrap().clone();
+        assert_eq!(data.len(), 28 + 20);
+        assert_eq!(u32_at(&data, 0), SECTION_HEADER_BLOCK);
+        assert_eq!(u32_at(&data, 8), BYTE_ORDER_MAGIC);
+        assert_eq!(u32_at(&data, 28), INTERFACE_DESCRIPTION_BLOCK);
+        assert_eq!(u32_at(&data, 36) & 0xFFFF, u32::from(LINK_TYPE));
+    }
+
+    #[test]
+    fn test_capture_record_frame() {
+        let buffer = SharedBuffer::default();
+        let capture = Capture::new(buffer.clone()).unwrap();
+        capture.record(Direction::FromDevice, &[0, 7, 0, 2, 0xAA, 0xBB]);
+
+        let data = buffer.0.lock().unwrap().clone();
+        let block = &data[48..];
+        assert_eq!(u32_at(block, 0), ENHANCED_PACKET_BLOCK);
+        // 32 bytes of block fields, 5 bytes packet padded to 8
+        assert_eq!(u32_at(block, 4), 40);
+        assert_eq!(u32_at(block, 20), 5);
+        assert_eq!(&block[28..33], &[1, 0, 7, 0xAA, 0xBB]);
+        assert_eq!(u32_at(block, 36), 40);
+    }
+
+    #[test]
+    fn test_capture_ignores_short_frame() {
+        let buffer = SharedBuffer::default();
+        let capture = Capture::new(buffer.clone()).unwrap();
+        capture.record(Direction::ToDevice, &[0, 7]);
+        assert_eq!(buffer.0.lock().unwrap().len(), 48);
+    }
+}
@@ -4,30 +4,143 @@
 /// ESPHome protocol messages. It can optionally handle ping requests automatically to keep the connection alive.
 ///
 /// Use [`EspHomeTcpStream::builder`] to create a builder for establishing a connection.
+mod address;
+mod batch;
+mod bluetooth;
+mod buffer_pool;
+#[cfg(feature = "codec")]
+pub mod codec;
+mod config;
+mod dispatch;
+mod entities;
+mod lifecycle;
+mod maintenance;
+mod message_writer;
 mod noise;
 mod plain;
+mod reconnect;
+mod registry;
+mod runtime;
+#[cfg(feature = "runtime-tokio")]
+pub mod server;
+#[cfg(feature = "sink")]
+mod sink;
+mod snapshot;
+mod statistics;
 
 mod stream_reader;
 mod stream_writer;
-use std::{fmt::Debug, time::Duration};
+#[cfg(feature = "supervisor")]
+mod supervisor;
+#[cfg(feature = "test-util")]
+pub mod test_util;
+#[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+mod update;
+use std::{
+    collections::VecDeque,
+    fmt::{self, Debug},
+    fs,
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::{
+        Arc, Mutex as SyncMutex, PoisonError,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
 
-use stream_reader::StreamReader;
+pub use address::ServerAddress;
+use address::SocketOptions;
+pub use batch::WriteBatch;
+pub use buffer_pool::BufferPool;
+pub use config::{ClientConfig, KeepaliveConfig, ReconnectConfig};
+use dispatch::Handlers;
+pub use entities::Entities;
+pub use lifecycle::LifecycleEvent;
+use lifecycle::{Lifecycle, LifecycleHooks};
+use maintenance::MaintenanceAction;
+use message_writer::MessageWriter;
+pub use reconnect::{BackoffPolicy, ConnectionEvent, EspHomeConnectionManager};
+use registry::EntityRegistry;
+pub use registry::{EntityInfo, EntityKind};
+use runtime::Clock;
+#[cfg(feature = "runtime-tokio")]
+pub use runtime::TokioTimer;
+pub use runtime::{Sleep, Timer, Transport};
+pub use statistics::ClientStatistics;
+use statistics::Statistics;
+pub use stream_reader::ReadBufferPolicy;
+use stream_reader::{Frame, StreamReader};
 use stream_writer::StreamWriter;
-use tokio::time::timeout;
+#[cfg(feature = "supervisor")]
+pub use supervisor::{DeviceHandle, DeviceId, Supervisor, SupervisorEvent};
+#[cfg(feature = "runtime-tokio")]
+use tokio::net::TcpStream;
+use tokio::sync::{Mutex, broadcast, watch};
+use tracing::{Instrument as _, Span, field};
 
+#[cfg(feature = "discovery")]
+use crate::discovery::DeviceInfo;
+#[cfg(feature = "metrics")]
+use crate::metrics;
+#[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+use crate::proto::{
+    ListEntitiesUpdateResponse, NoiseEncryptionSetKeyRequest, NoiseEncryptionSetKeyResponse,
+    UpdateStateResponse,
+};
 use crate::{
     API_VERSION,
-    error::{ClientError, ProtocolError},
-    proto::{DisconnectRequest, EspHomeMessage, HelloRequest, PingResponse},
+    capture::Capture,
+    error::{ClientError, ConnectionError, ProtocolError},
+    home_assistant::{StateSubscription, StateSubscriptions},
+    proto::{
+        BluetoothDeviceConnectionResponse, BluetoothGattService, ButtonCommandRequest,
+        DeviceInfoRequest, DeviceInfoResponse, DisconnectRequest, EspHomeMessage, HelloRequest,
+        HelloResponse, HomeAssistantStateResponse, MessageType, PingRequest, PingResponse,
+        SubscribeHomeAssistantStatesRequest, SubscribeStatesRequest,
+    },
+    states::{EntityState, StateCache, StateStream},
 };
 
+/// Maximum number of messages buffered while waiting for a response, the oldest are dropped beyond it.
+const MAX_PENDING_MESSAGES: usize = 1024;
+
 type StreamPair = (StreamReader, StreamWriter);
+/// Pre-connected stream of a builder, shared between its clones and taken by the first connection.
+type StreamSlot = Arc<SyncMutex<Option<Connected>>>;
+
+/// Connection established before the client connects, see [`EspHomeClientBuilder::with_transport`].
+#[derive(Debug)]
+enum Connected {
+    /// TCP connection, the socket options of the builder are applied to it.
+    #[cfg(feature = "runtime-tokio")]
+    Tcp(TcpStream),
+    Transport(Box<dyn Transport>),
+}
 
 /// Client for sending and receiving messages to an ESPHome API server.
+///
+/// All operations take `&self`, so the client can be wrapped in an [`Arc`](std::sync::Arc) and shared
+/// between tasks. Reads are serialized internally, writes can be issued concurrently.
 #[derive(Debug)]
 pub struct EspHomeClient {
-    streams: StreamPair,
+    reader: Mutex<StreamReader>,
+    writer: MessageWriter,
     handle_ping: bool,
+    unanswered_pings: AtomicUsize,
+    broadcast: broadcast::Sender<EspHomeMessage>,
+    handlers: Handlers,
+    pending: SyncMutex<VecDeque<EspHomeMessage>>,
+    request_timeout: Duration,
+    idle_timeout: Option<Duration>,
+    last_received: SyncMutex<Instant>,
+    clock: Clock,
+    statistics: Arc<Statistics>,
+    state_cache: Option<StateCache>,
+    entity_registry: Option<EntityRegistry>,
+    home_assistant_states: StateSubscriptions,
+    server_name: Option<String>,
+    server_mac: Option<String>,
 }
 
 impl EspHomeClient {
@@ -37,46 +150,1038 @@ impl EspHomeClient {
         EspHomeClientBuilder::new()
     }
 
-    /// Sends a message to the ESPHome device.
+    /// Sends a message to the ESPHome device.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails for example due to a disconnected stream.
+    pub async fn try_write<M>(&self, message: M) -> Result<(), ClientError>
+    where
+        M: Into<EspHomeMessage> + Debug,
+    {
+        let message: EspHomeMessage = message.into();
+        self.writer.span().in_scope(|| {
+            tracing::debug!("Send: {message}");
+            tracing::trace!("Send: {message:?}");
+        });
+        self.writer.write(message).await
+    }
+
+    /// Sends several messages to the ESPHome device with a single write on the connection.
+    ///
+    /// The messages are encoded up front and written as one contiguous buffer, in order, which reduces
+    /// the overhead of a burst of messages, like pushing many Home Assistant states after a reconnect.
+    /// Nothing is written if any of the messages isn't supported by the device.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::{EspHomeClient, types::HomeAssistantStateResponse};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+    /// let states = [("sun.sun", "above_horizon"), ("person.anna", "home")];
+    /// client
+    ///     .try_write_many(states.into_iter().map(|(entity_id, state)| HomeAssistantStateResponse {
+    ///         entity_id: entity_id.to_owned(),
+    ///         state: state.to_owned(),
+    ///         ..Default::default()
+    ///     }))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails for example due to a disconnected stream.
+    pub async fn try_write_many<I>(&self, messages: I) -> Result<(), ClientError>
+    where
+        I: IntoIterator,
+        I::Item: Into<EspHomeMessage>,
+    {
+        let messages: Vec<EspHomeMessage> = messages
+            .into_iter()
+            .map(|message| {
+                let message: EspHomeMessage = message.into();
+                self.writer.span().in_scope(|| {
+                    tracing::debug!("Send: {message}");
+                    tracing::trace!("Send: {message:?}");
+                });
+                message
+            })
+            .collect();
+        self.writer.write_messages(messages).await
+    }
+
+    /// Reads the next message from the stream.
+    ///
+    /// It will automatically handle ping requests if ping handling is enabled.
+    /// When called from multiple tasks at once, each message is delivered to exactly one caller.
+    /// Messages buffered by [`EspHomeClient::send_and_wait`] are returned first.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe, e.g. as a branch of `tokio::select!`. Bytes of a partially received
+    /// frame are kept for the next read, and a message is only taken from the stream once it is
+    /// returned. A ping request answered when the read is cancelled is answered by the next read.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the read operation fails, for example due to a disconnected stream
+    pub async fn try_read(&self) -> Result<EspHomeMessage, ClientError> {
+        let message = match self.take_pending(|_| true) {
+            Some(message) => message,
+            None => self.read_message().await?,
+        };
+        self.publish(&message);
+        Ok(message)
+    }
+
+    /// Returns the next message if it was already received, without waiting for the stream.
+    ///
+    /// Returns buffered messages and complete frames already received from the device, or `None`
+    /// when no complete message is available or another task is reading. Useful to drain a burst of
+    /// messages, or to integrate the client into a custom event loop. Only answering a ping request
+    /// is awaited, if ping handling is enabled.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe, like [`EspHomeClient::try_read`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the read operation fails, for example due to a disconnected stream.
+    pub async fn try_read_now(&self) -> Result<Option<EspHomeMessage>, ClientError> {
+        if let Some(message) = self.take_pending(|_| true) {
+            self.publish(&message);
+            return Ok(Some(message));
+        }
+        async {
+            loop {
+                self.answer_pings().await?;
+                let Ok(mut reader) = self.reader.try_lock() else {
+                    return Ok(None);
+                };
+                let payload = reader
+                    .try_read_next_message()
+                    .inspect_err(|e| self.connection_lost(e))?;
+                drop(reader);
+                let Some(payload) = payload else {
+                    return Ok(None);
+                };
+                self.mark_received();
+                if let Some(message) = self.handle_payload(&payload)? {
+                    self.publish(&message);
+                    return Ok(Some(message));
+                }
+            }
+        }
+        .instrument(self.writer.span().clone())
+        .await
+    }
+
+    /// Reads the next message from the stream, waiting at most the given duration.
+    ///
+    /// Behaves like [`EspHomeClient::try_read`], a partially received message is kept for the next read.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::Timeout`] if no message is received within the timeout, or an error
+    /// if the read operation fails, for example due to a disconnected stream.
+    pub async fn try_read_timeout(
+        &self,
+        duration: Duration,
+    ) -> Result<EspHomeMessage, ClientError> {
+        self.clock
+            .timeout(duration, self.try_read())
+            .await
+            .map_err(|_e| timeout_error(duration))?
+    }
+
+    /// Returns the name the device announced during the noise handshake.
+    ///
+    /// Only available for encrypted connections, as plain connections don't have a handshake.
+    #[must_use]
+    pub fn server_name(&self) -> Option<&str> {
+        self.server_name.as_deref()
+    }
+
+    /// Returns the MAC address the device announced during the noise handshake, as lower case hex digits
+    /// without separators, e.g. `aabbccddeeff`.
+    ///
+    /// Only available for encrypted connections, as plain connections don't have a handshake.
+    #[must_use]
+    pub fn server_mac(&self) -> Option<&str> {
+        self.server_mac.as_deref()
+    }
+
+    /// Returns the API version reported by the device in the `HelloResponse`, as (major, minor).
+    ///
+    /// Only available if connection setup is enabled.
+    #[must_use]
+    pub fn api_version(&self) -> Option<(u32, u32)> {
+        self.writer
+            .hello()
+            .get()
+            .map(|hello| (hello.api_version_major, hello.api_version_minor))
+    }
+
+    /// Returns the server info reported by the device in the `HelloResponse`, e.g. `livingroom (esphome v2025.8.0)`.
+    ///
+    /// Only available if connection setup is enabled.
+    #[must_use]
+    pub fn server_info(&self) -> Option<&str> {
+        self.writer
+            .hello()
+            .get()
+            .map(|hello| hello.server_info.as_str())
+    }
+
+    /// Returns the `HelloResponse` the device sent during connection setup.
+    ///
+    /// Only available if connection setup is enabled.
+    #[must_use]
+    pub fn hello_response(&self) -> Option<&HelloResponse> {
+        self.writer.hello().get()
+    }
+
+    /// Sends a message and waits for the response of type `R`.
+    ///
+    /// Other messages received while waiting are buffered, and returned by subsequent calls to
+    /// [`EspHomeClient::try_read`]. Up to 1024 messages are buffered, beyond that the oldest are
+    /// dropped. Waiting is limited by the request timeout, see [`EspHomeClientBuilder::request_timeout`].
+    ///
+    /// The response is matched by its type only, as the API has no request IDs. Concurrent calls
+    /// waiting for the same response type may take each other's response, so these should be
+    /// serialized by the caller.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::{EspHomeClient, types::{DeviceInfoRequest, DeviceInfoResponse}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+    /// let info = client.send_and_wait::<DeviceInfoResponse>(DeviceInfoRequest {}).await?;
+    /// println!("Connected to {}", info.name);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::Timeout`] if the response is not received within the request timeout,
+    /// or an error if the communication with the device fails.
+    pub async fn send_and_wait<R: MessageType>(
+        &self,
+        request: impl Into<EspHomeMessage> + Debug,
+    ) -> Result<R, ClientError> {
+        self.try_write(request).await?;
+        let response = self
+            .with_request_timeout(self.wait_for(|message| R::from_message(message).is_some()))
+            .await?;
+        self.publish(&response);
+        R::try_from(response).map_err(|message| ClientError::InvalidInternalState {
+            reason: format!("Unexpected response type: {message:?}"),
+        })
+    }
+
+    /// Sends a ping to the device, and returns the round-trip time.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::Timeout`] if the device doesn't respond within the request timeout,
+    /// or an error if the communication with the device fails.
+    pub async fn ping(&self) -> Result<Duration, ClientError> {
+        let start = Instant::now();
+        self.send_and_wait::<PingResponse>(PingRequest {}).await?;
+        Ok(start.elapsed())
+    }
+
+    /// Lists all entities of the device.
+    ///
+    /// Sends a `ListEntitiesRequest` and collects the entity descriptions until the device reports the
+    /// list is complete. Other messages received in the meantime are buffered, and returned by subsequent
+    /// calls to [`EspHomeClient::try_read`].
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::Timeout`] if the list is not complete within the request timeout,
+    /// or an error if the communication with the device fails.
+    pub async fn list_entities(&self) -> Result<Entities, ClientError> {
+        entities::list(self).await
+    }
+
+    /// Requests a single image from the camera with the given key, and returns the (JPEG) image data.
+    ///
+    /// The image is sent in multiple chunks, which are reassembled. Note that the device sends the image of
+    /// all cameras, chunks of other cameras are kept for other readers of the client.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::Timeout`] if the image isn't received within the request timeout,
+    /// or an error if the communication with the device fails.
+    pub async fn capture_image(&self, key: u32) -> Result<Vec<u8>, ClientError> {
+        snapshot::capture(self, key).await
+    }
+
+    /// Subscribes to the entity states, and returns a stream of the state updates.
+    ///
+    /// The device sends the current state of all entities, followed by updates when a state changes.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::{EspHomeClient, states::EntityState};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+    /// let mut states = client.subscribe_states().await?;
+    /// loop {
+    ///     match states.next().await? {
+    ///         EntityState::Sensor(state) => println!("Sensor {}: {}", state.key, state.state),
+    ///         other => println!("Entity {} changed", other.key()),
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if sending the `SubscribeStatesRequest` fails.
+    pub async fn subscribe_states(&self) -> Result<StateStream<'_>, ClientError> {
+        self.try_write(SubscribeStatesRequest {}).await?;
+        Ok(StateStream::new(self))
+    }
+
+    /// Subscribes to the entity states, and returns a stream of the state updates of the given entities.
+    ///
+    /// Entities are given by the ID of their device and their key, see [`EntityState::id`].
+    ///
+    /// State updates of other entities are dropped, useful on devices with many entities of which only
+    /// a few are of interest. The dropped updates are still delivered to subscribers of
+    /// [`EspHomeClient::subscribe`] and tracked by the state cache.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::EspHomeClient;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+    /// let mut states = client.subscribe_states_filtered(&[(0, 1234), (0, 5678)]).await?;
+    /// loop {
+    ///     println!("{:?}", states.next().await?);
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if sending the `SubscribeStatesRequest` fails.
+    pub async fn subscribe_states_filtered(
+        &self,
+        entities: &[(u32, u32)],
+    ) -> Result<StateStream<'_>, ClientError> {
+        Ok(self.subscribe_states().await?.filter_keys(entities))
+    }
+
+    /// Watches the state of the entity with the given device ID and key, see [`EntityState::id`].
+    ///
+    /// The receiver holds `None` until the first state of the entity is received. States are only
+    /// tracked while messages are read from the client, e.g. through [`EspHomeClient::run`] or a
+    /// [`StateStream`], after subscribing to the states.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::EspHomeClient;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = EspHomeClient::builder()
+    ///     .address("192.168.0.2:6053")
+    ///     .with_state_cache()
+    ///     .connect()
+    ///     .await?;
+    /// let mut temperature = client.watch(0, 1234)?;
+    /// let mut states = client.subscribe_states().await?;
+    /// tokio::spawn(async move {
+    ///     while temperature.changed().await.is_ok() {
+    ///         println!("Temperature: {:?}", *temperature.borrow_and_update());
+    ///     }
+    /// });
+    /// loop {
+    ///     states.next().await?;
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::Configuration`] if the state cache is not enabled with
+    /// [`EspHomeClientBuilder::with_state_cache`].
+    pub fn watch(
+        &self,
+        device_id: u32,
+        key: u32,
+    ) -> Result<watch::Receiver<Option<EntityState>>, ClientError> {
+        self.state_cache
+            .as_ref()
+            .map(|state_cache| state_cache.watch(device_id, key))
+            .ok_or_else(|| ClientError::Configuration {
+                message: "State cache is not enabled".into(),
+            })
+    }
+
+    /// Gets the statistics of the connection, like the number of messages and bytes sent and received.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::EspHomeClient;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+    /// let statistics = client.statistics();
+    /// println!("Received {} messages in {:?}", statistics.messages_received, statistics.uptime);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn statistics(&self) -> ClientStatistics {
+        self.statistics.snapshot()
+    }
+
+    /// Gets the entity with the given device ID and key, to label the state messages of the entity.
+    ///
+    /// Entities are recorded from the `ListEntities*Response` messages read from the client, e.g. by
+    /// [`EspHomeClient::list_entities`]. Returns `None` if the entity is unknown, or if the registry is
+    /// not enabled with [`EspHomeClientBuilder::with_entity_registry`].
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::{EspHomeClient, states::EntityState};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = EspHomeClient::builder()
+    ///     .address("192.168.0.2:6053")
+    ///     .with_entity_registry()
+    ///     .connect()
+    ///     .await?;
+    /// client.list_entities().await?;
+    /// let mut states = client.subscribe_states().await?;
+    /// loop {
+    ///     let state = states.next().await?;
+    ///     if let Some(entity) = client.entity(state.device_id(), state.key()) {
+    ///         println!("{} changed: {state:?}", entity.name);
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn entity(&self, device_id: u32, key: u32) -> Option<EntityInfo> {
+        self.entity_registry.as_ref()?.get(device_id, key)
+    }
+
+    /// Gets the entity with the given object id, e.g. `kitchen_temperature`.
+    ///
+    /// Returns `None` if the entity is unknown, or if the registry is not enabled, see
+    /// [`EspHomeClient::entity`].
+    #[must_use]
+    pub fn entity_by_object_id(&self, object_id: &str) -> Option<EntityInfo> {
+        self.entity_registry.as_ref()?.by_object_id(object_id)
+    }
+
+    /// Reads messages until one matches the predicate, buffering the others.
+    ///
+    /// The matching message is returned without publishing it to subscribers.
+    async fn wait_for(
+        &self,
+        mut predicate: impl FnMut(&EspHomeMessage) -> bool,
+    ) -> Result<EspHomeMessage, ClientError> {
+        loop {
+            // Check the buffer first, another task may have read the message
+            if let Some(message) = self.take_pending(&mut predicate) {
+                return Ok(message);
+            }
+            let message = self.read_message().await?;
+            if predicate(&message) {
+                return Ok(message);
+            }
+            self.push_pending(message);
+        }
+    }
+
+    /// Buffers a message read while waiting for another one.
+    ///
+    /// The oldest buffered message is dropped once [`MAX_PENDING_MESSAGES`] are buffered, so a
+    /// device flooding the connection can't grow the buffer while nobody reads it.
+    fn push_pending(&self, message: EspHomeMessage) {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        if pending.len() >= MAX_PENDING_MESSAGES {
+            if let Some(dropped) = pending.pop_front() {
+                tracing::warn!("Too many buffered messages, dropping: {dropped}");
+            }
+        }
+        pending.push_back(message);
+    }
+
+    /// Limits the duration of a request to the request timeout.
+    async fn with_request_timeout<T>(
+        &self,
+        request: impl Future<Output = Result<T, ClientError>>,
+    ) -> Result<T, ClientError> {
+        self.clock
+            .timeout(self.request_timeout, request)
+            .await
+            .map_err(|_e| ClientError::Timeout {
+                timeout_ms: self.request_timeout.as_millis(),
+            })?
+    }
+
+    /// Reads and decodes the next message from the stream, handling ping requests.
+    async fn read_message(&self) -> Result<EspHomeMessage, ClientError> {
+        async {
+            loop {
+                self.answer_pings().await?;
+                let payload = self
+                    .read_payload()
+                    .await
+                    .inspect_err(|e| self.connection_lost(e))?;
+                // Once the payload is taken from the stream, the message is returned without awaiting,
+                // so it can't be lost by cancelling the read
+                if let Some(message) = self.handle_payload(&payload)? {
+                    return Ok(message);
+                }
+            }
+        }
+        .instrument(self.writer.span().clone())
+        .await
+    }
+
+    /// Decodes a received payload, returns `None` for ping requests answered by the client.
+    ///
+    /// Ping requests are answered by [`EspHomeClient::answer_pings`] before the next payload is read.
+    /// With API version negotiation, messages that can't be decoded are skipped instead of failing.
+    fn handle_payload(&self, payload: &Frame) -> Result<Option<EspHomeMessage>, ClientError> {
+        #[cfg(feature = "metrics")]
+        let started = Instant::now();
+        let decoded = payload.decode();
+        #[cfg(feature = "metrics")]
+        metrics::decoded(started.elapsed());
+        let message = match decoded {
+            Ok(message) => message,
+            Err(e) => {
+                self.statistics.decode_error();
+                return Err(ProtocolError::ValidationFailed {
+                    reason: format!("Failed to decode EspHomeMessage: {e}"),
+                }
+                .into());
+            }
+        };
+        tracing::debug!("Receive: {message}");
+        tracing::trace!("Receive: {message:?}");
+        self.statistics.message_received();
+        #[cfg(feature = "metrics")]
+        metrics::message_received(&message);
+        if let Some(state_cache) = &self.state_cache {
+            state_cache.update(&message);
+        }
+        if let Some(entity_registry) = &self.entity_registry {
+            entity_registry.update(&message);
+        }
+        self.home_assistant_states.update(&message);
+        match message {
+            EspHomeMessage::PingRequest(_) if self.handle_ping => {
+                self.unanswered_pings.fetch_add(1, Ordering::AcqRel);
+                Ok(None)
+            }
+            msg => Ok(Some(msg)),
+        }
+    }
+
+    /// Answers the received ping requests.
+    ///
+    /// A ping request is only marked as answered once the response is written, so a cancelled read
+    /// leaves it for the next read.
+    async fn answer_pings(&self) -> Result<(), ClientError> {
+        while self.unanswered_pings.load(Ordering::Acquire) > 0 {
+            self.try_write(PingResponse {}).await?;
+            // Concurrent readers may answer the same ping request, which is harmless
+            if self
+                .unanswered_pings
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                    count.checked_sub(1)
+                })
+                .is_ok()
+            {
+                self.statistics.ping_answered();
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the next payload, failing when the idle timeout elapses since the last received message.
+    async fn read_payload(&self) -> Result<Frame, ClientError> {
+        let read = async { self.reader.lock().await.read_next_message().await };
+        let payload = match self.idle_timeout {
+            Some(idle_timeout) => {
+                let last_received = *self
+                    .last_received
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner);
+                self.clock
+                    .timeout_at(last_received + idle_timeout, read)
+                    .await
+                    .map_err(|_e| ClientError::IdleTimeout {
+                        timeout_ms: idle_timeout.as_millis(),
+                    })??
+            }
+            None => read.await?,
+        };
+        self.mark_received();
+        Ok(payload)
+    }
+
+    /// Reports the disconnect to the lifecycle hooks, if the error means the connection is lost.
+    fn connection_lost(&self, error: &ClientError) {
+        self.writer.connection_lost(error);
+    }
+
+    fn mark_received(&self) {
+        *self
+            .last_received
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Instant::now();
+    }
+
+    /// Removes the first buffered message matching the predicate.
+    fn take_pending(
+        &self,
+        predicate: impl FnMut(&EspHomeMessage) -> bool,
+    ) -> Option<EspHomeMessage> {
+        let mut pending = self.pending.lock().unwrap_or_else(PoisonError::into_inner);
+        let index = pending.iter().position(predicate)?;
+        pending.remove(index)
+    }
+
+    /// Sends the message to all subscribers.
+    fn publish(&self, message: &EspHomeMessage) {
+        if self.broadcast.receiver_count() > 0 {
+            // Sending only fails if all receivers were dropped in the meantime
+            let _ignored = self.broadcast.send(message.clone());
+        }
+    }
+
+    /// Subscribes to the incoming messages, so multiple tasks can consume the same message stream.
+    ///
+    /// Every message returned by [`EspHomeClient::try_read`] is also sent to all subscribers, so a
+    /// task needs to keep reading from the client for the subscribers to receive messages.
+    /// Only messages read after subscribing are received. A subscriber which falls more than the
+    /// buffer size (see [`EspHomeClientBuilder::broadcast_capacity`]) behind, skips the oldest messages
+    /// and receives a [`broadcast::error::RecvError::Lagged`] error.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::{EspHomeClient, types::EspHomeMessage};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+    /// let mut logs = client.subscribe();
+    /// tokio::spawn(async move {
+    ///     while let Ok(message) = logs.recv().await {
+    ///         if let EspHomeMessage::SubscribeLogsResponse(log) = message {
+    ///             println!("Log: {log:?}");
+    ///         }
+    ///     }
+    /// });
+    /// loop {
+    ///     client.try_read().await?;
+    /// }
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn subscribe(&self) -> broadcast::Receiver<EspHomeMessage> {
+        self.broadcast.subscribe()
+    }
+
+    /// Registers a callback for incoming messages of type `T`, called by [`EspHomeClient::run`].
+    ///
+    /// Multiple callbacks can be registered for the same type, they are called in order of registration.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::{EspHomeClient, types::{SensorStateResponse, SubscribeStatesRequest}};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+    /// client.on::<SensorStateResponse>(|state| println!("Sensor {}: {}", state.key, state.state));
+    /// client.try_write(SubscribeStatesRequest {}).await?;
+    /// client.run().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on<T: MessageType>(&self, handler: impl Fn(&T) + Send + Sync + 'static) {
+        self.handlers.register(handler);
+    }
+
+    /// Reads messages and dispatches them to the callbacks registered with [`EspHomeClient::on`].
+    ///
+    /// Messages without a registered callback are discarded. Only returns when reading fails.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the read operation fails, for example due to a disconnected stream
+    pub async fn run(&self) -> Result<(), ClientError> {
+        loop {
+            let message = self.try_read().await?;
+            if !self.handlers.dispatch(&message) {
+                self.writer.span().in_scope(|| {
+                    tracing::trace!("No handler registered for message: {message:?}");
+                });
+            }
+        }
+    }
+
+    /// Subscribes to the Home Assistant states used by the device, e.g. by `homeassistant` platform sensors.
+    ///
+    /// The device responds with a `SubscribeHomeAssistantStateResponse` per requested state. These are
+    /// registered while messages are read from the client, see [`EspHomeClient::requested_home_assistant_states`].
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::{EspHomeClient, types::EspHomeMessage};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+    /// client.subscribe_home_assistant_states().await?;
+    /// loop {
+    ///     if let EspHomeMessage::SubscribeHomeAssistantStateResponse(request) = client.try_read().await? {
+    ///         let state = "on"; // Look up the state of request.entity_id
+    ///         client
+    ///             .send_home_assistant_state(&request.entity_id, &request.attribute, state)
+    ///             .await?;
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails, for example due to a disconnected stream
+    pub async fn subscribe_home_assistant_states(&self) -> Result<(), ClientError> {
+        self.try_write(SubscribeHomeAssistantStatesRequest {}).await
+    }
+
+    /// Returns the Home Assistant states requested by the device so far.
+    ///
+    /// States requested only once are removed after they are sent with
+    /// [`EspHomeClient::send_home_assistant_state`].
+    #[must_use]
+    pub fn requested_home_assistant_states(&self) -> Vec<StateSubscription> {
+        self.home_assistant_states.requested()
+    }
+
+    /// Sends the state of a Home Assistant entity to the device.
+    ///
+    /// Use an empty `attribute` to send the state of the entity itself.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails, for example due to a disconnected stream
+    pub async fn send_home_assistant_state(
+        &self,
+        entity_id: &str,
+        attribute: &str,
+        state: impl Into<String>,
+    ) -> Result<(), ClientError> {
+        self.try_write(HomeAssistantStateResponse {
+            entity_id: entity_id.to_owned(),
+            state: state.into(),
+            attribute: attribute.to_owned(),
+        })
+        .await?;
+        self.home_assistant_states.provided(entity_id, attribute);
+        Ok(())
+    }
+
+    /// Presses the button with the given key.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails, for example due to a disconnected stream
+    pub async fn press(&self, key: u32) -> Result<(), ClientError> {
+        self.try_write(ButtonCommandRequest {
+            key,
+            #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+            device_id: 0,
+        })
+        .await
+    }
+
+    #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+    /// Presses the button with the given key, belonging to the (sub) device with the given id.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails, for example due to a disconnected stream
+    pub async fn press_on_device(&self, key: u32, device_id: u32) -> Result<(), ClientError> {
+        self.try_write(ButtonCommandRequest { key, device_id })
+            .await
+    }
+
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// Installs the update of the given update entity, and waits until the installation is completed.
+    ///
+    /// Subscribes to the entity states, and returns the state of the entity once it reports the latest version
+    /// is installed. Installing can take several minutes, so choose a generous timeout.
+    ///
+    /// While waiting, the received messages, including the states of all entities, are published to the
+    /// subscribers, see [`EspHomeClient::subscribe`], but not buffered for [`EspHomeClient::try_read`].
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::Timeout`] if the installation doesn't complete within the timeout, or
+    /// an error if the communication with the device fails, which is also the case when the device
+    /// restarts to complete the installation.
+    pub async fn install_update(
+        &self,
+        info: &ListEntitiesUpdateResponse,
+        timeout: Duration,
+    ) -> Result<UpdateStateResponse, ClientError> {
+        update::install(self, info, timeout).await
+    }
+
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// Stores a new 32-byte base64-encoded encryption key on the device.
+    ///
+    /// The current connection is not affected, the device expects the new key for new connections. Connect
+    /// again with the new key, see [`EspHomeClientBuilder::key`], to verify it is applied. Note that a key set
+    /// in the device configuration takes precedence after the device is flashed again.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::EspHomeClient;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let new_key = "px7tsbK3C7bpXHr2OevEV2ZMg/FrNBw2+O2pNPbedtA=";
+    /// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+    /// client.set_encryption_key(new_key).await?;
+    /// client.close().await?;
+    ///
+    /// let client = EspHomeClient::builder()
+    ///     .address("192.168.0.2:6053")
+    ///     .key(new_key)
+    ///     .connect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::Configuration`] if the key is not a valid 32-byte base64-encoded key,
+    /// [`ClientError::UnsupportedOperation`] if the device rejects the key, or an error if the communication
+    /// with the device fails.
+    pub async fn set_encryption_key(&self, key: &str) -> Result<(), ClientError> {
+        let key = noise::decode_key(key).map_err(|e| ClientError::Configuration {
+            message: e.to_string(),
+        })?;
+        let response = self
+            .send_and_wait::<NoiseEncryptionSetKeyResponse>(NoiseEncryptionSetKeyRequest {
+                key: key.to_vec(),
+            })
+            .await?;
+        if !response.success {
+            return Err(ClientError::UnsupportedOperation {
+                reason: "device rejected the encryption key".to_owned(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Restarts the device, by pressing its restart button (or turning on its restart switch).
+    ///
+    /// The entity is located by listing the entities of the device, see [`EspHomeClient::list_entities`].
+    /// Buttons match the `restart` device class or an object id ending in `restart`, switches only
+    /// match the object id `restart`.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::EntityNotFound`] if the device exposes no restart entity,
+    /// or an error if the communication with the device fails.
+    pub async fn restart(&self) -> Result<(), ClientError> {
+        maintenance::trigger(self, MaintenanceAction::Restart).await
+    }
+
+    /// Restarts the device in safe mode, by pressing its safe mode button (or turning on its safe mode switch).
+    ///
+    /// The entity is located by listing the entities of the device, see [`EspHomeClient::list_entities`].
+    /// Buttons match an object id ending in `safe_mode` or `safe_mode_boot`, switches only match the
+    /// object id `safe_mode`.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::EntityNotFound`] if the device exposes no safe mode entity,
+    /// or an error if the communication with the device fails.
+    pub async fn safe_mode(&self) -> Result<(), ClientError> {
+        maintenance::trigger(self, MaintenanceAction::SafeMode).await
+    }
+
+    /// Connects the Bluetooth proxy of the device to the Bluetooth device with the given address.
+    ///
+    /// The address type (0 for public, 1 for random addresses) is reported in the advertisements of the
+    /// device. With `cache`, the proxy caches the GATT services of the device, which requires a proxy
+    /// announcing the remote caching feature (`0x04`) in the `bluetooth_proxy_feature_flags` of its
+    /// `DeviceInfoResponse`. Returns the connection state reported by the proxy, which includes the
+    /// negotiated MTU.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::EspHomeClient;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+    /// let address = 0xA4C1_3812_3456;
+    /// client.ble_connect(address, 0, true).await?;
+    /// for service in client.gatt_get_services(address).await? {
+    ///     for characteristic in service.characteristics {
+    ///         println!("Characteristic with handle {}", characteristic.handle);
+    ///     }
+    /// }
+    /// let battery = client.gatt_read(address, 0x2a).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return [`BluetoothError::ConnectionFailed`](crate::error::BluetoothError::ConnectionFailed) if the
+    /// proxy can't connect, [`ClientError::Timeout`] if it doesn't respond within the request timeout,
+    /// or an error if the communication with the device fails.
+    pub async fn ble_connect(
+        &self,
+        address: u64,
+        address_type: u32,
+        cache: bool,
+    ) -> Result<BluetoothDeviceConnectionResponse, ClientError> {
+        bluetooth::connect(self, address, address_type, cache).await
+    }
+
+    /// Disconnects the Bluetooth proxy of the device from the Bluetooth device with the given address.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::Timeout`] if the proxy doesn't report the disconnect within the request timeout,
+    /// or an error if the communication with the device fails.
+    pub async fn ble_disconnect(&self, address: u64) -> Result<(), ClientError> {
+        bluetooth::disconnect(self, address).await
+    }
+
+    /// Pairs with the connected Bluetooth device with the given address, see [`EspHomeClient::ble_connect`].
+    ///
+    /// # Errors
+    ///
+    /// Will return [`BluetoothError::PairingFailed`](crate::error::BluetoothError::PairingFailed) if the proxy
+    /// reports the pairing failed, [`ClientError::Timeout`] if it doesn't respond within the request timeout,
+    /// or an error if the communication with the device fails.
+    pub async fn ble_pair(&self, address: u64) -> Result<(), ClientError> {
+        bluetooth::pair(self, address).await
+    }
+
+    /// Removes the pairing with the Bluetooth device with the given address from the proxy.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`BluetoothError::UnpairingFailed`](crate::error::BluetoothError::UnpairingFailed) if the
+    /// proxy reports a failure, [`ClientError::Timeout`] if it doesn't respond within the request timeout,
+    /// or an error if the communication with the device fails.
+    pub async fn ble_unpair(&self, address: u64) -> Result<(), ClientError> {
+        bluetooth::unpair(self, address).await
+    }
+
+    /// Clears the GATT services the proxy cached for the Bluetooth device with the given address, so they are
+    /// discovered again on the next connection.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`BluetoothError::ClearCacheFailed`](crate::error::BluetoothError::ClearCacheFailed) if the
+    /// proxy reports a failure, [`ClientError::Timeout`] if it doesn't respond within the request timeout,
+    /// or an error if the communication with the device fails.
+    pub async fn ble_clear_cache(&self, address: u64) -> Result<(), ClientError> {
+        bluetooth::clear_cache(self, address).await
+    }
+
+    /// Lists the GATT services of a connected Bluetooth device, see [`EspHomeClient::ble_connect`].
+    ///
+    /// # Errors
+    ///
+    /// Will return a [`BluetoothError`](crate::error::BluetoothError) if the proxy reports an error or the
+    /// Bluetooth device disconnects, [`ClientError::Timeout`] if the services aren't received within the request
+    /// timeout, or an error if the communication with the device fails.
+    pub async fn gatt_get_services(
+        &self,
+        address: u64,
+    ) -> Result<Vec<BluetoothGattService>, ClientError> {
+        bluetooth::get_services(self, address).await
+    }
+
+    /// Reads the value of the characteristic with the given handle of a connected Bluetooth device.
+    ///
+    /// # Errors
+    ///
+    /// Will return a [`BluetoothError`](crate::error::BluetoothError) if the proxy reports an error or the
+    /// Bluetooth device disconnects, [`ClientError::Timeout`] if the value isn't received within the request
+    /// timeout, or an error if the communication with the device fails.
+    pub async fn gatt_read(&self, address: u64, handle: u32) -> Result<Vec<u8>, ClientError> {
+        bluetooth::read(self, address, handle).await
+    }
+
+    /// Writes the value of the characteristic with the given handle of a connected Bluetooth device.
+    ///
+    /// With `response` set, the write is acknowledged by the Bluetooth device, and this waits for the
+    /// acknowledgement. Otherwise it returns as soon as the request is sent.
+    ///
+    /// # Errors
+    ///
+    /// Will return a [`BluetoothError`](crate::error::BluetoothError) if the proxy reports an error or the
+    /// Bluetooth device disconnects, [`ClientError::Timeout`] if the write isn't acknowledged within the request
+    /// timeout, or an error if the communication with the device fails.
+    pub async fn gatt_write(
+        &self,
+        address: u64,
+        handle: u32,
+        data: impl Into<Vec<u8>>,
+        response: bool,
+    ) -> Result<(), ClientError> {
+        bluetooth::write(self, address, handle, data.into(), response).await
+    }
+
+    /// Reads the value of the descriptor with the given handle of a connected Bluetooth device.
     ///
     /// # Errors
     ///
-    /// Will return an error if the write operation fails for example due to a disconnected stream.
-    pub async fn try_write<M>(&mut self, message: M) -> Result<(), ClientError>
-    where
-        M: Into<EspHomeMessage> + Debug,
-    {
-        tracing::debug!("Send: {message:?}");
-        let message: EspHomeMessage = message.into();
-        let payload: Vec<u8> = message.into();
-        self.streams.1.write_message(payload).await
+    /// Will return a [`BluetoothError`](crate::error::BluetoothError) if the proxy reports an error or the
+    /// Bluetooth device disconnects, [`ClientError::Timeout`] if the value isn't received within the request
+    /// timeout, or an error if the communication with the device fails.
+    pub async fn gatt_read_descriptor(
+        &self,
+        address: u64,
+        handle: u32,
+    ) -> Result<Vec<u8>, ClientError> {
+        bluetooth::read_descriptor(self, address, handle).await
     }
 
-    /// Reads the next message from the stream.
-    ///
-    /// It will automatically handle ping requests if ping handling is enabled.
+    /// Writes the value of the descriptor with the given handle of a connected Bluetooth device, and waits
+    /// for the write to be acknowledged.
     ///
     /// # Errors
     ///
-    /// Will return an error if the read operation fails, for example due to a disconnected stream
-    pub async fn try_read(&mut self) -> Result<EspHomeMessage, ClientError> {
-        loop {
-            let payload = self.streams.0.read_next_message().await?;
-            let message: EspHomeMessage =
-                payload
-                    .clone()
-                    .try_into()
-                    .map_err(|e| ProtocolError::ValidationFailed {
-                        reason: format!("Failed to decode EspHomeMessage: {e}"),
-                    })?;
-            tracing::debug!("Receive: {message:?}");
-            match message {
-                EspHomeMessage::PingRequest(_) if self.handle_ping => {
-                    self.try_write(PingResponse {}).await?;
-                }
-                msg => return Ok(msg),
-            }
-        }
+    /// Will return a [`BluetoothError`](crate::error::BluetoothError) if the proxy reports an error or the
+    /// Bluetooth device disconnects, [`ClientError::Timeout`] if the write isn't acknowledged within the request
+    /// timeout, or an error if the communication with the device fails.
+    pub async fn gatt_write_descriptor(
+        &self,
+        address: u64,
+        handle: u32,
+        data: impl Into<Vec<u8>>,
+    ) -> Result<(), ClientError> {
+        bluetooth::write_descriptor(self, address, handle, data.into()).await
     }
 
     /// Closes the connection gracefully by sending a `DisconnectRequest` message.
@@ -84,27 +1189,59 @@ impl EspHomeClient {
     /// # Errors
     ///
     /// Will return an error if the write operation fails, for example due to a disconnected stream
-    pub async fn close(mut self) -> Result<(), ClientError> {
+    pub async fn close(self) -> Result<(), ClientError> {
         self.try_write(DisconnectRequest {}).await?;
-        // Dropping self & self.streams will close the streams automatically.
+        self.writer.lifecycle().disconnected("Connection closed");
+        // Dropping self & the streams will close the connection automatically.
         Ok(())
     }
 
     /// Returns a clone-able write stream for sending messages to the ESPHome device.
     #[must_use]
     pub fn write_stream(&self) -> EspHomeClientWriteStream {
-        EspHomeClientWriteStream {
-            writer: self.streams.1.clone(),
-        }
+        EspHomeClientWriteStream::new(self.writer.clone())
+    }
+
+    /// Starts collecting messages to write to the device at once, see [`WriteBatch`].
+    pub fn begin_batch(&self) -> WriteBatch {
+        WriteBatch::new(self.writer.clone())
     }
 }
 
 /// Clone-able write stream for sending messages to the ESPHome device.
-#[derive(Debug, Clone)]
+///
+/// With the `sink` feature enabled, the write stream implements `futures::Sink<EspHomeMessage>`.
+/// At most one message is in flight, `poll_ready` waits until the previous message is written.
 pub struct EspHomeClientWriteStream {
-    writer: StreamWriter,
+    writer: MessageWriter,
+    #[cfg(feature = "sink")]
+    pending: Option<sink::PendingWrite>,
+}
+
+impl Clone for EspHomeClientWriteStream {
+    fn clone(&self) -> Self {
+        // A pending write belongs to the sink it was started on
+        Self::new(self.writer.clone())
+    }
 }
+
+impl Debug for EspHomeClientWriteStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EspHomeClientWriteStream")
+            .field("writer", &self.writer)
+            .finish_non_exhaustive()
+    }
+}
+
 impl EspHomeClientWriteStream {
+    const fn new(writer: MessageWriter) -> Self {
+        Self {
+            writer,
+            #[cfg(feature = "sink")]
+            pending: None,
+        }
+    }
+
     /// Sends a message to the ESPHome device.
     ///
     /// # Errors
@@ -114,23 +1251,57 @@ impl EspHomeClientWriteStream {
     where
         M: Into<EspHomeMessage> + Debug,
     {
-        tracing::debug!("Send: {message:?}");
         let message: EspHomeMessage = message.into();
-        let payload: Vec<u8> = message.into();
-        self.writer.write_message(payload).await
+        self.writer.span().in_scope(|| {
+            tracing::debug!("Send: {message}");
+            tracing::trace!("Send: {message:?}");
+        });
+        self.writer.write(message).await
+    }
+
+    /// Starts collecting messages to write to the device at once, see [`WriteBatch`].
+    pub fn begin_batch(&self) -> WriteBatch {
+        WriteBatch::new(self.writer.clone())
     }
 }
 
 /// Builder for configuring and connecting to an ESPHome API server.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[allow(
+    clippy::struct_excessive_bools,
+    reason = "Builder options are independent flags"
+)]
 pub struct EspHomeClientBuilder {
-    addr: Option<String>,
+    addr: Option<ServerAddress>,
     key: Option<String>,
+    key_bytes: Option<[u8; 32]>,
+    key_file: Option<PathBuf>,
     password: Option<String>,
     client_info: String,
-    timeout: Duration,
+    connect_timeout: Duration,
+    handshake_timeout: Duration,
+    setup_timeout: Duration,
     connection_setup: bool,
     handle_ping: bool,
+    capture: Option<Capture>,
+    broadcast_capacity: usize,
+    request_timeout: Duration,
+    idle_timeout: Option<Duration>,
+    state_cache: bool,
+    entity_registry: bool,
+    lifecycle: LifecycleHooks,
+    expected_server_name: Option<String>,
+    expected_mac: Option<String>,
+    outgoing_queue: Option<usize>,
+    stream: Option<StreamSlot>,
+    local_address: Option<IpAddr>,
+    socket_options: SocketOptions,
+    requires_encryption: bool,
+    negotiate_api_version: bool,
+    buffer_pool: Option<BufferPool>,
+    read_buffer: ReadBufferPolicy,
+    backoff_policy: Option<BackoffPolicy>,
+    timer: Option<Arc<dyn Timer>>,
 }
 
 impl EspHomeClientBuilder {
@@ -138,20 +1309,121 @@ impl EspHomeClientBuilder {
         Self {
             addr: None,
             key: None,
+            key_bytes: None,
+            key_file: None,
             password: None,
             client_info: format!("{}:{}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
-            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(30),
+            handshake_timeout: Duration::from_secs(30),
+            setup_timeout: Duration::from_secs(30),
             connection_setup: true,
             handle_ping: true,
+            capture: None,
+            broadcast_capacity: 256,
+            request_timeout: Duration::from_secs(10),
+            idle_timeout: None,
+            state_cache: false,
+            entity_registry: false,
+            lifecycle: LifecycleHooks::default(),
+            expected_server_name: None,
+            expected_mac: None,
+            outgoing_queue: None,
+            stream: None,
+            local_address: None,
+            socket_options: SocketOptions::default(),
+            requires_encryption: false,
+            negotiate_api_version: false,
+            buffer_pool: None,
+            read_buffer: ReadBufferPolicy::new(),
+            backoff_policy: None,
+            timer: None,
+        }
+    }
+
+    /// Creates a builder pre-filled with the addresses of a discovered device.
+    ///
+    /// All known addresses of the device are tried, in order of preference. When the device announces
+    /// that it requires encryption, connecting without a key fails with [`ConnectionError::EncryptionRequired`].
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::{EspHomeClientBuilder, discovery::Client};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut discovery = Client::default().discover()?;
+    /// let device = discovery.next().await?;
+    /// let client = EspHomeClientBuilder::from_device(&device)
+    ///     .key("px7tsbK3C7bpXHr2OevEV2ZMg/FrNBw2+O2pNPbedtA=")
+    ///     .connect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "discovery")]
+    #[must_use]
+    pub fn from_device(device: &DeviceInfo) -> Self {
+        let mut builder = Self::new();
+        let addrs: Vec<_> = device.socket_addresses().collect();
+        if !addrs.is_empty() {
+            builder.addr = Some(addrs.into());
+        }
+        builder.requires_encryption = device.has_encryption();
+        builder
+    }
+
+    /// Creates a builder pre-filled with the settings of the given [`ClientConfig`].
+    #[must_use]
+    pub fn from_config(config: ClientConfig) -> Self {
+        let mut builder = Self::new();
+        builder.addr = config.address.map(ServerAddress::from);
+        builder.key = config.key;
+        builder.key_file = config.key_file;
+        builder.password = config.password;
+        if let Some(client_info) = config.client_info {
+            builder.client_info = client_info;
+        }
+        if let Some(timeout_ms) = config.timeout_ms {
+            builder = builder.timeout(Duration::from_millis(timeout_ms));
+        }
+        if let Some(timeout_ms) = config.connect_timeout_ms {
+            builder.connect_timeout = Duration::from_millis(timeout_ms);
+        }
+        if let Some(timeout_ms) = config.handshake_timeout_ms {
+            builder.handshake_timeout = Duration::from_millis(timeout_ms);
+        }
+        if let Some(timeout_ms) = config.setup_timeout_ms {
+            builder.setup_timeout = Duration::from_millis(timeout_ms);
+        }
+        if let Some(request_timeout_ms) = config.request_timeout_ms {
+            builder.request_timeout = Duration::from_millis(request_timeout_ms);
         }
+        builder.idle_timeout = config.idle_timeout_ms.map(Duration::from_millis);
+        if let Some(connection_setup) = config.connection_setup {
+            builder.connection_setup = connection_setup;
+        }
+        if let Some(handle_ping) = config.handle_ping {
+            builder.handle_ping = handle_ping;
+        }
+        if let Some(nodelay) = config.tcp_nodelay {
+            builder.socket_options.nodelay = nodelay;
+        }
+        builder.socket_options.keepalive = config.keepalive.map(|keepalive| {
+            (
+                Duration::from_millis(keepalive.interval_ms),
+                keepalive.retries,
+            )
+        });
+        builder.backoff_policy = config.reconnect.map(BackoffPolicy::from);
+        builder
     }
 
-    /// Sets the host address of the ESPHome API server to connect to.
+    /// Sets the address of the ESPHome API server to connect to.
     ///
-    /// Takes the address of the server in the format "host:port".
+    /// Takes the address of the server in the format "host:port", or one or more socket addresses.
+    /// All resolved addresses are tried, see [`ServerAddress`].
     #[must_use]
-    pub fn address(mut self, addr: &str) -> Self {
-        self.addr = Some(addr.to_owned());
+    pub fn address(mut self, addr: impl Into<ServerAddress>) -> Self {
+        self.addr = Some(addr.into());
         self
     }
 
@@ -162,6 +1434,174 @@ impl EspHomeClientBuilder {
     #[must_use]
     pub fn key(mut self, key: &str) -> Self {
         self.key = Some(key.to_owned());
+        self.key_bytes = None;
+        self
+    }
+
+    /// Binds the outgoing connection to the given local IP address.
+    ///
+    /// Needed on hosts with multiple networks, where the device is only reachable through one of them.
+    /// Only server addresses of the same family as the local address are tried.
+    #[must_use]
+    pub const fn local_address(mut self, addr: IpAddr) -> Self {
+        self.local_address = Some(addr);
+        self
+    }
+
+    /// Sets the `TCP_NODELAY` option of the connection.
+    ///
+    /// Defaults to `true`, so small frames like commands are sent right away instead of being
+    /// delayed to combine them with later writes.
+    #[must_use]
+    pub const fn tcp_nodelay(mut self, nodelay: bool) -> Self {
+        self.socket_options.nodelay = nodelay;
+        self
+    }
+
+    /// Enables TCP keepalive probes, to detect unreachable devices at the socket level.
+    ///
+    /// Probes are sent after the connection has been idle for the given interval, the connection is
+    /// dropped after the given number of unanswered probes. Not all platforms support configuring the
+    /// interval between probes and the number of probes, in which case the system defaults are used.
+    #[must_use]
+    pub const fn tcp_keepalive(mut self, interval: Duration, retries: u32) -> Self {
+        self.socket_options.keepalive = Some((interval, retries));
+        self
+    }
+
+    /// Connects over an already established TCP connection, instead of connecting to the address.
+    ///
+    /// Allows custom socket setup, like binding to an interface or racing connection attempts, while the
+    /// handshake and connection setup are performed as usual. The stream is only used for the first
+    /// connection, reconnecting with [`EspHomeConnectionManager`] requires an address to be set.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::EspHomeClient;
+    /// use tokio::net::TcpSocket;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let socket = TcpSocket::new_v4()?;
+    /// socket.set_nodelay(true)?;
+    /// let stream = socket.connect("192.168.0.2:6053".parse()?).await?;
+    /// let client = EspHomeClient::builder().with_stream(stream).connect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "runtime-tokio")]
+    #[must_use]
+    pub fn with_stream(mut self, stream: TcpStream) -> Self {
+        self.stream = Some(Arc::new(SyncMutex::new(Some(Connected::Tcp(stream)))));
+        self
+    }
+
+    /// Connects over an already established connection of any runtime, instead of connecting to the address.
+    ///
+    /// Unlike with a TCP stream, the socket options of the builder aren't applied, and the stream is only
+    /// used for the first connection. Without the `runtime-tokio` feature, this is the only way to
+    /// connect, together with a [`Timer`] of the runtime, see [`EspHomeClientBuilder::timer`].
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::EspHomeClient;
+    /// use tokio::io::duplex;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let (stream, _device) = duplex(4096);
+    /// let client = EspHomeClient::builder().with_transport(stream).connect().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_transport(mut self, transport: impl Transport) -> Self {
+        self.stream = Some(Arc::new(SyncMutex::new(Some(Connected::Transport(
+            Box::new(transport),
+        )))));
+        self
+    }
+
+    /// Sets the timer of the runtime, used for all timeouts of the client and the delays between
+    /// reconnection attempts.
+    ///
+    /// Defaults to [`TokioTimer`] with the `runtime-tokio` feature, without it a timer must be set.
+    #[must_use]
+    pub fn timer(mut self, timer: impl Timer) -> Self {
+        self.timer = Some(Arc::new(timer));
+        self
+    }
+
+    /// Returns the clock using the timer of the builder, or the timer of tokio by default.
+    #[cfg_attr(
+        feature = "runtime-tokio",
+        expect(
+            clippy::unnecessary_wraps,
+            reason = "Only fails without the runtime-tokio feature"
+        )
+    )]
+    fn clock(&self) -> Result<Clock, ClientError> {
+        #[cfg(feature = "runtime-tokio")]
+        let timer = self.timer.clone().unwrap_or_else(|| Arc::new(TokioTimer));
+        #[cfg(not(feature = "runtime-tokio"))]
+        let timer = self
+            .timer
+            .clone()
+            .ok_or_else(|| ClientError::Configuration {
+                message: "A timer is required without the runtime-tokio feature".into(),
+            })?;
+        Ok(Clock::new(timer))
+    }
+
+    /// Enables encrypted communication with the raw 32-byte key, instead of its base64 encoding.
+    ///
+    /// Replaces a key set with [`EspHomeClientBuilder::key`], and takes precedence over a key file.
+    ///
+    /// # Usage:
+    /// ```rust
+    /// use esphome_client::EspHomeClient;
+    ///
+    /// # fn example(stored: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+    /// let builder = EspHomeClient::builder()
+    ///     .address("192.168.0.2:6053")
+    ///     .key_bytes(<[u8; 32]>::try_from(stored)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn key_bytes(mut self, key: [u8; 32]) -> Self {
+        self.key_bytes = Some(key);
+        self.key = None;
+        self
+    }
+
+    /// Only accepts a connection to the device with the given name.
+    ///
+    /// Protects against connecting to another device after its address changed, for example due to DHCP.
+    /// The name is verified against the name announced in the noise handshake, or in the `HelloResponse`
+    /// for plain connections with connection setup enabled. Devices that don't announce a name are accepted.
+    #[must_use]
+    pub fn expected_server_name(mut self, name: &str) -> Self {
+        self.expected_server_name = Some(name.to_owned());
+        self
+    }
+
+    /// Only accepts a connection to the device with the given MAC address, e.g. `aa:bb:cc:dd:ee:ff`.
+    ///
+    /// The address is verified against the MAC address announced in the noise handshake. For plain
+    /// connections it's requested with a `DeviceInfoRequest`, which requires connection setup to be enabled.
+    /// Connecting fails with [`ClientError::DeviceMismatch`] if the addresses don't match.
+    #[must_use]
+    pub fn expected_mac(mut self, mac: &str) -> Self {
+        self.expected_mac = Some(mac.to_owned());
+        self
+    }
+
+    /// Enables encrypted communication with a key read from the given file.
+    ///
+    /// The file should contain the 32-byte base64-encoded key, surrounding whitespace is ignored.
+    /// The file is read when connecting, a key set with [`EspHomeClientBuilder::key`] takes precedence.
+    #[must_use]
+    pub fn key_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.key_file = Some(path.into());
         self
     }
 
@@ -175,10 +1615,97 @@ impl EspHomeClientBuilder {
         self
     }
 
-    /// Sets the timeout duration during the tcp connection.
+    /// Sets the timeout of each phase of connecting, see the connect, handshake and setup timeouts.
+    ///
+    /// Defaults to 30 seconds.
     #[must_use]
     pub const fn timeout(mut self, timeout: Duration) -> Self {
-        self.timeout = timeout;
+        self.connect_timeout = timeout;
+        self.handshake_timeout = timeout;
+        self.setup_timeout = timeout;
+        self
+    }
+
+    /// Sets the timeout for establishing the tcp connection, including resolving the address.
+    #[must_use]
+    pub const fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Sets the timeout for the noise handshake of encrypted connections.
+    #[must_use]
+    pub const fn handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.handshake_timeout = timeout;
+        self
+    }
+
+    /// Sets the timeout for the connection setup, the `HelloRequest` and `ConnectRequest` exchange
+    /// and verifying the device.
+    #[must_use]
+    pub const fn setup_timeout(mut self, timeout: Duration) -> Self {
+        self.setup_timeout = timeout;
+        self
+    }
+
+    /// Sets the time [`EspHomeClient::send_and_wait`] waits for a response.
+    ///
+    /// Defaults to 10 seconds.
+    #[must_use]
+    pub const fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Enables detection of stalled connections.
+    ///
+    /// When no message is received from the device for the given duration, reading fails with
+    /// [`ClientError::IdleTimeout`]. ESPHome devices send a ping request after a period of inactivity,
+    /// so the timeout should be well above the keepalive interval of the device (around 20 seconds).
+    #[must_use]
+    pub const fn idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Enable tracking of the latest state per entity, see [`EspHomeClient::watch`].
+    #[must_use]
+    pub const fn with_state_cache(mut self) -> Self {
+        self.state_cache = true;
+        self
+    }
+
+    /// Enable recording of the entities described by the device, see [`EspHomeClient::entity`].
+    #[must_use]
+    pub const fn with_entity_registry(mut self) -> Self {
+        self.entity_registry = true;
+        self
+    }
+
+    /// Registers a callback for the lifecycle changes of the connection.
+    ///
+    /// The callback is kept by clones of the builder, so it is also called for the connections of
+    /// an [`EspHomeConnectionManager`] or a `Supervisor`.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::{EspHomeClient, LifecycleEvent};
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = EspHomeClient::builder()
+    ///     .address("192.168.0.2:6053")
+    ///     .on_lifecycle(|event| match event {
+    ///         LifecycleEvent::Disconnected { reason } => println!("Disconnected: {reason}"),
+    ///         event => println!("{event:?}"),
+    ///     })
+    ///     .connect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn on_lifecycle(mut self, hook: impl Fn(&LifecycleEvent) + Send + Sync + 'static) -> Self {
+        self.lifecycle.register(hook);
         self
     }
 
@@ -205,6 +1732,19 @@ impl EspHomeClientBuilder {
         self
     }
 
+    /// Checks the outgoing messages against the API version reported by the device in the `HelloResponse`.
+    ///
+    /// Sending a message which is newer than the API version of the device fails with
+    /// [`ProtocolError::UnsupportedMessage`], instead of being sent to a device which would ignore it.
+    /// Received messages are still decoded with the API version the client is compiled for (see the
+    /// `api-1-*` features), fields missing in older versions are decoded with their default value.
+    /// This requires connection setup to be enabled.
+    #[must_use]
+    pub const fn negotiate_api_version(mut self) -> Self {
+        self.negotiate_api_version = true;
+        self
+    }
+
     /// Disable automatic handling of ping request.
     ///
     /// The ESPHome API server will send a ping request to the client on a regular interval.
@@ -215,44 +1755,305 @@ impl EspHomeClientBuilder {
         self
     }
 
+    /// Sets the number of messages buffered for each subscriber, see [`EspHomeClient::subscribe`].
+    ///
+    /// Defaults to 256 messages, a capacity of 0 is treated as 1.
+    #[must_use]
+    pub const fn broadcast_capacity(mut self, capacity: usize) -> Self {
+        self.broadcast_capacity = capacity;
+        self
+    }
+
+    /// Limits the number of messages waiting to be written to the device.
+    ///
+    /// When the given number of messages is waiting, writing fails with [`ClientError::Backpressure`]
+    /// instead of waiting for the connection. Messages keeping the connection alive, like ping and
+    /// disconnect messages, are always accepted and written before other waiting messages.
+    #[must_use]
+    pub const fn outgoing_queue(mut self, capacity: usize) -> Self {
+        self.outgoing_queue = Some(capacity);
+        self
+    }
+
+    /// Records all frames sent and received over the connection to the given capture.
+    ///
+    /// The frames are recorded after decryption, see [`Capture`] for the format.
+    #[must_use]
+    pub fn capture(mut self, capture: Capture) -> Self {
+        self.capture = Some(capture);
+        self
+    }
+
+    /// Borrows the read buffer, and the buffers for encoding and decrypting messages, from a pool shared
+    /// with other connections, instead of allocating buffers per connection and message, see [`BufferPool`].
+    #[must_use]
+    pub fn buffer_pool(mut self, pool: BufferPool) -> Self {
+        self.buffer_pool = Some(pool);
+        self
+    }
+
+    /// Sets the initial capacity and the growth and shrink policy of the read buffer.
+    ///
+    /// With a [`BufferPool`], the buffers of the pool are used, only the growth and shrink settings apply.
+    #[must_use]
+    pub const fn read_buffer(mut self, policy: ReadBufferPolicy) -> Self {
+        self.read_buffer = policy;
+        self
+    }
+
     /// Connect to the ESPHome API server.
     ///
     /// # Errors
     ///
     /// Will return an error if the connection fails, or if the connection setup fails.
     pub async fn connect(self) -> Result<EspHomeClient, ClientError> {
-        let addr = self.addr.ok_or_else(|| ClientError::Configuration {
-            message: "Address is not set".into(),
-        })?;
+        // Logs of the connection carry the address and, once known, the name of the device
+        let span = tracing::info_span!("esphome", address = field::Empty, name = field::Empty);
+        if let Some(addr) = &self.addr {
+            span.record("address", field::display(addr));
+        }
+        self.establish(span.clone()).instrument(span).await
+    }
 
-        let streams = timeout(self.timeout, async {
-            match self.key {
-                Some(key) => noise::connect(&addr, &key).await,
-                None => plain::connect(&addr).await,
-            }
-        })
-        .await
-        .map_err(|_e| ClientError::Timeout {
-            timeout_ms: self.timeout.as_millis(),
-        })??;
+    /// Uses the pre-connected stream or connects to the address, and applies the socket options to TCP
+    /// connections.
+    async fn connect_stream(
+        &self,
+        clock: &Clock,
+        #[cfg_attr(
+            not(feature = "runtime-tokio"),
+            expect(
+                unused_variables,
+                reason = "Only TCP connections record the peer address"
+            )
+        )]
+        span: &Span,
+    ) -> Result<Box<dyn Transport>, ClientError> {
+        let connected = self
+            .stream
+            .as_ref()
+            .and_then(|slot| slot.lock().unwrap_or_else(PoisonError::into_inner).take());
+        clock
+            .timeout(self.connect_timeout, async {
+                match (connected, &self.addr) {
+                    (Some(Connected::Transport(transport)), _) => Ok(transport),
+                    #[cfg(feature = "runtime-tokio")]
+                    (Some(Connected::Tcp(tcp_stream)), _) => {
+                        if let Ok(peer) = tcp_stream.peer_addr() {
+                            span.record("address", field::display(peer));
+                        }
+                        self.socket_options.apply(&tcp_stream)?;
+                        let transport: Box<dyn Transport> = Box::new(tcp_stream);
+                        Ok(transport)
+                    }
+                    #[cfg(feature = "runtime-tokio")]
+                    (None, Some(addr)) => {
+                        let tcp_stream = addr.connect(self.local_address).await?;
+                        self.socket_options.apply(&tcp_stream)?;
+                        let transport: Box<dyn Transport> = Box::new(tcp_stream);
+                        Ok(transport)
+                    }
+                    #[cfg(not(feature = "runtime-tokio"))]
+                    (None, Some(_)) => Err(ClientError::Configuration {
+                        message: "Connecting to an address requires the runtime-tokio feature"
+                            .into(),
+                    }),
+                    (None, None) => Err(ClientError::Configuration {
+                        message: "Address is not set".into(),
+                    }),
+                }
+            })
+            .await
+            .map_err(|_e| timeout_error(self.connect_timeout))?
+    }
 
-        let mut stream = EspHomeClient {
-            streams,
-            handle_ping: self.handle_ping,
+    async fn establish(self, span: Span) -> Result<EspHomeClient, ClientError> {
+        let clock = self.clock()?;
+
+        let key = self.resolve_key()?;
+        if self.requires_encryption && key.is_none() {
+            return Err(ConnectionError::EncryptionRequired.into());
+        }
+
+        let expected_mac = self.normalized_expected_mac(key.is_some())?;
+
+        let transport = self.connect_stream(&clock, &span).await?;
+
+        let (streams, (server_name, server_mac)) = match key {
+            Some(key) => clock
+                .timeout(
+                    self.handshake_timeout,
+                    noise::connect(transport, &key, self.buffer_pool.clone()),
+                )
+                .await
+                .map_err(|_e| timeout_error(self.handshake_timeout))??,
+            None => (plain::connect(transport), (None, None)),
         };
-        if self.connection_setup {
-            Self::connection_setup(&mut stream, self.client_info, self.password).await?;
+
+        let server_name = server_name.filter(|name| !name.is_empty());
+        if let Some(name) = &server_name {
+            span.record("name", name.as_str());
+            verify_server_name(self.expected_server_name.as_deref(), name)?;
         }
+        let server_mac = server_mac.filter(|mac| !mac.is_empty());
+        if let (Some(expected), Some(actual)) = (&expected_mac, &server_mac) {
+            verify_mac(expected, actual)?;
+        }
+
+        let (reader, writer) = streams;
+        let statistics = Arc::new(Statistics::default());
+        let stream = EspHomeClient {
+            reader: Mutex::new(
+                reader
+                    .with_capture(self.capture.clone())
+                    .with_statistics(Arc::clone(&statistics))
+                    .with_buffer_policy(self.read_buffer)
+                    .with_buffer_pool(self.buffer_pool.clone()),
+            ),
+            writer: MessageWriter::new(
+                writer
+                    .with_buffer_pool(self.buffer_pool)
+                    .with_capture(self.capture)
+                    .with_statistics(Arc::clone(&statistics))
+                    .with_queue(self.outgoing_queue)
+                    .with_span(span),
+                Lifecycle::new(self.lifecycle),
+                self.negotiate_api_version,
+            ),
+            handle_ping: self.handle_ping,
+            unanswered_pings: AtomicUsize::new(0),
+            broadcast: broadcast::channel(self.broadcast_capacity.max(1)).0,
+            handlers: Handlers::default(),
+            pending: SyncMutex::new(VecDeque::new()),
+            request_timeout: self.request_timeout,
+            idle_timeout: self.idle_timeout,
+            last_received: SyncMutex::new(Instant::now()),
+            clock: clock.clone(),
+            statistics,
+            state_cache: self.state_cache.then(StateCache::default),
+            entity_registry: self.entity_registry.then(EntityRegistry::default),
+            home_assistant_states: StateSubscriptions::default(),
+            server_name,
+            server_mac,
+        };
+        stream.writer.lifecycle().emit(&LifecycleEvent::Connected);
+        // Every reported connection is matched by a disconnect, also when its setup failed
+        clock
+            .timeout(self.setup_timeout, async {
+                if self.connection_setup {
+                    Self::connection_setup(
+                        &stream,
+                        self.client_info,
+                        self.password,
+                        self.expected_server_name.as_deref(),
+                    )
+                    .await
+                    .map_err(|e| {
+                        if key.is_some() {
+                            e
+                        } else {
+                            encryption_required(e)
+                        }
+                    })?;
+                }
+                if let (Some(expected), None) = (&expected_mac, &stream.server_mac) {
+                    let info = stream
+                        .send_and_wait::<DeviceInfoResponse>(DeviceInfoRequest {})
+                        .await?;
+                    verify_mac(expected, &info.mac_address)?;
+                }
+                Ok::<_, ClientError>(())
+            })
+            .await
+            .map_err(|_e| timeout_error(self.setup_timeout))
+            .and_then(|result| result)
+            .inspect_err(|e| stream.writer.lifecycle().disconnected(e.to_string()))?;
+        stream
+            .writer
+            .lifecycle()
+            .emit(&LifecycleEvent::SetupComplete);
         Ok(stream)
     }
 
+    /// Returns the key used for encrypted communication, in order of precedence the raw key, the
+    /// base64-encoded key or the key file.
+    fn resolve_key(&self) -> Result<Option<[u8; 32]>, ClientError> {
+        let key = match (
+            self.key_bytes,
+            self.key.as_deref(),
+            self.key_file.as_deref(),
+        ) {
+            (Some(key), _, _) => Some(key),
+            (None, Some(key), _) => Some(noise::decode_key(key)?),
+            (None, None, Some(path)) => Some(noise::decode_key(&read_key_file(path)?)?),
+            (None, None, None) => None,
+        };
+        Ok(key)
+    }
+
+    /// Returns the normalized expected MAC address, validating it can be verified for the connection.
+    fn normalized_expected_mac(&self, encrypted: bool) -> Result<Option<String>, ClientError> {
+        let expected_mac = self
+            .expected_mac
+            .as_deref()
+            .map(|mac| {
+                normalize_mac(mac).ok_or_else(|| ClientError::Configuration {
+                    message: format!("Invalid expected MAC address: {mac}"),
+                })
+            })
+            .transpose()?;
+        if expected_mac.is_some() && !encrypted && !self.connection_setup {
+            return Err(ClientError::Configuration {
+                message:
+                    "Verifying the MAC address of a plain connection requires connection setup"
+                        .into(),
+            });
+        }
+        Ok(expected_mac)
+    }
+
+    /// Connect to the ESPHome API server, and keep reconnecting when the connection is lost.
+    ///
+    /// See [`EspHomeConnectionManager`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the initial connection fails, or if the connection setup fails.
+    pub async fn connect_with_auto_reconnect(
+        self,
+        policy: BackoffPolicy,
+    ) -> Result<EspHomeConnectionManager, ClientError> {
+        EspHomeConnectionManager::connect(self, policy).await
+    }
+
+    /// Sets the backoff policy used by [`EspHomeClientBuilder::connect_managed`].
+    #[must_use]
+    pub const fn backoff_policy(mut self, policy: BackoffPolicy) -> Self {
+        self.backoff_policy = Some(policy);
+        self
+    }
+
+    /// Connect to the ESPHome API server, and keep reconnecting with the backoff policy of the builder,
+    /// e.g. from the `reconnect` settings of a [`ClientConfig`], or the default [`BackoffPolicy`].
+    ///
+    /// See [`EspHomeConnectionManager`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the initial connection fails, or if the connection setup fails.
+    pub async fn connect_managed(self) -> Result<EspHomeConnectionManager, ClientError> {
+        let policy = self.backoff_policy.unwrap_or_default();
+        EspHomeConnectionManager::connect(self, policy).await
+    }
+
     /// Sets up the connection by sending the `HelloRequest` and `ConnectRequest` messages.
     ///
     /// Details: <https://github.com/esphome/aioesphomeapi/blob/4707c424e5dab921fa15466ecc31148a8c0ee4a9/aioesphomeapi/api.proto#L85>
     async fn connection_setup(
-        stream: &mut EspHomeClient,
+        stream: &EspHomeClient,
         client_info: String,
         password: Option<String>,
+        expected_server_name: Option<&str>,
     ) -> Result<(), ClientError> {
         stream
             .try_write(HelloRequest {
@@ -274,7 +2075,9 @@ impl EspHomeClientBuilder {
                             ),
                         });
                     }
-                    if response.api_version_minor != API_VERSION.1 {
+                    if response.api_version_minor != API_VERSION.1
+                        && !stream.writer.negotiate_api_version()
+                    {
                         tracing::warn!(
                             "API version mismatch: expected {}.{}, got {}.{}, expect breaking changes in messages",
                             API_VERSION.0,
@@ -283,6 +2086,12 @@ impl EspHomeClientBuilder {
                             response.api_version_minor
                         );
                     }
+                    if !response.name.is_empty() {
+                        stream.writer.span().record("name", response.name.as_str());
+                        verify_server_name(expected_server_name, &response.name)?;
+                    }
+                    // Connection setup runs once, before the client is handed out
+                    drop(stream.writer.hello().set(response));
                     break;
                 }
                 _ => {
@@ -304,7 +2113,7 @@ impl EspHomeClientBuilder {
         feature = "api-1-8"
     )))]
     async fn authenticate(
-        stream: &mut EspHomeClient,
+        stream: &EspHomeClient,
         password: Option<String>,
     ) -> Result<(), ClientError> {
         use crate::proto::AuthenticationRequest;
@@ -341,7 +2150,7 @@ impl EspHomeClientBuilder {
         feature = "api-1-8"
     ))]
     async fn authenticate(
-        stream: &mut EspHomeClient,
+        stream: &EspHomeClient,
         password: Option<String>,
     ) -> Result<(), ClientError> {
         use crate::proto::ConnectRequest;
@@ -371,3 +2180,65 @@ impl EspHomeClientBuilder {
         Ok(())
     }
 }
+
+/// Creates the error for a connection phase that didn't complete within its timeout.
+const fn timeout_error(timeout: Duration) -> ClientError {
+    ClientError::Timeout {
+        timeout_ms: timeout.as_millis(),
+    }
+}
+
+/// Reads a base64-encoded key from a file, trimming surrounding whitespace.
+fn read_key_file(path: &Path) -> Result<String, ClientError> {
+    fs::read_to_string(path)
+        .map(|key| key.trim().to_owned())
+        .map_err(|e| ClientError::Configuration {
+            message: format!("Failed to read key file {}: {e}", path.display()),
+        })
+}
+
+/// Detects a plain connection to a device that requires encryption, which responds with a noise frame.
+///
+/// A disconnect without a noise frame isn't mapped, as the device may as well be rebooting or have
+/// reached its maximum number of connections, which are recoverable.
+fn encryption_required(error: ClientError) -> ClientError {
+    match error {
+        ClientError::Protocol(ProtocolError::UnexpectedEncryption) => {
+            ConnectionError::EncryptionRequired.into()
+        }
+        other => other,
+    }
+}
+
+/// Verifies the name announced by the device, if a name is expected.
+fn verify_server_name(expected: Option<&str>, actual: &str) -> Result<(), ConnectionError> {
+    match expected {
+        Some(expected) if expected != actual => Err(ConnectionError::ServerNameMismatch {
+            expected: expected.to_owned(),
+            actual: actual.to_owned(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Normalizes a MAC address to lower case hex digits without separators, as announced in the noise handshake.
+fn normalize_mac(mac: &str) -> Option<String> {
+    let digits: String = mac
+        .chars()
+        .filter(|c| !matches!(c, ':' | '-'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    (digits.len() == 12 && digits.chars().all(|c| c.is_ascii_hexdigit())).then_some(digits)
+}
+
+/// Verifies the MAC address reported by the device against the normalized expected address.
+fn verify_mac(expected: &str, actual: &str) -> Result<(), ClientError> {
+    if normalize_mac(actual).as_deref() == Some(expected) {
+        Ok(())
+    } else {
+        Err(ClientError::DeviceMismatch {
+            expected: expected.to_owned(),
+            actual: actual.to_owned(),
+        })
+    }
+}
@@ -0,0 +1,378 @@
+use std::{
+    fmt,
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+#[cfg(feature = "runtime-tokio")]
+use std::{
+    future::{Future, poll_fn},
+    io,
+    pin::Pin,
+    task::Poll,
+};
+
+#[cfg(feature = "runtime-tokio")]
+use socket2::{SockRef, TcpKeepalive};
+#[cfg(feature = "runtime-tokio")]
+use tokio::{
+    net::{TcpSocket, TcpStream, lookup_host},
+    time::{Instant, sleep},
+};
+
+#[cfg(all(feature = "discovery", feature = "runtime-tokio"))]
+use crate::discovery;
+#[cfg(feature = "runtime-tokio")]
+use crate::error::{ClientError, ConnectionError};
+
+/// Delay before the next address is tried while the previous attempt is still pending.
+///
+/// Follows the recommended connection attempt delay of "Happy Eyeballs", see RFC 8305.
+#[cfg(feature = "runtime-tokio")]
+const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+/// Time to wait for an answer when resolving a `.local` hostname with mDNS.
+#[cfg(all(feature = "discovery", feature = "runtime-tokio"))]
+const MDNS_RESOLVE_TIMEOUT: Duration = Duration::from_secs(3);
+
+#[cfg(feature = "runtime-tokio")]
+type ConnectAttempt = Pin<Box<dyn Future<Output = io::Result<TcpStream>> + Send>>;
+
+/// Address of an ESPHome API server.
+///
+/// Either a host name or IP address with a port in the format "host:port", which is resolved when
+/// connecting, or a list of socket addresses. When multiple addresses are available, they are tried
+/// in order, starting the next attempt when the previous one doesn't connect within 250 milliseconds.
+///
+/// # Usage:
+/// ```rust
+/// use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+/// use esphome_client::{EspHomeClient, ServerAddress};
+///
+/// let builder = EspHomeClient::builder().address("livingroom.local:6053");
+/// let builder = EspHomeClient::builder().address((IpAddr::V4(Ipv4Addr::new(192, 168, 0, 2)), 6053));
+/// let builder = EspHomeClient::builder().address(vec![
+///     "[fd00::2]:6053".parse::<SocketAddr>().unwrap(),
+///     "192.168.0.2:6053".parse::<SocketAddr>().unwrap(),
+/// ]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerAddress(Address);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Address {
+    Host(String),
+    Resolved(Vec<SocketAddr>),
+}
+
+#[cfg(feature = "runtime-tokio")]
+impl ServerAddress {
+    /// Resolves the address and connects to the first address that accepts the connection.
+    ///
+    /// With a local address, the outgoing socket is bound to it and only addresses of the same family are tried.
+    pub(super) async fn connect(
+        &self,
+        local_address: Option<IpAddr>,
+    ) -> Result<TcpStream, ClientError> {
+        let addrs = match &self.0 {
+            Address::Host(host) => self.resolve(host).await?,
+            Address::Resolved(addrs) => addrs.clone(),
+        };
+        let stream = connect_any(addrs, local_address)
+            .await
+            .map_err(|e| self.connect_error(e))?;
+        tracing::debug!("Tcp connection established to {self}");
+        Ok(stream)
+    }
+
+    /// Resolves the host name, `.local` host names are resolved with mDNS when the `discovery` feature
+    /// is enabled, falling back to the system resolver.
+    async fn resolve(&self, host: &str) -> Result<Vec<SocketAddr>, ClientError> {
+        #[cfg(feature = "discovery")]
+        if let Some(addrs) = resolve_mdns(host).await {
+            return Ok(addrs);
+        }
+        Ok(lookup_host(host)
+            .await
+            .map_err(|e| self.connect_error(e))?
+            .collect())
+    }
+
+    fn connect_error(&self, source: io::Error) -> ClientError {
+        ConnectionError::TcpConnect {
+            address: self.to_string(),
+            source,
+        }
+        .into()
+    }
+}
+
+impl fmt::Display for ServerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Address::Host(host) => f.write_str(host),
+            Address::Resolved(addrs) => {
+                for (index, addr) in addrs.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{addr}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl From<&str> for ServerAddress {
+    fn from(host: &str) -> Self {
+        Self(Address::Host(host.to_owned()))
+    }
+}
+
+impl From<&String> for ServerAddress {
+    fn from(host: &String) -> Self {
+        Self(Address::Host(host.clone()))
+    }
+}
+
+impl From<String> for ServerAddress {
+    fn from(host: String) -> Self {
+        Self(Address::Host(host))
+    }
+}
+
+impl From<SocketAddr> for ServerAddress {
+    fn from(addr: SocketAddr) -> Self {
+        Self(Address::Resolved(vec![addr]))
+    }
+}
+
+impl From<(IpAddr, u16)> for ServerAddress {
+    fn from(addr: (IpAddr, u16)) -> Self {
+        SocketAddr::from(addr).into()
+    }
+}
+
+impl From<Vec<SocketAddr>> for ServerAddress {
+    fn from(addrs: Vec<SocketAddr>) -> Self {
+        Self(Address::Resolved(addrs))
+    }
+}
+
+impl From<&[SocketAddr]> for ServerAddress {
+    fn from(addrs: &[SocketAddr]) -> Self {
+        Self(Address::Resolved(addrs.to_vec()))
+    }
+}
+
+/// Splits a "host:port" address into the host name and port if the host is a `.local` host name.
+#[cfg(all(feature = "discovery", feature = "runtime-tokio"))]
+fn local_host_name(host: &str) -> Option<(&str, u16)> {
+    let (hostname, port) = host.rsplit_once(':')?;
+    let port = port.parse().ok()?;
+    hostname
+        .trim_end_matches('.')
+        .to_ascii_lowercase()
+        .ends_with(".local")
+        .then_some((hostname, port))
+}
+
+/// Resolves a `.local` host name with mDNS, returns `None` if the host name can't be resolved.
+#[cfg(all(feature = "discovery", feature = "runtime-tokio"))]
+async fn resolve_mdns(host: &str) -> Option<Vec<SocketAddr>> {
+    let (hostname, port) = local_host_name(host)?;
+    match discovery::resolve_hostname(hostname, MDNS_RESOLVE_TIMEOUT).await {
+        Ok(ips) if !ips.is_empty() => {
+            tracing::debug!("Resolved {hostname} with mDNS: {ips:?}");
+            Some(
+                ips.into_iter()
+                    .map(|ip| SocketAddr::new(ip, port))
+                    .collect(),
+            )
+        }
+        Ok(_) => {
+            tracing::debug!("No mDNS answer for {hostname}, using the system resolver");
+            None
+        }
+        Err(e) => {
+            tracing::debug!("Failed to resolve {hostname} with mDNS: {e}");
+            None
+        }
+    }
+}
+
+/// Options applied to the socket of the TCP connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct SocketOptions {
+    pub(super) nodelay: bool,
+    /// Interval between keepalive probes and the number of unanswered probes after which the connection is dropped.
+    pub(super) keepalive: Option<(Duration, u32)>,
+}
+
+impl Default for SocketOptions {
+    fn default() -> Self {
+        Self {
+            nodelay: true,
+            keepalive: None,
+        }
+    }
+}
+
+#[cfg(feature = "runtime-tokio")]
+impl SocketOptions {
+    /// Applies the options to the TCP connection.
+    ///
+    /// Platforms without support for the keepalive interval and number of probes only use the
+    /// interval as the idle time before the first probe.
+    pub(super) fn apply(self, stream: &TcpStream) -> Result<(), ClientError> {
+        let socket_error = |source| ConnectionError::SocketOptions { source };
+        stream.set_nodelay(self.nodelay).map_err(socket_error)?;
+        if let Some((interval, retries)) = self.keepalive {
+            SockRef::from(stream)
+                .set_tcp_keepalive(&keepalive_params(interval, retries))
+                .map_err(socket_error)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(
+    feature = "runtime-tokio",
+    any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "windows",
+    )
+))]
+const fn keepalive_params(interval: Duration, retries: u32) -> TcpKeepalive {
+    TcpKeepalive::new()
+        .with_time(interval)
+        .with_interval(interval)
+        .with_retries(retries)
+}
+
+#[cfg(all(
+    feature = "runtime-tokio",
+    not(any(
+        target_os = "android",
+        target_os = "freebsd",
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "windows",
+    ))
+))]
+const fn keepalive_params(interval: Duration, _retries: u32) -> TcpKeepalive {
+    TcpKeepalive::new().with_time(interval)
+}
+
+/// Connects to the first of the addresses that accepts the connection.
+///
+/// Attempts are started in order, the next attempt starts when the previous one fails or doesn't
+/// complete within [`CONNECTION_ATTEMPT_DELAY`]. Pending attempts are dropped once one succeeds.
+#[cfg(feature = "runtime-tokio")]
+async fn connect_any(
+    addrs: Vec<SocketAddr>,
+    local_address: Option<IpAddr>,
+) -> io::Result<TcpStream> {
+    let mut remaining = addrs
+        .into_iter()
+        .filter(move |addr| local_address.is_none_or(|local| local.is_ipv4() == addr.is_ipv4()));
+    let mut attempts: Vec<ConnectAttempt> = Vec::new();
+    let mut last_error = None;
+    let mut delay = Box::pin(sleep(Duration::ZERO));
+    poll_fn(move |cx| {
+        loop {
+            if attempts.is_empty() || delay.as_mut().poll(cx).is_ready() {
+                if let Some(addr) = remaining.next() {
+                    attempts.push(Box::pin(connect_from(local_address, addr)));
+                    delay
+                        .as_mut()
+                        .reset(Instant::now() + CONNECTION_ATTEMPT_DELAY);
+                    continue;
+                }
+                if attempts.is_empty() {
+                    return Poll::Ready(Err(last_error.take().unwrap_or_else(|| {
+                        io::Error::new(io::ErrorKind::NotFound, "No addresses to connect to")
+                    })));
+                }
+            }
+
+            let mut failed = false;
+            let mut index = 0;
+            while let Some(attempt) = attempts.get_mut(index) {
+                match attempt.as_mut().poll(cx) {
+                    Poll::Ready(Ok(stream)) => return Poll::Ready(Ok(stream)),
+                    Poll::Ready(Err(e)) => {
+                        tracing::debug!("Connection attempt failed: {e}");
+                        drop(attempts.swap_remove(index));
+                        last_error = Some(e);
+                        failed = true;
+                    }
+                    Poll::Pending => index += 1,
+                }
+            }
+            if !failed {
+                return Poll::Pending;
+            }
+        }
+    })
+    .await
+}
+
+/// Connects to the address, from the local address if given.
+#[cfg(feature = "runtime-tokio")]
+async fn connect_from(local_address: Option<IpAddr>, addr: SocketAddr) -> io::Result<TcpStream> {
+    let Some(local_address) = local_address else {
+        return TcpStream::connect(addr).await;
+    };
+    let socket = if addr.is_ipv4() {
+        TcpSocket::new_v4()?
+    } else {
+        TcpSocket::new_v6()?
+    };
+    socket.bind(SocketAddr::new(local_address, 0))?;
+    socket.connect(addr).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_address_display() {
+        assert_eq!(
+            ServerAddress::from("livingroom.local:6053").to_string(),
+            "livingroom.local:6053"
+        );
+        let addrs: Vec<SocketAddr> = vec![
+            "[fd00::2]:6053".parse().unwrap(),
+            "192.168.0.2:6053".parse().unwrap(),
+        ];
+        assert_eq!(
+            ServerAddress::from(addrs).to_string(),
+            "[fd00::2]:6053, 192.168.0.2:6053"
+        );
+    }
+
+    #[cfg(feature = "discovery")]
+    #[test]
+    fn test_local_host_name() {
+        assert_eq!(
+            local_host_name("livingroom.local:6053"),
+            Some(("livingroom.local", 6053))
+        );
+        assert_eq!(
+            local_host_name("Livingroom.LOCAL.:6053"),
+            Some(("Livingroom.LOCAL.", 6053))
+        );
+        assert_eq!(local_host_name("livingroom.lan:6053"), None);
+        assert_eq!(local_host_name("192.168.0.2:6053"), None);
+        assert_eq!(local_host_name("livingroom.local"), None);
+    }
+}
@@ -0,0 +1,203 @@
+use std::{
+    fmt::Debug,
+    mem::{Discriminant, discriminant},
+};
+
+use super::message_writer::MessageWriter;
+use crate::{error::ClientError, proto::EspHomeMessage, states::entity_id};
+
+/// Messages collected to be written to the device at once.
+///
+/// All messages are written with a single write on the connection, which reduces the overhead of
+/// sending a burst of small messages, like commands for several lights. Messages are only sent on
+/// [`WriteBatch::flush`], dropping the batch discards them.
+///
+/// With [`WriteBatch::coalesce_commands`], only the most recent command for each entity is kept, e.g.
+/// rapid number slider updates collapse into the final value.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, types::{SwitchCommandRequest, ButtonCommandRequest}};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// let mut batch = client.begin_batch();
+/// batch
+///     .push(SwitchCommandRequest { key: 1, state: true, ..Default::default() })
+///     .push(ButtonCommandRequest { key: 2, ..Default::default() });
+/// batch.flush().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "messages are only sent when the batch is flushed"]
+pub struct WriteBatch {
+    writer: MessageWriter,
+    messages: Vec<EspHomeMessage>,
+    /// Entity of each command in the messages, only tracked when coalescing commands.
+    commands: Option<Vec<Option<CommandTarget>>>,
+}
+
+/// Kind of command, and device ID and key of the entity it is sent to.
+type CommandTarget = (Discriminant<EspHomeMessage>, u32, u32);
+
+impl WriteBatch {
+    pub(super) const fn new(writer: MessageWriter) -> Self {
+        Self {
+            writer,
+            messages: Vec::new(),
+            commands: None,
+        }
+    }
+
+    /// Keeps only the most recent command for each entity, dropping the commands pushed before it.
+    ///
+    /// The kept command is moved to the end of the batch. Only commands which carry the complete
+    /// target state of an entity are coalesced: switch, number, select, text, date/time and lock
+    /// commands. Commands updating a part of the state, like a light command setting only the
+    /// brightness, button presses and other messages are never coalesced.
+    pub fn coalesce_commands(mut self) -> Self {
+        self.commands = Some(self.messages.iter().map(|_| None).collect());
+        self
+    }
+
+    /// Adds a message to the batch.
+    pub fn push<M>(&mut self, message: M) -> &mut Self
+    where
+        M: Into<EspHomeMessage> + Debug,
+    {
+        let message: EspHomeMessage = message.into();
+        self.writer.span().in_scope(|| {
+            tracing::debug!("Send (batched): {message}");
+            tracing::trace!("Send (batched): {message:?}");
+        });
+        if let Some(commands) = &mut self.commands {
+            let target = command_target(&message);
+            if let Some(index) = target
+                .and_then(|target| commands.iter().position(|command| *command == Some(target)))
+            {
+                commands.remove(index);
+                self.messages.remove(index);
+            }
+            commands.push(target);
+        }
+        self.messages.push(message);
+        self
+    }
+
+    /// Returns the number of messages in the batch.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Returns `true` if no messages were added to the batch.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Writes all messages of the batch to the device.
+    ///
+    /// Nothing is written if any of the messages isn't supported by the device, see
+    /// [`EspHomeClientBuilder::negotiate_api_version`](super::EspHomeClientBuilder::negotiate_api_version).
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails for example due to a disconnected stream.
+    pub async fn flush(self) -> Result<(), ClientError> {
+        self.writer.write_messages(self.messages).await
+    }
+}
+
+/// Gets the target of a command which sets the complete state of an entity.
+const fn command_target(message: &EspHomeMessage) -> Option<CommandTarget> {
+    let (device_id, key) = match message {
+        EspHomeMessage::SwitchCommandRequest(command) => entity_id!(command),
+        EspHomeMessage::NumberCommandRequest(command) => entity_id!(command),
+        EspHomeMessage::SelectCommandRequest(command) => entity_id!(command),
+        EspHomeMessage::LockCommandRequest(command) => entity_id!(command),
+        #[cfg(not(any(feature = "api-1-8")))]
+        EspHomeMessage::TextCommandRequest(command) => entity_id!(command),
+        #[cfg(not(any(feature = "api-1-8")))]
+        EspHomeMessage::DateCommandRequest(command) => entity_id!(command),
+        #[cfg(not(any(feature = "api-1-8")))]
+        EspHomeMessage::TimeCommandRequest(command) => entity_id!(command),
+        #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+        EspHomeMessage::DateTimeCommandRequest(command) => entity_id!(command),
+        _ => return None,
+    };
+    Some((discriminant(message), device_id, key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::{
+        ButtonCommandRequest, LightCommandRequest, NumberCommandRequest, SwitchCommandRequest,
+    };
+
+    #[test]
+    fn test_command_target() {
+        let mut number = NumberCommandRequest {
+            key: 1,
+            ..Default::default()
+        };
+        number.state = 0.5;
+        let number = EspHomeMessage::from(number);
+        let switch = EspHomeMessage::from(SwitchCommandRequest {
+            key: 1,
+            ..Default::default()
+        });
+        assert_eq!(
+            command_target(&number),
+            command_target(
+                &NumberCommandRequest {
+                    key: 1,
+                    ..Default::default()
+                }
+                .into()
+            )
+        );
+        assert_ne!(command_target(&number), command_target(&switch));
+        assert_eq!(
+            command_target(&ButtonCommandRequest::default().into()),
+            None
+        );
+    }
+
+    #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+    #[test]
+    fn test_command_target_device_id() {
+        let switch = |device_id| {
+            command_target(
+                &SwitchCommandRequest {
+                    key: 1,
+                    device_id,
+                    ..Default::default()
+                }
+                .into(),
+            )
+        };
+        assert_ne!(switch(1), switch(2));
+    }
+
+    #[test]
+    fn test_partial_commands_not_coalesced() {
+        // A light command only carries the fields it changes
+        let turn_on = LightCommandRequest {
+            key: 1,
+            has_state: true,
+            state: true,
+            ..Default::default()
+        };
+        let brightness = LightCommandRequest {
+            key: 1,
+            has_brightness: true,
+            brightness: 0.5,
+            ..Default::default()
+        };
+        assert_eq!(command_target(&turn_on.into()), None);
+        assert_eq!(command_target(&brightness.into()), None);
+    }
+}
@@ -0,0 +1,335 @@
+use super::EspHomeClient;
+use crate::{
+    error::{BluetoothError, ClientError},
+    proto::{
+        BluetoothDeviceConnectionResponse, BluetoothDeviceRequest, BluetoothDeviceRequestType,
+        BluetoothGattGetServicesRequest, BluetoothGattReadDescriptorRequest,
+        BluetoothGattReadRequest, BluetoothGattService, BluetoothGattWriteDescriptorRequest,
+        BluetoothGattWriteRequest, EspHomeMessage,
+    },
+};
+
+/// Connects to the Bluetooth device, and waits until the proxy reports the connection state.
+pub(super) async fn connect(
+    client: &EspHomeClient,
+    address: u64,
+    address_type: u32,
+    cache: bool,
+) -> Result<BluetoothDeviceConnectionResponse, ClientError> {
+    let request_type = if cache {
+        BluetoothDeviceRequestType::ConnectV3WithCache
+    } else {
+        BluetoothDeviceRequestType::ConnectV3WithoutCache
+    };
+    client
+        .try_write(BluetoothDeviceRequest {
+            address,
+            request_type: request_type.into(),
+            has_address_type: true,
+            address_type,
+        })
+        .await?;
+    let message = client
+        .with_request_timeout(client.wait_for(|message| match message {
+            EspHomeMessage::BluetoothDeviceConnectionResponse(response) => {
+                response.address == address
+            }
+            _ => false,
+        }))
+        .await?;
+    client.publish(&message);
+    let EspHomeMessage::BluetoothDeviceConnectionResponse(response) = message else {
+        return Err(unexpected(&message));
+    };
+    if !response.connected {
+        return Err(BluetoothError::ConnectionFailed {
+            address,
+            error: response.error,
+        }
+        .into());
+    }
+    Ok(response)
+}
+
+/// Disconnects from the Bluetooth device, and waits until the proxy reports it's disconnected.
+pub(super) async fn disconnect(client: &EspHomeClient, address: u64) -> Result<(), ClientError> {
+    device_request(client, address, BluetoothDeviceRequestType::Disconnect).await?;
+    let message = client
+        .with_request_timeout(client.wait_for(|message| match message {
+            EspHomeMessage::BluetoothDeviceConnectionResponse(response) => {
+                response.address == address && !response.connected
+            }
+            _ => false,
+        }))
+        .await?;
+    client.publish(&message);
+    Ok(())
+}
+
+/// Pairs with the connected device, and waits until the proxy reports the result.
+pub(super) async fn pair(client: &EspHomeClient, address: u64) -> Result<(), ClientError> {
+    device_request(client, address, BluetoothDeviceRequestType::Pair).await?;
+    let message = client
+        .with_request_timeout(client.wait_for(|message| match message {
+            EspHomeMessage::BluetoothDevicePairingResponse(response) => response.address == address,
+            _ => false,
+        }))
+        .await?;
+    client.publish(&message);
+    match message {
+        EspHomeMessage::BluetoothDevicePairingResponse(response) if response.paired => Ok(()),
+        EspHomeMessage::BluetoothDevicePairingResponse(response) => {
+            Err(BluetoothError::PairingFailed {
+                address,
+                error: response.error,
+            }
+            .into())
+        }
+        other => Err(unexpected(&other)),
+    }
+}
+
+/// Removes the pairing with the device, and waits until the proxy reports the result.
+pub(super) async fn unpair(client: &EspHomeClient, address: u64) -> Result<(), ClientError> {
+    device_request(client, address, BluetoothDeviceRequestType::Unpair).await?;
+    let message = client
+        .with_request_timeout(client.wait_for(|message| match message {
+            EspHomeMessage::BluetoothDeviceUnpairingResponse(response) => {
+                response.address == address
+            }
+            _ => false,
+        }))
+        .await?;
+    client.publish(&message);
+    match message {
+        EspHomeMessage::BluetoothDeviceUnpairingResponse(response) if response.success => Ok(()),
+        EspHomeMessage::BluetoothDeviceUnpairingResponse(response) => {
+            Err(BluetoothError::UnpairingFailed {
+                address,
+                error: response.error,
+            }
+            .into())
+        }
+        other => Err(unexpected(&other)),
+    }
+}
+
+/// Clears the GATT services cached by the proxy for the device, and waits until the proxy reports the result.
+pub(super) async fn clear_cache(client: &EspHomeClient, address: u64) -> Result<(), ClientError> {
+    device_request(client, address, BluetoothDeviceRequestType::ClearCache).await?;
+    let message = client
+        .with_request_timeout(client.wait_for(|message| match message {
+            EspHomeMessage::BluetoothDeviceClearCacheResponse(response) => {
+                response.address == address
+            }
+            _ => false,
+        }))
+        .await?;
+    client.publish(&message);
+    match message {
+        EspHomeMessage::BluetoothDeviceClearCacheResponse(response) if response.success => Ok(()),
+        EspHomeMessage::BluetoothDeviceClearCacheResponse(response) => {
+            Err(BluetoothError::ClearCacheFailed {
+                address,
+                error: response.error,
+            }
+            .into())
+        }
+        other => Err(unexpected(&other)),
+    }
+}
+
+/// Requests the services of the connected device, collecting them until the proxy reports it's done.
+pub(super) async fn get_services(
+    client: &EspHomeClient,
+    address: u64,
+) -> Result<Vec<BluetoothGattService>, ClientError> {
+    client
+        .try_write(BluetoothGattGetServicesRequest { address })
+        .await?;
+    let mut services = Vec::new();
+    client
+        .with_request_timeout(async {
+            loop {
+                let message = wait_for_gatt(client, address, 0, |message| match message {
+                    EspHomeMessage::BluetoothGattGetServicesResponse(response) => {
+                        response.address == address
+                    }
+                    EspHomeMessage::BluetoothGattGetServicesDoneResponse(response) => {
+                        response.address == address
+                    }
+                    _ => false,
+                })
+                .await?;
+                match message {
+                    EspHomeMessage::BluetoothGattGetServicesResponse(response) => {
+                        services.extend(response.services);
+                    }
+                    _ => return Ok(services),
+                }
+            }
+        })
+        .await
+}
+
+/// Reads the value of a characteristic.
+pub(super) async fn read(
+    client: &EspHomeClient,
+    address: u64,
+    handle: u32,
+) -> Result<Vec<u8>, ClientError> {
+    client
+        .try_write(BluetoothGattReadRequest { address, handle })
+        .await?;
+    read_response(client, address, handle).await
+}
+
+/// Reads the value of a descriptor, which the proxy answers like a characteristic read.
+pub(super) async fn read_descriptor(
+    client: &EspHomeClient,
+    address: u64,
+    handle: u32,
+) -> Result<Vec<u8>, ClientError> {
+    client
+        .try_write(BluetoothGattReadDescriptorRequest { address, handle })
+        .await?;
+    read_response(client, address, handle).await
+}
+
+/// Writes the value of a characteristic, only waiting for the write to be confirmed if a response is requested.
+pub(super) async fn write(
+    client: &EspHomeClient,
+    address: u64,
+    handle: u32,
+    data: Vec<u8>,
+    response: bool,
+) -> Result<(), ClientError> {
+    client
+        .try_write(BluetoothGattWriteRequest {
+            address,
+            handle,
+            response,
+            data,
+        })
+        .await?;
+    if response {
+        write_response(client, address, handle).await?;
+    }
+    Ok(())
+}
+
+/// Writes the value of a descriptor, and waits for the write to be confirmed.
+pub(super) async fn write_descriptor(
+    client: &EspHomeClient,
+    address: u64,
+    handle: u32,
+    data: Vec<u8>,
+) -> Result<(), ClientError> {
+    client
+        .try_write(BluetoothGattWriteDescriptorRequest {
+            address,
+            handle,
+            data,
+        })
+        .await?;
+    write_response(client, address, handle).await
+}
+
+async fn device_request(
+    client: &EspHomeClient,
+    address: u64,
+    request_type: BluetoothDeviceRequestType,
+) -> Result<(), ClientError> {
+    client
+        .try_write(BluetoothDeviceRequest {
+            address,
+            request_type: request_type.into(),
+            ..Default::default()
+        })
+        .await
+}
+
+async fn read_response(
+    client: &EspHomeClient,
+    address: u64,
+    handle: u32,
+) -> Result<Vec<u8>, ClientError> {
+    let message = client
+        .with_request_timeout(wait_for_gatt(
+            client,
+            address,
+            handle,
+            |message| match message {
+                EspHomeMessage::BluetoothGattReadResponse(response) => {
+                    response.address == address && response.handle == handle
+                }
+                _ => false,
+            },
+        ))
+        .await?;
+    let EspHomeMessage::BluetoothGattReadResponse(response) = message else {
+        return Err(unexpected(&message));
+    };
+    Ok(response.data)
+}
+
+async fn write_response(
+    client: &EspHomeClient,
+    address: u64,
+    handle: u32,
+) -> Result<(), ClientError> {
+    client
+        .with_request_timeout(wait_for_gatt(
+            client,
+            address,
+            handle,
+            |message| match message {
+                EspHomeMessage::BluetoothGattWriteResponse(response) => {
+                    response.address == address && response.handle == handle
+                }
+                _ => false,
+            },
+        ))
+        .await?;
+    Ok(())
+}
+
+/// Waits for the response to a GATT operation, failing if the proxy reports an error for the same handle,
+/// or the device disconnects.
+async fn wait_for_gatt(
+    client: &EspHomeClient,
+    address: u64,
+    handle: u32,
+    mut is_response: impl FnMut(&EspHomeMessage) -> bool,
+) -> Result<EspHomeMessage, ClientError> {
+    let message = client
+        .wait_for(|message| match message {
+            EspHomeMessage::BluetoothGattErrorResponse(error) => {
+                error.address == address && error.handle == handle
+            }
+            EspHomeMessage::BluetoothDeviceConnectionResponse(response) => {
+                response.address == address && !response.connected
+            }
+            other => is_response(other),
+        })
+        .await?;
+    client.publish(&message);
+    match message {
+        EspHomeMessage::BluetoothGattErrorResponse(error) => Err(BluetoothError::Gatt {
+            address,
+            handle,
+            error: error.error,
+        }
+        .into()),
+        EspHomeMessage::BluetoothDeviceConnectionResponse(_) => {
+            Err(BluetoothError::Disconnected { address }.into())
+        }
+        other => Ok(other),
+    }
+}
+
+fn unexpected(message: &EspHomeMessage) -> ClientError {
+    ClientError::InvalidInternalState {
+        reason: format!("Unexpected response type: {message:?}"),
+    }
+}
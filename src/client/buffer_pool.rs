@@ -0,0 +1,142 @@
+use std::sync::{Arc, Mutex, PoisonError};
+
+use bytes::BytesMut;
+
+/// Capacity of the buffers handed out by default, the maximum size of a frame.
+const DEFAULT_CAPACITY: usize = 65535;
+
+/// Number of idle buffers kept by default.
+const DEFAULT_MAX_IDLE: usize = 16;
+
+/// Pool of read buffers shared by many connections, see
+/// [`EspHomeClientBuilder::buffer_pool`](super::EspHomeClientBuilder::buffer_pool).
+///
+/// Without a pool, every connection keeps its own read buffer for its whole lifetime. With a pool, a
+/// connection only borrows a buffer while it reads from the stream, and returns it as soon as no
+/// partial frame is left. Idle connections then hold no buffer at all, and the memory of a hub with
+/// many connections is bound by the number of connections reading at the same time.
+///
+/// Writes also encode their frames into a buffer of the pool, and encrypted connections decrypt every
+/// message into a buffer of the pool, so no buffer is allocated per message. The memory of a decrypted
+/// message is reclaimed by the pool once the message is decoded.
+///
+/// Clones share the same buffers.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{BufferPool, EspHomeClient};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let pool = BufferPool::new().max_idle(4);
+/// let mut clients = Vec::new();
+/// for address in ["192.168.0.2:6053", "192.168.0.3:6053"] {
+///     clients.push(
+///         EspHomeClient::builder()
+///             .address(address)
+///             .buffer_pool(pool.clone())
+///             .connect()
+///             .await?,
+///     );
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BufferPool {
+    buffers: Arc<Mutex<Vec<BytesMut>>>,
+    capacity: usize,
+    max_idle: usize,
+}
+
+impl Default for BufferPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BufferPool {
+    /// Creates a pool of 64KB buffers, keeping up to 16 idle buffers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            buffers: Arc::new(Mutex::new(Vec::new())),
+            capacity: DEFAULT_CAPACITY,
+            max_idle: DEFAULT_MAX_IDLE,
+        }
+    }
+
+    /// Sets the capacity of the buffers handed out, buffers grow when a frame doesn't fit.
+    ///
+    /// Defaults to 65535 bytes.
+    #[must_use]
+    pub const fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets the maximum number of idle buffers kept in the pool, returned buffers beyond it are freed.
+    ///
+    /// Defaults to 16 buffers.
+    #[must_use]
+    pub const fn max_idle(mut self, max_idle: usize) -> Self {
+        self.max_idle = max_idle;
+        self
+    }
+
+    /// Gets the number of idle buffers in the pool.
+    #[must_use]
+    pub fn idle(&self) -> usize {
+        self.buffers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .len()
+    }
+
+    /// Takes a buffer from the pool, or allocates one if the pool is empty.
+    pub(crate) fn take(&self) -> BytesMut {
+        let buffer = self
+            .buffers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop();
+        let mut buffer = buffer.unwrap_or_default();
+        // Reclaims the space of the frames split off before the buffer was returned
+        buffer.reserve(self.capacity);
+        buffer
+    }
+
+    /// Returns an empty buffer to the pool.
+    pub(crate) fn put(&self, buffer: BytesMut) {
+        debug_assert!(buffer.is_empty(), "Only empty buffers are returned");
+        let mut buffers = self.buffers.lock().unwrap_or_else(PoisonError::into_inner);
+        if buffers.len() < self.max_idle {
+            buffers.push(buffer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_pool_reuses_buffers() {
+        let pool = BufferPool::new().capacity(1024).max_idle(1);
+        let mut buffer = pool.take();
+        assert!(buffer.capacity() >= 1024);
+        buffer.extend_from_slice(&[1, 2, 3]);
+        drop(buffer.split());
+        pool.put(buffer);
+        assert_eq!(pool.idle(), 1);
+
+        let first = pool.take();
+        let second = pool.take();
+        assert!(first.capacity() >= 1024);
+        assert!(second.capacity() >= 1024);
+        assert_eq!(pool.idle(), 0);
+
+        pool.put(first);
+        pool.put(second);
+        assert_eq!(pool.idle(), 1);
+    }
+}
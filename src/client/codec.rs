@@ -0,0 +1,150 @@
+//! Codecs for the ESPHome framing, to use with the tokio-util `Framed` transports.
+
+#![allow(
+    clippy::module_name_repetitions,
+    reason = "Codec suffix matches the tokio-util naming"
+)]
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder};
+
+use super::{noise, plain};
+use crate::{
+    error::{CodecError, ProtocolError, StreamError},
+    proto::EspHomeMessage,
+};
+
+/// Codec for plain text connections, decoding frames into messages.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{codec::PlainCodec, types::{EspHomeMessage, HelloRequest}};
+/// use futures_util::{SinkExt as _, StreamExt as _};
+/// use tokio::net::TcpStream;
+/// use tokio_util::codec::Framed;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let stream = TcpStream::connect("192.168.0.2:6053").await?;
+/// let mut framed = Framed::new(stream, PlainCodec);
+/// framed.send(HelloRequest::default().into()).await?;
+/// while let Some(message) = framed.next().await {
+///     println!("Received: {:?}", message?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PlainCodec;
+
+impl Decoder for PlainCodec {
+    type Item = EspHomeMessage;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        plain::split_frame_from_buffer(src)?
+            .map(|(type_id, payload)| {
+                EspHomeMessage::decode_frame(type_id, &payload).map_err(|e| {
+                    ProtocolError::ValidationFailed {
+                        reason: format!("Failed to decode EspHomeMessage: {e}"),
+                    }
+                    .into()
+                })
+            })
+            .transpose()
+            .map_err(CodecError)
+    }
+}
+
+impl Encoder<EspHomeMessage> for PlainCodec {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: EspHomeMessage, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        let payload: Vec<u8> = item.into();
+        dst.extend_from_slice(&plain::create_frame(&payload)?);
+        Ok(())
+    }
+}
+
+/// Codec for the framing of noise encrypted connections.
+///
+/// Items are the payloads of the frames, encrypting and decrypting them, including the handshake,
+/// is up to the user of the codec.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoiseFrameCodec;
+
+impl Decoder for NoiseFrameCodec {
+    type Item = BytesMut;
+    type Error = CodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        Ok(noise::read_frame_from_buffer(src)?)
+    }
+}
+
+impl Encoder<Vec<u8>> for NoiseFrameCodec {
+    type Error = CodecError;
+
+    fn encode(&mut self, item: Vec<u8>, dst: &mut BytesMut) -> Result<(), Self::Error> {
+        if u16::try_from(item.len()).is_err() {
+            return Err(CodecError(
+                StreamError::FrameTooLarge {
+                    size: item.len(),
+                    max_size: usize::from(u16::MAX),
+                }
+                .into(),
+            ));
+        }
+        dst.extend_from_slice(&noise::create_noise_frame(item));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::ClientError, proto::HelloRequest};
+
+    #[test]
+    fn test_plain_codec_round_trip() {
+        let hello = HelloRequest {
+            client_info: "codec".to_owned(),
+            api_version_major: 1,
+            api_version_minor: 10,
+        };
+        let mut buffer = BytesMut::new();
+        PlainCodec
+            .encode(hello.clone().into(), &mut buffer)
+            .unwrap();
+        PlainCodec
+            .encode(hello.clone().into(), &mut buffer)
+            .unwrap();
+
+        for _ in 0..2 {
+            let message = PlainCodec.decode(&mut buffer).unwrap();
+            assert!(
+                matches!(&message, Some(EspHomeMessage::HelloRequest(request)) if *request == hello),
+                "Unexpected message: {message:?}"
+            );
+        }
+        assert!(PlainCodec.decode(&mut buffer).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_noise_frame_codec() {
+        let mut buffer = BytesMut::new();
+        NoiseFrameCodec.encode(vec![1, 2, 3], &mut buffer).unwrap();
+        assert_eq!(&buffer[..], &[0x01, 0x00, 0x03, 1, 2, 3]);
+        let frame = NoiseFrameCodec.decode(&mut buffer).unwrap().unwrap();
+        assert_eq!(&frame[..], &[1, 2, 3]);
+
+        let error = NoiseFrameCodec
+            .encode(vec![0; 70_000], &mut buffer)
+            .unwrap_err();
+        assert!(
+            matches!(
+                error,
+                CodecError(ClientError::Stream(StreamError::FrameTooLarge { .. }))
+            ),
+            "Unexpected error: {error}"
+        );
+    }
+}
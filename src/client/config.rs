@@ -0,0 +1,199 @@
+use std::{path::PathBuf, time::Duration};
+
+use super::BackoffPolicy;
+
+/// Connection settings for an ESPHome API server, which can be loaded from a configuration file.
+///
+/// All fields are optional and fall back to the defaults of [`EspHomeClientBuilder`].
+/// With the `serde` feature enabled, the config can be deserialized from any serde format (TOML, YAML, JSON, ...).
+///
+/// ```rust
+/// use esphome_client::{ClientConfig, EspHomeClientBuilder, KeepaliveConfig, ReconnectConfig};
+///
+/// let config = ClientConfig {
+///     address: Some("192.168.0.2:6053".to_owned()),
+///     key_file: Some("/etc/esphome/livingroom.key".into()),
+///     timeout_ms: Some(5_000),
+///     keepalive: Some(KeepaliveConfig { interval_ms: 30_000, retries: 3 }),
+///     reconnect: Some(ReconnectConfig { max_attempts: Some(10), ..ReconnectConfig::default() }),
+///     ..ClientConfig::default()
+/// };
+/// let builder = EspHomeClientBuilder::from_config(config);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct ClientConfig {
+    /// Address of the ESPHome API server in the format "host:port".
+    pub address: Option<String>,
+    /// 32-byte base64-encoded key used for encrypted communication.
+    pub key: Option<String>,
+    /// Path to a file containing the base64-encoded key, used when `key` is not set.
+    pub key_file: Option<PathBuf>,
+    /// Password used to authenticate the client (deprecated by ESPHome).
+    pub password: Option<String>,
+    /// Client info string sent in the `HelloRequest`.
+    pub client_info: Option<String>,
+    /// Timeout in milliseconds of each phase of connecting: the tcp connection, handshake and setup.
+    pub timeout_ms: Option<u64>,
+    /// Timeout in milliseconds of the tcp connection, overrides `timeout_ms`.
+    pub connect_timeout_ms: Option<u64>,
+    /// Timeout in milliseconds of the noise handshake, overrides `timeout_ms`.
+    pub handshake_timeout_ms: Option<u64>,
+    /// Timeout in milliseconds of the connection setup, overrides `timeout_ms`.
+    pub setup_timeout_ms: Option<u64>,
+    /// Time in milliseconds to wait for the response of a request.
+    pub request_timeout_ms: Option<u64>,
+    /// Time in milliseconds without any received message after which the connection is considered stalled.
+    pub idle_timeout_ms: Option<u64>,
+    /// Whether the `HelloRequest`/`ConnectRequest` setup sequence is performed, defaults to `true`.
+    pub connection_setup: Option<bool>,
+    /// Whether ping requests are answered automatically, defaults to `true`.
+    pub handle_ping: Option<bool>,
+    /// Whether the `TCP_NODELAY` option is set on the connection, defaults to `true`.
+    pub tcp_nodelay: Option<bool>,
+    /// TCP keepalive probes, disabled by default.
+    pub keepalive: Option<KeepaliveConfig>,
+    /// Backoff between reconnection attempts, used by
+    /// [`EspHomeClientBuilder::connect_managed`](super::EspHomeClientBuilder::connect_managed).
+    pub reconnect: Option<ReconnectConfig>,
+}
+
+/// TCP keepalive settings of a [`ClientConfig`], see
+/// [`EspHomeClientBuilder::tcp_keepalive`](super::EspHomeClientBuilder::tcp_keepalive).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(deny_unknown_fields)
+)]
+#[allow(
+    clippy::module_name_repetitions,
+    reason = "Named after the config it is part of"
+)]
+pub struct KeepaliveConfig {
+    /// Time in milliseconds the connection is idle before a probe is sent.
+    pub interval_ms: u64,
+    /// Number of unanswered probes after which the connection is dropped.
+    pub retries: u32,
+}
+
+/// Reconnect settings of a [`ClientConfig`], unset fields fall back to the defaults of [`BackoffPolicy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(default, deny_unknown_fields)
+)]
+#[allow(
+    clippy::module_name_repetitions,
+    reason = "Named after the config it is part of"
+)]
+pub struct ReconnectConfig {
+    /// Delay in milliseconds before the first reconnection attempt.
+    pub initial_delay_ms: Option<u64>,
+    /// Maximum delay in milliseconds between reconnection attempts.
+    pub max_delay_ms: Option<u64>,
+    /// Factor by which the delay grows after each failed attempt.
+    pub multiplier: Option<u32>,
+    /// Maximum number of consecutive reconnection attempts, unlimited by default.
+    pub max_attempts: Option<u32>,
+}
+
+impl From<ReconnectConfig> for BackoffPolicy {
+    fn from(config: ReconnectConfig) -> Self {
+        let mut policy = Self::new();
+        if let Some(delay) = config.initial_delay_ms {
+            policy = policy.initial_delay(Duration::from_millis(delay));
+        }
+        if let Some(delay) = config.max_delay_ms {
+            policy = policy.max_delay(Duration::from_millis(delay));
+        }
+        if let Some(multiplier) = config.multiplier {
+            policy = policy.multiplier(multiplier);
+        }
+        if let Some(attempts) = config.max_attempts {
+            policy = policy.max_attempts(attempts);
+        }
+        policy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EspHomeClientBuilder;
+
+    #[test]
+    fn test_from_config_defaults() {
+        let builder = EspHomeClientBuilder::from_config(ClientConfig::default());
+        let defaults = EspHomeClientBuilder::new();
+        assert_eq!(builder.addr, defaults.addr);
+        assert_eq!(builder.client_info, defaults.client_info);
+        assert_eq!(builder.connect_timeout, defaults.connect_timeout);
+        assert_eq!(builder.setup_timeout, defaults.setup_timeout);
+        assert_eq!(builder.request_timeout, defaults.request_timeout);
+        assert_eq!(builder.idle_timeout, None);
+        assert!(builder.connection_setup);
+        assert!(builder.handle_ping);
+        assert_eq!(builder.socket_options, defaults.socket_options);
+        assert_eq!(builder.backoff_policy, None);
+    }
+
+    #[test]
+    fn test_from_config_overrides() {
+        let builder = EspHomeClientBuilder::from_config(ClientConfig {
+            address: Some("127.0.0.1:6053".to_owned()),
+            key: Some("key".to_owned()),
+            key_file: Some("/tmp/key".into()),
+            password: Some("secret".to_owned()),
+            client_info: Some("my-app".to_owned()),
+            timeout_ms: Some(1500),
+            request_timeout_ms: Some(2500),
+            idle_timeout_ms: Some(60_000),
+            connect_timeout_ms: None,
+            handshake_timeout_ms: Some(3000),
+            setup_timeout_ms: None,
+            connection_setup: Some(false),
+            handle_ping: Some(false),
+            tcp_nodelay: Some(false),
+            keepalive: Some(KeepaliveConfig {
+                interval_ms: 30_000,
+                retries: 3,
+            }),
+            reconnect: Some(ReconnectConfig {
+                initial_delay_ms: Some(500),
+                max_attempts: Some(5),
+                ..ReconnectConfig::default()
+            }),
+        });
+        assert_eq!(builder.addr, Some("127.0.0.1:6053".into()));
+        assert_eq!(builder.key.as_deref(), Some("key"));
+        assert_eq!(builder.key_file, Some(PathBuf::from("/tmp/key")));
+        assert_eq!(builder.password.as_deref(), Some("secret"));
+        assert_eq!(builder.client_info, "my-app");
+        assert_eq!(builder.connect_timeout, Duration::from_millis(1500));
+        assert_eq!(builder.handshake_timeout, Duration::from_secs(3));
+        assert_eq!(builder.setup_timeout, Duration::from_millis(1500));
+        assert_eq!(builder.request_timeout, Duration::from_millis(2500));
+        assert_eq!(builder.idle_timeout, Some(Duration::from_secs(60)));
+        assert!(!builder.connection_setup);
+        assert!(!builder.handle_ping);
+        assert!(!builder.socket_options.nodelay);
+        assert_eq!(
+            builder.socket_options.keepalive,
+            Some((Duration::from_secs(30), 3))
+        );
+        assert_eq!(
+            builder.backoff_policy,
+            Some(
+                BackoffPolicy::new()
+                    .initial_delay(Duration::from_millis(500))
+                    .max_attempts(5)
+            )
+        );
+    }
+}
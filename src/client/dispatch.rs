@@ -0,0 +1,98 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use crate::proto::{EspHomeMessage, MessageType};
+
+type Handler = Arc<dyn Fn(&EspHomeMessage) + Send + Sync>;
+
+/// Registry of callbacks per message type.
+#[derive(Default)]
+pub(super) struct Handlers {
+    handlers: Mutex<HashMap<u16, Vec<Handler>>>,
+}
+
+impl fmt::Debug for Handlers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let handlers = self.handlers.lock().unwrap_or_else(PoisonError::into_inner);
+        f.debug_struct("Handlers")
+            .field("types", &handlers.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Handlers {
+    /// Registers a callback for messages of type `T`.
+    pub(super) fn register<T: MessageType>(&self, handler: impl Fn(&T) + Send + Sync + 'static) {
+        let handler: Handler = Arc::new(move |message| {
+            if let Some(message) = T::from_message(message) {
+                handler(message);
+            }
+        });
+        self.handlers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(T::TYPE_ID)
+            .or_default()
+            .push(handler);
+    }
+
+    /// Calls all callbacks registered for the type of the message.
+    ///
+    /// Returns `false` if there are no callbacks for the message type.
+    pub(super) fn dispatch(&self, message: &EspHomeMessage) -> bool {
+        // The callbacks are called without holding the lock, so they can register new callbacks
+        let handlers = self
+            .handlers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&message.get_message_type())
+            .cloned()
+            .unwrap_or_default();
+        for handler in &handlers {
+            handler(message);
+        }
+        !handlers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::{PingRequest, SensorStateResponse};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[test]
+    fn test_dispatch_to_registered_type() {
+        let handlers = Handlers::default();
+        let last_key = Arc::new(AtomicU32::new(0));
+        handlers.register::<SensorStateResponse>({
+            let last_key = Arc::clone(&last_key);
+            move |state| last_key.store(state.key, Ordering::SeqCst)
+        });
+
+        let state = SensorStateResponse {
+            key: 42,
+            ..Default::default()
+        };
+        assert!(handlers.dispatch(&state.into()));
+        assert_eq!(last_key.load(Ordering::SeqCst), 42);
+        assert!(!handlers.dispatch(&PingRequest {}.into()));
+    }
+
+    #[test]
+    fn test_dispatch_to_multiple_handlers() {
+        let handlers = Handlers::default();
+        let calls = Arc::new(AtomicU32::new(0));
+        for _ in 0..2 {
+            let calls = Arc::clone(&calls);
+            handlers.register::<PingRequest>(move |_| {
+                calls.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+        assert!(handlers.dispatch(&PingRequest {}.into()));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,360 @@
+#[cfg(not(any(feature = "api-1-8")))]
+use crate::proto::{
+    ListEntitiesAlarmControlPanelResponse, ListEntitiesDateResponse, ListEntitiesTextResponse,
+    ListEntitiesTimeResponse,
+};
+#[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+use crate::proto::{
+    ListEntitiesDateTimeResponse, ListEntitiesEventResponse, ListEntitiesSirenResponse,
+    ListEntitiesUpdateResponse, ListEntitiesValveResponse,
+};
+#[cfg(not(any(
+    feature = "api-1-13",
+    feature = "api-1-12",
+    feature = "api-1-10",
+    feature = "api-1-9",
+    feature = "api-1-8"
+)))]
+use crate::proto::{
+    ListEntitiesInfraredResponse, ListEntitiesRadioFrequencyResponse,
+    ListEntitiesWaterHeaterResponse,
+};
+use crate::{
+    error::ClientError,
+    proto::{
+        EspHomeMessage, ListEntitiesBinarySensorResponse, ListEntitiesButtonResponse,
+        ListEntitiesCameraResponse, ListEntitiesClimateResponse, ListEntitiesCoverResponse,
+        ListEntitiesDoneResponse, ListEntitiesFanResponse, ListEntitiesLightResponse,
+        ListEntitiesLockResponse, ListEntitiesMediaPlayerResponse, ListEntitiesNumberResponse,
+        ListEntitiesRequest, ListEntitiesSelectResponse, ListEntitiesSensorResponse,
+        ListEntitiesServicesResponse, ListEntitiesSwitchResponse, ListEntitiesTextSensorResponse,
+        MessageType as _,
+    },
+};
+
+use super::EspHomeClient;
+
+/// Lists the entities of the device, other messages received in the meantime are buffered.
+pub(super) async fn list(client: &EspHomeClient) -> Result<Entities, ClientError> {
+    client.try_write(ListEntitiesRequest {}).await?;
+    client
+        .with_request_timeout(async {
+            let mut entities = Entities::default();
+            loop {
+                let message = client
+                    .wait_for(|message| {
+                        matches!(message, EspHomeMessage::ListEntitiesDoneResponse(_))
+                            || entities.add(message.clone()).is_none()
+                    })
+                    .await?;
+                if ListEntitiesDoneResponse::from_message(&message).is_some() {
+                    return Ok(entities);
+                }
+            }
+        })
+        .await
+}
+
+/// Snapshot of all entities exposed by a device, grouped by entity kind.
+///
+/// Created by [`EspHomeClient::list_entities`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Entities {
+    /// All binary sensors.
+    pub binary_sensors: Vec<ListEntitiesBinarySensorResponse>,
+    /// All covers.
+    pub covers: Vec<ListEntitiesCoverResponse>,
+    /// All fans.
+    pub fans: Vec<ListEntitiesFanResponse>,
+    /// All lights.
+    pub lights: Vec<ListEntitiesLightResponse>,
+    /// All sensors.
+    pub sensors: Vec<ListEntitiesSensorResponse>,
+    /// All switches.
+    pub switches: Vec<ListEntitiesSwitchResponse>,
+    /// All text sensors.
+    pub text_sensors: Vec<ListEntitiesTextSensorResponse>,
+    /// All user defined services (actions).
+    pub services: Vec<ListEntitiesServicesResponse>,
+    /// All cameras.
+    pub cameras: Vec<ListEntitiesCameraResponse>,
+    /// All climate devices.
+    pub climates: Vec<ListEntitiesClimateResponse>,
+    /// All numbers.
+    pub numbers: Vec<ListEntitiesNumberResponse>,
+    /// All selects.
+    pub selects: Vec<ListEntitiesSelectResponse>,
+    /// All locks.
+    pub locks: Vec<ListEntitiesLockResponse>,
+    /// All buttons.
+    pub buttons: Vec<ListEntitiesButtonResponse>,
+    /// All media players.
+    pub media_players: Vec<ListEntitiesMediaPlayerResponse>,
+    #[cfg(not(any(feature = "api-1-8")))]
+    /// All alarm control panels.
+    pub alarm_control_panels: Vec<ListEntitiesAlarmControlPanelResponse>,
+    #[cfg(not(any(feature = "api-1-8")))]
+    /// All texts.
+    pub texts: Vec<ListEntitiesTextResponse>,
+    #[cfg(not(any(feature = "api-1-8")))]
+    /// All dates.
+    pub dates: Vec<ListEntitiesDateResponse>,
+    #[cfg(not(any(feature = "api-1-8")))]
+    /// All times.
+    pub times: Vec<ListEntitiesTimeResponse>,
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// All sirens.
+    pub sirens: Vec<ListEntitiesSirenResponse>,
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// All events.
+    pub events: Vec<ListEntitiesEventResponse>,
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// All valves.
+    pub valves: Vec<ListEntitiesValveResponse>,
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// All date times.
+    pub date_times: Vec<ListEntitiesDateTimeResponse>,
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// All updates.
+    pub updates: Vec<ListEntitiesUpdateResponse>,
+    #[cfg(not(any(
+        feature = "api-1-13",
+        feature = "api-1-12",
+        feature = "api-1-10",
+        feature = "api-1-9",
+        feature = "api-1-8"
+    )))]
+    /// All water heaters.
+    pub water_heaters: Vec<ListEntitiesWaterHeaterResponse>,
+    #[cfg(not(any(
+        feature = "api-1-13",
+        feature = "api-1-12",
+        feature = "api-1-10",
+        feature = "api-1-9",
+        feature = "api-1-8"
+    )))]
+    /// All infrared transmitters/receivers.
+    pub infrared: Vec<ListEntitiesInfraredResponse>,
+    #[cfg(not(any(
+        feature = "api-1-13",
+        feature = "api-1-12",
+        feature = "api-1-10",
+        feature = "api-1-9",
+        feature = "api-1-8"
+    )))]
+    /// All radio frequency transmitters/receivers.
+    pub radio_frequencies: Vec<ListEntitiesRadioFrequencyResponse>,
+}
+
+impl Entities {
+    /// Adds a `ListEntities*Response` message to the group of its kind.
+    ///
+    /// Returns the message back if it's not an entity description.
+    #[allow(clippy::too_many_lines, reason = "One match arm per entity kind")]
+    pub fn add(&mut self, message: EspHomeMessage) -> Option<EspHomeMessage> {
+        match message {
+            EspHomeMessage::ListEntitiesBinarySensorResponse(entity) => {
+                self.binary_sensors.push(entity);
+            }
+            EspHomeMessage::ListEntitiesCoverResponse(entity) => {
+                self.covers.push(entity);
+            }
+            EspHomeMessage::ListEntitiesFanResponse(entity) => {
+                self.fans.push(entity);
+            }
+            EspHomeMessage::ListEntitiesLightResponse(entity) => {
+                self.lights.push(entity);
+            }
+            EspHomeMessage::ListEntitiesSensorResponse(entity) => {
+                self.sensors.push(entity);
+            }
+            EspHomeMessage::ListEntitiesSwitchResponse(entity) => {
+                self.switches.push(entity);
+            }
+            EspHomeMessage::ListEntitiesTextSensorResponse(entity) => {
+                self.text_sensors.push(entity);
+            }
+            EspHomeMessage::ListEntitiesServicesResponse(entity) => {
+                self.services.push(entity);
+            }
+            EspHomeMessage::ListEntitiesCameraResponse(entity) => {
+                self.cameras.push(entity);
+            }
+            EspHomeMessage::ListEntitiesClimateResponse(entity) => {
+                self.climates.push(entity);
+            }
+            EspHomeMessage::ListEntitiesNumberResponse(entity) => {
+                self.numbers.push(entity);
+            }
+            EspHomeMessage::ListEntitiesSelectResponse(entity) => {
+                self.selects.push(entity);
+            }
+            EspHomeMessage::ListEntitiesLockResponse(entity) => {
+                self.locks.push(entity);
+            }
+            EspHomeMessage::ListEntitiesButtonResponse(entity) => {
+                self.buttons.push(entity);
+            }
+            EspHomeMessage::ListEntitiesMediaPlayerResponse(entity) => {
+                self.media_players.push(entity);
+            }
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesAlarmControlPanelResponse(entity) => {
+                self.alarm_control_panels.push(entity);
+            }
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesTextResponse(entity) => {
+                self.texts.push(entity);
+            }
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesDateResponse(entity) => {
+                self.dates.push(entity);
+            }
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesTimeResponse(entity) => {
+                self.times.push(entity);
+            }
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesSirenResponse(entity) => {
+                self.sirens.push(entity);
+            }
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesEventResponse(entity) => {
+                self.events.push(entity);
+            }
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesValveResponse(entity) => {
+                self.valves.push(entity);
+            }
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesDateTimeResponse(entity) => {
+                self.date_times.push(entity);
+            }
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesUpdateResponse(entity) => {
+                self.updates.push(entity);
+            }
+            #[cfg(not(any(
+                feature = "api-1-13",
+                feature = "api-1-12",
+                feature = "api-1-10",
+                feature = "api-1-9",
+                feature = "api-1-8"
+            )))]
+            EspHomeMessage::ListEntitiesWaterHeaterResponse(entity) => {
+                self.water_heaters.push(entity);
+            }
+            #[cfg(not(any(
+                feature = "api-1-13",
+                feature = "api-1-12",
+                feature = "api-1-10",
+                feature = "api-1-9",
+                feature = "api-1-8"
+            )))]
+            EspHomeMessage::ListEntitiesInfraredResponse(entity) => {
+                self.infrared.push(entity);
+            }
+            #[cfg(not(any(
+                feature = "api-1-13",
+                feature = "api-1-12",
+                feature = "api-1-10",
+                feature = "api-1-9",
+                feature = "api-1-8"
+            )))]
+            EspHomeMessage::ListEntitiesRadioFrequencyResponse(entity) => {
+                self.radio_frequencies.push(entity);
+            }
+            other => return Some(other),
+        }
+        None
+    }
+
+    /// Total number of entities.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let mut len = 0;
+        len += self.binary_sensors.len();
+        len += self.covers.len();
+        len += self.fans.len();
+        len += self.lights.len();
+        len += self.sensors.len();
+        len += self.switches.len();
+        len += self.text_sensors.len();
+        len += self.services.len();
+        len += self.cameras.len();
+        len += self.climates.len();
+        len += self.numbers.len();
+        len += self.selects.len();
+        len += self.locks.len();
+        len += self.buttons.len();
+        len += self.media_players.len();
+        #[cfg(not(any(feature = "api-1-8")))]
+        {
+            len += self.alarm_control_panels.len();
+            len += self.texts.len();
+            len += self.dates.len();
+            len += self.times.len();
+        }
+        #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+        {
+            len += self.sirens.len();
+            len += self.events.len();
+            len += self.valves.len();
+            len += self.date_times.len();
+            len += self.updates.len();
+        }
+        #[cfg(not(any(
+            feature = "api-1-13",
+            feature = "api-1-12",
+            feature = "api-1-10",
+            feature = "api-1-9",
+            feature = "api-1-8"
+        )))]
+        {
+            len += self.water_heaters.len();
+            len += self.infrared.len();
+            len += self.radio_frequencies.len();
+        }
+        len
+    }
+
+    /// Check if the device has no entities.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::PingRequest;
+
+    #[test]
+    fn test_entities_add() {
+        let mut entities = Entities::default();
+        assert!(entities.is_empty());
+        assert!(
+            entities
+                .add(ListEntitiesSensorResponse::default().into())
+                .is_none()
+        );
+        assert!(
+            entities
+                .add(ListEntitiesSwitchResponse::default().into())
+                .is_none()
+        );
+        assert!(
+            entities
+                .add(ListEntitiesSwitchResponse::default().into())
+                .is_none()
+        );
+        assert_eq!(entities.sensors.len(), 1);
+        assert_eq!(entities.switches.len(), 2);
+        assert_eq!(entities.len(), 3);
+
+        let message = entities.add(PingRequest {}.into()).unwrap();
+        assert_eq!(message, PingRequest {}.into());
+        assert_eq!(entities.len(), 3);
+    }
+}
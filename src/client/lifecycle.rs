@@ -0,0 +1,112 @@
+use std::{
+    fmt,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+/// Lifecycle change of a connection, reported to the hooks registered with
+/// [`EspHomeClientBuilder::on_lifecycle`](super::EspHomeClientBuilder::on_lifecycle).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LifecycleEvent {
+    /// The connection to the device was established, including the encryption handshake.
+    Connected,
+    /// The connection setup finished, the client is returned to the caller.
+    ///
+    /// Also reported when the connection setup is disabled.
+    SetupComplete,
+    /// The connection was lost, or closed with [`EspHomeClient::close`](super::EspHomeClient::close).
+    ///
+    /// Also reported when the connection setup failed or timed out. Only reported once per
+    /// connection.
+    Disconnected {
+        /// Description of the error which caused the disconnect.
+        reason: String,
+    },
+}
+
+type Hook = Arc<dyn Fn(&LifecycleEvent) + Send + Sync>;
+
+/// Callbacks for the lifecycle events, shared by the clones of a builder and their connections.
+#[derive(Clone, Default)]
+pub(super) struct LifecycleHooks {
+    hooks: Vec<Hook>,
+}
+
+impl fmt::Debug for LifecycleHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LifecycleHooks")
+            .field("hooks", &self.hooks.len())
+            .finish()
+    }
+}
+
+impl LifecycleHooks {
+    pub(super) fn register(&mut self, hook: impl Fn(&LifecycleEvent) + Send + Sync + 'static) {
+        self.hooks.push(Arc::new(hook));
+    }
+
+    pub(super) fn emit(&self, event: &LifecycleEvent) {
+        for hook in &self.hooks {
+            hook(event);
+        }
+    }
+}
+
+/// Lifecycle of a single connection, which reports the disconnect only once.
+#[derive(Debug)]
+pub(super) struct Lifecycle {
+    hooks: LifecycleHooks,
+    disconnected: AtomicBool,
+}
+
+impl Lifecycle {
+    pub(super) const fn new(hooks: LifecycleHooks) -> Self {
+        Self {
+            hooks,
+            disconnected: AtomicBool::new(false),
+        }
+    }
+
+    pub(super) fn emit(&self, event: &LifecycleEvent) {
+        self.hooks.emit(event);
+    }
+
+    /// Reports the disconnect, unless it was already reported.
+    pub(super) fn disconnected(&self, reason: impl Into<String>) {
+        if !self.disconnected.swap(true, Ordering::AcqRel) {
+            self.emit(&LifecycleEvent::Disconnected {
+                reason: reason.into(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_lifecycle_disconnected_once() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut hooks = LifecycleHooks::default();
+        let recorded = Arc::clone(&events);
+        hooks.register(move |event| recorded.lock().unwrap().push(event.clone()));
+
+        let lifecycle = Lifecycle::new(hooks);
+        lifecycle.emit(&LifecycleEvent::Connected);
+        lifecycle.disconnected("Disconnected");
+        lifecycle.disconnected("Connection closed");
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                LifecycleEvent::Connected,
+                LifecycleEvent::Disconnected {
+                    reason: "Disconnected".to_owned()
+                }
+            ]
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use super::EspHomeClient;
+use crate::{
+    error::ClientError,
+    proto::{ButtonCommandRequest, SwitchCommandRequest},
+};
+
+/// Device maintenance actions, triggered through the restart/safe mode entities of the device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum MaintenanceAction {
+    Restart,
+    SafeMode,
+}
+
+impl MaintenanceAction {
+    const fn description(self) -> &'static str {
+        match self {
+            Self::Restart => "restart button or switch",
+            Self::SafeMode => "safe mode button or switch",
+        }
+    }
+
+    /// Object ID of the entity of this action, as named by the `restart` and `safe_mode`
+    /// button/switch platforms of ESPHome.
+    const fn object_id(self) -> &'static str {
+        match self {
+            Self::Restart => "restart",
+            Self::SafeMode => "safe_mode",
+        }
+    }
+
+    /// Checks if the object ID is the one of this action, possibly prefixed with the device name.
+    fn is_named(self, object_id: &str) -> bool {
+        let suffixes: &[&str] = match self {
+            Self::Restart => &["restart"],
+            Self::SafeMode => &["safe_mode", "safe_mode_boot"],
+        };
+        suffixes.iter().any(|suffix| {
+            object_id
+                .strip_suffix(suffix)
+                .is_some_and(|prefix| prefix.is_empty() || prefix.ends_with('_'))
+        })
+    }
+
+    /// Checks if a button is the one for this action.
+    ///
+    /// Restart buttons are also recognised by their device class, which the safe mode and factory
+    /// reset buttons share.
+    fn matches_button(self, object_id: &str, device_class: &str) -> bool {
+        match self {
+            Self::SafeMode => self.is_named(object_id),
+            Self::Restart => {
+                self.is_named(object_id)
+                    || (device_class == "restart"
+                        && !Self::SafeMode.is_named(object_id)
+                        && !object_id.contains("factory_reset"))
+            }
+        }
+    }
+
+    /// Checks if a switch is the one for this action, which requires the exact object ID as any
+    /// switch could control a relay named after it.
+    fn matches_switch(self, object_id: &str) -> bool {
+        object_id == self.object_id()
+    }
+}
+
+/// Lists the entities of the device, and presses/turns on the entity matching the action.
+///
+/// Buttons are preferred over switches when a device exposes both.
+pub(super) async fn trigger(
+    client: &EspHomeClient,
+    action: MaintenanceAction,
+) -> Result<(), ClientError> {
+    let entities = client.list_entities().await?;
+    let button = entities
+        .buttons
+        .into_iter()
+        .find(|entity| action.matches_button(&entity.object_id, &entity.device_class));
+    let switch = entities
+        .switches
+        .into_iter()
+        .find(|entity| action.matches_switch(&entity.object_id));
+
+    if let Some(entity) = button {
+        tracing::info!("Pressing button '{}' for {action:?}", entity.object_id);
+        return client
+            .try_write(ButtonCommandRequest {
+                key: entity.key,
+                #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+                device_id: entity.device_id,
+            })
+            .await;
+    }
+    if let Some(entity) = switch {
+        tracing::info!("Turning on switch '{}' for {action:?}", entity.object_id);
+        return client
+            .try_write(SwitchCommandRequest {
+                key: entity.key,
+                state: true,
+                #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+                device_id: entity.device_id,
+            })
+            .await;
+    }
+    Err(ClientError::EntityNotFound {
+        name: action.description().to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restart_matches() {
+        assert!(MaintenanceAction::Restart.matches_button("restart", ""));
+        assert!(MaintenanceAction::Restart.matches_button("device_restart", ""));
+        assert!(MaintenanceAction::Restart.matches_button("reboot", "restart"));
+        assert!(!MaintenanceAction::Restart.matches_button("safe_mode_boot", "restart"));
+        assert!(!MaintenanceAction::Restart.matches_button("factory_reset", "restart"));
+        assert!(!MaintenanceAction::Restart.matches_button("relay", ""));
+        assert!(!MaintenanceAction::Restart.matches_button("restart_counter", ""));
+    }
+
+    #[test]
+    fn test_safe_mode_matches() {
+        assert!(MaintenanceAction::SafeMode.matches_button("safe_mode_boot", "restart"));
+        assert!(MaintenanceAction::SafeMode.matches_button("restart_safe_mode", ""));
+        assert!(!MaintenanceAction::SafeMode.matches_button("restart", "restart"));
+        assert!(!MaintenanceAction::SafeMode.matches_button("unsafe_mode", ""));
+    }
+
+    #[test]
+    fn test_switch_matches() {
+        assert!(MaintenanceAction::Restart.matches_switch("restart"));
+        assert!(MaintenanceAction::SafeMode.matches_switch("safe_mode"));
+        assert!(!MaintenanceAction::Restart.matches_switch("pump_restart_relay"));
+        assert!(!MaintenanceAction::Restart.matches_switch("device_restart"));
+        assert!(!MaintenanceAction::SafeMode.matches_switch("safe_mode_boot"));
+    }
+}
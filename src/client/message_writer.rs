@@ -0,0 +1,115 @@
+use std::sync::{Arc, OnceLock};
+
+use tracing::Span;
+
+use super::{lifecycle::Lifecycle, stream_writer::StreamWriter};
+use crate::{
+    error::{ClientError, ProtocolError},
+    proto::{EspHomeMessage, HelloResponse},
+};
+
+/// Writer of messages, shared by the client, its write streams and batches.
+///
+/// Whichever handle writes a message, messages the device doesn't know are rejected when API version
+/// negotiation is enabled, and a lost connection is reported to the lifecycle hooks.
+#[derive(Debug, Clone)]
+pub(super) struct MessageWriter {
+    writer: StreamWriter,
+    connection: Arc<Connection>,
+}
+
+/// State of the connection needed to write messages.
+#[derive(Debug)]
+struct Connection {
+    lifecycle: Lifecycle,
+    hello: OnceLock<HelloResponse>,
+    negotiate_api_version: bool,
+}
+
+impl MessageWriter {
+    pub(super) fn new(
+        writer: StreamWriter,
+        lifecycle: Lifecycle,
+        negotiate_api_version: bool,
+    ) -> Self {
+        Self {
+            writer,
+            connection: Arc::new(Connection {
+                lifecycle,
+                hello: OnceLock::new(),
+                negotiate_api_version,
+            }),
+        }
+    }
+
+    /// Span of the connection, carrying the address and name of the device.
+    pub(super) const fn span(&self) -> &Span {
+        self.writer.span()
+    }
+
+    pub(super) fn lifecycle(&self) -> &Lifecycle {
+        &self.connection.lifecycle
+    }
+
+    /// The `HelloResponse` of the device, set once during the connection setup.
+    pub(super) fn hello(&self) -> &OnceLock<HelloResponse> {
+        &self.connection.hello
+    }
+
+    pub(super) fn negotiate_api_version(&self) -> bool {
+        self.connection.negotiate_api_version
+    }
+
+    /// Writes the message to the device.
+    pub(super) async fn write(&self, message: EspHomeMessage) -> Result<(), ClientError> {
+        self.write_messages(vec![message]).await
+    }
+
+    /// Writes the messages to the device at once, nothing is written if any of them isn't supported by
+    /// the device.
+    pub(super) async fn write_messages(
+        &self,
+        messages: Vec<EspHomeMessage>,
+    ) -> Result<(), ClientError> {
+        let mut payloads = Vec::with_capacity(messages.len());
+        for message in messages {
+            self.check_api_version(&message)?;
+            payloads.push(message.into());
+        }
+        if payloads.is_empty() {
+            return Ok(());
+        }
+        self.writer
+            .write_messages(payloads)
+            .await
+            .inspect_err(|e| self.connection_lost(e))
+    }
+
+    /// Reports the disconnect to the lifecycle hooks, if the error means the connection is lost.
+    pub(super) fn connection_lost(&self, error: &ClientError) {
+        if error.is_connection_lost() {
+            self.connection.lifecycle.disconnected(error.to_string());
+        }
+    }
+
+    /// Rejects messages the device doesn't know when API version negotiation is enabled.
+    fn check_api_version(&self, message: &EspHomeMessage) -> Result<(), ClientError> {
+        if !self.connection.negotiate_api_version {
+            return Ok(());
+        }
+        let Some(hello) = self.connection.hello.get() else {
+            return Ok(());
+        };
+        let actual = (hello.api_version_major, hello.api_version_minor);
+        let required = message.min_api_version();
+        if required > actual {
+            return Err(ProtocolError::UnsupportedMessage {
+                type_id: message.get_message_type(),
+                required: format!("{}.{}", required.0, required.1),
+                actual: format!("{}.{}", actual.0, actual.1),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
@@ -1,11 +1,17 @@
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, PoisonError};
 
-use snow::{HandshakeState, TransportState};
-use tokio::net::TcpStream;
+use bytes::{Buf as _, BytesMut};
+use snow::{HandshakeState, StatelessTransportState};
 
 use crate::error::{ClientError, ConnectionError, NoiseError, ProtocolError, StreamError};
 
-use super::{plain::PLAIN_PREAMBLE, stream_reader::StreamDecoder, stream_writer::StreamEncoder};
+use super::{
+    buffer_pool::BufferPool,
+    plain::PLAIN_PREAMBLE,
+    runtime::{self, Transport},
+    stream_reader::{Frame, StreamDecoder},
+    stream_writer::StreamEncoder,
+};
 
 use super::{StreamPair, stream_reader::StreamReader, stream_writer::StreamWriter};
 
@@ -13,19 +19,23 @@ const ZERO_BYTE: u8 = 0x00;
 const NOISE_PROLOGUE: &[u8; 14] = b"NoiseAPIInit\x00\x00";
 const NOISE_HELLO: &[u8; 3] = b"\x01\x00\x00";
 pub(super) const NOISE_PREAMBLE: u8 = 0x01;
-
-/// Establishes a TCP connection to the given address and performs a Noise handshake using the provided key.
-/// Returns a `StreamPair` with the encrypted streams.
+/// Length of the preamble and the frame length.
+const FRAME_HEADER_LEN: usize = 3;
+/// Length of the authentication tag added to every encrypted payload.
+const NOISE_TAG_LEN: usize = 16;
+/// Reason reported by the device when the handshake fails due to a mismatching key.
+pub(super) const HANDSHAKE_MAC_FAILURE: &str = "Handshake MAC failure";
+
+/// Performs a Noise handshake over the established connection using the provided key.
+/// Returns a `StreamPair` with the encrypted streams, and the server name and MAC address announced by the device.
 /// For more information on the Noise protocol, see: <http://www.noiseprotocol.org/noise.html#pre-shared-symmetric-keys>
-pub(crate) async fn connect(addr: &str, key: &str) -> Result<StreamPair, ClientError> {
-    let (read, write) = TcpStream::connect(addr)
-        .await
-        .map_err(|e| ConnectionError::TcpConnect {
-            address: addr.to_owned(),
-            source: e,
-        })?
-        .into_split();
-    tracing::debug!("Tcp connection established to {addr}");
+/// The buffers for decrypting messages are taken from the pool, if any.
+pub(crate) async fn connect(
+    stream: Box<dyn Transport>,
+    key: &[u8; 32],
+    pool: Option<BufferPool>,
+) -> Result<(StreamPair, ServerIdentity), ClientError> {
+    let (read, write) = runtime::split(stream);
     let pre_handshake_decoder: Box<dyn StreamDecoder> = Box::new(PreHandshakeDecoder);
     let (mut reader, writer) = (
         StreamReader::new(read).with_decoder(pre_handshake_decoder),
@@ -39,13 +49,77 @@ pub(crate) async fn connect(addr: &str, key: &str) -> Result<StreamPair, ClientE
     writer
         .write_message(noise_handshake(&mut noise_client))
         .await?;
-    parse_server_and_mac(reader.read_next_message().await?)?;
-    parse_noise_response(reader.read_next_message().await?, &mut noise_client)?;
+    let identity = parse_server_and_mac(&reader.read_next_message().await?.payload)?;
+    parse_noise_response(
+        &reader.read_next_message().await?.payload,
+        &mut noise_client,
+    )?;
 
     // Init coder with noise client
     let coder = NoiseCoder::new(
         noise_client
-            .into_transport_mode()
+            .into_stateless_transport_mode()
+            .map_err(<snow::Error as Into<NoiseError>>::into)?,
+    )
+    .with_buffer_pool(pool);
+    tracing::debug!("Noise handshake completed successfully");
+    let decoder: Box<dyn StreamDecoder> = Box::new(coder.clone());
+    let encoder: Box<dyn StreamEncoder> = Box::new(coder);
+    Ok((
+        (reader.with_decoder(decoder), writer.with_encoder(encoder)),
+        identity,
+    ))
+}
+
+#[cfg(feature = "runtime-tokio")]
+/// Responds to the Noise handshake of a client over the accepted TCP connection, announcing the given
+/// server name and MAC address. Returns a `StreamPair` with the encrypted streams.
+///
+/// When the key of the client doesn't match, the failure is reported to the client like a device does.
+pub(crate) async fn accept(
+    stream: Box<dyn Transport>,
+    key: &[u8; 32],
+    identity: (&str, &str),
+) -> Result<StreamPair, ClientError> {
+    let (read, write) = runtime::split(stream);
+    let pre_handshake_decoder: Box<dyn StreamDecoder> = Box::new(PreHandshakeDecoder);
+    let (mut reader, writer) = (
+        StreamReader::new(read).with_decoder(pre_handshake_decoder),
+        StreamWriter::new(write),
+    );
+
+    let mut noise_server = noise_builder(key)?
+        .build_responder()
+        .map_err(<snow::Error as Into<NoiseError>>::into)?;
+
+    // The client hello is an empty frame, followed by the handshake message
+    let mut handshake = reader.read_next_message().await?.payload;
+    if handshake.is_empty() {
+        handshake = reader.read_next_message().await?.payload;
+    }
+    writer.write_message(server_hello(identity)).await?;
+    if handshake.first() != Some(&ZERO_BYTE) {
+        return Err(ConnectionError::NoiseHandshake {
+            reason: "Invalid handshake message".to_owned(),
+        }
+        .into());
+    }
+    let mut payload = vec![0u8; 65535];
+    if noise_server
+        .read_message(&handshake[1..], &mut payload)
+        .is_err()
+    {
+        let failure = [&[NOISE_PREAMBLE], HANDSHAKE_MAC_FAILURE.as_bytes()].concat();
+        writer.write_message(create_noise_frame(failure)).await?;
+        return Err(NoiseError::InvalidPsk.into());
+    }
+    writer
+        .write_message(noise_handshake(&mut noise_server))
+        .await?;
+
+    let coder = NoiseCoder::new(
+        noise_server
+            .into_stateless_transport_mode()
             .map_err(<snow::Error as Into<NoiseError>>::into)?,
     );
     tracing::debug!("Noise handshake completed successfully");
@@ -58,68 +132,101 @@ pub(crate) async fn connect(addr: &str, key: &str) -> Result<StreamPair, ClientE
 #[derive(Debug)]
 struct PreHandshakeDecoder;
 impl StreamDecoder for PreHandshakeDecoder {
-    fn decode(&self, buffer: &mut Vec<u8>) -> Result<Option<Vec<u8>>, ClientError> {
-        read_frame_from_buffer(buffer)
+    fn decode(&self, buffer: &mut BytesMut) -> Result<Option<Frame>, ClientError> {
+        Ok(read_frame_from_buffer(buffer)?.map(Frame::raw))
     }
 }
 
 // Decoder and encoder for Noise encrypted frames.
+// Each direction has its own nonce, so reading and writing don't contend for the same lock.
 #[derive(Debug, Clone)]
 struct NoiseCoder {
-    noise: Arc<Mutex<TransportState>>,
+    noise: Arc<StatelessTransportState>,
+    read_nonce: Arc<Mutex<u64>>,
+    write_nonce: Arc<Mutex<u64>>,
+    pool: Option<BufferPool>,
 }
 impl NoiseCoder {
-    fn new(noise: TransportState) -> Self {
+    fn new(noise: StatelessTransportState) -> Self {
         Self {
-            noise: Arc::new(Mutex::new(noise)),
+            noise: Arc::new(noise),
+            read_nonce: Arc::default(),
+            write_nonce: Arc::default(),
+            pool: None,
         }
     }
-    fn decrypt(&self, payload: &[u8]) -> Result<Vec<u8>, ClientError> {
-        let mut decrypted_payload = vec![0u8; 65535];
+    /// Decrypts into buffers taken from the pool, instead of allocating a buffer per message.
+    fn with_buffer_pool(self, pool: Option<BufferPool>) -> Self {
+        Self { pool, ..self }
+    }
+    fn decrypt(&self, payload: &[u8]) -> Result<BytesMut, ClientError> {
+        let mut buffer = self
+            .pool
+            .as_ref()
+            .map_or_else(BytesMut::new, BufferPool::take);
+        // The decrypted payload is shorter than the encrypted payload, as the tag is removed
+        buffer.resize(payload.len(), 0);
+        // The nonce only advances on success, so the state is valid even if the lock was poisoned
+        let mut nonce = self
+            .read_nonce
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
         let size = self
             .noise
-            .lock()
-            .map_err(|e| ClientError::InvalidInternalState {
-                reason: format!("Failed to lock noise state: {e}"),
-            })?
-            .read_message(payload, &mut decrypted_payload)
+            .read_message(*nonce, payload, &mut buffer)
             .map_err(<snow::Error as Into<NoiseError>>::into)?;
-        decrypted_payload.truncate(size);
+        *nonce += 1;
+        drop(nonce);
+        buffer.truncate(size);
+        // The split off message keeps its memory until it is dropped, the pool reclaims it afterwards
+        let decrypted_payload = buffer.split();
+        if let Some(pool) = &self.pool {
+            pool.put(buffer);
+        }
         Ok(decrypted_payload)
     }
-    fn encrypt(&self, payload: &[u8]) -> Result<Vec<u8>, ClientError> {
-        let mut encrypted_payload = vec![0u8; 65535];
+    /// Encrypts the payload directly into a frame appended to the frames, leaving room for the frame header.
+    fn encrypt_frame(&self, payload: &[u8], frames: &mut BytesMut) -> Result<(), ClientError> {
+        let start = frames.len();
+        frames.resize(start + FRAME_HEADER_LEN + payload.len() + NOISE_TAG_LEN, 0);
+        let frame = &mut frames[start..];
+        let mut nonce = self
+            .write_nonce
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
         let size = self
             .noise
-            .lock()
-            .map_err(|e| ClientError::InvalidInternalState {
-                reason: format!("Failed to lock noise state: {e}"),
-            })?
-            .write_message(payload, &mut encrypted_payload)
+            .write_message(*nonce, payload, &mut frame[FRAME_HEADER_LEN..])
             .map_err(<snow::Error as Into<NoiseError>>::into)?;
-        encrypted_payload.truncate(size);
-        Ok(encrypted_payload)
+        *nonce += 1;
+        drop(nonce);
+        let frame_len = u16::try_from(size).map_err(|_e| StreamError::FrameTooLarge {
+            size,
+            max_size: usize::from(u16::MAX),
+        })?;
+        frame[0] = NOISE_PREAMBLE;
+        frame[1..FRAME_HEADER_LEN].copy_from_slice(&frame_len.to_be_bytes());
+        frames.truncate(start + FRAME_HEADER_LEN + size);
+        Ok(())
     }
 }
 impl StreamDecoder for NoiseCoder {
-    fn decode(&self, buffer: &mut Vec<u8>) -> Result<Option<Vec<u8>>, ClientError> {
-        match read_frame_from_buffer(buffer) {
-            Ok(Some(data)) => Ok(Some(self.decrypt(&data)?)),
-            v => v,
-        }
+    fn decode(&self, buffer: &mut BytesMut) -> Result<Option<Frame>, ClientError> {
+        read_frame_from_buffer(buffer)?
+            .map(|frame| Frame::from_prefixed(self.decrypt(&frame)?))
+            .transpose()
     }
 }
 impl StreamEncoder for NoiseCoder {
-    fn encode(&self, payload: Vec<u8>) -> Result<Vec<u8>, ClientError> {
-        let payload = self.encrypt(&payload)?;
-        let payload = create_noise_frame(payload);
-        Ok(payload)
+    fn encode(&self, payload: Vec<u8>, frames: &mut BytesMut) -> Result<(), ClientError> {
+        self.encrypt_frame(&payload, frames)
     }
 }
 
-fn create_noise_client(key: &str) -> Result<snow::HandshakeState, ClientError> {
+/// Decodes a 32-byte base64-encoded key.
+pub(super) fn decode_key(key: &str) -> Result<[u8; 32], NoiseError> {
     use base64::{Engine as _, engine::general_purpose};
-    let key_bytes: [u8; 32] = general_purpose::STANDARD
+    general_purpose::STANDARD
         .decode(key)
         .map_err(|e| NoiseError::InvalidKey {
             reason: e.to_string(),
@@ -127,25 +234,32 @@ fn create_noise_client(key: &str) -> Result<snow::HandshakeState, ClientError> {
         .try_into()
         .map_err(|e: Vec<u8>| NoiseError::InvalidKey {
             reason: format!("Invalid PSK length: {}", e.len()),
+        })
+}
+
+fn create_noise_client(key: &[u8; 32]) -> Result<snow::HandshakeState, ClientError> {
+    let noise = noise_builder(key)?
+        .build_initiator()
+        .map_err(|e| NoiseError::InvalidKey {
+            reason: e.to_string(),
         })?;
+    Ok(noise)
+}
 
+/// Builder for the Noise protocol used by the API, with the prologue and the pre-shared key.
+pub(super) fn noise_builder(key: &[u8; 32]) -> Result<snow::Builder<'_>, NoiseError> {
     #[allow(clippy::unwrap_in_result, reason = "Valid encryption protocol")]
-    let noise = snow::Builder::new(
+    snow::Builder::new(
         "Noise_NNpsk0_25519_ChaChaPoly_SHA256"
             .parse()
             .expect("Valid encryption protocol"),
     )
     .prologue(NOISE_PROLOGUE)
     .expect("Valid prologue")
-    .psk(0, &key_bytes)
-    .map_err(|e| NoiseError::InvalidKey {
-        reason: e.to_string(),
-    })?
-    .build_initiator()
+    .psk(0, key)
     .map_err(|e| NoiseError::InvalidKey {
         reason: e.to_string(),
-    })?;
-    Ok(noise)
+    })
 }
 
 /// Initial header, indicating a Noise handshake.
@@ -154,17 +268,36 @@ fn noise_hello() -> Vec<u8> {
 }
 
 // Noise handshake message, to verify PSK and establish a secure channel.
-fn noise_handshake(noise_client: &mut HandshakeState) -> Vec<u8> {
+fn noise_handshake(noise: &mut HandshakeState) -> Vec<u8> {
     let mut payload = vec![0u8; 65535];
-    let size = noise_client.write_message(&[], &mut payload).expect("OK");
+    let size = noise.write_message(&[], &mut payload).expect("OK");
     payload.truncate(size);
     payload.insert(0, ZERO_BYTE);
     create_noise_frame(payload)
 }
 
+#[cfg(feature = "runtime-tokio")]
+/// Frame announcing the server name and MAC address, the MAC address in lowercase hexadecimal without separators.
+fn server_hello((name, mac): (&str, &str)) -> Vec<u8> {
+    let mac = mac.replace(':', "").to_ascii_lowercase();
+    create_noise_frame(
+        [
+            &[NOISE_PREAMBLE],
+            name.as_bytes(),
+            &[ZERO_BYTE],
+            mac.as_bytes(),
+            &[ZERO_BYTE],
+        ]
+        .concat(),
+    )
+}
+
+/// Server name and MAC address announced by the device during the Noise handshake.
+pub(super) type ServerIdentity = (Option<String>, Option<String>);
+
 // Retrieves the server name and MAC address from the Noise handshake response.
-fn parse_server_and_mac(data: Vec<u8>) -> Result<(Option<String>, Option<String>), ClientError> {
-    let mut data = data.into_iter();
+fn parse_server_and_mac(data: &[u8]) -> Result<ServerIdentity, ClientError> {
+    let mut data = data.iter().copied();
     if data.next() != Some(NOISE_PREAMBLE) {
         return Err(ProtocolError::UnexpectedPlain.into());
     }
@@ -192,11 +325,8 @@ fn parse_server_and_mac(data: Vec<u8>) -> Result<(Option<String>, Option<String>
 }
 
 /// Reads the key verification from noise handshake response
-fn parse_noise_response(
-    data: Vec<u8>,
-    noise_client: &mut HandshakeState,
-) -> Result<(), ClientError> {
-    let mut data = data.into_iter();
+fn parse_noise_response(data: &[u8], noise_client: &mut HandshakeState) -> Result<(), ClientError> {
+    let mut data = data.iter().copied();
     let preamble = data.next();
     if preamble != Some(ZERO_BYTE) {
         let reason = if data.len() >= 2 {
@@ -204,6 +334,9 @@ fn parse_noise_response(
         } else {
             "Unknown reason".to_owned()
         };
+        if reason.trim_end_matches('\0') == HANDSHAKE_MAC_FAILURE {
+            return Err(NoiseError::InvalidPsk.into());
+        }
         return Err(ConnectionError::NoiseHandshake {
             reason: format!("Incorrect preamble: {preamble:?}, {reason}"),
         }
@@ -212,12 +345,15 @@ fn parse_noise_response(
     let mut handshake_frame = vec![0u8; 65535];
     noise_client
         .read_message(&data.collect::<Vec<u8>>(), &mut handshake_frame)
-        .map_err(<snow::Error as Into<NoiseError>>::into)?;
+        .map_err(|e| match e {
+            snow::Error::Decrypt => NoiseError::InvalidPsk,
+            other => other.into(),
+        })?;
     Ok(())
 }
 
 /// Create a frame with the given payload, including the preamble and length.
-fn create_noise_frame(payload: Vec<u8>) -> Vec<u8> {
+pub(super) fn create_noise_frame(payload: Vec<u8>) -> Vec<u8> {
     let frame_len = u16::try_from(payload.len()).expect("Payload length should fit in u16");
     [
         vec![NOISE_PREAMBLE],
@@ -228,7 +364,9 @@ fn create_noise_frame(payload: Vec<u8>) -> Vec<u8> {
 }
 
 /// Attempts to read a frame from the buffer.
-fn read_frame_from_buffer(buffer: &mut Vec<u8>) -> Result<Option<Vec<u8>>, ClientError> {
+pub(super) fn read_frame_from_buffer(
+    buffer: &mut BytesMut,
+) -> Result<Option<BytesMut>, ClientError> {
     if buffer.len() < 3 {
         return Ok(None);
     }
@@ -246,16 +384,16 @@ fn read_frame_from_buffer(buffer: &mut Vec<u8>) -> Result<Option<Vec<u8>>, Clien
         }
     }
     let frame_len = usize::from(u16::from_be_bytes([buffer[1], buffer[2]]));
-    if buffer.len() < frame_len {
+    if buffer.len() < FRAME_HEADER_LEN + frame_len {
         tracing::debug!(
             "Waiting for more data, expected {} bytes, got {}",
             frame_len,
-            buffer.len()
+            buffer.len() - FRAME_HEADER_LEN
         );
         return Ok(None);
     }
-    let frame = buffer.drain(..frame_len + 3).skip(3).collect();
-    Ok(Some(frame))
+    buffer.advance(FRAME_HEADER_LEN);
+    Ok(Some(buffer.split_to(frame_len)))
 }
 
 #[cfg(test)]
@@ -297,6 +435,19 @@ mod tests {
         Ok(noise)
     }
 
+    #[test]
+    fn test_decode_key() {
+        assert_eq!(decode_key(&create_key(7)).unwrap(), [7; 32]);
+        assert!(matches!(
+            decode_key("AAAA").unwrap_err(),
+            NoiseError::InvalidKey { reason } if reason == "Invalid PSK length: 3"
+        ));
+        assert!(matches!(
+            decode_key("not base64!").unwrap_err(),
+            NoiseError::InvalidKey { .. }
+        ));
+    }
+
     #[test]
     fn test_create_noise_frame_and_read_frame_from_buffer() {
         let payload = vec![1, 2, 3, 4, 5];
@@ -304,22 +455,42 @@ mod tests {
         assert_eq!(frame[0], NOISE_PREAMBLE);
         let len = usize::from(u16::from_be_bytes([frame[1], frame[2]]));
         assert_eq!(len, payload.len());
-        let mut buffer = frame;
-        let decoded = read_frame_from_buffer(&mut buffer).unwrap();
-        assert_eq!(decoded, Some(payload));
+        let mut buffer = BytesMut::from(&frame[..]);
+        let decoded = read_frame_from_buffer(&mut buffer).unwrap().unwrap();
+        assert_eq!(decoded, payload);
         assert!(buffer.is_empty());
     }
 
+    #[test]
+    fn test_read_frame_from_buffer_consecutive_frames() {
+        let mut buffer = BytesMut::new();
+        buffer.extend_from_slice(&create_noise_frame(vec![1, 2, 3]));
+        buffer.extend_from_slice(&create_noise_frame(vec![4, 5, 6, 7]));
+        // Header and part of the payload of the third frame
+        buffer.extend_from_slice(&create_noise_frame(vec![8, 9, 10, 11])[..5]);
+
+        assert_eq!(
+            read_frame_from_buffer(&mut buffer).unwrap().as_deref(),
+            Some(&[1, 2, 3][..])
+        );
+        assert_eq!(
+            read_frame_from_buffer(&mut buffer).unwrap().as_deref(),
+            Some(&[4, 5, 6, 7][..])
+        );
+        assert_eq!(read_frame_from_buffer(&mut buffer).unwrap(), None);
+        assert_eq!(buffer.len(), 5, "Incomplete frame is kept");
+    }
+
     #[test]
     fn test_read_frame_from_buffer_with_insufficient_data() {
-        let mut buffer = vec![NOISE_PREAMBLE, 0x00];
+        let mut buffer = BytesMut::from(&[NOISE_PREAMBLE, 0x00][..]);
         let result = read_frame_from_buffer(&mut buffer);
         assert!(matches!(result, Ok(None)));
     }
 
     #[test]
     fn test_read_frame_from_buffer_with_unknown_preamble() {
-        let mut buffer = vec![0xFF, 0x00, 0x05, 1, 2, 3, 4, 5];
+        let mut buffer = BytesMut::from(&[0xFF, 0x00, 0x05, 1, 2, 3, 4, 5][..]);
         let result = read_frame_from_buffer(&mut buffer);
         result.unwrap_err();
     }
@@ -331,7 +502,7 @@ mod tests {
         data.push(ZERO_BYTE);
         data.extend(b"aa:bb:cc:dd:ee:ff");
         data.push(ZERO_BYTE);
-        let (server, mac) = parse_server_and_mac(data).unwrap();
+        let (server, mac) = parse_server_and_mac(&data).unwrap();
         assert_eq!(server, Some("server_name".to_owned()));
         assert_eq!(mac, Some("aa:bb:cc:dd:ee:ff".to_owned()));
     }
@@ -341,7 +512,7 @@ mod tests {
         let mut data = vec![0xFF];
         data.extend(b"server_name");
         data.push(ZERO_BYTE);
-        let result = parse_server_and_mac(data);
+        let result = parse_server_and_mac(&data);
         result.unwrap_err();
     }
 
@@ -352,24 +523,24 @@ mod tests {
     }
 
     #[test]
-    fn test_create_noise_client_invalid_key_length() {
+    fn test_decode_key_invalid_length() {
         use base64::{Engine as _, engine::general_purpose};
         let key = general_purpose::STANDARD.encode([0u8; 16]);
-        let result = create_noise_client(&key);
+        let result = decode_key(&key);
         result.unwrap_err();
     }
 
     #[test]
     fn test_create_noise_client_valid_key() {
         let key = create_key(1u8);
-        let result = create_noise_client(&key);
+        let result = create_noise_client(&decode_key(&key).unwrap());
         result.unwrap();
     }
 
     #[test]
     fn test_noise_handshake_frame_structure() {
         let key = create_key(2u8);
-        let mut client = create_noise_client(&key).unwrap();
+        let mut client = create_noise_client(&decode_key(&key).unwrap()).unwrap();
         let frame = noise_handshake(&mut client);
         assert_eq!(frame[0], NOISE_PREAMBLE);
         // Length field is 2 bytes
@@ -383,7 +554,7 @@ mod tests {
     fn test_parse_noise_response_valid() {
         // Prepare a valid handshake state and message
         let key = create_key(3u8);
-        let mut client = create_noise_client(&key).unwrap();
+        let mut client = create_noise_client(&decode_key(&key).unwrap()).unwrap();
         let mut server = create_noise_server(&key).unwrap();
 
         // Simulate handshake
@@ -398,15 +569,15 @@ mod tests {
         let size = server.write_message(&[], &mut write_data).unwrap();
         write_data.truncate(size);
         write_data.insert(0, ZERO_BYTE);
-        parse_noise_response(write_data, &mut client).expect("Should parse valid response");
+        parse_noise_response(&write_data, &mut client).expect("Should parse valid response");
     }
 
     #[test]
     fn test_parse_noise_response_invalid_preamble() {
         let key = create_key(4u8);
-        let mut client = create_noise_client(&key).unwrap();
+        let mut client = create_noise_client(&decode_key(&key).unwrap()).unwrap();
         let data = vec![0xFF, 0x01, 0x02];
-        let result = parse_noise_response(data, &mut client);
+        let result = parse_noise_response(&data, &mut client);
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert!(
@@ -418,15 +589,85 @@ mod tests {
     #[test]
     fn test_parse_noise_response_invalid_handshake_message() {
         let key = create_key(5u8);
-        let mut client = create_noise_client(&key).unwrap();
+        let mut client = create_noise_client(&decode_key(&key).unwrap()).unwrap();
         // Valid preamble, but invalid handshake message (random bytes)
         let data = vec![ZERO_BYTE, 0xAA, 0xBB, 0xCC];
-        let result = parse_noise_response(data, &mut client);
+        let result = parse_noise_response(&data, &mut client);
         assert!(result.is_err());
         let err = result.unwrap_err();
         assert_eq!(
             err.to_string(),
-            "Connection error: Noise handshake failed: Noise transport error: state error: NotTurnToRead"
+            "Connection error: Noise error: Noise transport error: state error: NotTurnToRead"
+        );
+    }
+
+    #[test]
+    fn test_parse_noise_response_invalid_psk() {
+        let key = create_key(6u8);
+        let mut client = create_noise_client(&decode_key(&key).unwrap()).unwrap();
+        let mut data = vec![0x01];
+        data.extend_from_slice(HANDSHAKE_MAC_FAILURE.as_bytes());
+        let err = parse_noise_response(&data, &mut client).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ClientError::Connection(ConnectionError::Noise(NoiseError::InvalidPsk))
+            ),
+            "Unexpected error: {err}"
         );
     }
+
+    #[test]
+    fn test_noise_coder_round_trip() {
+        let key = create_key(7u8);
+        let mut client = create_noise_client(&decode_key(&key).unwrap()).unwrap();
+        let mut server = create_noise_server(&key).unwrap();
+        let mut message = vec![0u8; 65535];
+        let request_len = client.write_message(&[], &mut message).unwrap();
+        server
+            .read_message(&message[..request_len], &mut [])
+            .unwrap();
+        let response_len = server.write_message(&[], &mut message).unwrap();
+        client
+            .read_message(&message[..response_len], &mut [])
+            .unwrap();
+        let pool = BufferPool::new().capacity(64);
+        let coder = NoiseCoder::new(client.into_stateless_transport_mode().unwrap())
+            .with_buffer_pool(Some(pool.clone()));
+        let mut server = server.into_transport_mode().unwrap();
+
+        let payload = vec![0x00, 0x07, 0x00, 0x02, 0xAA, 0xBB];
+        let mut frame = BytesMut::new();
+        coder.encode(payload.clone(), &mut frame).unwrap();
+        assert_eq!(frame[0], NOISE_PREAMBLE);
+        assert_eq!(
+            usize::from(u16::from_be_bytes([frame[1], frame[2]])),
+            payload.len() + NOISE_TAG_LEN
+        );
+        let mut decrypted = vec![0u8; 65535];
+        let decrypted_len = server.read_message(&frame[3..], &mut decrypted).unwrap();
+        assert_eq!(&decrypted[..decrypted_len], &payload[..]);
+
+        // Every direction advances its own nonce
+        let mut buffer = BytesMut::new();
+        for _ in 0..2 {
+            let mut encrypted = vec![0u8; 65535];
+            let encrypted_len = server.write_message(&payload, &mut encrypted).unwrap();
+            encrypted.truncate(encrypted_len);
+            buffer.extend_from_slice(&create_noise_frame(encrypted));
+        }
+        for _ in 0..2 {
+            let decoded = coder.decode(&mut buffer).unwrap().unwrap();
+            assert_eq!(decoded.type_id, Some(7));
+            assert_eq!(&decoded.payload[..], &payload[4..]);
+        }
+        // The buffer of the decrypted messages is returned to the pool
+        assert_eq!(pool.idle(), 1);
+        let mut second_frame = BytesMut::new();
+        coder.encode(payload.clone(), &mut second_frame).unwrap();
+        let second_len = server
+            .read_message(&second_frame[3..], &mut decrypted)
+            .unwrap();
+        assert_eq!(&decrypted[..second_len], &payload[..]);
+    }
 }
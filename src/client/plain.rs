@@ -1,34 +1,28 @@
-use tokio::net::TcpStream;
-
 use super::{
     StreamPair,
     noise::NOISE_PREAMBLE,
-    stream_reader::{StreamDecoder, StreamReader},
+    runtime::{self, Transport},
+    stream_reader::{Frame, StreamDecoder, StreamReader},
     stream_writer::{StreamEncoder, StreamWriter},
 };
-use crate::error::{ClientError, ConnectionError, ProtocolError, StreamError};
+use crate::error::{ClientError, ProtocolError, StreamError};
+use bytes::{Buf as _, BytesMut};
 
 pub(super) const PLAIN_PREAMBLE: u8 = 0x00;
 
-pub(crate) async fn connect(addr: &str) -> Result<StreamPair, ClientError> {
-    let (read_stream, write_stream) = TcpStream::connect(addr)
-        .await
-        .map_err(|e| ConnectionError::TcpConnect {
-            address: addr.to_owned(),
-            source: e,
-        })?
-        .into_split();
-    tracing::debug!("Tcp connection established to {addr}");
-    Ok((
+/// Wraps an established connection into plain text streams.
+pub(crate) fn connect(stream: Box<dyn Transport>) -> StreamPair {
+    let (read_stream, write_stream) = runtime::split(stream);
+    (
         StreamReader::new(read_stream).with_decoder(Box::new(PlainDecoder)),
         StreamWriter::new(write_stream).with_encoder(Box::new(PlainEncoder)),
-    ))
+    )
 }
 
 #[derive(Debug)]
 struct PlainDecoder;
 impl StreamDecoder for PlainDecoder {
-    fn decode(&self, buffer: &mut Vec<u8>) -> Result<Option<Vec<u8>>, ClientError> {
+    fn decode(&self, buffer: &mut BytesMut) -> Result<Option<Frame>, ClientError> {
         read_frame_from_buffer(buffer)
     }
 }
@@ -36,13 +30,14 @@ impl StreamDecoder for PlainDecoder {
 #[derive(Debug)]
 struct PlainEncoder;
 impl StreamEncoder for PlainEncoder {
-    fn encode(&self, payload: Vec<u8>) -> Result<Vec<u8>, ClientError> {
-        create_frame(&payload)
+    fn encode(&self, payload: Vec<u8>, frames: &mut BytesMut) -> Result<(), ClientError> {
+        frames.extend_from_slice(&create_frame(&payload)?);
+        Ok(())
     }
 }
 
 /// Create a frame with the given payload, including the preamble and length.
-fn create_frame(payload: &[u8]) -> Result<Vec<u8>, ClientError> {
+pub(super) fn create_frame(payload: &[u8]) -> Result<Vec<u8>, ClientError> {
     // Plain payload are structured differently than Noise payloads.
     // Noise payloads have 2 bytes for the type and then 2 bytes for the length
     // Plain payloads use leb128 compression for first the length, then the type
@@ -63,12 +58,19 @@ fn create_frame(payload: &[u8]) -> Result<Vec<u8>, ClientError> {
     .concat())
 }
 
-/// Attempts to read a frame from the buffer.
-fn read_frame_from_buffer(buffer: &mut Vec<u8>) -> Result<Option<Vec<u8>>, ClientError> {
-    if buffer.len() < 3 {
+/// Attempts to read a frame from the buffer, the payload is split off the buffer without copying.
+pub(super) fn read_frame_from_buffer(buffer: &mut BytesMut) -> Result<Option<Frame>, ClientError> {
+    Ok(split_frame_from_buffer(buffer)?.map(|(type_id, payload)| Frame::message(type_id, payload)))
+}
+
+/// Attempts to split a frame off the buffer, returning the message type id and the protobuf payload.
+pub(super) fn split_frame_from_buffer(
+    buffer: &mut BytesMut,
+) -> Result<Option<(u16, BytesMut)>, ClientError> {
+    // The preamble is checked first, so a device requiring encryption is detected from its first byte
+    let Some(&preamble) = buffer.first() else {
         return Ok(None);
-    }
-    let preamble = buffer[0];
+    };
     match preamble {
         PLAIN_PREAMBLE => {}
         NOISE_PREAMBLE => {
@@ -81,6 +83,9 @@ fn read_frame_from_buffer(buffer: &mut Vec<u8>) -> Result<Option<Vec<u8>>, Clien
             .into());
         }
     }
+    if buffer.len() < 3 {
+        return Ok(None);
+    }
     let (frame_len, next_index) = match convert_from_leb128(buffer, 1) {
         Some((len, index)) => (usize::from(len), index),
         None => return Ok(None),
@@ -96,24 +101,9 @@ fn read_frame_from_buffer(buffer: &mut Vec<u8>) -> Result<Option<Vec<u8>>, Clien
         );
         return Ok(None);
     }
-    let frame = buffer
-        .drain(..frame_len + next_index)
-        .skip(next_index)
-        .collect();
-    let frame_len = u16::try_from(frame_len).map_err(|_e| StreamError::FrameTooLarge {
-        size: frame_len,
-        #[allow(clippy::as_conversions, reason = "u16:MAX should always fit in usize")]
-        max_size: u16::MAX as usize,
-    })?;
-    // Reconstruct frame as it came from noise encrypted stream, 2 bytes for type and 2 bytes for length
-    Ok(Some(
-        [
-            type_id.to_be_bytes().to_vec(),
-            frame_len.to_be_bytes().to_vec(),
-            frame,
-        ]
-        .concat(),
-    ))
+    let mut frame = buffer.split_to(next_index + frame_len);
+    frame.advance(next_index);
+    Ok(Some((type_id, frame)))
 }
 
 fn convert_to_leb128(mut value: u16) -> Vec<u8> {
@@ -183,15 +173,14 @@ mod test {
         payload.extend_from_slice(&frame_len.to_be_bytes());
         payload.extend_from_slice(&payload_data);
 
-        let mut buffer = create_frame(&payload).expect("Frame should be created");
+        let mut buffer =
+            BytesMut::from(&create_frame(&payload).expect("Frame should be created")[..]);
 
         let decoded = read_frame_from_buffer(&mut buffer)
             .expect("Should decode")
             .expect("Should have frame");
-        // The decoded frame should reconstruct the original type_id, frame_len, and payload_data
-        assert_eq!(&decoded[0..2], &type_id.to_be_bytes());
-        assert_eq!(&decoded[2..4], &frame_len.to_be_bytes());
-        assert_eq!(&decoded[4..], &payload_data);
+        assert_eq!(decoded.type_id, Some(type_id));
+        assert_eq!(&decoded.payload[..], &payload_data);
         assert!(buffer.is_empty());
     }
 
@@ -204,14 +193,27 @@ mod test {
 
     #[test]
     fn test_read_frame_from_buffer_with_noise_preamble() {
-        let mut buffer = vec![NOISE_PREAMBLE, 0x01, 0x02, 0x03];
+        let mut buffer = BytesMut::from(&[NOISE_PREAMBLE, 0x01, 0x02, 0x03][..]);
         let result = read_frame_from_buffer(&mut buffer);
         result.unwrap_err();
     }
 
+    #[test]
+    fn test_read_frame_from_buffer_with_partial_noise_frame() {
+        let mut buffer = BytesMut::from(&[NOISE_PREAMBLE][..]);
+        let error = read_frame_from_buffer(&mut buffer).unwrap_err();
+        assert!(
+            matches!(
+                error,
+                ClientError::Protocol(ProtocolError::UnexpectedEncryption)
+            ),
+            "Unexpected error: {error}"
+        );
+    }
+
     #[test]
     fn test_read_frame_from_buffer_with_invalid_preamble() {
-        let mut buffer = vec![0xFF, 0x01, 0x02, 0x03];
+        let mut buffer = BytesMut::from(&[0xFF, 0x01, 0x02, 0x03][..]);
         let result = read_frame_from_buffer(&mut buffer);
         result.unwrap_err();
     }
@@ -219,7 +221,7 @@ mod test {
     #[test]
     fn test_read_frame_from_buffer_incomplete_leb128() {
         // Only preamble and one byte, not enough for length/type
-        let mut buffer = vec![PLAIN_PREAMBLE, 0x81];
+        let mut buffer = BytesMut::from(&[PLAIN_PREAMBLE, 0x81][..]);
         let result = read_frame_from_buffer(&mut buffer);
         assert!(result.unwrap().is_none());
     }
@@ -234,7 +236,7 @@ mod test {
         frame.extend(convert_to_leb128(type_id));
         frame.extend(vec![0u8; 5]); // not enough data
 
-        let mut buffer = frame;
+        let mut buffer = BytesMut::from(&frame[..]);
         let result = read_frame_from_buffer(&mut buffer);
         assert!(result.unwrap().is_none());
     }
@@ -0,0 +1,304 @@
+use std::{fmt::Debug, sync::Arc, time::Duration};
+
+use tokio::sync::{Mutex, RwLock, broadcast};
+
+use super::{EspHomeClient, EspHomeClientBuilder};
+#[cfg(feature = "metrics")]
+use crate::metrics;
+use crate::{
+    error::ClientError,
+    proto::{DisconnectRequest, EspHomeMessage},
+};
+
+/// Exponential backoff used between reconnection attempts.
+///
+/// The first attempt waits `initial_delay`, every following attempt multiplies the delay with
+/// `multiplier`, up to `max_delay`. Defaults to 1 second, doubling up to 1 minute, without an attempt limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackoffPolicy {
+    initial_delay: Duration,
+    max_delay: Duration,
+    multiplier: u32,
+    pub(super) max_attempts: Option<u32>,
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BackoffPolicy {
+    /// Creates the default backoff policy.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            multiplier: 2,
+            max_attempts: None,
+        }
+    }
+
+    /// Sets the delay before the first reconnection attempt.
+    #[must_use]
+    pub const fn initial_delay(mut self, delay: Duration) -> Self {
+        self.initial_delay = delay;
+        self
+    }
+
+    /// Sets the maximum delay between reconnection attempts.
+    #[must_use]
+    pub const fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Sets the factor by which the delay grows after each failed attempt.
+    #[must_use]
+    pub const fn multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Limits the number of consecutive reconnection attempts, after which the last error is returned.
+    #[must_use]
+    pub const fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = Some(attempts);
+        self
+    }
+
+    /// Gets the delay before the given (zero based) attempt.
+    #[must_use]
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.initial_delay
+            .saturating_mul(self.multiplier.saturating_pow(attempt))
+            .min(self.max_delay)
+    }
+}
+
+/// Connection state changes reported by the [`EspHomeConnectionManager`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// The connection was lost.
+    Disconnected {
+        /// Description of the error which caused the disconnect.
+        reason: String,
+    },
+    /// A reconnection attempt is scheduled.
+    Reconnecting {
+        /// Number of the attempt, starting at 1.
+        attempt: u32,
+        /// Delay before the attempt is made.
+        delay: Duration,
+    },
+    /// The connection was re-established, including the connection setup.
+    Reconnected,
+    /// The maximum number of attempts was reached without re-establishing the connection, or an
+    /// attempt failed with an error which isn't recoverable.
+    GaveUp,
+}
+
+/// Client wrapper which transparently re-establishes the connection when it is lost.
+///
+/// When reading or writing fails due to a broken connection, a new connection is set up with the
+/// settings of the original builder, waiting between attempts according to the [`BackoffPolicy`].
+/// Reads continue on the new connection, a failed write is retried once on the new connection.
+/// State changes can be observed with [`EspHomeConnectionManager::events`].
+///
+/// Note that subscriptions (e.g. `SubscribeStatesRequest`) are bound to a connection, and need to be sent
+/// again after a [`ConnectionEvent::Reconnected`] event.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{BackoffPolicy, EspHomeClient};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let manager = EspHomeClient::builder()
+///     .address("192.168.0.2:6053")
+///     .connect_with_auto_reconnect(BackoffPolicy::new())
+///     .await?;
+/// loop {
+///     let message = manager.try_read().await?;
+///     println!("Received: {message:?}");
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct EspHomeConnectionManager {
+    builder: EspHomeClientBuilder,
+    policy: BackoffPolicy,
+    client: RwLock<Arc<EspHomeClient>>,
+    /// Serialises the reconnections, without blocking access to the client while waiting.
+    reconnecting: Mutex<()>,
+    events: broadcast::Sender<ConnectionEvent>,
+}
+
+impl EspHomeConnectionManager {
+    /// Connects using the given builder, the builder is reused for reconnecting.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the initial connection fails, it is not retried.
+    pub async fn connect(
+        builder: EspHomeClientBuilder,
+        policy: BackoffPolicy,
+    ) -> Result<Self, ClientError> {
+        let client = builder.clone().connect().await?;
+        let (events, _) = broadcast::channel(16);
+        Ok(Self {
+            builder,
+            policy,
+            client: RwLock::new(Arc::new(client)),
+            reconnecting: Mutex::new(()),
+            events,
+        })
+    }
+
+    /// Subscribes to the connection state changes.
+    #[must_use]
+    pub fn events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.events.subscribe()
+    }
+
+    /// Gets the client of the current connection.
+    pub async fn client(&self) -> Arc<EspHomeClient> {
+        Arc::clone(&*self.client.read().await)
+    }
+
+    /// Sends a message to the ESPHome device, reconnecting if the connection was lost.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if reconnecting fails, or if the write fails on the new connection.
+    pub async fn try_write<M>(&self, message: M) -> Result<(), ClientError>
+    where
+        M: Into<EspHomeMessage> + Debug,
+    {
+        let message: EspHomeMessage = message.into();
+        let client = self.client().await;
+        match client.try_write(message.clone()).await {
+            Err(e) if e.is_recoverable() => {
+                self.reconnect(&client, &e).await?;
+                self.client().await.try_write(message).await
+            }
+            result => result,
+        }
+    }
+
+    /// Reads the next message from the device, reconnecting if the connection was lost.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if reconnecting fails, or if the message can't be decoded.
+    pub async fn try_read(&self) -> Result<EspHomeMessage, ClientError> {
+        loop {
+            let client = self.client().await;
+            match client.try_read().await {
+                Err(e) if e.is_recoverable() => self.reconnect(&client, &e).await?,
+                result => return result,
+            }
+        }
+    }
+
+    /// Closes the current connection gracefully by sending a `DisconnectRequest` message.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails, for example due to a disconnected stream
+    pub async fn close(self) -> Result<(), ClientError> {
+        self.client().await.try_write(DisconnectRequest {}).await
+    }
+
+    /// Replaces the failed client with a new connection.
+    ///
+    /// Concurrent callers wait for the reconnection, if the client was already replaced nothing is done.
+    /// Gives up right away when a connection attempt fails with an error which isn't recoverable,
+    /// like a wrong encryption key.
+    async fn reconnect(
+        &self,
+        failed: &Arc<EspHomeClient>,
+        error: &ClientError,
+    ) -> Result<(), ClientError> {
+        let _reconnecting = self.reconnecting.lock().await;
+        if !Arc::ptr_eq(&*self.client.read().await, failed) {
+            return Ok(());
+        }
+        tracing::warn!("Connection lost: {error}");
+        self.emit(ConnectionEvent::Disconnected {
+            reason: error.to_string(),
+        });
+        let mut attempt = 0;
+        loop {
+            let delay = self.policy.delay(attempt);
+            attempt = attempt.saturating_add(1);
+            self.emit(ConnectionEvent::Reconnecting { attempt, delay });
+            failed.clock.sleep(delay).await;
+            #[cfg(feature = "metrics")]
+            metrics::reconnect_attempt();
+            match self.builder.clone().connect().await {
+                Ok(client) => {
+                    *self.client.write().await = Arc::new(client);
+                    #[cfg(feature = "metrics")]
+                    metrics::reconnected();
+                    tracing::info!("Reconnected after {attempt} attempt(s)");
+                    self.emit(ConnectionEvent::Reconnected);
+                    return Ok(());
+                }
+                Err(e) if !e.is_recoverable() => {
+                    tracing::error!("Giving up reconnecting after attempt {attempt}: {e}");
+                    self.emit(ConnectionEvent::GaveUp);
+                    return Err(e);
+                }
+                Err(e) if self.policy.max_attempts.is_some_and(|max| attempt >= max) => {
+                    tracing::error!("Giving up reconnecting after {attempt} attempt(s): {e}");
+                    self.emit(ConnectionEvent::GaveUp);
+                    return Err(e);
+                }
+                Err(e) => tracing::debug!("Reconnection attempt {attempt} failed: {e}"),
+            }
+        }
+    }
+
+    fn emit(&self, event: ConnectionEvent) {
+        // Sending only fails if there are no subscribers
+        let _ignored = self.events.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{ProtocolError, StreamError};
+
+    #[test]
+    fn test_backoff_delay() {
+        let policy = BackoffPolicy::new()
+            .initial_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_secs(1));
+        assert_eq!(policy.delay(0), Duration::from_millis(100));
+        assert_eq!(policy.delay(1), Duration::from_millis(200));
+        assert_eq!(policy.delay(3), Duration::from_millis(800));
+        assert_eq!(policy.delay(4), Duration::from_secs(1));
+        assert_eq!(policy.delay(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_is_recoverable() {
+        assert!(ClientError::Disconnected.is_recoverable());
+        assert!(ClientError::Timeout { timeout_ms: 1 }.is_recoverable());
+        assert!(ClientError::IdleTimeout { timeout_ms: 1 }.is_recoverable());
+        assert!(
+            ClientError::from(StreamError::InvalidFrame {
+                reason: String::new()
+            })
+            .is_recoverable()
+        );
+        assert!(
+            !ClientError::from(ProtocolError::ValidationFailed {
+                reason: String::new()
+            })
+            .is_recoverable()
+        );
+    }
+}
@@ -0,0 +1,446 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+
+use crate::{proto::EspHomeMessage, states::entity_id};
+
+/// Kind of an entity, after the `ListEntities*Response` message describing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityKind {
+    /// A binary sensor.
+    BinarySensor,
+    /// A cover.
+    Cover,
+    /// A fan.
+    Fan,
+    /// A light.
+    Light,
+    /// A sensor.
+    Sensor,
+    /// A switch.
+    Switch,
+    /// A text sensor.
+    TextSensor,
+    /// A user defined service (action).
+    Service,
+    /// A camera.
+    Camera,
+    /// A climate device.
+    Climate,
+    /// A number.
+    Number,
+    /// A select.
+    Select,
+    /// A lock.
+    Lock,
+    /// A button.
+    Button,
+    /// A media player.
+    MediaPlayer,
+    #[cfg(not(any(feature = "api-1-8")))]
+    /// An alarm control panel.
+    AlarmControlPanel,
+    #[cfg(not(any(feature = "api-1-8")))]
+    /// A text.
+    Text,
+    #[cfg(not(any(feature = "api-1-8")))]
+    /// A date.
+    Date,
+    #[cfg(not(any(feature = "api-1-8")))]
+    /// A time.
+    Time,
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// A siren.
+    Siren,
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// An event.
+    Event,
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// A valve.
+    Valve,
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// A date time.
+    DateTime,
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// An update.
+    Update,
+    #[cfg(not(any(
+        feature = "api-1-13",
+        feature = "api-1-12",
+        feature = "api-1-10",
+        feature = "api-1-9",
+        feature = "api-1-8"
+    )))]
+    /// A water heater.
+    WaterHeater,
+    #[cfg(not(any(
+        feature = "api-1-13",
+        feature = "api-1-12",
+        feature = "api-1-10",
+        feature = "api-1-9",
+        feature = "api-1-8"
+    )))]
+    /// An infrared transmitter/receiver.
+    Infrared,
+    #[cfg(not(any(
+        feature = "api-1-13",
+        feature = "api-1-12",
+        feature = "api-1-10",
+        feature = "api-1-9",
+        feature = "api-1-8"
+    )))]
+    /// A radio frequency transmitter/receiver.
+    RadioFrequency,
+}
+
+/// Identity of an entity, to label the state messages of the entity which only carry its key.
+///
+/// Created from the `ListEntities*Response` messages, see [`EspHomeClient::entity`](super::EspHomeClient::entity).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityInfo {
+    /// ID of the device the entity belongs to, 0 for the main device and for API versions without
+    /// sub devices.
+    pub device_id: u32,
+    /// Key of the entity, used in the state and command messages. Only unique per device.
+    pub key: u32,
+    /// Object id of the entity, e.g. `kitchen_temperature`. Empty for services, which have no object id.
+    pub object_id: String,
+    /// Display name of the entity.
+    pub name: String,
+    /// Kind of the entity.
+    pub kind: EntityKind,
+}
+
+impl EntityInfo {
+    /// Gets the identity of the entity described by a `ListEntities*Response` message.
+    ///
+    /// Returns `None` if the message is not an entity description.
+    #[must_use]
+    #[allow(clippy::too_many_lines, reason = "One match arm per entity kind")]
+    pub fn from_message(message: &EspHomeMessage) -> Option<Self> {
+        let (kind, (device_id, key), object_id, name) = match message {
+            EspHomeMessage::ListEntitiesBinarySensorResponse(entity) => (
+                EntityKind::BinarySensor,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            EspHomeMessage::ListEntitiesCoverResponse(entity) => (
+                EntityKind::Cover,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            EspHomeMessage::ListEntitiesFanResponse(entity) => (
+                EntityKind::Fan,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            EspHomeMessage::ListEntitiesLightResponse(entity) => (
+                EntityKind::Light,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            EspHomeMessage::ListEntitiesSensorResponse(entity) => (
+                EntityKind::Sensor,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            EspHomeMessage::ListEntitiesSwitchResponse(entity) => (
+                EntityKind::Switch,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            EspHomeMessage::ListEntitiesTextSensorResponse(entity) => (
+                EntityKind::TextSensor,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            EspHomeMessage::ListEntitiesServicesResponse(entity) => {
+                return Some(Self {
+                    device_id: 0,
+                    key: entity.key,
+                    object_id: String::new(),
+                    name: entity.name.clone(),
+                    kind: EntityKind::Service,
+                });
+            }
+            EspHomeMessage::ListEntitiesCameraResponse(entity) => (
+                EntityKind::Camera,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            EspHomeMessage::ListEntitiesClimateResponse(entity) => (
+                EntityKind::Climate,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            EspHomeMessage::ListEntitiesNumberResponse(entity) => (
+                EntityKind::Number,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            EspHomeMessage::ListEntitiesSelectResponse(entity) => (
+                EntityKind::Select,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            EspHomeMessage::ListEntitiesLockResponse(entity) => (
+                EntityKind::Lock,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            EspHomeMessage::ListEntitiesButtonResponse(entity) => (
+                EntityKind::Button,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            EspHomeMessage::ListEntitiesMediaPlayerResponse(entity) => (
+                EntityKind::MediaPlayer,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesAlarmControlPanelResponse(entity) => (
+                EntityKind::AlarmControlPanel,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesTextResponse(entity) => (
+                EntityKind::Text,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesDateResponse(entity) => (
+                EntityKind::Date,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesTimeResponse(entity) => (
+                EntityKind::Time,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesSirenResponse(entity) => (
+                EntityKind::Siren,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesEventResponse(entity) => (
+                EntityKind::Event,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesValveResponse(entity) => (
+                EntityKind::Valve,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesDateTimeResponse(entity) => (
+                EntityKind::DateTime,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::ListEntitiesUpdateResponse(entity) => (
+                EntityKind::Update,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            #[cfg(not(any(
+                feature = "api-1-13",
+                feature = "api-1-12",
+                feature = "api-1-10",
+                feature = "api-1-9",
+                feature = "api-1-8"
+            )))]
+            EspHomeMessage::ListEntitiesWaterHeaterResponse(entity) => (
+                EntityKind::WaterHeater,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            #[cfg(not(any(
+                feature = "api-1-13",
+                feature = "api-1-12",
+                feature = "api-1-10",
+                feature = "api-1-9",
+                feature = "api-1-8"
+            )))]
+            EspHomeMessage::ListEntitiesInfraredResponse(entity) => (
+                EntityKind::Infrared,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            #[cfg(not(any(
+                feature = "api-1-13",
+                feature = "api-1-12",
+                feature = "api-1-10",
+                feature = "api-1-9",
+                feature = "api-1-8"
+            )))]
+            EspHomeMessage::ListEntitiesRadioFrequencyResponse(entity) => (
+                EntityKind::RadioFrequency,
+                entity_id!(entity),
+                &entity.object_id,
+                &entity.name,
+            ),
+            _ => return None,
+        };
+        Some(Self {
+            device_id,
+            key,
+            object_id: object_id.clone(),
+            name: name.clone(),
+            kind,
+        })
+    }
+}
+
+/// Registry of the entities described by the received `ListEntities*Response` messages.
+#[derive(Debug, Default)]
+pub(crate) struct EntityRegistry {
+    entities: Mutex<HashMap<(u32, u32), EntityInfo>>,
+}
+
+impl EntityRegistry {
+    /// Records the entity if the message is an entity description.
+    pub(crate) fn update(&self, message: &EspHomeMessage) {
+        let Some(info) = EntityInfo::from_message(message) else {
+            return;
+        };
+        self.entities
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert((info.device_id, info.key), info);
+    }
+
+    /// Gets the entity with the given device ID and key.
+    pub(crate) fn get(&self, device_id: u32, key: u32) -> Option<EntityInfo> {
+        self.entities
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&(device_id, key))
+            .cloned()
+    }
+
+    /// Gets the entity with the given object id.
+    pub(crate) fn by_object_id(&self, object_id: &str) -> Option<EntityInfo> {
+        self.entities
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .values()
+            .find(|info| info.object_id == object_id)
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::{
+        ListEntitiesSensorResponse, ListEntitiesServicesResponse, SensorStateResponse,
+    };
+
+    #[test]
+    fn test_entity_registry() {
+        let registry = EntityRegistry::default();
+        registry.update(
+            &ListEntitiesSensorResponse {
+                key: 7,
+                object_id: "kitchen_temp".to_owned(),
+                name: "Kitchen temperature".to_owned(),
+                ..Default::default()
+            }
+            .into(),
+        );
+        registry.update(
+            &ListEntitiesServicesResponse {
+                key: 8,
+                name: "reset".to_owned(),
+                ..Default::default()
+            }
+            .into(),
+        );
+        registry.update(
+            &SensorStateResponse {
+                key: 9,
+                ..Default::default()
+            }
+            .into(),
+        );
+
+        let expected = EntityInfo {
+            device_id: 0,
+            key: 7,
+            object_id: "kitchen_temp".to_owned(),
+            name: "Kitchen temperature".to_owned(),
+            kind: EntityKind::Sensor,
+        };
+        assert_eq!(registry.get(0, 7), Some(expected.clone()));
+        assert_eq!(registry.by_object_id("kitchen_temp"), Some(expected));
+        assert_eq!(
+            registry.get(0, 8).map(|info| info.kind),
+            Some(EntityKind::Service)
+        );
+        assert_eq!(registry.get(0, 9), None);
+        assert_eq!(registry.by_object_id("unknown"), None);
+    }
+
+    #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+    #[test]
+    fn test_entity_registry_sub_devices() {
+        let registry = EntityRegistry::default();
+        for (device_id, name) in [(1, "Kitchen"), (2, "Garage")] {
+            registry.update(
+                &ListEntitiesSensorResponse {
+                    key: 7,
+                    object_id: "temperature".to_owned(),
+                    name: name.to_owned(),
+                    device_id,
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+        assert_eq!(
+            registry.get(1, 7).map(|info| info.name).as_deref(),
+            Some("Kitchen")
+        );
+        assert_eq!(
+            registry.get(2, 7).map(|info| info.name).as_deref(),
+            Some("Garage")
+        );
+        assert_eq!(registry.get(0, 7), None);
+    }
+}
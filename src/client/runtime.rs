@@ -0,0 +1,157 @@
+use std::{
+    fmt::{self, Debug},
+    future::{Future, poll_fn},
+    pin::{Pin, pin},
+    sync::Arc,
+    task::Poll,
+    time::{Duration, Instant},
+};
+
+use tokio::io::{self, AsyncRead, AsyncWrite, ReadHalf, WriteHalf};
+#[cfg(feature = "runtime-tokio")]
+use tokio::time;
+
+/// Connection to a device, like a TCP connection of any async runtime.
+///
+/// Implemented for all streams with the tokio I/O traits, which don't need the tokio runtime. Streams
+/// of other runtimes, implementing the `futures` I/O traits, can be adapted with the compat wrappers of
+/// `tokio-util` or `async-compat`.
+pub trait Transport: AsyncRead + AsyncWrite + Send + Sync + Unpin + 'static {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Sync + Unpin + 'static> Transport for T {}
+
+impl Debug for dyn Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Transport")
+    }
+}
+
+/// Future returned by [`Timer::sleep`].
+pub type Sleep = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Timer of an async runtime, used for the timeouts of the client.
+///
+/// With the `runtime-tokio` feature, the client uses [`TokioTimer`] unless another timer is set.
+///
+/// # Usage:
+/// ```rust
+/// use std::time::Duration;
+/// use esphome_client::{Sleep, Timer};
+///
+/// #[derive(Debug)]
+/// struct TokioSleep;
+///
+/// impl Timer for TokioSleep {
+///     fn sleep(&self, duration: Duration) -> Sleep {
+///         Box::pin(tokio::time::sleep(duration))
+///     }
+/// }
+/// ```
+pub trait Timer: Debug + Send + Sync + 'static {
+    /// Returns a future completing once the duration elapsed.
+    fn sleep(&self, duration: Duration) -> Sleep;
+}
+
+/// Timer of the tokio runtime, the default with the `runtime-tokio` feature.
+#[cfg(feature = "runtime-tokio")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioTimer;
+
+#[cfg(feature = "runtime-tokio")]
+impl Timer for TokioTimer {
+    fn sleep(&self, duration: Duration) -> Sleep {
+        Box::pin(time::sleep(duration))
+    }
+}
+
+pub(crate) type ReadStream = ReadHalf<Box<dyn Transport>>;
+pub(crate) type WriteStream = WriteHalf<Box<dyn Transport>>;
+
+/// Splits the transport into the halves read and written by the client.
+pub(crate) fn split(transport: Box<dyn Transport>) -> (ReadStream, WriteStream) {
+    io::split(transport)
+}
+
+/// Error of [`Clock::timeout`], when the duration elapsed before the future completed.
+#[derive(Debug)]
+pub(crate) struct Elapsed;
+
+/// Timeouts of a connection, using the timer of the runtime.
+#[derive(Debug, Clone)]
+pub(crate) struct Clock(Arc<dyn Timer>);
+
+impl Clock {
+    pub(crate) fn new(timer: Arc<dyn Timer>) -> Self {
+        Self(timer)
+    }
+
+    pub(crate) fn sleep(&self, duration: Duration) -> Sleep {
+        self.0.sleep(duration)
+    }
+
+    /// Runs the future, failing when it doesn't complete within the duration.
+    pub(crate) async fn timeout<F: Future>(
+        &self,
+        duration: Duration,
+        future: F,
+    ) -> Result<F::Output, Elapsed> {
+        let mut future = pin!(future);
+        let mut sleep = self.sleep(duration);
+        poll_fn(|cx| {
+            if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                return Poll::Ready(Ok(output));
+            }
+            sleep.as_mut().poll(cx).map(|()| Err(Elapsed))
+        })
+        .await
+    }
+
+    /// Runs the future, failing when it doesn't complete before the deadline.
+    pub(crate) async fn timeout_at<F: Future>(
+        &self,
+        deadline: Instant,
+        future: F,
+    ) -> Result<F::Output, Elapsed> {
+        self.timeout(deadline.saturating_duration_since(Instant::now()), future)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::future;
+
+    /// Timer whose sleeps complete right away, or never.
+    #[derive(Debug)]
+    struct FixedTimer(bool);
+
+    impl Timer for FixedTimer {
+        fn sleep(&self, _duration: Duration) -> Sleep {
+            if self.0 {
+                Box::pin(future::ready(()))
+            } else {
+                Box::pin(future::pending())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clock_timeout() {
+        let never = Clock::new(Arc::new(FixedTimer(false)));
+        assert_eq!(
+            never
+                .timeout(Duration::from_secs(1), async { 42 })
+                .await
+                .ok(),
+            Some(42)
+        );
+        let elapsed = Clock::new(Arc::new(FixedTimer(true)));
+        assert!(
+            elapsed
+                .timeout(Duration::from_secs(1), future::pending::<()>())
+                .await
+                .is_err()
+        );
+    }
+}
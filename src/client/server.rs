@@ -0,0 +1,426 @@
+//! Responder side of the ESPHome API, to build virtual devices, protocol proxies and debugging tools.
+
+#![allow(
+    clippy::module_name_repetitions,
+    reason = "Server names mirror the EspHomeClient naming"
+)]
+
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use tokio::{
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    time::timeout,
+};
+
+use super::{
+    StreamPair, noise, plain, runtime::Transport, stream_reader::StreamReader,
+    stream_writer::StreamWriter,
+};
+#[cfg(not(any(
+    feature = "api-1-12",
+    feature = "api-1-10",
+    feature = "api-1-9",
+    feature = "api-1-8"
+)))]
+use crate::proto::AuthenticationResponse;
+#[cfg(any(
+    feature = "api-1-12",
+    feature = "api-1-10",
+    feature = "api-1-9",
+    feature = "api-1-8"
+))]
+use crate::proto::ConnectResponse;
+use crate::{
+    API_VERSION,
+    error::{ClientError, ConnectionError, ProtocolError},
+    proto::{DisconnectResponse, EspHomeMessage, HelloResponse, PingResponse},
+};
+
+/// Server for the ESPHome API, accepting connections of clients like Home Assistant.
+///
+/// The server takes care of the framing, the noise encryption and the protocol requests, see
+/// [`ServerConnection::read`]. Answering the other requests, like listing the entities, is up to the
+/// application.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{
+///     server::EspHomeServer,
+///     types::{DeviceInfoResponse, EspHomeMessage, ListEntitiesDoneResponse},
+/// };
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let server = EspHomeServer::builder()
+///     .name("virtual-device")
+///     .key("px7tsbK3C7bpXHr2OevEV2ZMg/FrNBw2+O2pNPbedtA=")
+///     .bind("0.0.0.0:6053")
+///     .await?;
+/// loop {
+///     let connection = server.accept().await?;
+///     tokio::spawn(connection.serve(|request| match request {
+///         EspHomeMessage::DeviceInfoRequest(_) => vec![
+///             DeviceInfoResponse { name: "virtual-device".into(), ..Default::default() }.into(),
+///         ],
+///         EspHomeMessage::ListEntitiesRequest(_) => vec![ListEntitiesDoneResponse {}.into()],
+///         _ => Vec::new(),
+///     }));
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct EspHomeServer {
+    listener: TcpListener,
+    config: Arc<ServerConfig>,
+}
+
+impl EspHomeServer {
+    /// Creates a builder to configure the server.
+    #[must_use]
+    pub fn builder() -> EspHomeServerBuilder {
+        EspHomeServerBuilder::new()
+    }
+
+    /// Returns the address the server is listening on.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the address of the socket can't be retrieved.
+    pub fn local_addr(&self) -> Result<SocketAddr, ClientError> {
+        self.listener
+            .local_addr()
+            .map_err(|e| ClientError::Configuration {
+                message: format!("Failed to get the address of the server: {e}"),
+            })
+    }
+
+    /// Accepts the next client, and completes the noise handshake when a key is configured.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if accepting the connection fails, or if the handshake fails or doesn't
+    /// complete within the handshake timeout. The server keeps listening after a failed handshake.
+    pub async fn accept(&self) -> Result<ServerConnection, ClientError> {
+        let (stream, peer) =
+            self.listener
+                .accept()
+                .await
+                .map_err(|e| ConnectionError::TcpConnect {
+                    address: self.listener.local_addr().map_or_else(
+                        |_| "unknown address".to_owned(),
+                        |address| address.to_string(),
+                    ),
+                    source: e,
+                })?;
+        tracing::debug!("Accepted connection from {peer}");
+        self.accept_stream(stream, peer).await
+    }
+
+    async fn accept_stream(
+        &self,
+        stream: TcpStream,
+        peer: SocketAddr,
+    ) -> Result<ServerConnection, ClientError> {
+        stream
+            .set_nodelay(true)
+            .map_err(|source| ConnectionError::SocketOptions { source })?;
+        let stream: Box<dyn Transport> = Box::new(stream);
+        let (reader, writer): StreamPair = match &self.config.key {
+            Some(key) => timeout(
+                self.config.handshake_timeout,
+                noise::accept(stream, key, (&self.config.name, &self.config.mac)),
+            )
+            .await
+            .map_err(|_elapsed| ClientError::Timeout {
+                timeout_ms: self.config.handshake_timeout.as_millis(),
+            })??,
+            None => plain::connect(stream),
+        };
+        Ok(ServerConnection {
+            reader,
+            writer,
+            peer,
+            config: Arc::clone(&self.config),
+            client_info: None,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct ServerConfig {
+    key: Option<[u8; 32]>,
+    name: String,
+    mac: String,
+    server_info: String,
+    api_version: (u32, u32),
+    password: Option<String>,
+    handshake_timeout: Duration,
+}
+
+/// Builder for an [`EspHomeServer`].
+#[derive(Debug, Clone)]
+pub struct EspHomeServerBuilder {
+    key: Option<String>,
+    name: String,
+    mac: String,
+    server_info: String,
+    api_version: (u32, u32),
+    password: Option<String>,
+    handshake_timeout: Duration,
+}
+
+impl Default for EspHomeServerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EspHomeServerBuilder {
+    /// Creates a builder for a plain text server, reporting the API version of the crate.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            key: None,
+            name: "esphome-client".to_owned(),
+            mac: "00:00:00:00:00:00".to_owned(),
+            server_info: format!("esphome-client {}", env!("CARGO_PKG_VERSION")),
+            api_version: API_VERSION,
+            password: None,
+            handshake_timeout: Duration::from_secs(30),
+        }
+    }
+
+    /// Enables encrypted communication with a 32-byte base64-encoded key, clients have to use the same key.
+    #[must_use]
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.to_owned());
+        self
+    }
+
+    /// Sets the name of the device, announced during the noise handshake and in the `HelloResponse`.
+    #[must_use]
+    pub fn name(mut self, name: &str) -> Self {
+        name.clone_into(&mut self.name);
+        self
+    }
+
+    /// Sets the MAC address of the device announced during the noise handshake, e.g. `AA:BB:CC:DD:EE:FF`.
+    #[must_use]
+    pub fn mac(mut self, mac: &str) -> Self {
+        mac.clone_into(&mut self.mac);
+        self
+    }
+
+    /// Sets the server info reported in the `HelloResponse`, defaults to the name and version of the crate.
+    #[must_use]
+    pub fn server_info(mut self, server_info: &str) -> Self {
+        server_info.clone_into(&mut self.server_info);
+        self
+    }
+
+    /// Sets the API version reported in the `HelloResponse`, defaults to the version of the crate.
+    #[must_use]
+    pub const fn api_version(mut self, major: u32, minor: u32) -> Self {
+        self.api_version = (major, minor);
+        self
+    }
+
+    /// Sets the password clients have to authenticate with, any password is accepted by default.
+    #[must_use]
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_owned());
+        self
+    }
+
+    /// Sets the time clients have to complete the noise handshake after connecting, defaults to 30 seconds.
+    #[must_use]
+    pub const fn handshake_timeout(mut self, handshake_timeout: Duration) -> Self {
+        self.handshake_timeout = handshake_timeout;
+        self
+    }
+
+    /// Starts listening for clients on the given address, e.g. "0.0.0.0:6053".
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the key is invalid, or if the server can't listen on the address.
+    pub async fn bind(self, address: impl ToSocketAddrs) -> Result<EspHomeServer, ClientError> {
+        let key = self.key.as_deref().map(noise::decode_key).transpose()?;
+        let listener =
+            TcpListener::bind(address)
+                .await
+                .map_err(|e| ClientError::Configuration {
+                    message: format!("Failed to listen: {e}"),
+                })?;
+        Ok(EspHomeServer {
+            listener,
+            config: Arc::new(ServerConfig {
+                key,
+                name: self.name,
+                mac: self.mac,
+                server_info: self.server_info,
+                api_version: self.api_version,
+                password: self.password,
+                handshake_timeout: self.handshake_timeout,
+            }),
+        })
+    }
+}
+
+/// Connection of a client to an [`EspHomeServer`].
+#[derive(Debug)]
+pub struct ServerConnection {
+    reader: StreamReader,
+    writer: StreamWriter,
+    peer: SocketAddr,
+    config: Arc<ServerConfig>,
+    client_info: Option<String>,
+}
+
+impl ServerConnection {
+    /// Returns the address of the client.
+    #[must_use]
+    pub const fn peer_addr(&self) -> SocketAddr {
+        self.peer
+    }
+
+    /// Returns the client info the client sent in the `HelloRequest`, e.g. "Home Assistant 2025.1.0".
+    #[must_use]
+    pub fn client_info(&self) -> Option<&str> {
+        self.client_info.as_deref()
+    }
+
+    /// Reads the next message of the client.
+    ///
+    /// Requests of the protocol are answered before they are returned: the `HelloRequest`, the
+    /// authentication with the configured password, the `PingRequest` and the `DisconnectRequest`.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::Disconnected`] when the client closed the connection, or an error if
+    /// the communication with the client fails.
+    pub async fn read(&mut self) -> Result<EspHomeMessage, ClientError> {
+        let payload = self.reader.read_next_message().await?;
+        let message = payload
+            .decode()
+            .map_err(|e| ProtocolError::ValidationFailed {
+                reason: format!("Failed to decode EspHomeMessage: {e}"),
+            })?;
+        tracing::debug!("Receive from {}: {message}", self.peer);
+        tracing::trace!("Receive from {}: {message:?}", self.peer);
+        if let Some(response) = self.protocol_response(&message) {
+            self.write(response).await?;
+        }
+        Ok(message)
+    }
+
+    /// Sends a message to the client.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails.
+    pub async fn write(&self, message: impl Into<EspHomeMessage>) -> Result<(), ClientError> {
+        let message = message.into();
+        tracing::debug!("Send to {}: {message}", self.peer);
+        tracing::trace!("Send to {}: {message:?}", self.peer);
+        self.writer.write_message(message.into()).await
+    }
+
+    /// Sends the messages to the client at once.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails.
+    pub async fn write_all(
+        &self,
+        messages: impl IntoIterator<Item = EspHomeMessage>,
+    ) -> Result<(), ClientError> {
+        let payloads = messages
+            .into_iter()
+            .map(|message| {
+                tracing::debug!("Send to {}: {message}", self.peer);
+                message.into()
+            })
+            .collect::<Vec<Vec<u8>>>();
+        if payloads.is_empty() {
+            return Ok(());
+        }
+        self.writer.write_messages(payloads).await
+    }
+
+    /// Answers the messages of the client with the responses of the handler, until the client disconnects.
+    ///
+    /// Protocol requests are answered by the connection, see [`ServerConnection::read`], and are passed
+    /// to the handler as well.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the communication with the client fails.
+    pub async fn serve<F>(mut self, mut handler: F) -> Result<(), ClientError>
+    where
+        F: FnMut(&EspHomeMessage) -> Vec<EspHomeMessage>,
+    {
+        loop {
+            let message = match self.read().await {
+                Ok(message) => message,
+                Err(ClientError::Disconnected) => return Ok(()),
+                Err(e) => return Err(e),
+            };
+            self.write_all(handler(&message)).await?;
+            if let EspHomeMessage::DisconnectRequest(_) = message {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Returns the response to a request of the protocol, if the message is one.
+    fn protocol_response(&mut self, message: &EspHomeMessage) -> Option<EspHomeMessage> {
+        match message {
+            EspHomeMessage::HelloRequest(request) => {
+                self.client_info = Some(request.client_info.clone());
+                Some(
+                    HelloResponse {
+                        api_version_major: self.config.api_version.0,
+                        api_version_minor: self.config.api_version.1,
+                        server_info: self.config.server_info.clone(),
+                        name: self.config.name.clone(),
+                    }
+                    .into(),
+                )
+            }
+            #[cfg(not(any(
+                feature = "api-1-12",
+                feature = "api-1-10",
+                feature = "api-1-9",
+                feature = "api-1-8"
+            )))]
+            EspHomeMessage::AuthenticationRequest(request) => Some(
+                AuthenticationResponse {
+                    invalid_password: self.is_invalid_password(&request.password),
+                }
+                .into(),
+            ),
+            #[cfg(any(
+                feature = "api-1-12",
+                feature = "api-1-10",
+                feature = "api-1-9",
+                feature = "api-1-8"
+            ))]
+            EspHomeMessage::ConnectRequest(request) => Some(
+                ConnectResponse {
+                    invalid_password: self.is_invalid_password(&request.password),
+                }
+                .into(),
+            ),
+            EspHomeMessage::PingRequest(_) => Some(PingResponse {}.into()),
+            EspHomeMessage::DisconnectRequest(_) => Some(DisconnectResponse {}.into()),
+            _ => None,
+        }
+    }
+
+    fn is_invalid_password(&self, password: &str) -> bool {
+        self.config
+            .password
+            .as_deref()
+            .is_some_and(|expected| expected != password)
+    }
+}
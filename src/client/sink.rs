@@ -0,0 +1,58 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+use futures_sink::Sink;
+
+use super::EspHomeClientWriteStream;
+use crate::{error::ClientError, proto::EspHomeMessage};
+
+/// Write which was started by `start_send`, and is driven to completion by the other sink methods.
+pub(super) type PendingWrite = Pin<Box<dyn Future<Output = Result<(), ClientError>> + Send + Sync>>;
+
+impl Sink<EspHomeMessage> for EspHomeClientWriteStream {
+    type Error = ClientError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        poll_pending(self.get_mut(), cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: EspHomeMessage) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        if this.pending.is_some() {
+            return Err(ClientError::InvalidInternalState {
+                reason: "start_send called before poll_ready completed".to_owned(),
+            });
+        }
+        this.writer.span().in_scope(|| {
+            tracing::debug!("Send: {item}");
+            tracing::trace!("Send: {item:?}");
+        });
+        let writer = this.writer.clone();
+        this.pending = Some(Box::pin(async move { writer.write(item).await }));
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        poll_pending(self.get_mut(), cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        poll_pending(self.get_mut(), cx)
+    }
+}
+
+/// Drives the pending write of the stream, if any, to completion.
+fn poll_pending(
+    stream: &mut EspHomeClientWriteStream,
+    cx: &mut Context<'_>,
+) -> Poll<Result<(), ClientError>> {
+    let Some(pending) = stream.pending.as_mut() else {
+        return Poll::Ready(Ok(()));
+    };
+    let result = ready!(pending.as_mut().poll(cx));
+    stream.pending = None;
+    Poll::Ready(result)
+}
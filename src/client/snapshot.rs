@@ -0,0 +1,35 @@
+use super::EspHomeClient;
+use crate::{
+    camera::FrameAssembler,
+    error::ClientError,
+    proto::{CameraImageRequest, EspHomeMessage},
+};
+
+/// Requests a single image, and reassembles the chunks of the given camera.
+pub(super) async fn capture(client: &EspHomeClient, key: u32) -> Result<Vec<u8>, ClientError> {
+    client
+        .try_write(CameraImageRequest {
+            single: true,
+            stream: false,
+        })
+        .await?;
+    let mut assembler = FrameAssembler::new();
+    client
+        .with_request_timeout(async {
+            loop {
+                let message = client
+                    .wait_for(|message| match message {
+                        EspHomeMessage::CameraImageResponse(chunk) => chunk.key == key,
+                        _ => false,
+                    })
+                    .await?;
+                client.publish(&message);
+                if let EspHomeMessage::CameraImageResponse(chunk) = message {
+                    if let Some(frame) = assembler.push(&chunk) {
+                        return Ok(frame.data);
+                    }
+                }
+            }
+        })
+        .await
+}
@@ -0,0 +1,157 @@
+use std::{
+    sync::{
+        Mutex, PoisonError,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant, SystemTime},
+};
+
+/// Snapshot of the statistics of a connection, created by [`EspHomeClient::statistics`](super::EspHomeClient::statistics).
+///
+/// Counters start at the moment the connection is established, the messages of the handshake are not
+/// counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientStatistics {
+    /// Number of messages sent to the device.
+    pub messages_sent: u64,
+    /// Number of messages received from the device.
+    pub messages_received: u64,
+    /// Number of bytes written to the connection, including framing and encryption.
+    pub bytes_sent: u64,
+    /// Number of bytes read from the connection, including framing and encryption.
+    pub bytes_received: u64,
+    /// Number of received messages which couldn't be decoded.
+    pub decode_errors: u64,
+    /// Number of ping requests of the device answered by the client.
+    pub pings_answered: u64,
+    /// Moment the connection was established.
+    pub connected_at: SystemTime,
+    /// Time since the connection was established.
+    pub uptime: Duration,
+    /// Moment the last message was sent, `None` if no message was sent yet.
+    pub last_sent: Option<SystemTime>,
+    /// Moment the last message was received, `None` if no message was received yet.
+    pub last_received: Option<SystemTime>,
+}
+
+/// Counters of a connection, shared by the client and its streams.
+#[derive(Debug)]
+pub(crate) struct Statistics {
+    messages_sent: AtomicU64,
+    messages_received: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+    decode_errors: AtomicU64,
+    pings_answered: AtomicU64,
+    connected_at: SystemTime,
+    started: Instant,
+    last_sent: Mutex<Option<SystemTime>>,
+    last_received: Mutex<Option<SystemTime>>,
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Self {
+            messages_sent: AtomicU64::new(0),
+            messages_received: AtomicU64::new(0),
+            bytes_sent: AtomicU64::new(0),
+            bytes_received: AtomicU64::new(0),
+            decode_errors: AtomicU64::new(0),
+            pings_answered: AtomicU64::new(0),
+            connected_at: SystemTime::now(),
+            started: Instant::now(),
+            last_sent: Mutex::new(None),
+            last_received: Mutex::new(None),
+        }
+    }
+}
+
+impl Statistics {
+    pub(crate) fn messages_sent(&self, count: usize) {
+        add(&self.messages_sent, count);
+        *self
+            .last_sent
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(SystemTime::now());
+    }
+
+    pub(crate) fn message_received(&self) {
+        add(&self.messages_received, 1);
+        *self
+            .last_received
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(SystemTime::now());
+    }
+
+    pub(crate) fn bytes_sent(&self, bytes: usize) {
+        add(&self.bytes_sent, bytes);
+    }
+
+    pub(crate) fn bytes_received(&self, bytes: usize) {
+        add(&self.bytes_received, bytes);
+    }
+
+    pub(crate) fn decode_error(&self) {
+        add(&self.decode_errors, 1);
+    }
+
+    pub(crate) fn ping_answered(&self) {
+        add(&self.pings_answered, 1);
+    }
+
+    pub(crate) fn snapshot(&self) -> ClientStatistics {
+        ClientStatistics {
+            messages_sent: self.messages_sent.load(Ordering::Relaxed),
+            messages_received: self.messages_received.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            bytes_received: self.bytes_received.load(Ordering::Relaxed),
+            decode_errors: self.decode_errors.load(Ordering::Relaxed),
+            pings_answered: self.pings_answered.load(Ordering::Relaxed),
+            connected_at: self.connected_at,
+            uptime: self.started.elapsed(),
+            last_sent: *self
+                .last_sent
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+            last_received: *self
+                .last_received
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+}
+
+fn add(counter: &AtomicU64, count: usize) {
+    counter.fetch_add(u64::try_from(count).unwrap_or(u64::MAX), Ordering::Relaxed);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statistics_snapshot() {
+        let statistics = Statistics::default();
+        let initial = statistics.snapshot();
+        assert_eq!(initial.messages_sent, 0);
+        assert_eq!(initial.last_sent, None);
+        assert_eq!(initial.last_received, None);
+
+        statistics.messages_sent(2);
+        statistics.bytes_sent(20);
+        statistics.message_received();
+        statistics.bytes_received(12);
+        statistics.decode_error();
+        statistics.ping_answered();
+        let snapshot = statistics.snapshot();
+        assert_eq!(snapshot.messages_sent, 2);
+        assert_eq!(snapshot.messages_received, 1);
+        assert_eq!(snapshot.bytes_sent, 20);
+        assert_eq!(snapshot.bytes_received, 12);
+        assert_eq!(snapshot.decode_errors, 1);
+        assert_eq!(snapshot.pings_answered, 1);
+        assert!(snapshot.last_sent.is_some());
+        assert!(snapshot.last_received >= snapshot.last_sent);
+        assert!(snapshot.last_received.unwrap() >= snapshot.connected_at);
+    }
+}
@@ -1,33 +1,183 @@
-use crate::error::{ClientError, StreamError};
-use std::{fmt::Debug, io, mem};
-use tokio::{io::Interest, net::tcp::OwnedReadHalf};
+use super::{buffer_pool::BufferPool, runtime::ReadStream, statistics::Statistics};
+#[cfg(feature = "metrics")]
+use crate::metrics;
+use crate::{
+    capture::{Capture, Direction},
+    error::{ClientError, StreamError},
+    proto::EspHomeMessage,
+};
+use bytes::{Buf as _, BytesMut};
+use std::{
+    fmt::Debug,
+    future::{Future as _, poll_fn},
+    io, mem,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
+use tokio::io::AsyncReadExt as _;
 
 #[derive(Debug)]
 struct NoopDecoder;
 impl StreamDecoder for NoopDecoder {}
 
+/// Decodes frames from the bytes read from the stream.
+///
+/// Decoders consume a frame by splitting it off the front of the buffer, which doesn't move the remaining bytes.
 pub(crate) trait StreamDecoder: Send + Sync + Debug {
-    fn decode(&self, buffer: &mut Vec<u8>) -> Result<Option<Vec<u8>>, ClientError> {
+    fn decode(&self, buffer: &mut BytesMut) -> Result<Option<Frame>, ClientError> {
         if buffer.is_empty() {
             return Ok(None);
         }
-        Ok(Some(mem::take(buffer)))
+        Ok(Some(Frame::raw(buffer.split())))
+    }
+}
+
+/// Frame read from the stream, the payload shares the memory of the buffer it was decoded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Frame {
+    /// Type id of the message, `None` for the frames of the handshake, which don't carry a message.
+    pub(crate) type_id: Option<u16>,
+    pub(crate) payload: BytesMut,
+}
+
+impl Frame {
+    /// Frame carrying the message with the given type id.
+    pub(crate) const fn message(type_id: u16, payload: BytesMut) -> Self {
+        Self {
+            type_id: Some(type_id),
+            payload,
+        }
+    }
+
+    /// Frame without a message, like the frames of the handshake.
+    pub(crate) const fn raw(payload: BytesMut) -> Self {
+        Self {
+            type_id: None,
+            payload,
+        }
+    }
+
+    /// Frame of a message prefixed by the 2 byte type id and 2 byte length, like a decrypted noise frame.
+    pub(crate) fn from_prefixed(mut payload: BytesMut) -> Result<Self, ClientError> {
+        if payload.len() < 4 {
+            return Err(StreamError::InvalidFrame {
+                reason: "Message must be at least 4 bytes long".to_owned(),
+            }
+            .into());
+        }
+        let type_id = u16::from_be_bytes([payload[0], payload[1]]);
+        payload.advance(4);
+        Ok(Self::message(type_id, payload))
+    }
+
+    /// Decodes the message of the frame, directly from the payload.
+    pub(crate) fn decode(&self) -> Result<EspHomeMessage, String> {
+        let type_id = self
+            .type_id
+            .ok_or_else(|| "Frame doesn't carry a message".to_owned())?;
+        EspHomeMessage::decode_frame(type_id, &self.payload)
+    }
+}
+
+/// Capacity of the read buffer by default, the maximum size of a frame.
+const DEFAULT_CAPACITY: usize = 65535;
+
+/// Sizing of the read buffer of a connection.
+///
+/// By default, the buffer starts with room for the largest possible frame (64KB), grows as needed and
+/// is never shrunk. Gateways handling many devices can start with a small buffer, which grows for the
+/// rare large frames and is shrunk again once the large frame is read.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, ReadBufferPolicy};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = EspHomeClient::builder()
+///     .address("192.168.0.2:6053")
+///     .read_buffer(
+///         ReadBufferPolicy::new()
+///             .initial_capacity(1024)
+///             .min_free(512)
+///             .shrink_above(8192),
+///     )
+///     .connect()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadBufferPolicy {
+    initial_capacity: usize,
+    min_free: usize,
+    shrink_above: Option<usize>,
+}
+
+impl Default for ReadBufferPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReadBufferPolicy {
+    /// Creates the default policy.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            initial_capacity: DEFAULT_CAPACITY,
+            min_free: 0,
+            shrink_above: None,
+        }
+    }
+
+    /// Sets the capacity the buffer is allocated with, defaults to 65535 bytes.
+    #[must_use]
+    pub const fn initial_capacity(mut self, capacity: usize) -> Self {
+        self.initial_capacity = capacity;
+        self
+    }
+
+    /// Sets the free space reserved before each read, growing the buffer in steps of at least this
+    /// size. Defaults to 0, which leaves growing to the default of the buffer.
+    #[must_use]
+    pub const fn min_free(mut self, bytes: usize) -> Self {
+        self.min_free = bytes;
+        self
+    }
+
+    /// Replaces the buffer with one of the initial capacity when it grew beyond the given capacity and
+    /// all frames in it are read. Defaults to never shrinking.
+    #[must_use]
+    pub const fn shrink_above(mut self, capacity: usize) -> Self {
+        self.shrink_above = Some(capacity);
+        self
     }
 }
 
 #[derive(Debug)]
 pub(crate) struct StreamReader {
     decoder: Box<dyn StreamDecoder>,
-    read_stream: OwnedReadHalf,
-    buffer: Vec<u8>,
+    read_stream: ReadStream,
+    buffer: BytesMut,
+    capture: Option<Capture>,
+    statistics: Option<Arc<Statistics>>,
+    pool: Option<BufferPool>,
+    policy: ReadBufferPolicy,
+    oversized: bool,
 }
 
 impl StreamReader {
-    pub(crate) fn new(read_stream: OwnedReadHalf) -> Self {
+    pub(crate) fn new(read_stream: ReadStream) -> Self {
         Self {
             read_stream,
             decoder: Box::new(NoopDecoder),
-            buffer: Vec::with_capacity(65535),
+            buffer: BytesMut::with_capacity(DEFAULT_CAPACITY),
+            capture: None,
+            statistics: None,
+            pool: None,
+            policy: ReadBufferPolicy::new(),
+            oversized: false,
         }
     }
 
@@ -36,41 +186,172 @@ impl StreamReader {
             decoder,
             read_stream: self.read_stream,
             buffer: self.buffer,
+            capture: self.capture,
+            statistics: self.statistics,
+            pool: self.pool,
+            policy: self.policy,
+            oversized: self.oversized,
         }
     }
 
-    pub(crate) async fn read_next_message(&mut self) -> Result<Vec<u8>, ClientError> {
-        if let Ok(Some(decoded)) = self.decoder.decode(&mut self.buffer) {
-            tracing::trace!("Read {} bytes: {decoded:?}", decoded.len());
-            return Ok(decoded);
+    pub(crate) fn with_capture(self, capture: Option<Capture>) -> Self {
+        Self { capture, ..self }
+    }
+
+    /// Counts the read bytes in the statistics of the connection.
+    pub(crate) fn with_statistics(self, statistics: Arc<Statistics>) -> Self {
+        Self {
+            statistics: Some(statistics),
+            ..self
         }
-        loop {
-            let ready = self
-                .read_stream
-                .ready(Interest::READABLE)
-                .await
-                .map_err(|e| StreamError::Read { source: e })?;
-            if ready.is_readable() {
-                match self.read_stream.try_read_buf(&mut self.buffer) {
-                    Ok(0) => {
-                        return Err(StreamError::Read {
-                            source: io::Error::new(
-                                io::ErrorKind::UnexpectedEof,
-                                "connection closed by remote",
-                            ),
-                        }
-                        .into());
-                    }
-                    Ok(_) => {
-                        if let Ok(Some(decoded)) = self.decoder.decode(&mut self.buffer) {
-                            tracing::trace!("Read {} bytes: {:?}", decoded.len(), decoded);
-                            return Ok(decoded);
-                        }
-                    }
-                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
-                    Err(e) => return Err(StreamError::Read { source: e }.into()),
+    }
+
+    /// Sizes the read buffer according to the policy, reallocating the buffer if it is empty.
+    pub(crate) fn with_buffer_policy(mut self, policy: ReadBufferPolicy) -> Self {
+        if self.buffer.is_empty() && self.buffer.capacity() != policy.initial_capacity {
+            self.buffer = BytesMut::with_capacity(policy.initial_capacity);
+        }
+        self.policy = policy;
+        self
+    }
+
+    /// Borrows the read buffer from the pool, only holding it while a partial frame is left.
+    pub(crate) fn with_buffer_pool(mut self, pool: Option<BufferPool>) -> Self {
+        self.pool = pool;
+        self.release_buffer();
+        self
+    }
+
+    /// Once no partial frame is left in the buffer, returns it to the pool, if any, or shrinks it when
+    /// it grew too large.
+    fn release_buffer(&mut self) {
+        if !self.buffer.is_empty() {
+            return;
+        }
+        let oversized = mem::take(&mut self.oversized);
+        match &self.pool {
+            Some(pool) => {
+                let buffer = mem::take(&mut self.buffer);
+                if !oversized {
+                    pool.put(buffer);
                 }
             }
+            None if oversized => {
+                self.buffer = BytesMut::with_capacity(self.policy.initial_capacity);
+            }
+            None => {}
+        }
+    }
+
+    fn decoded(&mut self, decoded: Frame) -> Frame {
+        self.release_buffer();
+        tracing::trace!("Read {} bytes: {decoded:?}", decoded.payload.len());
+        if let (Some(capture), Some(type_id)) = (&self.capture, decoded.type_id) {
+            capture.record_message(Direction::FromDevice, type_id, &decoded.payload);
+        }
+        decoded
+    }
+
+    /// Reads the bytes available on the stream into the buffer.
+    ///
+    /// The buffer is only taken from the pool for the duration of the poll, so connections waiting
+    /// for data don't hold a buffer.
+    fn poll_read_buf(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        if let (Some(pool), 0) = (&self.pool, self.buffer.capacity()) {
+            self.buffer = pool.take();
+        }
+        if self.buffer.capacity() - self.buffer.len() < self.policy.min_free {
+            self.buffer.reserve(self.policy.min_free);
         }
+        let Poll::Ready(read) = pin!(self.read_stream.read_buf(&mut self.buffer)).poll(cx) else {
+            self.release_buffer();
+            return Poll::Pending;
+        };
+        if self
+            .policy
+            .shrink_above
+            .is_some_and(|capacity| self.buffer.capacity() > capacity)
+        {
+            self.oversized = true;
+        }
+        if let (Some(statistics), Ok(bytes)) = (&self.statistics, &read) {
+            statistics.bytes_received(*bytes);
+        }
+        #[cfg(feature = "metrics")]
+        if let Ok(bytes) = read {
+            metrics::bytes_received(bytes);
+        }
+        Poll::Ready(read)
+    }
+
+    /// Reads the next message if a complete frame was already received, without waiting for the stream.
+    pub(crate) fn try_read_next_message(&mut self) -> Result<Option<Frame>, ClientError> {
+        loop {
+            if let Some(decoded) = self.decoder.decode(&mut self.buffer)? {
+                return Ok(Some(self.decoded(decoded)));
+            }
+            let Poll::Ready(read) = self.poll_read_buf(&mut Context::from_waker(Waker::noop()))
+            else {
+                return Ok(None);
+            };
+            Self::check_read(read)?;
+        }
+    }
+
+    pub(crate) async fn read_next_message(&mut self) -> Result<Frame, ClientError> {
+        loop {
+            if let Some(decoded) = self.decoder.decode(&mut self.buffer)? {
+                return Ok(self.decoded(decoded));
+            }
+            Self::check_read(poll_fn(|cx| self.poll_read_buf(cx)).await)?;
+        }
+    }
+
+    /// Maps the end of the stream and a reset connection to [`ClientError::Disconnected`].
+    fn check_read(read: io::Result<usize>) -> Result<(), ClientError> {
+        match read {
+            Ok(0) => Err(ClientError::Disconnected),
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::ConnectionReset => Err(ClientError::Disconnected),
+            Err(e) => Err(StreamError::Read { source: e }.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::runtime;
+    use tokio::io::{AsyncWriteExt as _, duplex};
+
+    /// Decodes frames of a fixed size.
+    #[derive(Debug)]
+    struct FixedSizeDecoder(usize);
+    impl StreamDecoder for FixedSizeDecoder {
+        fn decode(&self, buffer: &mut BytesMut) -> Result<Option<Frame>, ClientError> {
+            Ok((buffer.len() >= self.0).then(|| Frame::raw(buffer.split_to(self.0))))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_buffer_policy_shrinks() {
+        let (mut device, stream) = duplex(8192);
+        let (read, _write) = runtime::split(Box::new(stream));
+        let mut reader = StreamReader::new(read)
+            .with_decoder(Box::new(FixedSizeDecoder(4096)))
+            .with_buffer_policy(
+                ReadBufferPolicy::new()
+                    .initial_capacity(64)
+                    .min_free(64)
+                    .shrink_above(1024),
+            );
+        assert_eq!(reader.buffer.capacity(), 64);
+
+        device.write_all(&[1; 4096]).await.unwrap();
+        assert_eq!(
+            reader.read_next_message().await.unwrap().payload.len(),
+            4096
+        );
+        assert_eq!(reader.buffer.capacity(), 64);
     }
 }
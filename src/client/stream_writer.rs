@@ -1,30 +1,65 @@
-use std::{fmt::Debug, io, sync::Arc};
-use tokio::{io::Interest, net::tcp::OwnedWriteHalf};
+use bytes::BytesMut;
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+use std::{
+    fmt::Debug,
+    io,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+use tokio::{
+    io::AsyncWriteExt as _,
+    sync::{Mutex, MutexGuard},
+};
+use tracing::{Instrument as _, Span};
 
-use crate::error::{ClientError, StreamError};
+use super::{buffer_pool::BufferPool, runtime::WriteStream, statistics::Statistics};
+#[cfg(feature = "metrics")]
+use crate::metrics;
+use crate::{
+    capture::{Capture, Direction},
+    error::{ClientError, StreamError},
+    proto::{DisconnectRequest, DisconnectResponse, MessageType as _, PingRequest, PingResponse},
+};
 
 #[derive(Debug)]
 struct NoopEncoder;
 impl StreamEncoder for NoopEncoder {}
 
+/// Encodes payloads into frames.
+///
+/// Encoders append the frame to the frames written at once, so no buffer is needed per frame.
 pub(crate) trait StreamEncoder: Send + Sync + Debug {
-    fn encode(&self, payload: Vec<u8>) -> Result<Vec<u8>, ClientError> {
-        Ok(payload)
+    fn encode(&self, payload: Vec<u8>, frames: &mut BytesMut) -> Result<(), ClientError> {
+        frames.extend_from_slice(&payload);
+        Ok(())
     }
 }
 
 #[derive(Debug, Clone)]
 pub(crate) struct StreamWriter {
     encoder: Arc<Box<dyn StreamEncoder>>,
-    write_stream: Arc<OwnedWriteHalf>,
+    write_stream: Arc<Mutex<WriteStream>>,
+    capture: Option<Capture>,
+    statistics: Option<Arc<Statistics>>,
+    queue: Option<Arc<OutgoingQueue>>,
+    pool: Option<BufferPool>,
+    span: Span,
 }
 
 impl StreamWriter {
-    pub(crate) fn new(write_stream: OwnedWriteHalf) -> Self {
+    pub(crate) fn new(write_stream: WriteStream) -> Self {
         let encoder: Box<dyn StreamEncoder> = Box::new(NoopEncoder);
         Self {
-            write_stream: write_stream.into(),
+            write_stream: Arc::new(Mutex::new(write_stream)),
             encoder: encoder.into(),
+            capture: None,
+            statistics: None,
+            queue: None,
+            pool: None,
+            span: Span::none(),
         }
     }
 
@@ -32,29 +67,246 @@ impl StreamWriter {
         Self {
             encoder: encoder.into(),
             write_stream: self.write_stream,
+            capture: self.capture,
+            statistics: self.statistics,
+            queue: self.queue,
+            pool: self.pool,
+            span: self.span,
         }
     }
 
+    pub(crate) fn with_capture(self, capture: Option<Capture>) -> Self {
+        Self { capture, ..self }
+    }
+
+    /// Counts the written messages and bytes in the statistics of the connection.
+    pub(crate) fn with_statistics(self, statistics: Arc<Statistics>) -> Self {
+        Self {
+            statistics: Some(statistics),
+            ..self
+        }
+    }
+
+    /// Sets the span of the connection, in which all writes are logged.
+    pub(crate) fn with_span(self, span: Span) -> Self {
+        Self { span, ..self }
+    }
+
+    /// Span of the connection, carrying the address and name of the device.
+    pub(crate) const fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Encodes the frames into buffers taken from the pool, instead of allocating a buffer per write.
+    pub(crate) fn with_buffer_pool(self, pool: Option<BufferPool>) -> Self {
+        Self { pool, ..self }
+    }
+
+    /// Limits the number of messages waiting to be written, see [`OutgoingQueue`].
+    pub(crate) fn with_queue(self, capacity: Option<usize>) -> Self {
+        Self {
+            queue: capacity.map(|capacity| Arc::new(OutgoingQueue::new(capacity))),
+            ..self
+        }
+    }
+
+    /// Encodes the payload and writes the whole frame to the stream.
     pub(crate) async fn write_message(&self, payload: Vec<u8>) -> Result<(), ClientError> {
-        let payload = self.encoder.encode(payload)?;
-        loop {
-            let ready = self
-                .write_stream
-                .ready(Interest::WRITABLE)
-                .await
-                .map_err(|e| StreamError::Write { source: e })?;
-            if ready.is_writable() {
-                match self.write_stream.try_write(&payload) {
-                    Ok(n) => {
-                        tracing::trace!("Wrote {n} bytes: {payload:?}");
-                        return Ok(());
-                    }
-                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
-                    Err(e) => {
-                        return Err(StreamError::Write { source: e }.into());
-                    }
-                }
+        self.write_messages(vec![payload]).await
+    }
+
+    /// Encodes the payloads and writes all frames to the stream at once.
+    ///
+    /// Encoding and writing happen while holding the lock on the stream, so frames of concurrent writers
+    /// are never interleaved, and are written in the order they were encoded.
+    pub(crate) async fn write_messages(&self, payloads: Vec<Vec<u8>>) -> Result<(), ClientError> {
+        self.write_frames(payloads)
+            .instrument(self.span.clone())
+            .await
+    }
+
+    async fn write_frames(&self, payloads: Vec<Vec<u8>>) -> Result<(), ClientError> {
+        let admission = match &self.queue {
+            Some(queue) => Some(queue.admit(Priority::of(&payloads)).await?),
+            None => None,
+        };
+        let mut write_stream = self.write_stream.lock().await;
+        let count = payloads.len();
+        let mut frames = self
+            .pool
+            .as_ref()
+            .map_or_else(BytesMut::new, BufferPool::take);
+        for payload in payloads {
+            if let Some(capture) = &self.capture {
+                capture.record(Direction::ToDevice, &payload);
             }
+            #[cfg(feature = "metrics")]
+            metrics::message_sent(&payload);
+            #[cfg(feature = "metrics")]
+            let started = Instant::now();
+            self.encoder.encode(payload, &mut frames)?;
+            #[cfg(feature = "metrics")]
+            metrics::encoded(started.elapsed());
+        }
+        // Transports buffering the written bytes, unlike a TCP connection, send them on flush
+        let written = async {
+            write_stream.write_all(&frames).await?;
+            write_stream.flush().await
+        };
+        written.await.map_err(|e| match e.kind() {
+            io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset => ClientError::Disconnected,
+            _ => StreamError::Write { source: e }.into(),
+        })?;
+        drop(write_stream);
+        drop(admission);
+        if let Some(statistics) = &self.statistics {
+            statistics.messages_sent(count);
+            statistics.bytes_sent(frames.len());
         }
+        #[cfg(feature = "metrics")]
+        metrics::bytes_sent(frames.len());
+        tracing::trace!("Wrote {} bytes: {frames:?}", frames.len());
+        if let Some(pool) = &self.pool {
+            frames.clear();
+            pool.put(frames);
+        }
+        Ok(())
+    }
+}
+
+/// Priority of outgoing messages, messages keeping the connection alive are written first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Priority {
+    Normal,
+    High,
+}
+
+impl Priority {
+    /// Returns the priority of the payloads, which is only high if all messages have a high priority.
+    fn of(payloads: &[Vec<u8>]) -> Self {
+        let is_high = |payload: &Vec<u8>| {
+            let type_id = payload
+                .get(..2)
+                .map(|id| u16::from_be_bytes([id[0], id[1]]));
+            matches!(
+                type_id,
+                Some(
+                    PingRequest::TYPE_ID
+                        | PingResponse::TYPE_ID
+                        | DisconnectRequest::TYPE_ID
+                        | DisconnectResponse::TYPE_ID
+                )
+            )
+        };
+        if payloads.iter().all(is_high) {
+            Self::High
+        } else {
+            Self::Normal
+        }
+    }
+}
+
+/// Bounded queue of outgoing messages.
+///
+/// Messages with a normal priority fail with [`ClientError::Backpressure`] when the queue is full.
+/// They also pass a separate lock before the lock on the stream, so at most one of them waits for the
+/// stream, and a high priority message is written after at most one other frame.
+#[derive(Debug)]
+struct OutgoingQueue {
+    capacity: usize,
+    pending: AtomicUsize,
+    normal: Mutex<()>,
+}
+
+impl OutgoingQueue {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            pending: AtomicUsize::new(0),
+            normal: Mutex::new(()),
+        }
+    }
+
+    /// Reserves a place in the queue, high priority messages are always accepted.
+    fn reserve(&self, priority: Priority) -> Result<Reservation<'_>, ClientError> {
+        if priority == Priority::Normal {
+            self.pending
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |pending| {
+                    (pending < self.capacity).then_some(pending + 1)
+                })
+                .map_err(|_pending| ClientError::Backpressure {
+                    capacity: self.capacity,
+                })?;
+        }
+        Ok(Reservation {
+            queue: self,
+            priority,
+        })
+    }
+
+    async fn admit(&self, priority: Priority) -> Result<Admission<'_>, ClientError> {
+        let reservation = self.reserve(priority)?;
+        let turn = match priority {
+            Priority::Normal => Some(self.normal.lock().await),
+            Priority::High => None,
+        };
+        Ok(Admission {
+            _turn: turn,
+            _reservation: reservation,
+        })
+    }
+}
+
+/// Place in the queue, released when the message is written or the write is cancelled.
+#[derive(Debug)]
+struct Reservation<'a> {
+    queue: &'a OutgoingQueue,
+    priority: Priority,
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if self.priority == Priority::Normal {
+            self.queue.pending.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Admission<'a> {
+    _turn: Option<MutexGuard<'a, ()>>,
+    _reservation: Reservation<'a>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::{EspHomeMessage, HelloRequest};
+
+    fn payload(message: impl Into<EspHomeMessage>) -> Vec<u8> {
+        message.into().into()
+    }
+
+    #[test]
+    fn test_priority() {
+        assert_eq!(Priority::of(&[payload(PingResponse {})]), Priority::High);
+        assert_eq!(
+            Priority::of(&[payload(PingResponse {}), payload(HelloRequest::default())]),
+            Priority::Normal
+        );
+    }
+
+    #[test]
+    fn test_outgoing_queue_capacity() {
+        let queue = OutgoingQueue::new(1);
+        let reservation = queue.reserve(Priority::Normal).unwrap();
+        let error = queue.reserve(Priority::Normal).unwrap_err();
+        assert!(
+            matches!(error, ClientError::Backpressure { capacity: 1 }),
+            "Unexpected error: {error}"
+        );
+        queue.reserve(Priority::High).unwrap();
+        drop(reservation);
+        queue.reserve(Priority::Normal).unwrap();
     }
 }
@@ -0,0 +1,401 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use tokio::{
+    sync::{mpsc, watch},
+    task::JoinHandle,
+    time::sleep,
+};
+
+use super::{BackoffPolicy, ConnectionEvent, EspHomeClient, EspHomeClientBuilder};
+#[cfg(feature = "metrics")]
+use crate::metrics;
+use crate::{error::ClientError, proto::EspHomeMessage};
+
+/// Number of events buffered before the device tasks wait for the events to be read.
+const EVENT_CAPACITY: usize = 256;
+
+/// Identifier of a device managed by a [`Supervisor`], chosen when the device is added.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeviceId(Arc<str>);
+
+impl DeviceId {
+    /// Gets the identifier as a string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for DeviceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<&str> for DeviceId {
+    fn from(id: &str) -> Self {
+        Self(id.into())
+    }
+}
+
+impl From<String> for DeviceId {
+    fn from(id: String) -> Self {
+        Self(id.into())
+    }
+}
+
+/// Event of one of the devices managed by a [`Supervisor`].
+#[derive(Debug, Clone)]
+#[allow(
+    clippy::large_enum_variant,
+    reason = "Messages are the most common event, boxing would allocate for every message"
+)]
+pub enum SupervisorEvent {
+    /// The first connection to the device was established, including the connection setup.
+    Connected {
+        /// Device the connection was established to.
+        device: DeviceId,
+    },
+    /// Connection state change of the device, after the first connection was established.
+    Connection {
+        /// Device of which the connection state changed.
+        device: DeviceId,
+        /// The state change.
+        event: ConnectionEvent,
+    },
+    /// Message received from the device.
+    Message {
+        /// Device the message was received from.
+        device: DeviceId,
+        /// The received message.
+        message: EspHomeMessage,
+    },
+    /// The device is given up on, due to an error which isn't resolved by reconnecting, like a wrong key,
+    /// or because the maximum number of attempts of the backoff policy was reached.
+    Failed {
+        /// Device which is given up on.
+        device: DeviceId,
+        /// Description of the error.
+        reason: String,
+    },
+}
+
+impl SupervisorEvent {
+    /// Gets the device of the event.
+    #[must_use]
+    pub const fn device(&self) -> &DeviceId {
+        match self {
+            Self::Connected { device }
+            | Self::Connection { device, .. }
+            | Self::Message { device, .. }
+            | Self::Failed { device, .. } => device,
+        }
+    }
+}
+
+/// Owner of the connections to many devices, merging their messages into a single stream of events.
+///
+/// Every device gets its own task, which connects to the device, reconnects according to the
+/// [`BackoffPolicy`] when the connection is lost, and forwards the received messages. Messages are
+/// sent to a device with its [`DeviceHandle`].
+///
+/// Note that subscriptions (e.g. `SubscribeStatesRequest`) are bound to a connection, and need to be sent
+/// again after a [`SupervisorEvent::Connected`] or [`ConnectionEvent::Reconnected`] event.
+///
+/// Requires the `supervisor` feature to be enabled.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{BackoffPolicy, EspHomeClient, Supervisor, SupervisorEvent, types::SubscribeStatesRequest};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut supervisor = Supervisor::new();
+/// for (id, address) in [("kitchen", "192.168.0.2:6053"), ("garage", "192.168.0.3:6053")] {
+///     supervisor.add(id, EspHomeClient::builder().address(address), BackoffPolicy::new());
+/// }
+/// loop {
+///     match supervisor.next().await {
+///         SupervisorEvent::Connected { device } => {
+///             if let Some(handle) = supervisor.device(&device) {
+///                 handle.try_write(SubscribeStatesRequest {}).await?;
+///             }
+///         }
+///         SupervisorEvent::Message { device, message } => println!("{device}: {message}"),
+///         event => println!("{event:?}"),
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Supervisor {
+    devices: HashMap<DeviceId, SupervisedDevice>,
+    events_tx: mpsc::Sender<SupervisorEvent>,
+    events_rx: mpsc::Receiver<SupervisorEvent>,
+}
+
+#[derive(Debug)]
+struct SupervisedDevice {
+    handle: DeviceHandle,
+    task: JoinHandle<()>,
+}
+
+impl Drop for SupervisedDevice {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Supervisor {
+    /// Creates a supervisor without devices.
+    #[must_use]
+    pub fn new() -> Self {
+        let (events_tx, events_rx) = mpsc::channel(EVENT_CAPACITY);
+        Self {
+            devices: HashMap::new(),
+            events_tx,
+            events_rx,
+        }
+    }
+
+    /// Adds a device, and starts connecting to it with the settings of the builder.
+    ///
+    /// Failed connection attempts are retried according to the policy, like reconnects. A device with
+    /// the same id is replaced, closing its connection.
+    pub fn add(
+        &mut self,
+        id: impl Into<DeviceId>,
+        builder: EspHomeClientBuilder,
+        policy: BackoffPolicy,
+    ) -> DeviceHandle {
+        let id = id.into();
+        let (client_tx, client_rx) = watch::channel(None);
+        let handle = DeviceHandle {
+            id: id.clone(),
+            client: client_rx,
+        };
+        let task = tokio::spawn(
+            DeviceTask {
+                id: id.clone(),
+                builder,
+                policy,
+                client: client_tx,
+                events: self.events_tx.clone(),
+            }
+            .run(),
+        );
+        self.devices.insert(
+            id,
+            SupervisedDevice {
+                handle: handle.clone(),
+                task,
+            },
+        );
+        handle
+    }
+
+    /// Removes the device, closing its connection. Returns `false` if the device is unknown.
+    pub fn remove(&mut self, id: &DeviceId) -> bool {
+        self.devices.remove(id).is_some()
+    }
+
+    /// Gets the handle to send messages to the device.
+    #[must_use]
+    pub fn device(&self, id: &DeviceId) -> Option<DeviceHandle> {
+        self.devices.get(id).map(|device| device.handle.clone())
+    }
+
+    /// Gets the ids of all devices.
+    pub fn devices(&self) -> impl Iterator<Item = &DeviceId> + '_ {
+        self.devices.keys()
+    }
+
+    /// Waits for the next event of any of the devices.
+    ///
+    /// Events of a device are returned in order, events of different devices are interleaved.
+    pub async fn next(&mut self) -> SupervisorEvent {
+        loop {
+            // The supervisor holds a sender, so the channel is never closed
+            if let Some(event) = self.events_rx.recv().await {
+                if self.devices.contains_key(event.device()) {
+                    return event;
+                }
+            }
+        }
+    }
+}
+
+/// Handle to send messages to a device managed by a [`Supervisor`].
+///
+/// The handle uses the current connection of the device, it stays valid when the device reconnects.
+#[derive(Debug, Clone)]
+pub struct DeviceHandle {
+    id: DeviceId,
+    client: watch::Receiver<Option<Arc<EspHomeClient>>>,
+}
+
+impl DeviceHandle {
+    /// Gets the id of the device.
+    #[must_use]
+    pub const fn id(&self) -> &DeviceId {
+        &self.id
+    }
+
+    /// Gets the client of the current connection, if the device is connected.
+    #[must_use]
+    pub fn client(&self) -> Option<Arc<EspHomeClient>> {
+        self.client.borrow().clone()
+    }
+
+    /// Whether the device is currently connected.
+    #[must_use]
+    pub fn is_connected(&self) -> bool {
+        self.client.borrow().is_some()
+    }
+
+    /// Sends a message to the device.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::Disconnected`] if the device isn't connected, or an error if the write
+    /// operation fails.
+    pub async fn try_write<M>(&self, message: M) -> Result<(), ClientError>
+    where
+        M: Into<EspHomeMessage> + fmt::Debug,
+    {
+        match self.client() {
+            Some(client) => client.try_write(message).await,
+            None => Err(ClientError::Disconnected),
+        }
+    }
+}
+
+/// Connection loop of a single device.
+struct DeviceTask {
+    id: DeviceId,
+    builder: EspHomeClientBuilder,
+    policy: BackoffPolicy,
+    client: watch::Sender<Option<Arc<EspHomeClient>>>,
+    events: mpsc::Sender<SupervisorEvent>,
+}
+
+impl DeviceTask {
+    async fn run(self) {
+        let mut connected_before = false;
+        loop {
+            let client = match self.connect(connected_before).await {
+                Ok(client) => Arc::new(client),
+                Err(e) => {
+                    tracing::error!("Giving up on device {}: {e}", self.id);
+                    if connected_before {
+                        self.emit_connection(ConnectionEvent::GaveUp).await;
+                    }
+                    self.emit(SupervisorEvent::Failed {
+                        device: self.id.clone(),
+                        reason: e.to_string(),
+                    })
+                    .await;
+                    return;
+                }
+            };
+            self.client.send_replace(Some(Arc::clone(&client)));
+            if connected_before {
+                self.emit_connection(ConnectionEvent::Reconnected).await;
+            } else {
+                connected_before = true;
+                self.emit(SupervisorEvent::Connected {
+                    device: self.id.clone(),
+                })
+                .await;
+            }
+            let error = self.forward_messages(&client).await;
+            self.client.send_replace(None);
+            tracing::warn!("Connection to device {} lost: {error}", self.id);
+            self.emit_connection(ConnectionEvent::Disconnected {
+                reason: error.to_string(),
+            })
+            .await;
+        }
+    }
+
+    /// Connects to the device, waiting between attempts according to the backoff policy.
+    ///
+    /// The first connection is attempted right away, reconnections are announced as events.
+    async fn connect(&self, reconnect: bool) -> Result<EspHomeClient, ClientError> {
+        let mut attempt = 0;
+        loop {
+            if reconnect || attempt > 0 {
+                let delay = if reconnect {
+                    self.policy.delay(attempt)
+                } else {
+                    self.policy.delay(attempt - 1)
+                };
+                if reconnect {
+                    self.emit_connection(ConnectionEvent::Reconnecting {
+                        attempt: attempt.saturating_add(1),
+                        delay,
+                    })
+                    .await;
+                }
+                sleep(delay).await;
+                #[cfg(feature = "metrics")]
+                metrics::reconnect_attempt();
+            }
+            attempt = attempt.saturating_add(1);
+            match self.builder.clone().connect().await {
+                Ok(client) => {
+                    #[cfg(feature = "metrics")]
+                    if reconnect {
+                        metrics::reconnected();
+                    }
+                    return Ok(client);
+                }
+                Err(e) if !e.is_recoverable() => return Err(e),
+                Err(e) if self.policy.max_attempts.is_some_and(|max| attempt >= max) => {
+                    return Err(e);
+                }
+                Err(e) => {
+                    tracing::debug!(
+                        "Connection attempt {attempt} to device {} failed: {e}",
+                        self.id
+                    );
+                }
+            }
+        }
+    }
+
+    /// Forwards the messages of the device until the connection is lost, returns the error.
+    async fn forward_messages(&self, client: &EspHomeClient) -> ClientError {
+        loop {
+            match client.try_read().await {
+                Ok(message) => {
+                    self.emit(SupervisorEvent::Message {
+                        device: self.id.clone(),
+                        message,
+                    })
+                    .await;
+                }
+                Err(e) if e.is_recoverable() => return e,
+                Err(e) => tracing::warn!("Failed to read from device {}: {e}", self.id),
+            }
+        }
+    }
+
+    async fn emit_connection(&self, event: ConnectionEvent) {
+        self.emit(SupervisorEvent::Connection {
+            device: self.id.clone(),
+            event,
+        })
+        .await;
+    }
+
+    async fn emit(&self, event: SupervisorEvent) {
+        // Sending only fails if the supervisor was dropped, which aborts this task
+        let _ignored = self.events.send(event).await;
+    }
+}
@@ -0,0 +1,280 @@
+//! Mock ESPHome API server, to integration test code using the client without a device.
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use tokio::task::{JoinHandle, JoinSet};
+
+use super::server::{EspHomeServer, ServerConnection};
+use crate::{
+    API_VERSION,
+    error::ClientError,
+    proto::{DeviceInfoResponse, EspHomeMessage, ListEntitiesDoneResponse, MessageType},
+};
+
+/// Mock ESPHome API server, answering the requests of clients like a device would.
+///
+/// Built on the [`EspHomeServer`](crate::server::EspHomeServer), which answers the requests of the
+/// protocol. The mock answers the `DeviceInfoRequest` and `ListEntitiesRequest` by default, responses
+/// to other requests can be scripted with [`MockEspHomeServerBuilder::respond_to`], which also replaces
+/// the default responses. All messages
+/// received from clients are recorded, see [`MockEspHomeServer::received`].
+///
+/// The server runs on the tokio runtime until it is closed or dropped.
+///
+/// # Usage:
+/// ```rust
+/// use esphome_client::{
+///     EspHomeClient,
+///     test_util::MockEspHomeServer,
+///     types::{EspHomeMessage, ListEntitiesSwitchResponse, SwitchCommandRequest, SwitchStateResponse},
+/// };
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let server = MockEspHomeServer::builder()
+///     .name("kitchen")
+///     .entity(ListEntitiesSwitchResponse { key: 1, object_id: "light".into(), ..Default::default() })
+///     .respond_to::<SwitchCommandRequest>(SwitchStateResponse { key: 1, state: true, ..Default::default() })
+///     .start()
+///     .await?;
+///
+/// let client = EspHomeClient::builder().address(server.address()).connect().await?;
+/// assert_eq!(client.list_entities().await?.switches.len(), 1);
+/// client.try_write(SwitchCommandRequest { key: 1, state: true, ..Default::default() }).await?;
+/// assert!(matches!(client.try_read().await?, EspHomeMessage::SwitchStateResponse(state) if state.state));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MockEspHomeServer {
+    address: SocketAddr,
+    received: Arc<Mutex<Vec<EspHomeMessage>>>,
+    handle: JoinHandle<()>,
+}
+
+impl MockEspHomeServer {
+    /// Creates a builder to configure and start a mock server.
+    #[must_use]
+    pub fn builder() -> MockEspHomeServerBuilder {
+        MockEspHomeServerBuilder::new()
+    }
+
+    /// Returns the address the server is listening on.
+    #[must_use]
+    pub const fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Returns all messages received from clients, in the order they were received.
+    #[must_use]
+    pub fn received(&self) -> Vec<EspHomeMessage> {
+        self.received
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Stops the server, closing all connections.
+    pub fn close(self) {
+        drop(self);
+    }
+}
+
+impl Drop for MockEspHomeServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Builder for a [`MockEspHomeServer`].
+#[derive(Debug, Clone)]
+pub struct MockEspHomeServerBuilder {
+    address: String,
+    key: Option<String>,
+    password: Option<String>,
+    name: String,
+    mac: String,
+    api_version: (u32, u32),
+    entities: Vec<EspHomeMessage>,
+    responses: HashMap<u16, Vec<EspHomeMessage>>,
+}
+
+impl Default for MockEspHomeServerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockEspHomeServerBuilder {
+    /// Creates a builder for a plain text server, listening on a free port on localhost.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            address: "127.0.0.1:0".to_owned(),
+            key: None,
+            password: None,
+            name: "mock-server".to_owned(),
+            mac: "AB:CD:EF:01:23:45".to_owned(),
+            api_version: API_VERSION,
+            entities: Vec::new(),
+            responses: HashMap::new(),
+        }
+    }
+
+    /// Sets the address to listen on, defaults to a free port on localhost.
+    #[must_use]
+    pub fn address(mut self, address: &str) -> Self {
+        address.clone_into(&mut self.address);
+        self
+    }
+
+    /// Sets the base64-encoded 32-byte key, clients have to connect with noise encryption.
+    #[must_use]
+    pub fn key(mut self, key: &str) -> Self {
+        self.key = Some(key.to_owned());
+        self
+    }
+
+    /// Sets the password clients have to authenticate with, any password is accepted by default.
+    #[must_use]
+    pub fn password(mut self, password: &str) -> Self {
+        self.password = Some(password.to_owned());
+        self
+    }
+
+    /// Sets the name of the device, defaults to "mock-server".
+    #[must_use]
+    pub fn name(mut self, name: &str) -> Self {
+        name.clone_into(&mut self.name);
+        self
+    }
+
+    /// Sets the MAC address of the device, defaults to "AB:CD:EF:01:23:45".
+    #[must_use]
+    pub fn mac(mut self, mac: &str) -> Self {
+        mac.clone_into(&mut self.mac);
+        self
+    }
+
+    /// Sets the API version reported in the `HelloResponse`, defaults to the version of the client.
+    #[must_use]
+    pub const fn api_version(mut self, major: u32, minor: u32) -> Self {
+        self.api_version = (major, minor);
+        self
+    }
+
+    /// Adds an entity description, like `ListEntitiesSwitchResponse`, sent when entities are listed.
+    #[must_use]
+    pub fn entity(mut self, entity: impl Into<EspHomeMessage>) -> Self {
+        self.entities.push(entity.into());
+        self
+    }
+
+    /// Adds a response sent when a request of type `R` is received, replacing the default response.
+    ///
+    /// Requests of the protocol, like the `HelloRequest`, are always answered by the server, scripted
+    /// responses to them are sent in addition. Multiple responses to the same request are sent in the order they were added.
+    #[must_use]
+    pub fn respond_to<R: MessageType>(mut self, response: impl Into<EspHomeMessage>) -> Self {
+        self.responses
+            .entry(R::TYPE_ID)
+            .or_default()
+            .push(response.into());
+        self
+    }
+
+    /// Starts listening for clients.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the key is invalid, or if the server can't listen on the address.
+    pub async fn start(self) -> Result<MockEspHomeServer, ClientError> {
+        let mut server = EspHomeServer::builder()
+            .name(&self.name)
+            .mac(&self.mac)
+            .server_info("mock-server")
+            .api_version(self.api_version.0, self.api_version.1);
+        if let Some(key) = &self.key {
+            server = server.key(key);
+        }
+        if let Some(password) = &self.password {
+            server = server.password(password);
+        }
+        let server = server.bind(self.address.as_str()).await?;
+        let address = server.local_addr()?;
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let device = Arc::new(MockDevice {
+            config: self,
+            received: Arc::clone(&received),
+        });
+        let handle = tokio::spawn(accept(server, device));
+        Ok(MockEspHomeServer {
+            address,
+            received,
+            handle,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct MockDevice {
+    config: MockEspHomeServerBuilder,
+    received: Arc<Mutex<Vec<EspHomeMessage>>>,
+}
+
+impl MockDevice {
+    /// Records the message, and returns the scripted responses to it, or the default responses.
+    fn respond(&self, message: &EspHomeMessage) -> Vec<EspHomeMessage> {
+        self.received
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(message.clone());
+        if let Some(responses) = self.config.responses.get(&message.get_message_type()) {
+            return responses.clone();
+        }
+        match message {
+            EspHomeMessage::DeviceInfoRequest(_) => vec![
+                DeviceInfoResponse {
+                    name: self.config.name.clone(),
+                    mac_address: self.config.mac.clone(),
+                    ..Default::default()
+                }
+                .into(),
+            ],
+            EspHomeMessage::ListEntitiesRequest(_) => {
+                let mut responses = self.config.entities.clone();
+                responses.push(ListEntitiesDoneResponse {}.into());
+                responses
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Accepts clients until the server is stopped, connections are closed when the server stops.
+#[expect(clippy::infinite_loop, reason = "Runs until the task is aborted")]
+async fn accept(server: EspHomeServer, device: Arc<MockDevice>) {
+    let mut connections = JoinSet::new();
+    loop {
+        match server.accept().await {
+            Ok(connection) => {
+                connections.spawn(serve(connection, Arc::clone(&device)));
+            }
+            Err(e) => tracing::debug!("Mock server failed to accept connection: {e}"),
+        }
+        // Clean up finished connections
+        while connections.try_join_next().is_some() {}
+    }
+}
+
+/// Answers the messages of a single client until it disconnects.
+async fn serve(connection: ServerConnection, device: Arc<MockDevice>) {
+    let peer = connection.peer_addr();
+    if let Err(e) = connection.serve(|message| device.respond(message)).await {
+        tracing::debug!("Mock server connection to {peer} failed: {e}");
+    }
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use super::{EspHomeClient, timeout_error};
+use crate::{
+    commands::update_command,
+    error::ClientError,
+    proto::{
+        EspHomeMessage, ListEntitiesUpdateResponse, SubscribeStatesRequest, UpdateCommand,
+        UpdateStateResponse,
+    },
+};
+
+/// Starts the installation of the update, and waits until the entity reports it's up to date.
+///
+/// The messages received while waiting, like the states of all entities, are published to the
+/// subscribers instead of being buffered for the next read.
+pub(super) async fn install(
+    client: &EspHomeClient,
+    info: &ListEntitiesUpdateResponse,
+    duration: Duration,
+) -> Result<UpdateStateResponse, ClientError> {
+    client.try_write(SubscribeStatesRequest {}).await?;
+    client
+        .try_write(update_command(info, UpdateCommand::Update))
+        .await?;
+    client
+        .clock
+        .timeout(duration, async {
+            loop {
+                let message = client.read_message().await?;
+                client.publish(&message);
+                match message {
+                    EspHomeMessage::UpdateStateResponse(state)
+                        if state.key == info.key && is_installed(&state) =>
+                    {
+                        return Ok(state);
+                    }
+                    _ => {}
+                }
+            }
+        })
+        .await
+        .map_err(|_e| timeout_error(duration))?
+}
+
+/// Checks if the state reports the latest version is installed, without an installation in progress.
+fn is_installed(state: &UpdateStateResponse) -> bool {
+    !state.missing_state && !state.in_progress && state.current_version == state.latest_version
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_installed() {
+        let installed = UpdateStateResponse {
+            current_version: "2025.5.0".to_owned(),
+            latest_version: "2025.5.0".to_owned(),
+            ..Default::default()
+        };
+        assert!(is_installed(&installed));
+        assert!(!is_installed(&UpdateStateResponse {
+            in_progress: true,
+            ..installed.clone()
+        }));
+        assert!(!is_installed(&UpdateStateResponse {
+            missing_state: true,
+            ..installed.clone()
+        }));
+        assert!(!is_installed(&UpdateStateResponse {
+            latest_version: "2025.6.0".to_owned(),
+            ..installed
+        }));
+    }
+}
@@ -0,0 +1,1586 @@
+use std::{
+    ops::{BitAnd, BitOr, BitOrAssign},
+    time::Duration,
+};
+
+#[cfg(all(feature = "chrono", not(any(feature = "api-1-9", feature = "api-1-8"))))]
+use chrono::{DateTime, TimeZone};
+#[cfg(all(feature = "chrono", not(any(feature = "api-1-8"))))]
+use chrono::{Datelike as _, NaiveDate, NaiveTime, Timelike as _};
+
+#[cfg(all(feature = "chrono", not(any(feature = "api-1-8"))))]
+use crate::proto::{
+    DateCommandRequest, ListEntitiesDateResponse, ListEntitiesTimeResponse, TimeCommandRequest,
+};
+#[cfg(all(feature = "chrono", not(any(feature = "api-1-9", feature = "api-1-8"))))]
+use crate::proto::{DateTimeCommandRequest, ListEntitiesDateTimeResponse};
+#[cfg(not(any(feature = "api-1-8")))]
+use crate::proto::{ListEntitiesTextResponse, TextCommandRequest};
+#[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+use crate::proto::{ListEntitiesUpdateResponse, UpdateCommand, UpdateCommandRequest};
+use crate::{
+    error::{ClientError, CommandError},
+    proto::{
+        ColorMode, CoverCommandRequest, EspHomeMessage, FanCommandRequest, FanDirection,
+        LightCommandRequest, ListEntitiesCoverResponse, ListEntitiesFanResponse,
+        ListEntitiesLightResponse, ListEntitiesLockResponse, ListEntitiesNumberResponse,
+        ListEntitiesSelectResponse, LockCommand, LockCommandRequest, MediaPlayerCommand,
+        MediaPlayerCommandRequest, NumberCommandRequest, SelectCommandRequest,
+    },
+};
+
+/// Converts a color temperature in kelvin to mireds, the unit used by the API.
+#[must_use]
+pub fn kelvin_to_mireds(kelvin: f32) -> f32 {
+    1_000_000.0 / kelvin
+}
+
+/// Converts a color temperature in mireds, the unit used by the API, to kelvin.
+#[must_use]
+pub fn mireds_to_kelvin(mireds: f32) -> f32 {
+    1_000_000.0 / mireds
+}
+
+/// Builder for a [`LightCommandRequest`], which sets the `has_*` flag of every field that is set.
+///
+/// Fields which are not set are left untouched by the device.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use esphome_client::{EspHomeClient, commands::LightCommand};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// client
+///     .try_write(
+///         LightCommand::for_key(1234)
+///             .turn_on()
+///             .brightness(0.5)
+///             .rgb(255, 0, 0)
+///             .transition(Duration::from_secs(1)),
+///     )
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LightCommand {
+    request: LightCommandRequest,
+}
+
+impl LightCommand {
+    /// Creates an empty command for the light with the given key.
+    #[must_use]
+    pub fn for_key(key: u32) -> Self {
+        Self {
+            request: LightCommandRequest {
+                key,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+    /// Sets the id of the (sub) device the light belongs to.
+    #[must_use]
+    pub const fn device_id(mut self, device_id: u32) -> Self {
+        self.request.device_id = device_id;
+        self
+    }
+
+    /// Turns the light on.
+    #[must_use]
+    pub const fn turn_on(self) -> Self {
+        self.state(true)
+    }
+
+    /// Turns the light off.
+    #[must_use]
+    pub const fn turn_off(self) -> Self {
+        self.state(false)
+    }
+
+    /// Sets the on/off state of the light.
+    #[must_use]
+    pub const fn state(mut self, state: bool) -> Self {
+        self.request.has_state = true;
+        self.request.state = state;
+        self
+    }
+
+    /// Sets the master brightness, between `0.0` and `1.0`.
+    #[must_use]
+    pub const fn brightness(mut self, brightness: f32) -> Self {
+        self.request.has_brightness = true;
+        self.request.brightness = brightness.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the color mode.
+    #[must_use]
+    pub fn color_mode(mut self, color_mode: ColorMode) -> Self {
+        self.request.has_color_mode = true;
+        self.request.color_mode = color_mode.into();
+        self
+    }
+
+    /// Sets the brightness of the color channels, between `0.0` and `1.0`.
+    #[must_use]
+    pub const fn color_brightness(mut self, brightness: f32) -> Self {
+        self.request.has_color_brightness = true;
+        self.request.color_brightness = brightness.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the color from 8 bit red, green and blue values.
+    #[must_use]
+    pub fn rgb(self, red: u8, green: u8, blue: u8) -> Self {
+        let max = f32::from(u8::MAX);
+        self.rgb_f32(
+            f32::from(red) / max,
+            f32::from(green) / max,
+            f32::from(blue) / max,
+        )
+    }
+
+    /// Sets the color from red, green and blue values between `0.0` and `1.0`.
+    #[must_use]
+    pub const fn rgb_f32(mut self, red: f32, green: f32, blue: f32) -> Self {
+        self.request.has_rgb = true;
+        self.request.red = red.clamp(0.0, 1.0);
+        self.request.green = green.clamp(0.0, 1.0);
+        self.request.blue = blue.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the white channel, between `0.0` and `1.0`.
+    #[must_use]
+    pub const fn white(mut self, white: f32) -> Self {
+        self.request.has_white = true;
+        self.request.white = white.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the color temperature in mireds.
+    #[must_use]
+    pub const fn color_temperature(mut self, mireds: f32) -> Self {
+        self.request.has_color_temperature = true;
+        self.request.color_temperature = mireds;
+        self
+    }
+
+    /// Sets the color temperature in kelvin, which is converted to mireds.
+    #[must_use]
+    pub fn color_temperature_kelvin(self, kelvin: f32) -> Self {
+        self.color_temperature(kelvin_to_mireds(kelvin))
+    }
+
+    /// Sets the cold white channel, between `0.0` and `1.0`.
+    #[must_use]
+    pub const fn cold_white(mut self, cold_white: f32) -> Self {
+        self.request.has_cold_white = true;
+        self.request.cold_white = cold_white.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the warm white channel, between `0.0` and `1.0`.
+    #[must_use]
+    pub const fn warm_white(mut self, warm_white: f32) -> Self {
+        self.request.has_warm_white = true;
+        self.request.warm_white = warm_white.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the transition length, with millisecond precision.
+    #[must_use]
+    pub fn transition(mut self, length: Duration) -> Self {
+        self.request.has_transition_length = true;
+        self.request.transition_length = duration_millis(length);
+        self
+    }
+
+    /// Flashes the light for the given length, with millisecond precision.
+    #[must_use]
+    pub fn flash(mut self, length: Duration) -> Self {
+        self.request.has_flash_length = true;
+        self.request.flash_length = duration_millis(length);
+        self
+    }
+
+    /// Sets the effect by name.
+    #[must_use]
+    pub fn effect(mut self, effect: impl Into<String>) -> Self {
+        self.request.has_effect = true;
+        self.request.effect = effect.into();
+        self
+    }
+
+    /// Gets the request.
+    #[must_use]
+    pub fn build(self) -> LightCommandRequest {
+        self.request
+    }
+}
+
+impl From<LightCommand> for LightCommandRequest {
+    fn from(command: LightCommand) -> Self {
+        command.build()
+    }
+}
+
+impl From<LightCommand> for EspHomeMessage {
+    fn from(command: LightCommand) -> Self {
+        command.build().into()
+    }
+}
+
+/// Capabilities of a light, decoded from the color modes it supports.
+///
+/// The values of the [`ColorMode`]s are combinations of these capabilities, e.g. `Rgb` is `ON_OFF`,
+/// `BRIGHTNESS` and `RGB`.
+///
+/// # Usage:
+/// ```rust
+/// use esphome_client::{commands::ColorCapabilities, types::ListEntitiesLightResponse};
+///
+/// let light = ListEntitiesLightResponse { supported_color_modes: vec![11, 35], ..Default::default() };
+/// let capabilities = ColorCapabilities::from(&light);
+/// assert!(capabilities.supports_rgb());
+/// assert!(capabilities.supports_color_temperature());
+/// assert!(!capabilities.supports_white());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ColorCapabilities(u8);
+
+impl ColorCapabilities {
+    /// The light can be turned on and off.
+    pub const ON_OFF: Self = Self(1 << 0);
+    /// The brightness of the light can be set.
+    pub const BRIGHTNESS: Self = Self(1 << 1);
+    /// The light has a white channel.
+    pub const WHITE: Self = Self(1 << 2);
+    /// The color temperature of the light can be set.
+    pub const COLOR_TEMPERATURE: Self = Self(1 << 3);
+    /// The light has a cold white and a warm white channel.
+    pub const COLD_WARM_WHITE: Self = Self(1 << 4);
+    /// The light has red, green and blue channels.
+    pub const RGB: Self = Self(1 << 5);
+
+    /// No capabilities.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// Gets the capabilities of a single color mode, as sent in the `supported_color_modes` and
+    /// `color_mode` fields.
+    #[must_use]
+    pub const fn from_mode(mode: i32) -> Self {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::as_conversions,
+            reason = "Masked to the capability bits"
+        )]
+        Self((mode & 0x3f) as u8)
+    }
+
+    /// Gets the combined capabilities of the given color modes.
+    #[must_use]
+    pub fn from_modes(modes: &[i32]) -> Self {
+        modes.iter().fold(Self::empty(), |capabilities, &mode| {
+            capabilities | Self::from_mode(mode)
+        })
+    }
+
+    /// Gets the raw bits of the capabilities.
+    #[must_use]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Whether there are no capabilities.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether all the given capabilities are present.
+    #[must_use]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether the brightness can be set.
+    #[must_use]
+    pub const fn supports_brightness(self) -> bool {
+        self.contains(Self::BRIGHTNESS)
+    }
+
+    /// Whether the color can be set with red, green and blue values.
+    #[must_use]
+    pub const fn supports_rgb(self) -> bool {
+        self.contains(Self::RGB)
+    }
+
+    /// Whether the white channel can be set.
+    #[must_use]
+    pub const fn supports_white(self) -> bool {
+        self.contains(Self::WHITE)
+    }
+
+    /// Whether the color temperature can be set.
+    #[must_use]
+    pub const fn supports_color_temperature(self) -> bool {
+        self.contains(Self::COLOR_TEMPERATURE)
+    }
+
+    /// Whether the cold white and warm white channels can be set.
+    #[must_use]
+    pub const fn supports_cold_warm_white(self) -> bool {
+        self.contains(Self::COLD_WARM_WHITE)
+    }
+}
+
+impl BitOr for ColorCapabilities {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for ColorCapabilities {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for ColorCapabilities {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl From<ColorMode> for ColorCapabilities {
+    fn from(mode: ColorMode) -> Self {
+        Self::from_mode(mode.into())
+    }
+}
+
+impl From<&ListEntitiesLightResponse> for ColorCapabilities {
+    /// Gets the capabilities of the supported color modes, falling back to the legacy `supports_*` flags
+    /// of devices which don't report color modes.
+    #[allow(
+        deprecated,
+        reason = "Fallback for devices which don't report color modes"
+    )]
+    fn from(info: &ListEntitiesLightResponse) -> Self {
+        if !info.supported_color_modes.is_empty() {
+            return Self::from_modes(&info.supported_color_modes);
+        }
+        let mut capabilities = Self::ON_OFF;
+        if info.legacy_supports_brightness {
+            capabilities |= Self::BRIGHTNESS;
+        }
+        if info.legacy_supports_rgb {
+            capabilities |= Self::RGB;
+        }
+        if info.legacy_supports_white_value {
+            capabilities |= Self::WHITE;
+        }
+        if info.legacy_supports_color_temperature {
+            capabilities |= Self::COLOR_TEMPERATURE;
+        }
+        capabilities
+    }
+}
+
+/// Builder for a [`MediaPlayerCommandRequest`], which sets the `has_*` flag of every field that is set.
+///
+/// Only a single [`MediaPlayerCommand`] can be sent per request, the last one set is used.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, commands::MediaPlayerControl};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// client
+///     .try_write(
+///         MediaPlayerControl::for_key(1234)
+///             .play_url("http://192.168.0.3/doorbell.flac")
+///             .volume(0.8),
+///     )
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MediaPlayerControl {
+    request: MediaPlayerCommandRequest,
+}
+
+impl MediaPlayerControl {
+    /// Creates an empty command for the media player with the given key.
+    #[must_use]
+    pub fn for_key(key: u32) -> Self {
+        Self {
+            request: MediaPlayerCommandRequest {
+                key,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+    /// Sets the id of the (sub) device the media player belongs to.
+    #[must_use]
+    pub const fn device_id(mut self, device_id: u32) -> Self {
+        self.request.device_id = device_id;
+        self
+    }
+
+    /// Sets the command to send.
+    #[must_use]
+    pub fn command(mut self, command: MediaPlayerCommand) -> Self {
+        self.request.has_command = true;
+        self.request.command = command.into();
+        self
+    }
+
+    /// Starts or resumes playback.
+    #[must_use]
+    pub fn play(self) -> Self {
+        self.command(MediaPlayerCommand::Play)
+    }
+
+    /// Pauses playback.
+    #[must_use]
+    pub fn pause(self) -> Self {
+        self.command(MediaPlayerCommand::Pause)
+    }
+
+    /// Stops playback.
+    #[must_use]
+    pub fn stop(self) -> Self {
+        self.command(MediaPlayerCommand::Stop)
+    }
+
+    /// Mutes or unmutes the media player.
+    #[must_use]
+    pub fn mute(self, muted: bool) -> Self {
+        self.command(if muted {
+            MediaPlayerCommand::Mute
+        } else {
+            MediaPlayerCommand::Unmute
+        })
+    }
+
+    /// Sets the volume, between `0.0` and `1.0`.
+    #[must_use]
+    pub const fn volume(mut self, volume: f32) -> Self {
+        self.request.has_volume = true;
+        self.request.volume = volume.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Plays the media at the given URL.
+    #[must_use]
+    pub fn play_url(mut self, url: impl Into<String>) -> Self {
+        self.request.has_media_url = true;
+        self.request.media_url = url.into();
+        self
+    }
+
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// Marks the media as announcement, which interrupts and afterwards resumes the current playback.
+    #[must_use]
+    pub const fn announcement(mut self, announcement: bool) -> Self {
+        self.request.has_announcement = true;
+        self.request.announcement = announcement;
+        self
+    }
+
+    /// Gets the request.
+    #[must_use]
+    pub fn build(self) -> MediaPlayerCommandRequest {
+        self.request
+    }
+}
+
+impl From<MediaPlayerControl> for MediaPlayerCommandRequest {
+    fn from(command: MediaPlayerControl) -> Self {
+        command.build()
+    }
+}
+
+impl From<MediaPlayerControl> for EspHomeMessage {
+    fn from(command: MediaPlayerControl) -> Self {
+        command.build().into()
+    }
+}
+
+/// Builds [`CoverCommandRequest`]s for a cover, based on the capabilities of the entity.
+///
+/// Covers are opened and closed by moving to position `1.0` and `0.0`, which is also accepted by
+/// covers without position support, replacing the deprecated legacy open/close commands.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, commands::CoverCommand};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// let entities = client.list_entities().await?;
+/// let cover = CoverCommand::for_entity(&entities.covers[0]);
+/// client.try_write(cover.set_position(0.5)?).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct CoverCommand<'a> {
+    info: &'a ListEntitiesCoverResponse,
+}
+
+impl<'a> CoverCommand<'a> {
+    /// Creates the command builder for the given cover entity.
+    #[must_use]
+    pub const fn for_entity(info: &'a ListEntitiesCoverResponse) -> Self {
+        Self { info }
+    }
+
+    /// Opens the cover completely.
+    #[must_use]
+    pub fn open(self) -> CoverCommandRequest {
+        self.position_request(1.0)
+    }
+
+    /// Closes the cover completely.
+    #[must_use]
+    pub fn close(self) -> CoverCommandRequest {
+        self.position_request(0.0)
+    }
+
+    /// Stops the current movement of the cover.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::UnsupportedOperation`] if the cover can't be stopped.
+    pub fn stop(self) -> Result<CoverCommandRequest, ClientError> {
+        if !self.info.supports_stop {
+            return Err(self.unsupported("stop"));
+        }
+        Ok(CoverCommandRequest {
+            stop: true,
+            ..self.request()
+        })
+    }
+
+    /// Moves the cover to the given position, between `0.0` (closed) and `1.0` (open).
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::UnsupportedOperation`] if the cover doesn't support positions.
+    pub fn set_position(self, position: f32) -> Result<CoverCommandRequest, ClientError> {
+        if !self.info.supports_position {
+            return Err(self.unsupported("position"));
+        }
+        Ok(self.position_request(position))
+    }
+
+    /// Tilts the cover to the given position, between `0.0` (closed) and `1.0` (open).
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::UnsupportedOperation`] if the cover doesn't support tilting.
+    pub fn set_tilt(self, tilt: f32) -> Result<CoverCommandRequest, ClientError> {
+        if !self.info.supports_tilt {
+            return Err(self.unsupported("tilt"));
+        }
+        Ok(CoverCommandRequest {
+            has_tilt: true,
+            tilt: tilt.clamp(0.0, 1.0),
+            ..self.request()
+        })
+    }
+
+    fn position_request(self, position: f32) -> CoverCommandRequest {
+        CoverCommandRequest {
+            has_position: true,
+            position: position.clamp(0.0, 1.0),
+            ..self.request()
+        }
+    }
+
+    fn request(self) -> CoverCommandRequest {
+        CoverCommandRequest {
+            key: self.info.key,
+            #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+            device_id: self.info.device_id,
+            ..Default::default()
+        }
+    }
+
+    fn unsupported(self, operation: &str) -> ClientError {
+        ClientError::UnsupportedOperation {
+            reason: format!(
+                "cover '{}' doesn't support {operation}",
+                self.info.object_id
+            ),
+        }
+    }
+}
+
+/// Builder for a [`FanCommandRequest`], validating the settings against the capabilities of the entity.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, commands::FanCommand};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// let entities = client.list_entities().await?;
+/// let request = FanCommand::for_entity(&entities.fans[0])
+///     .speed_percentage(50)
+///     .oscillating(true)
+///     .build()?;
+/// client.try_write(request).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FanCommand<'a> {
+    info: &'a ListEntitiesFanResponse,
+    request: FanCommandRequest,
+    unsupported: Option<&'static str>,
+}
+
+impl<'a> FanCommand<'a> {
+    /// Creates an empty command for the given fan entity.
+    #[must_use]
+    pub fn for_entity(info: &'a ListEntitiesFanResponse) -> Self {
+        Self {
+            info,
+            request: FanCommandRequest {
+                key: info.key,
+                #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+                device_id: info.device_id,
+                ..Default::default()
+            },
+            unsupported: None,
+        }
+    }
+
+    /// Turns the fan on.
+    #[must_use]
+    pub const fn turn_on(self) -> Self {
+        self.state(true)
+    }
+
+    /// Turns the fan off.
+    #[must_use]
+    pub const fn turn_off(self) -> Self {
+        self.state(false)
+    }
+
+    /// Sets the on/off state of the fan.
+    #[must_use]
+    pub const fn state(mut self, state: bool) -> Self {
+        self.request.has_state = true;
+        self.request.state = state;
+        self
+    }
+
+    /// Sets the speed as percentage (0 - 100), mapped to the speed levels supported by the fan.
+    ///
+    /// A percentage of 0 turns the fan off, any other percentage turns the fan on.
+    #[must_use]
+    pub fn speed_percentage(mut self, percentage: u8) -> Self {
+        if !self.info.supports_speed {
+            self.unsupported = Some("speed");
+            return self;
+        }
+        if percentage == 0 {
+            return self.turn_off();
+        }
+        self.request.has_speed_level = true;
+        self.request.speed_level = speed_level(percentage, self.info.supported_speed_count);
+        self.turn_on()
+    }
+
+    /// Sets the oscillation of the fan.
+    #[must_use]
+    pub const fn oscillating(mut self, oscillating: bool) -> Self {
+        if !self.info.supports_oscillation {
+            self.unsupported = Some("oscillation");
+            return self;
+        }
+        self.request.has_oscillating = true;
+        self.request.oscillating = oscillating;
+        self
+    }
+
+    /// Sets the direction of the fan.
+    #[must_use]
+    pub fn direction(mut self, direction: FanDirection) -> Self {
+        if !self.info.supports_direction {
+            self.unsupported = Some("direction");
+            return self;
+        }
+        self.request.has_direction = true;
+        self.request.direction = direction.into();
+        self
+    }
+
+    /// Gets the request.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::UnsupportedOperation`] if a setting isn't supported by the fan.
+    pub fn build(self) -> Result<FanCommandRequest, ClientError> {
+        if let Some(operation) = self.unsupported {
+            return Err(ClientError::UnsupportedOperation {
+                reason: format!("fan '{}' doesn't support {operation}", self.info.object_id),
+            });
+        }
+        Ok(self.request)
+    }
+}
+
+/// Maps a percentage (1 - 100) to a speed level (1 - `speed_count`), rounding up.
+fn speed_level(percentage: u8, speed_count: i32) -> i32 {
+    let percentage = i32::from(percentage.min(100));
+    let speed_count = speed_count.max(1);
+    (percentage * speed_count + 99) / 100
+}
+
+/// Builds [`LockCommandRequest`]s for a lock, based on the metadata of the entity.
+///
+/// A code is only sent along when it's set, commands for locks which require a code are rejected
+/// without sending them when no code is set.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, commands::LockControl};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// let entities = client.list_entities().await?;
+/// let lock = LockControl::for_entity(&entities.locks[0]).with_code("1234");
+/// client.try_write(lock.unlock()?).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LockControl<'a> {
+    info: &'a ListEntitiesLockResponse,
+    code: Option<String>,
+}
+
+impl<'a> LockControl<'a> {
+    /// Creates the command builder for the given lock entity.
+    #[must_use]
+    pub const fn for_entity(info: &'a ListEntitiesLockResponse) -> Self {
+        Self { info, code: None }
+    }
+
+    /// Sets the code sent along with the commands.
+    #[must_use]
+    pub fn with_code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+
+    /// Locks the lock.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`CommandError::CodeRequired`] if the lock requires a code and none is set.
+    pub fn lock(&self) -> Result<LockCommandRequest, ClientError> {
+        self.request(LockCommand::LockLock)
+    }
+
+    /// Unlocks the lock.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`CommandError::CodeRequired`] if the lock requires a code and none is set.
+    pub fn unlock(&self) -> Result<LockCommandRequest, ClientError> {
+        self.request(LockCommand::LockUnlock)
+    }
+
+    /// Opens the door (e.g. unlatches) of the lock.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::UnsupportedOperation`] if the lock can't be opened, or
+    /// [`CommandError::CodeRequired`] if the lock requires a code and none is set.
+    pub fn open(&self) -> Result<LockCommandRequest, ClientError> {
+        if !self.info.supports_open {
+            return Err(ClientError::UnsupportedOperation {
+                reason: format!("lock '{}' doesn't support open", self.info.object_id),
+            });
+        }
+        self.request(LockCommand::LockOpen)
+    }
+
+    fn request(&self, command: LockCommand) -> Result<LockCommandRequest, ClientError> {
+        if self.info.requires_code && self.code.is_none() {
+            return Err(CommandError::CodeRequired {
+                entity: self.info.object_id.clone(),
+            }
+            .into());
+        }
+        Ok(LockCommandRequest {
+            key: self.info.key,
+            command: command.into(),
+            has_code: self.code.is_some(),
+            code: self.code.clone().unwrap_or_default(),
+            #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+            device_id: self.info.device_id,
+        })
+    }
+}
+
+/// Builds [`NumberCommandRequest`]s for a number, validated against the range and step size of the entity.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, commands::NumberControl};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// let entities = client.list_entities().await?;
+/// let number = NumberControl::for_entity(&entities.numbers[0]);
+/// client.try_write(number.set(21.5)?).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct NumberControl<'a> {
+    info: &'a ListEntitiesNumberResponse,
+}
+
+impl<'a> NumberControl<'a> {
+    /// Relative tolerance for floating point errors when checking the step size.
+    const STEP_TOLERANCE: f32 = 1e-4;
+
+    /// Creates the command builder for the given number entity.
+    #[must_use]
+    pub const fn for_entity(info: &'a ListEntitiesNumberResponse) -> Self {
+        Self { info }
+    }
+
+    /// Sets the number to the given value.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`CommandError::OutOfRange`] if the value is outside of the range of the entity, or
+    /// [`CommandError::InvalidStep`] if the value is not a multiple of the step size.
+    pub fn set(self, value: f32) -> Result<NumberCommandRequest, ClientError> {
+        let (min, max) = (self.info.min_value, self.info.max_value);
+        if !(min..=max).contains(&value) {
+            return Err(CommandError::OutOfRange {
+                entity: self.info.object_id.clone(),
+                value,
+                min,
+                max,
+            }
+            .into());
+        }
+        if (self.snap(value) - value).abs() > self.info.step * Self::STEP_TOLERANCE {
+            return Err(CommandError::InvalidStep {
+                entity: self.info.object_id.clone(),
+                value,
+                step: self.info.step,
+            }
+            .into());
+        }
+        Ok(self.request(value))
+    }
+
+    /// Sets the number to the given value, clamped to the range and rounded to the step size of the entity.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`CommandError::OutOfRange`] if the value is not a number.
+    pub fn set_clamped(self, value: f32) -> Result<NumberCommandRequest, ClientError> {
+        let (min, max) = (self.info.min_value, self.info.max_value);
+        if value.is_nan() {
+            return Err(CommandError::OutOfRange {
+                entity: self.info.object_id.clone(),
+                value,
+                min,
+                max,
+            }
+            .into());
+        }
+        let snapped = self.snap(value.clamp(min, max));
+        // Rounding to the step can exceed the maximum, when the range isn't a multiple of the step
+        if snapped > max {
+            return Ok(self.request(snapped - self.info.step));
+        }
+        Ok(self.request(snapped))
+    }
+
+    /// Rounds the value to the nearest step, counted from the minimum.
+    fn snap(self, value: f32) -> f32 {
+        let (min, step) = (self.info.min_value, self.info.step);
+        if step <= 0.0 {
+            return value;
+        }
+        ((value - min) / step).round().mul_add(step, min)
+    }
+
+    const fn request(self, state: f32) -> NumberCommandRequest {
+        NumberCommandRequest {
+            key: self.info.key,
+            state,
+            #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+            device_id: self.info.device_id,
+        }
+    }
+}
+
+/// Builds [`SelectCommandRequest`]s for a select, checking the option against the options of the entity.
+///
+/// Devices silently ignore unknown options, so these are rejected before sending.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, commands::SelectControl};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// let entities = client.list_entities().await?;
+/// let select = SelectControl::for_entity(&entities.selects[0]).case_insensitive();
+/// client.try_write(select.select("eco")?).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SelectControl<'a> {
+    info: &'a ListEntitiesSelectResponse,
+    case_insensitive: bool,
+}
+
+impl<'a> SelectControl<'a> {
+    /// Creates the command builder for the given select entity.
+    #[must_use]
+    pub const fn for_entity(info: &'a ListEntitiesSelectResponse) -> Self {
+        Self {
+            info,
+            case_insensitive: false,
+        }
+    }
+
+    /// Matches options case insensitive, the option is sent as defined by the entity.
+    #[must_use]
+    pub const fn case_insensitive(mut self) -> Self {
+        self.case_insensitive = true;
+        self
+    }
+
+    /// Selects the given option.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`CommandError::UnknownOption`] if the entity has no such option.
+    pub fn select(self, option: &str) -> Result<SelectCommandRequest, ClientError> {
+        let state = self
+            .info
+            .options
+            .iter()
+            .find(|candidate| {
+                if self.case_insensitive {
+                    candidate.eq_ignore_ascii_case(option)
+                } else {
+                    *candidate == option
+                }
+            })
+            .ok_or_else(|| CommandError::UnknownOption {
+                entity: self.info.object_id.clone(),
+                option: option.to_owned(),
+            })?;
+        Ok(SelectCommandRequest {
+            key: self.info.key,
+            state: state.clone(),
+            #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+            device_id: self.info.device_id,
+        })
+    }
+}
+
+#[cfg(not(any(feature = "api-1-8")))]
+/// Builds [`TextCommandRequest`]s for a text, checking the length against the limits of the entity.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, commands::TextControl};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// let entities = client.list_entities().await?;
+/// let text = TextControl::for_entity(&entities.texts[0]);
+/// client.try_write(text.set("Hello")?).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TextControl<'a> {
+    info: &'a ListEntitiesTextResponse,
+}
+
+#[cfg(not(any(feature = "api-1-8")))]
+impl<'a> TextControl<'a> {
+    /// Creates the command builder for the given text entity.
+    #[must_use]
+    pub const fn for_entity(info: &'a ListEntitiesTextResponse) -> Self {
+        Self { info }
+    }
+
+    /// Sets the text.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`CommandError::InvalidLength`] if the text is shorter or longer than allowed by the entity.
+    pub fn set(self, text: impl Into<String>) -> Result<TextCommandRequest, ClientError> {
+        let state = text.into();
+        let length = state.chars().count();
+        let (min, max) = (self.info.min_length, self.info.max_length);
+        let too_short = usize::try_from(min).is_ok_and(|min| length < min);
+        let too_long = max > 0 && usize::try_from(max).is_ok_and(|max| length > max);
+        if too_short || too_long {
+            return Err(CommandError::InvalidLength {
+                entity: self.info.object_id.clone(),
+                length,
+                min,
+                max,
+            }
+            .into());
+        }
+        Ok(TextCommandRequest {
+            key: self.info.key,
+            state,
+            #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+            device_id: self.info.device_id,
+        })
+    }
+}
+
+#[cfg(all(feature = "chrono", not(any(feature = "api-1-8"))))]
+/// Creates a [`DateCommandRequest`] setting the date entity to the given date.
+#[must_use]
+pub fn date_command(info: &ListEntitiesDateResponse, date: NaiveDate) -> DateCommandRequest {
+    DateCommandRequest {
+        key: info.key,
+        // Dates before year 0 can't be represented by the API
+        year: u32::try_from(date.year()).unwrap_or_default(),
+        month: date.month(),
+        day: date.day(),
+        #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+        device_id: info.device_id,
+    }
+}
+
+#[cfg(all(feature = "chrono", not(any(feature = "api-1-8"))))]
+/// Creates a [`TimeCommandRequest`] setting the time entity to the given time, fractional seconds are dropped.
+#[must_use]
+pub fn time_command(info: &ListEntitiesTimeResponse, time: NaiveTime) -> TimeCommandRequest {
+    TimeCommandRequest {
+        key: info.key,
+        hour: time.hour(),
+        minute: time.minute(),
+        second: time.second(),
+        #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+        device_id: info.device_id,
+    }
+}
+
+#[cfg(all(feature = "chrono", not(any(feature = "api-1-9", feature = "api-1-8"))))]
+/// Creates a [`DateTimeCommandRequest`] setting the date time entity to the given moment.
+///
+/// # Errors
+///
+/// Will return [`CommandError::InvalidTimestamp`] if the moment is before 1970 or after 2106.
+pub fn date_time_command<Tz: TimeZone>(
+    info: &ListEntitiesDateTimeResponse,
+    date_time: &DateTime<Tz>,
+) -> Result<DateTimeCommandRequest, ClientError> {
+    let timestamp = date_time.timestamp();
+    let epoch_seconds =
+        u32::try_from(timestamp).map_err(|_err| CommandError::InvalidTimestamp { timestamp })?;
+    Ok(DateTimeCommandRequest {
+        key: info.key,
+        epoch_seconds,
+        #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+        device_id: info.device_id,
+    })
+}
+
+#[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+/// Creates an [`UpdateCommandRequest`] for the update entity, to check for or install an update.
+///
+/// See [`EspHomeClient::install_update`](crate::EspHomeClient::install_update) to install and wait for completion.
+#[must_use]
+pub fn update_command(
+    info: &ListEntitiesUpdateResponse,
+    command: UpdateCommand,
+) -> UpdateCommandRequest {
+    UpdateCommandRequest {
+        key: info.key,
+        command: command.into(),
+        #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+        device_id: info.device_id,
+    }
+}
+
+/// Converts a duration to milliseconds, saturating at `u32::MAX`.
+fn duration_millis(duration: Duration) -> u32 {
+    u32::try_from(duration.as_millis()).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_light_command_sets_flags() {
+        let request = LightCommand::for_key(7)
+            .turn_on()
+            .brightness(0.5)
+            .rgb(255, 0, 51)
+            .transition(Duration::from_secs(1))
+            .build();
+        assert_eq!(request.key, 7);
+        assert!(request.has_state && request.state);
+        assert!(request.has_brightness);
+        assert!((request.brightness - 0.5).abs() < f32::EPSILON);
+        assert!(request.has_rgb);
+        assert!((request.red - 1.0).abs() < f32::EPSILON);
+        assert!(request.green.abs() < f32::EPSILON);
+        assert!((request.blue - 0.2).abs() < f32::EPSILON);
+        assert!(request.has_transition_length);
+        assert_eq!(request.transition_length, 1000);
+        assert!(!request.has_color_temperature);
+        assert!(!request.has_effect);
+        assert!(!request.has_flash_length);
+    }
+
+    #[test]
+    fn test_light_command_clamps_values() {
+        let request = LightCommand::for_key(1)
+            .brightness(1.5)
+            .white(-1.0)
+            .flash(Duration::MAX)
+            .build();
+        assert!((request.brightness - 1.0).abs() < f32::EPSILON);
+        assert!(request.white.abs() < f32::EPSILON);
+        assert_eq!(request.flash_length, u32::MAX);
+    }
+
+    #[test]
+    fn test_light_command_color_temperature_kelvin() {
+        let request = LightCommand::for_key(1)
+            .color_temperature_kelvin(4000.0)
+            .color_mode(ColorMode::ColorTemperature)
+            .build();
+        assert!(request.has_color_temperature);
+        assert!((request.color_temperature - 250.0).abs() < f32::EPSILON);
+        assert!(request.has_color_mode);
+        assert_eq!(request.color_mode, i32::from(ColorMode::ColorTemperature));
+    }
+
+    #[test]
+    fn test_kelvin_mireds_conversion() {
+        assert!((kelvin_to_mireds(2000.0) - 500.0).abs() < f32::EPSILON);
+        assert!((mireds_to_kelvin(153.0) - 6535.948).abs() < 0.001);
+        assert!((mireds_to_kelvin(kelvin_to_mireds(2700.0)) - 2700.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_color_capabilities() {
+        let capabilities = ColorCapabilities::from_modes(&[
+            ColorMode::ColorTemperature.into(),
+            ColorMode::Rgb.into(),
+        ]);
+        assert!(capabilities.supports_brightness());
+        assert!(capabilities.supports_rgb());
+        assert!(capabilities.supports_color_temperature());
+        assert!(!capabilities.supports_white());
+        assert!(!capabilities.supports_cold_warm_white());
+        assert!(capabilities.contains(ColorCapabilities::ON_OFF | ColorCapabilities::RGB));
+        assert!(ColorCapabilities::from(ColorMode::OnOff) == ColorCapabilities::ON_OFF);
+        assert!(ColorCapabilities::from_modes(&[]).is_empty());
+    }
+
+    #[test]
+    #[allow(deprecated, reason = "Tests the fallback to the legacy flags")]
+    fn test_color_capabilities_legacy_flags() {
+        let info = ListEntitiesLightResponse {
+            legacy_supports_brightness: true,
+            legacy_supports_rgb: true,
+            ..Default::default()
+        };
+        let capabilities = ColorCapabilities::from(&info);
+        assert_eq!(
+            capabilities,
+            ColorCapabilities::ON_OFF | ColorCapabilities::BRIGHTNESS | ColorCapabilities::RGB
+        );
+    }
+
+    #[test]
+    fn test_cover_command_position_based() {
+        let info = ListEntitiesCoverResponse {
+            key: 5,
+            supports_position: true,
+            supports_tilt: true,
+            supports_stop: true,
+            ..Default::default()
+        };
+        let cover = CoverCommand::for_entity(&info);
+
+        let open = cover.open();
+        assert_eq!(open.key, 5);
+        assert!(open.has_position);
+        assert!((open.position - 1.0).abs() < f32::EPSILON);
+        let close = cover.close();
+        assert!(close.has_position && close.position.abs() < f32::EPSILON);
+
+        let position = cover.set_position(0.25).unwrap();
+        assert!((position.position - 0.25).abs() < f32::EPSILON);
+        let tilt = cover.set_tilt(2.0).unwrap();
+        assert!(tilt.has_tilt && !tilt.has_position);
+        assert!((tilt.tilt - 1.0).abs() < f32::EPSILON);
+        let stop = cover.stop().unwrap();
+        assert!(stop.stop && !stop.has_position);
+    }
+
+    #[test]
+    fn test_cover_command_without_position() {
+        let info = ListEntitiesCoverResponse {
+            key: 6,
+            object_id: "gate".to_owned(),
+            ..Default::default()
+        };
+        let cover = CoverCommand::for_entity(&info);
+
+        let open = cover.open();
+        assert!(open.has_position);
+        assert!((open.position - 1.0).abs() < f32::EPSILON);
+        let close = cover.close();
+        assert!(close.has_position && close.position.abs() < f32::EPSILON);
+
+        assert!(matches!(
+            cover.set_position(0.5).unwrap_err(),
+            ClientError::UnsupportedOperation { reason } if reason == "cover 'gate' doesn't support position"
+        ));
+        cover.set_tilt(0.5).unwrap_err();
+        cover.stop().unwrap_err();
+    }
+
+    #[test]
+    fn test_speed_level() {
+        assert_eq!(speed_level(1, 3), 1);
+        assert_eq!(speed_level(33, 3), 1);
+        assert_eq!(speed_level(34, 3), 2);
+        assert_eq!(speed_level(100, 3), 3);
+        assert_eq!(speed_level(200, 3), 3);
+        assert_eq!(speed_level(50, 100), 50);
+        assert_eq!(speed_level(50, 0), 1);
+    }
+
+    #[test]
+    fn test_fan_command() {
+        let info = ListEntitiesFanResponse {
+            key: 9,
+            supports_speed: true,
+            supports_direction: true,
+            supported_speed_count: 4,
+            ..Default::default()
+        };
+        let request = FanCommand::for_entity(&info)
+            .speed_percentage(60)
+            .direction(FanDirection::Reverse)
+            .build()
+            .unwrap();
+        assert_eq!(request.key, 9);
+        assert!(request.has_state && request.state);
+        assert!(request.has_speed_level);
+        assert_eq!(request.speed_level, 3);
+        assert!(request.has_direction);
+        assert_eq!(request.direction, i32::from(FanDirection::Reverse));
+        assert!(!request.has_oscillating);
+
+        let off = FanCommand::for_entity(&info)
+            .speed_percentage(0)
+            .build()
+            .unwrap();
+        assert!(off.has_state && !off.state);
+        assert!(!off.has_speed_level);
+    }
+
+    #[test]
+    fn test_fan_command_unsupported() {
+        let info = ListEntitiesFanResponse {
+            object_id: "ceiling".to_owned(),
+            ..Default::default()
+        };
+        FanCommand::for_entity(&info).turn_on().build().unwrap();
+        assert!(matches!(
+            FanCommand::for_entity(&info).oscillating(true).build().unwrap_err(),
+            ClientError::UnsupportedOperation { reason } if reason == "fan 'ceiling' doesn't support oscillation"
+        ));
+        FanCommand::for_entity(&info)
+            .speed_percentage(50)
+            .build()
+            .unwrap_err();
+    }
+
+    #[test]
+    fn test_media_player_control() {
+        let request = MediaPlayerControl::for_key(4)
+            .pause()
+            .play()
+            .volume(1.2)
+            .play_url("http://host/file.mp3")
+            .build();
+        assert_eq!(request.key, 4);
+        assert!(request.has_command);
+        assert_eq!(request.command, i32::from(MediaPlayerCommand::Play));
+        assert!(request.has_volume);
+        assert!((request.volume - 1.0).abs() < f32::EPSILON);
+        assert!(request.has_media_url);
+        assert_eq!(request.media_url, "http://host/file.mp3");
+
+        let mute = MediaPlayerControl::for_key(4).mute(false).build();
+        assert_eq!(mute.command, i32::from(MediaPlayerCommand::Unmute));
+        assert!(!mute.has_volume && !mute.has_media_url);
+    }
+
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    #[test]
+    fn test_media_player_control_announcement() {
+        let request = MediaPlayerControl::for_key(4)
+            .play_url("http://host/chime.flac")
+            .announcement(true)
+            .build();
+        assert!(request.has_announcement && request.announcement);
+        assert!(!request.has_command);
+    }
+
+    #[test]
+    fn test_lock_control() {
+        let info = ListEntitiesLockResponse {
+            key: 2,
+            ..Default::default()
+        };
+        let lock = LockControl::for_entity(&info);
+        let request = lock.lock().unwrap();
+        assert_eq!(request.key, 2);
+        assert_eq!(request.command, i32::from(LockCommand::LockLock));
+        assert!(!request.has_code);
+        assert!(matches!(
+            lock.open().unwrap_err(),
+            ClientError::UnsupportedOperation { .. }
+        ));
+    }
+
+    #[test]
+    fn test_lock_control_requires_code() {
+        let info = ListEntitiesLockResponse {
+            object_id: "front_door".to_owned(),
+            requires_code: true,
+            supports_open: true,
+            ..Default::default()
+        };
+        assert!(matches!(
+            LockControl::for_entity(&info).unlock().unwrap_err(),
+            ClientError::Command(CommandError::CodeRequired { entity }) if entity == "front_door"
+        ));
+
+        let open = LockControl::for_entity(&info)
+            .with_code("1234")
+            .open()
+            .unwrap();
+        assert_eq!(open.command, i32::from(LockCommand::LockOpen));
+        assert!(open.has_code);
+        assert_eq!(open.code, "1234");
+    }
+
+    fn number_info() -> ListEntitiesNumberResponse {
+        ListEntitiesNumberResponse {
+            object_id: "target".to_owned(),
+            key: 8,
+            min_value: 10.0,
+            max_value: 30.0,
+            step: 0.5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_number_control_set() {
+        let info = number_info();
+        let number = NumberControl::for_entity(&info);
+        let request = number.set(21.5).unwrap();
+        assert_eq!(request.key, 8);
+        assert!((request.state - 21.5).abs() < f32::EPSILON);
+        number.set(10.0).unwrap();
+        number.set(30.0).unwrap();
+
+        assert!(matches!(
+            number.set(30.5).unwrap_err(),
+            ClientError::Command(CommandError::OutOfRange { entity, .. }) if entity == "target"
+        ));
+        assert!(matches!(
+            number.set(f32::NAN).unwrap_err(),
+            ClientError::Command(CommandError::OutOfRange { .. })
+        ));
+        assert!(matches!(
+            number.set(21.3).unwrap_err(),
+            ClientError::Command(CommandError::InvalidStep { .. })
+        ));
+    }
+
+    #[test]
+    fn test_number_control_set_clamped() {
+        let info = number_info();
+        let number = NumberControl::for_entity(&info);
+        assert!((number.set_clamped(21.3).unwrap().state - 21.5).abs() < f32::EPSILON);
+        assert!((number.set_clamped(-5.0).unwrap().state - 10.0).abs() < f32::EPSILON);
+        assert!((number.set_clamped(100.0).unwrap().state - 30.0).abs() < f32::EPSILON);
+        number.set_clamped(f32::NAN).unwrap_err();
+
+        let uneven = ListEntitiesNumberResponse {
+            min_value: 0.0,
+            max_value: 10.0,
+            step: 3.0,
+            ..Default::default()
+        };
+        let state = NumberControl::for_entity(&uneven)
+            .set_clamped(10.0)
+            .unwrap()
+            .state;
+        assert!((state - 9.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_select_control() {
+        let info = ListEntitiesSelectResponse {
+            object_id: "mode".to_owned(),
+            key: 11,
+            options: vec!["Eco".to_owned(), "Comfort".to_owned()],
+            ..Default::default()
+        };
+        let request = SelectControl::for_entity(&info).select("Eco").unwrap();
+        assert_eq!(request.key, 11);
+        assert_eq!(request.state, "Eco");
+
+        assert!(matches!(
+            SelectControl::for_entity(&info).select("eco").unwrap_err(),
+            ClientError::Command(CommandError::UnknownOption { entity, option }) if entity == "mode" && option == "eco"
+        ));
+        let insensitive = SelectControl::for_entity(&info)
+            .case_insensitive()
+            .select("comfort")
+            .unwrap();
+        assert_eq!(insensitive.state, "Comfort");
+    }
+
+    #[cfg(not(any(feature = "api-1-8")))]
+    #[test]
+    fn test_text_control() {
+        let info = ListEntitiesTextResponse {
+            object_id: "message".to_owned(),
+            key: 12,
+            min_length: 2,
+            max_length: 5,
+            ..Default::default()
+        };
+        let text = TextControl::for_entity(&info);
+        let request = text.set("héllo").unwrap();
+        assert_eq!(request.key, 12);
+        assert_eq!(request.state, "héllo");
+        assert!(matches!(
+            text.set("a").unwrap_err(),
+            ClientError::Command(CommandError::InvalidLength { length: 1, .. })
+        ));
+        assert!(matches!(
+            text.set("toolong").unwrap_err(),
+            ClientError::Command(CommandError::InvalidLength { entity, length: 7, min: 2, max: 5 }) if entity == "message"
+        ));
+    }
+
+    #[cfg(all(feature = "chrono", not(any(feature = "api-1-8"))))]
+    #[test]
+    fn test_date_and_time_commands() {
+        let date = date_command(
+            &ListEntitiesDateResponse {
+                key: 1,
+                ..Default::default()
+            },
+            NaiveDate::from_ymd_opt(2025, 3, 14).unwrap(),
+        );
+        assert_eq!(
+            (date.key, date.year, date.month, date.day),
+            (1, 2025, 3, 14)
+        );
+
+        let time = time_command(
+            &ListEntitiesTimeResponse {
+                key: 2,
+                ..Default::default()
+            },
+            NaiveTime::from_hms_milli_opt(7, 30, 15, 500).unwrap(),
+        );
+        assert_eq!(
+            (time.key, time.hour, time.minute, time.second),
+            (2, 7, 30, 15)
+        );
+    }
+
+    #[cfg(all(feature = "chrono", not(any(feature = "api-1-9", feature = "api-1-8"))))]
+    #[test]
+    fn test_date_time_command() {
+        let info = ListEntitiesDateTimeResponse {
+            key: 3,
+            ..Default::default()
+        };
+        let date_time = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let request = date_time_command(&info, &date_time).unwrap();
+        assert_eq!(request.key, 3);
+        assert_eq!(request.epoch_seconds, 1_700_000_000);
+
+        let before_epoch = DateTime::from_timestamp(-1, 0).unwrap();
+        assert!(matches!(
+            date_time_command(&info, &before_epoch).unwrap_err(),
+            ClientError::Command(CommandError::InvalidTimestamp { timestamp: -1 })
+        ));
+    }
+
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    #[test]
+    fn test_update_command() {
+        let info = ListEntitiesUpdateResponse {
+            key: 13,
+            ..Default::default()
+        };
+        let request = update_command(&info, UpdateCommand::Check);
+        assert_eq!(request.key, 13);
+        assert_eq!(request.command, i32::from(UpdateCommand::Check));
+    }
+
+    #[test]
+    fn test_light_command_into_message() {
+        let message: EspHomeMessage = LightCommand::for_key(3).turn_off().into();
+        let EspHomeMessage::LightCommandRequest(request) = message else {
+            panic!("Expected a LightCommandRequest");
+        };
+        assert!(request.has_state && !request.state);
+    }
+}
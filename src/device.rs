@@ -0,0 +1,532 @@
+use tokio::sync::watch;
+
+use crate::{
+    EspHomeClient,
+    client::Entities,
+    commands::{CoverCommand, NumberControl, SelectControl},
+    error::ClientError,
+    proto::{
+        ButtonCommandRequest, CoverCommandRequest, ListEntitiesBinarySensorResponse,
+        ListEntitiesButtonResponse, ListEntitiesCoverResponse, ListEntitiesNumberResponse,
+        ListEntitiesSelectResponse, ListEntitiesSensorResponse, ListEntitiesSwitchResponse,
+        SwitchCommandRequest,
+    },
+    states::{EntityState, entity_id},
+};
+
+/// High-level view of a device, giving access to its entities by name instead of by key.
+///
+/// Entities are looked up by their object id (e.g. `garage_door`) or their display name.
+/// State values are read from the state cache of the client, which needs to be enabled with
+/// [`EspHomeClientBuilder::with_state_cache`](crate::EspHomeClientBuilder::with_state_cache),
+/// and states need to be subscribed to and read (e.g. with [`EspHomeClient::run`]).
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, device::Device};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = EspHomeClient::builder()
+///     .address("192.168.0.2:6053")
+///     .with_state_cache()
+///     .connect()
+///     .await?;
+/// let device = Device::new(&client).await?;
+/// device.switch("garage_door")?.turn_on().await?;
+/// println!("Temperature: {:?}", device.sensor("temperature")?.value());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Device<'a> {
+    client: &'a EspHomeClient,
+    entities: Entities,
+}
+
+impl<'a> Device<'a> {
+    /// Lists the entities of the device connected to the client.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if listing the entities fails.
+    pub async fn new(client: &'a EspHomeClient) -> Result<Self, ClientError> {
+        let entities = client.list_entities().await?;
+        Ok(Self { client, entities })
+    }
+
+    /// Gets all entities of the device.
+    #[must_use]
+    pub const fn entities(&self) -> &Entities {
+        &self.entities
+    }
+
+    /// Gets the switch with the given object id or name.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::EntityNotFound`] if the device has no such switch.
+    pub fn switch(&self, name: &str) -> Result<Switch<'a>, ClientError> {
+        let info = find(&self.entities.switches, name, |e| (&e.object_id, &e.name))?;
+        Ok(Switch {
+            client: self.client,
+            state: self.watch(entity_id!(info)),
+            info: info.clone(),
+        })
+    }
+
+    /// Gets the button with the given object id or name.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::EntityNotFound`] if the device has no such button.
+    pub fn button(&self, name: &str) -> Result<Button<'a>, ClientError> {
+        let info = find(&self.entities.buttons, name, |e| (&e.object_id, &e.name))?;
+        Ok(Button {
+            client: self.client,
+            info: info.clone(),
+        })
+    }
+
+    /// Gets the cover with the given object id or name.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::EntityNotFound`] if the device has no such cover.
+    pub fn cover(&self, name: &str) -> Result<Cover<'a>, ClientError> {
+        let info = find(&self.entities.covers, name, |e| (&e.object_id, &e.name))?;
+        Ok(Cover {
+            client: self.client,
+            info: info.clone(),
+        })
+    }
+
+    /// Gets the number with the given object id or name.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::EntityNotFound`] if the device has no such number.
+    pub fn number(&self, name: &str) -> Result<Number<'a>, ClientError> {
+        let info = find(&self.entities.numbers, name, |e| (&e.object_id, &e.name))?;
+        Ok(Number {
+            client: self.client,
+            state: self.watch(entity_id!(info)),
+            info: info.clone(),
+        })
+    }
+
+    /// Gets the select with the given object id or name.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::EntityNotFound`] if the device has no such select.
+    pub fn select(&self, name: &str) -> Result<Select<'a>, ClientError> {
+        let info = find(&self.entities.selects, name, |e| (&e.object_id, &e.name))?;
+        Ok(Select {
+            client: self.client,
+            state: self.watch(entity_id!(info)),
+            info: info.clone(),
+        })
+    }
+
+    /// Gets the sensor with the given object id or name.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::EntityNotFound`] if the device has no such sensor.
+    pub fn sensor(&self, name: &str) -> Result<Sensor, ClientError> {
+        let info = find(&self.entities.sensors, name, |e| (&e.object_id, &e.name))?;
+        Ok(Sensor {
+            state: self.watch(entity_id!(info)),
+            info: info.clone(),
+        })
+    }
+
+    /// Gets the binary sensor with the given object id or name.
+    ///
+    /// # Errors
+    ///
+    /// Will return [`ClientError::EntityNotFound`] if the device has no such binary sensor.
+    pub fn binary_sensor(&self, name: &str) -> Result<BinarySensor, ClientError> {
+        let info = find(&self.entities.binary_sensors, name, |e| {
+            (&e.object_id, &e.name)
+        })?;
+        Ok(BinarySensor {
+            state: self.watch(entity_id!(info)),
+            info: info.clone(),
+        })
+    }
+
+    /// Gets a state receiver for the entity, `None` when the state cache is not enabled.
+    fn watch(&self, (device_id, key): (u32, u32)) -> Option<watch::Receiver<Option<EntityState>>> {
+        self.client.watch(device_id, key).ok()
+    }
+}
+
+/// Handle to a switch entity of a [`Device`].
+#[derive(Debug, Clone)]
+pub struct Switch<'a> {
+    client: &'a EspHomeClient,
+    info: ListEntitiesSwitchResponse,
+    state: Option<watch::Receiver<Option<EntityState>>>,
+}
+
+impl Switch<'_> {
+    /// Gets the entity description.
+    #[must_use]
+    pub const fn info(&self) -> &ListEntitiesSwitchResponse {
+        &self.info
+    }
+
+    /// Gets the latest known state, `None` if no state is known.
+    #[must_use]
+    pub fn state(&self) -> Option<bool> {
+        latest(self.state.as_ref(), |state| match state {
+            EntityState::Switch(state) => Some(state.state),
+            _ => None,
+        })
+    }
+
+    /// Turns the switch on.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails.
+    pub async fn turn_on(&self) -> Result<(), ClientError> {
+        self.set(true).await
+    }
+
+    /// Turns the switch off.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails.
+    pub async fn turn_off(&self) -> Result<(), ClientError> {
+        self.set(false).await
+    }
+
+    /// Sets the switch to the given state.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails.
+    pub async fn set(&self, state: bool) -> Result<(), ClientError> {
+        self.client
+            .try_write(SwitchCommandRequest {
+                key: self.info.key,
+                state,
+                #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+                device_id: self.info.device_id,
+            })
+            .await
+    }
+}
+
+/// Handle to a button entity of a [`Device`].
+#[derive(Debug, Clone)]
+pub struct Button<'a> {
+    client: &'a EspHomeClient,
+    info: ListEntitiesButtonResponse,
+}
+
+impl Button<'_> {
+    /// Gets the entity description.
+    #[must_use]
+    pub const fn info(&self) -> &ListEntitiesButtonResponse {
+        &self.info
+    }
+
+    /// Presses the button.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails.
+    pub async fn press(&self) -> Result<(), ClientError> {
+        self.client
+            .try_write(ButtonCommandRequest {
+                key: self.info.key,
+                #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+                device_id: self.info.device_id,
+            })
+            .await
+    }
+}
+
+/// Handle to a cover entity of a [`Device`], see [`CoverCommand`] for how commands are built.
+#[derive(Debug, Clone)]
+pub struct Cover<'a> {
+    client: &'a EspHomeClient,
+    info: ListEntitiesCoverResponse,
+}
+
+impl Cover<'_> {
+    /// Gets the entity description.
+    #[must_use]
+    pub const fn info(&self) -> &ListEntitiesCoverResponse {
+        &self.info
+    }
+
+    /// Opens the cover completely.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails.
+    pub async fn open(&self) -> Result<(), ClientError> {
+        self.send(self.command().open()).await
+    }
+
+    /// Closes the cover completely.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the write operation fails.
+    pub async fn close(&self) -> Result<(), ClientError> {
+        self.send(self.command().close()).await
+    }
+
+    /// Stops the current movement of the cover.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the cover can't be stopped, or if the write operation fails.
+    pub async fn stop(&self) -> Result<(), ClientError> {
+        self.send(self.command().stop()?).await
+    }
+
+    /// Moves the cover to the given position, between `0.0` (closed) and `1.0` (open).
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the cover doesn't support positions, or if the write operation fails.
+    pub async fn set_position(&self, position: f32) -> Result<(), ClientError> {
+        self.send(self.command().set_position(position)?).await
+    }
+
+    /// Tilts the cover to the given position, between `0.0` (closed) and `1.0` (open).
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the cover doesn't support tilting, or if the write operation fails.
+    pub async fn set_tilt(&self, tilt: f32) -> Result<(), ClientError> {
+        self.send(self.command().set_tilt(tilt)?).await
+    }
+
+    const fn command(&self) -> CoverCommand<'_> {
+        CoverCommand::for_entity(&self.info)
+    }
+
+    async fn send(&self, request: CoverCommandRequest) -> Result<(), ClientError> {
+        self.client.try_write(request).await
+    }
+}
+
+/// Handle to a number entity of a [`Device`].
+#[derive(Debug, Clone)]
+pub struct Number<'a> {
+    client: &'a EspHomeClient,
+    info: ListEntitiesNumberResponse,
+    state: Option<watch::Receiver<Option<EntityState>>>,
+}
+
+impl Number<'_> {
+    /// Gets the entity description.
+    #[must_use]
+    pub const fn info(&self) -> &ListEntitiesNumberResponse {
+        &self.info
+    }
+
+    /// Gets the latest known value, `None` if no value is known.
+    #[must_use]
+    pub fn value(&self) -> Option<f32> {
+        latest(self.state.as_ref(), |state| match state {
+            EntityState::Number(state) if !state.missing_state => Some(state.state),
+            _ => None,
+        })
+    }
+
+    /// Sets the value, which must be within the range and a multiple of the step size of the entity.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the value is invalid, or if the write operation fails.
+    pub async fn set(&self, value: f32) -> Result<(), ClientError> {
+        let request = NumberControl::for_entity(&self.info).set(value)?;
+        self.client.try_write(request).await
+    }
+}
+
+/// Handle to a select entity of a [`Device`].
+#[derive(Debug, Clone)]
+pub struct Select<'a> {
+    client: &'a EspHomeClient,
+    info: ListEntitiesSelectResponse,
+    state: Option<watch::Receiver<Option<EntityState>>>,
+}
+
+impl Select<'_> {
+    /// Gets the entity description.
+    #[must_use]
+    pub const fn info(&self) -> &ListEntitiesSelectResponse {
+        &self.info
+    }
+
+    /// Gets the latest known selected option, `None` if no option is known.
+    #[must_use]
+    pub fn option(&self) -> Option<String> {
+        latest(self.state.as_ref(), |state| match state {
+            EntityState::Select(state) if !state.missing_state => Some(state.state.clone()),
+            _ => None,
+        })
+    }
+
+    /// Selects the given option, which must be one of the options of the entity.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the option is unknown, or if the write operation fails.
+    pub async fn select(&self, option: &str) -> Result<(), ClientError> {
+        let request = SelectControl::for_entity(&self.info).select(option)?;
+        self.client.try_write(request).await
+    }
+}
+
+/// Handle to a sensor entity of a [`Device`].
+#[derive(Debug, Clone)]
+pub struct Sensor {
+    info: ListEntitiesSensorResponse,
+    state: Option<watch::Receiver<Option<EntityState>>>,
+}
+
+impl Sensor {
+    /// Gets the entity description.
+    #[must_use]
+    pub const fn info(&self) -> &ListEntitiesSensorResponse {
+        &self.info
+    }
+
+    /// Gets the latest known value, `None` if no value is known.
+    #[must_use]
+    pub fn value(&self) -> Option<f32> {
+        latest(self.state.as_ref(), |state| match state {
+            EntityState::Sensor(state) if !state.missing_state => Some(state.state),
+            _ => None,
+        })
+    }
+}
+
+/// Handle to a binary sensor entity of a [`Device`].
+#[derive(Debug, Clone)]
+pub struct BinarySensor {
+    info: ListEntitiesBinarySensorResponse,
+    state: Option<watch::Receiver<Option<EntityState>>>,
+}
+
+impl BinarySensor {
+    /// Gets the entity description.
+    #[must_use]
+    pub const fn info(&self) -> &ListEntitiesBinarySensorResponse {
+        &self.info
+    }
+
+    /// Gets the latest known state, `None` if no state is known.
+    #[must_use]
+    pub fn state(&self) -> Option<bool> {
+        latest(self.state.as_ref(), |state| match state {
+            EntityState::BinarySensor(state) if !state.missing_state => Some(state.state),
+            _ => None,
+        })
+    }
+}
+
+/// Finds an entity by object id or name.
+fn find<'e, T>(
+    entities: &'e [T],
+    name: &str,
+    names: impl Fn(&T) -> (&String, &String),
+) -> Result<&'e T, ClientError> {
+    entities
+        .iter()
+        .find(|entity| {
+            let (object_id, display_name) = names(entity);
+            object_id == name || display_name == name
+        })
+        .ok_or_else(|| ClientError::EntityNotFound {
+            name: name.to_owned(),
+        })
+}
+
+/// Extracts a value from the latest state in the receiver.
+fn latest<T>(
+    state: Option<&watch::Receiver<Option<EntityState>>>,
+    extract: impl Fn(&EntityState) -> Option<T>,
+) -> Option<T> {
+    state?.borrow().as_ref().and_then(extract)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::{SensorStateResponse, SwitchStateResponse};
+
+    #[test]
+    fn test_find_by_object_id_or_name() {
+        fn names(entity: &ListEntitiesSensorResponse) -> (&String, &String) {
+            (&entity.object_id, &entity.name)
+        }
+        let sensors = vec![ListEntitiesSensorResponse {
+            object_id: "temperature".to_owned(),
+            name: "Living Room Temperature".to_owned(),
+            key: 1,
+            ..Default::default()
+        }];
+        assert_eq!(find(&sensors, "temperature", names).unwrap().key, 1);
+        assert_eq!(
+            find(&sensors, "Living Room Temperature", names)
+                .unwrap()
+                .key,
+            1
+        );
+        assert!(matches!(
+            find(&sensors, "humidity", names).unwrap_err(),
+            ClientError::EntityNotFound { name } if name == "humidity"
+        ));
+    }
+
+    #[test]
+    fn test_sensor_value() {
+        let (sender, receiver) = watch::channel(None);
+        let sensor = Sensor {
+            info: ListEntitiesSensorResponse::default(),
+            state: Some(receiver),
+        };
+        assert_eq!(sensor.value(), None);
+
+        sender.send_replace(Some(EntityState::Sensor(SensorStateResponse {
+            state: 21.5,
+            ..Default::default()
+        })));
+        assert_eq!(sensor.value(), Some(21.5));
+
+        sender.send_replace(Some(EntityState::Sensor(SensorStateResponse {
+            state: 0.0,
+            missing_state: true,
+            ..Default::default()
+        })));
+        assert_eq!(sensor.value(), None);
+
+        // A state of another kind is ignored
+        sender.send_replace(Some(EntityState::Switch(SwitchStateResponse {
+            state: true,
+            ..Default::default()
+        })));
+        assert_eq!(sensor.value(), None);
+    }
+
+    #[test]
+    fn test_sensor_without_state_cache() {
+        let sensor = Sensor {
+            info: ListEntitiesSensorResponse::default(),
+            state: None,
+        };
+        assert_eq!(sensor.value(), None);
+    }
+}
@@ -1,37 +1,303 @@
+use crate::API_VERSION;
 use mdns_sd::{
-    Error as mdns_error, IfKind, Receiver, ResolvedService, ServiceDaemon, ServiceEvent,
+    Error as mdns_error, HostnameResolutionEvent, IfKind, Receiver, ResolvedService, ScopedIp,
+    ServiceDaemon, ServiceEvent, ServiceInfo,
 };
 use std::{
-    collections::HashMap,
-    fmt,
+    collections::{HashMap, HashSet},
+    fmt, mem,
     net::{IpAddr, SocketAddr},
+    sync::mpsc,
+    thread,
     time::Duration,
 };
-use tokio::{sync::mpsc, task::JoinHandle};
 
 const SERVICE_NAME: &str = "_esphomelib._tcp.local.";
 
+/// An address of a discovered device, together with the network interfaces it was announced on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeviceAddress {
+    ip: IpAddr,
+    interfaces: Vec<String>,
+}
+
+impl DeviceAddress {
+    /// Gets the ip address.
+    #[must_use]
+    pub const fn ip(&self) -> IpAddr {
+        self.ip
+    }
+
+    /// Gets the names of the network interfaces the address was announced on.
+    ///
+    /// Can be empty if the interface is unknown.
+    #[must_use]
+    pub fn interfaces(&self) -> &[String] {
+        &self.interfaces
+    }
+
+    /// Check if the address can be routed, i.e. it's not a loopback or link-local address.
+    #[must_use]
+    pub const fn is_routable(&self) -> bool {
+        match self.ip {
+            IpAddr::V4(ip) => !ip.is_loopback() && !ip.is_link_local() && !ip.is_unspecified(),
+            IpAddr::V6(ip) => {
+                !ip.is_loopback() && !ip.is_unicast_link_local() && !ip.is_unspecified()
+            }
+        }
+    }
+
+    /// Check if the address is link-local, i.e. only reachable on the network segment of an interface.
+    #[must_use]
+    pub const fn is_link_local(&self) -> bool {
+        match self.ip {
+            IpAddr::V4(ip) => ip.is_link_local(),
+            IpAddr::V6(ip) => ip.is_unicast_link_local(),
+        }
+    }
+
+    /// Scopes the address was announced in, as the address family (`true` for v4) and the name of the
+    /// interface, which is empty if unknown.
+    fn scopes(&self) -> impl Iterator<Item = (bool, &str)> {
+        let ipv4 = self.ip.is_ipv4();
+        let unknown = self.interfaces.is_empty().then_some("");
+        self.interfaces
+            .iter()
+            .map(String::as_str)
+            .chain(unknown)
+            .map(move |interface| (ipv4, interface))
+    }
+
+    fn merge_interfaces(&mut self, interfaces: Vec<String>) {
+        for interface in interfaces {
+            if !self.interfaces.contains(&interface) {
+                self.interfaces.push(interface);
+            }
+        }
+    }
+}
+
+impl From<&ScopedIp> for DeviceAddress {
+    fn from(addr: &ScopedIp) -> Self {
+        let interfaces = match addr {
+            ScopedIp::V4(v4) => v4
+                .interface_ids()
+                .iter()
+                .map(|id| id.name.clone())
+                .collect(),
+            ScopedIp::V6(v6) => vec![v6.scope_id().name.clone()],
+            _ => vec![],
+        };
+        Self {
+            ip: addr.to_ip_addr(),
+            interfaces: interfaces.into_iter().filter(|i| !i.is_empty()).collect(),
+        }
+    }
+}
+
+/// Preference used to order and filter the addresses of a device.
+///
+/// The default prefers routable addresses over link-local ones and v4 over v6 addresses, and keeps
+/// link-local addresses as a last resort.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::discovery::{AddressPreference, Client};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let preference = AddressPreference::new().without_link_local();
+/// let mut discovery_results = Client::default().discover()?;
+/// while let Ok(device) = discovery_results.next().await {
+///     println!("{}: {:?}", device.hostname(), device.socket_address_with(preference));
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AddressPreference {
+    ipv4_first: bool,
+    routable_first: bool,
+    link_local: bool,
+}
+
+impl Default for AddressPreference {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AddressPreference {
+    /// Creates the default preference: routable first, v4 first, including link-local addresses.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            ipv4_first: true,
+            routable_first: true,
+            link_local: true,
+        }
+    }
+
+    /// Set whether v4 addresses are preferred over v6 addresses, or v6 over v4 addresses.
+    #[must_use]
+    pub const fn with_ipv4_first(mut self, ipv4_first: bool) -> Self {
+        self.ipv4_first = ipv4_first;
+        self
+    }
+
+    /// Set whether routable addresses are preferred over loopback and link-local addresses.
+    ///
+    /// Takes precedence over the address family.
+    #[must_use]
+    pub const fn with_routable_first(mut self, routable_first: bool) -> Self {
+        self.routable_first = routable_first;
+        self
+    }
+
+    /// Leave out link-local addresses, which need the interface to be known to connect to them.
+    #[must_use]
+    pub const fn without_link_local(mut self) -> Self {
+        self.link_local = false;
+        self
+    }
+
+    /// Orders the addresses by this preference, leaving out the excluded ones.
+    pub fn apply<'a>(
+        self,
+        addresses: impl IntoIterator<Item = &'a DeviceAddress>,
+    ) -> Vec<&'a DeviceAddress> {
+        let mut addresses: Vec<&DeviceAddress> = addresses
+            .into_iter()
+            .filter(|address| self.link_local || !address.is_link_local())
+            .collect();
+        addresses.sort_by_key(|address| self.sort_key(address));
+        addresses
+    }
+
+    /// Sort key of the address, lower is better.
+    const fn sort_key(self, address: &DeviceAddress) -> (bool, bool, IpAddr) {
+        (
+            self.routable_first && !address.is_routable(),
+            self.ipv4_first == address.ip.is_ipv6(),
+            address.ip,
+        )
+    }
+}
+
 /// Information about a discovered ESPHome device.
+///
+/// Announcements of the same device received on multiple interfaces or address families are merged,
+/// so the device info carries all known addresses.
 #[derive(Clone, Debug)]
 pub struct DeviceInfo {
     record: ResolvedService,
+    addresses: Vec<DeviceAddress>,
 }
 
 impl Eq for DeviceInfo {}
 impl PartialEq for DeviceInfo {
     fn eq(&self, other: &Self) -> bool {
         self.record.get_fullname() == other.record.get_fullname()
-            && self.record.get_addresses() == other.record.get_addresses()
+            && self.addresses == other.addresses
             && self.record.get_port() == other.record.get_port()
     }
 }
 
 impl DeviceInfo {
+    fn new(record: ResolvedService) -> Self {
+        let addresses: Vec<DeviceAddress> = record
+            .get_addresses()
+            .iter()
+            .map(DeviceAddress::from)
+            .collect();
+        let mut info = Self {
+            addresses: vec![],
+            record,
+        };
+        info.merge_addresses(addresses);
+        info
+    }
+
+    /// Merges a newer announcement of the same device into this one.
+    ///
+    /// The service record of the newer announcement is kept. Its addresses replace the addresses of
+    /// the same family on the interfaces it was announced on, so stale addresses don't accumulate, the
+    /// addresses announced on other interfaces are kept.
+    fn merge(&mut self, other: Self) {
+        self.record = other.record;
+        let replaced: HashSet<(bool, &str)> = other
+            .addresses
+            .iter()
+            .flat_map(DeviceAddress::scopes)
+            .collect();
+        self.addresses.retain_mut(|address| {
+            let ipv4 = address.ip.is_ipv4();
+            if address.interfaces.is_empty() {
+                return !replaced.contains(&(ipv4, ""));
+            }
+            address
+                .interfaces
+                .retain(|interface| !replaced.contains(&(ipv4, interface.as_str())));
+            !address.interfaces.is_empty()
+        });
+        self.merge_addresses(other.addresses);
+    }
+
+    fn merge_addresses(&mut self, addresses: impl IntoIterator<Item = DeviceAddress>) {
+        for address in addresses {
+            if let Some(existing) = self.addresses.iter_mut().find(|a| a.ip == address.ip) {
+                existing.merge_interfaces(address.interfaces);
+            } else {
+                self.addresses.push(address);
+            }
+        }
+        let preference = AddressPreference::new();
+        self.addresses.sort_by_key(|a| preference.sort_key(a));
+    }
+
     /// Gets the device's socket address.
+    ///
+    /// Routable addresses are preferred over link-local ones, and v4 over v6 addresses.
     #[must_use]
     pub fn socket_address(&self) -> Option<SocketAddr> {
-        let addr = self.record.get_addresses().iter().next()?.to_owned();
-        Some(SocketAddr::new(addr.to_ip_addr(), self.record.get_port()))
+        let address = self.addresses.first()?;
+        Some(SocketAddr::new(address.ip, self.record.get_port()))
+    }
+
+    /// Gets the socket addresses of all known addresses of the device, ordered by preference.
+    pub fn socket_addresses(&self) -> impl Iterator<Item = SocketAddr> + '_ {
+        let port = self.record.get_port();
+        self.addresses
+            .iter()
+            .map(move |address| SocketAddr::new(address.ip, port))
+    }
+
+    /// Gets all known addresses of the device, ordered by preference.
+    #[must_use]
+    pub fn addresses(&self) -> &[DeviceAddress] {
+        &self.addresses
+    }
+
+    /// Gets the device's socket address, preferred by the given preference.
+    #[must_use]
+    pub fn socket_address_with(&self, preference: AddressPreference) -> Option<SocketAddr> {
+        self.socket_addresses_with(preference).into_iter().next()
+    }
+
+    /// Gets the socket addresses of the device, ordered and filtered by the given preference.
+    #[must_use]
+    pub fn socket_addresses_with(&self, preference: AddressPreference) -> Vec<SocketAddr> {
+        let port = self.record.get_port();
+        preference
+            .apply(&self.addresses)
+            .into_iter()
+            .map(|address| SocketAddr::new(address.ip, port))
+            .collect()
+    }
+
+    /// Gets the device's full service name.
+    #[must_use]
+    pub fn fullname(&self) -> &str {
+        self.record.get_fullname()
     }
 
     /// Gets the device's hostname.
@@ -46,6 +312,12 @@ impl DeviceInfo {
         self.record.get_properties().clone().into_property_map_str()
     }
 
+    /// Gets the well-known ESPHome attributes of the device.
+    #[must_use]
+    pub fn txt_record(&self) -> TxtRecord {
+        TxtRecord::from_record(&self.record)
+    }
+
     /// Check if the device has the `api_encryption` attribute.
     /// This indicates that the device requires a key for communication.
     #[must_use]
@@ -54,6 +326,197 @@ impl DeviceInfo {
     }
 }
 
+/// The well-known attributes ESPHome devices announce in their TXT record.
+///
+/// Attributes which the device doesn't announce, e.g. because its firmware is too old, are `None`.
+/// All attributes, including custom ones, are available with [`DeviceInfo::attributes`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TxtRecord {
+    version: Option<String>,
+    mac: Option<String>,
+    platform: Option<String>,
+    board: Option<String>,
+    network: Option<String>,
+    friendly_name: Option<String>,
+    project_name: Option<String>,
+    project_version: Option<String>,
+    api_encryption: Option<String>,
+}
+
+impl TxtRecord {
+    fn from_record(record: &ResolvedService) -> Self {
+        let property = |key: &str| record.get_property_val_str(key).map(str::to_owned);
+        Self {
+            version: property("version"),
+            mac: property("mac"),
+            platform: property("platform"),
+            board: property("board"),
+            network: property("network"),
+            friendly_name: property("friendly_name"),
+            project_name: property("project_name"),
+            project_version: property("project_version"),
+            api_encryption: property("api_encryption"),
+        }
+    }
+
+    /// Gets the ESPHome version of the firmware, e.g. "2025.6.0".
+    #[must_use]
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Gets the MAC address of the device, in lowercase hex without separators.
+    #[must_use]
+    pub fn mac(&self) -> Option<&str> {
+        self.mac.as_deref()
+    }
+
+    /// Gets the platform of the device, e.g. "ESP32" or "ESP8266".
+    #[must_use]
+    pub fn platform(&self) -> Option<&str> {
+        self.platform.as_deref()
+    }
+
+    /// Gets the board of the device, e.g. "esp32dev".
+    #[must_use]
+    pub fn board(&self) -> Option<&str> {
+        self.board.as_deref()
+    }
+
+    /// Gets the network the device is connected with, e.g. "wifi" or "ethernet".
+    #[must_use]
+    pub fn network(&self) -> Option<&str> {
+        self.network.as_deref()
+    }
+
+    /// Gets the human readable name of the device.
+    #[must_use]
+    pub fn friendly_name(&self) -> Option<&str> {
+        self.friendly_name.as_deref()
+    }
+
+    /// Gets the name of the project the firmware was built from, e.g. "esphome.bluetooth-proxy".
+    #[must_use]
+    pub fn project_name(&self) -> Option<&str> {
+        self.project_name.as_deref()
+    }
+
+    /// Gets the version of the project the firmware was built from.
+    #[must_use]
+    pub fn project_version(&self) -> Option<&str> {
+        self.project_version.as_deref()
+    }
+
+    /// Gets the encryption the API requires, e.g. `Noise_NNpsk0_25519_ChaChaPoly_SHA256`.
+    #[must_use]
+    pub fn api_encryption(&self) -> Option<&str> {
+        self.api_encryption.as_deref()
+    }
+}
+
+/// Last known details of a device, remembered in a [`Cache`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct CachedDevice {
+    /// Instance name of the device, e.g. "kitchen".
+    pub name: String,
+    /// Hostname of the device, e.g. "kitchen.local.".
+    pub hostname: String,
+    /// MAC address of the device, if announced.
+    pub mac: Option<String>,
+    /// Last known socket addresses of the device, ordered by preference.
+    pub addresses: Vec<SocketAddr>,
+    /// Whether the device requires a key for communication.
+    pub encryption: bool,
+}
+
+impl From<&DeviceInfo> for CachedDevice {
+    fn from(device: &DeviceInfo) -> Self {
+        let name = device
+            .fullname()
+            .strip_suffix(device.record.ty_domain.as_str())
+            .map_or_else(|| device.fullname(), |name| name.trim_end_matches('.'));
+        Self {
+            name: name.to_owned(),
+            hostname: device.hostname().to_owned(),
+            mac: device.txt_record().mac,
+            addresses: device.socket_addresses().collect(),
+            encryption: device.has_encryption(),
+        }
+    }
+}
+
+/// Cache of discovered devices, to connect to known devices at startup without waiting for their
+/// announcements.
+///
+/// With the `serde` feature enabled, the cache can be saved to and loaded from any serde format
+/// (TOML, YAML, JSON, ...). Devices are ordered by name.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, discovery::{Cache, Client}};
+///
+/// # async fn example(mut cache: Cache) -> Result<(), Box<dyn std::error::Error>> {
+/// if let Some(device) = cache.get("kitchen") {
+///     let client = EspHomeClient::builder().address(device.addresses.clone()).connect().await?;
+/// }
+/// let mut discovery_results = Client::default().discover_unique()?;
+/// while let Ok(device) = discovery_results.next().await {
+///     cache.insert(&device);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(transparent)
+)]
+pub struct Cache {
+    devices: Vec<CachedDevice>,
+}
+
+impl Cache {
+    /// Creates an empty cache.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            devices: Vec::new(),
+        }
+    }
+
+    /// Records a discovered device, replacing the details of a device with the same name.
+    pub fn insert(&mut self, device: &DeviceInfo) {
+        let device = CachedDevice::from(device);
+        match self
+            .devices
+            .binary_search_by(|cached| cached.name.cmp(&device.name))
+        {
+            Ok(index) => self.devices[index] = device,
+            Err(index) => self.devices.insert(index, device),
+        }
+    }
+
+    /// Gets the details of the device with the given name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&CachedDevice> {
+        self.devices.iter().find(|device| device.name == name)
+    }
+
+    /// Removes the device with the given name, returning its details.
+    pub fn remove(&mut self, name: &str) -> Option<CachedDevice> {
+        let index = self.devices.iter().position(|device| device.name == name)?;
+        Some(self.devices.remove(index))
+    }
+
+    /// Gets all cached devices, ordered by name.
+    #[must_use]
+    pub fn devices(&self) -> &[CachedDevice] {
+        &self.devices
+    }
+}
+
 pub use crate::error::DiscoveryError as Error;
 
 /// Client for discovering ESPHome devices using mDNS.
@@ -120,6 +583,55 @@ impl Client {
     ///
     /// Will return `Error` if discovery cannot be started due to initialization issues,
     pub fn discover(self) -> Result<ResultStream, Error> {
+        self.start(false)
+    }
+
+    /// Initialize the discovery client and start discovering devices, yielding each device only once.
+    ///
+    /// A device is only yielded again when its addresses or attributes changed, or after it was removed.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Error` if discovery cannot be started due to initialization issues,
+    pub fn discover_unique(self) -> Result<ResultStream, Error> {
+        self.start(true)
+    }
+
+    /// Discover devices for the given duration, and return each discovered device once.
+    ///
+    /// Devices are ordered by their full service name, devices which were removed during the
+    /// discovery are left out.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use esphome_client::discovery::Client;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// for device in Client::default().discover_for(Duration::from_secs(5)).await? {
+    ///     println!("{}: {:?}", device.hostname(), device.socket_address());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Will return `Error` if discovery cannot be started due to initialization issues,
+    pub async fn discover_for(self, duration: Duration) -> Result<Vec<DeviceInfo>, Error> {
+        let mut stream = self.start(true)?;
+        stream.stop_after(duration);
+        while let Ok(event) = stream.next_event().await {
+            if let DiscoveryEvent::SearchStopped(_) = event {
+                break;
+            }
+        }
+        let mut devices: Vec<DeviceInfo> = mem::take(&mut stream.devices).into_values().collect();
+        devices.sort_by(|a, b| a.fullname().cmp(b.fullname()));
+        Ok(devices)
+    }
+
+    fn start(self, unique: bool) -> Result<ResultStream, Error> {
         let service_name = self
             .service_name
             .as_deref()
@@ -149,57 +661,161 @@ impl Client {
                 reason: e.to_string(),
             })?;
 
-        Ok(ResultStream::new(mdns, receiver))
+        Ok(ResultStream::new(mdns, receiver, service_name, unique))
     }
 }
 
+/// Event of a discovery, see [`ResultStream::next_event`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(
+    clippy::module_name_repetitions,
+    reason = "Event alone is ambiguous with the events of the mDNS daemon"
+)]
+pub enum DiscoveryEvent {
+    /// A device was discovered, or a known device announced itself again.
+    Resolved(DeviceInfo),
+    /// A device is no longer available, with the full service name of the device.
+    Removed(String),
+    /// A search query was sent for the service name, which is repeated periodically.
+    SearchStarted(String),
+    /// The search for the service name stopped.
+    SearchStopped(String),
+}
+
 /// Stream of discovered devices.
 /// This stream will yield `DeviceInfo` for each discovered device.
-/// Note that this stream will not yield unique devices, so you may receive the same device multiple times.
+///
+/// Whether a device is yielded more than once depends on how the stream was started: a stream of
+/// [`Client::discover`] yields every announcement, so you may receive the same device multiple times,
+/// while a stream of [`Client::discover_unique`] only yields a device again when it changed.
+///
+/// The stream doesn't depend on the async runtime, announcements are received from the thread of the
+/// mDNS daemon while the stream is polled.
 pub struct ResultStream {
     mdns: ServiceDaemon,
-    handle: JoinHandle<()>,
-    rx: mpsc::Receiver<DeviceInfo>,
+    receiver: Receiver<ServiceEvent>,
+    service_name: String,
+    devices: HashMap<String, DeviceInfo>,
+    unique: bool,
+    stop_timer: Option<StopTimer>,
 }
 
 impl fmt::Debug for ResultStream {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Results")
             .field("mdns", &"ServiceDaemon {}")
-            .field("handle", &self.handle)
-            .field("rx", &self.rx)
+            .field("receiver", &self.receiver)
+            .field("service_name", &self.service_name)
+            .field("devices", &self.devices)
+            .field("unique", &self.unique)
+            .field("stop_timer", &self.stop_timer.is_some())
             .finish()
     }
 }
 
 impl ResultStream {
-    fn new(mdns: ServiceDaemon, receiver: Receiver<ServiceEvent>) -> Self {
-        let (tx, rx) = mpsc::channel(100);
-        let handle = tokio::spawn(async move {
-            while let Ok(event) = receiver.recv_async().await {
-                match event {
-                    ServiceEvent::ServiceResolved(info) => {
-                        tracing::debug!("Discovered device: {info:?}");
-                        if let Err(e) = tx.send(DeviceInfo { record: *info }).await {
-                            tracing::error!("Failed to send discovered device info: {e}");
-                        }
-                    }
-                    evt => tracing::debug!("Unhandled discovery event: {evt:?}"),
-                }
+    fn new(
+        mdns: ServiceDaemon,
+        receiver: Receiver<ServiceEvent>,
+        service_name: String,
+        unique: bool,
+    ) -> Self {
+        Self {
+            mdns,
+            receiver,
+            service_name,
+            devices: HashMap::new(),
+            unique,
+            stop_timer: None,
+        }
+    }
+
+    /// Stops the search after the duration, which ends the stream with a `SearchStopped` event.
+    ///
+    /// The search is stopped from a separate thread, so no timer of an async runtime is needed. The
+    /// timer is cancelled when the stream is dropped, which ends the thread right away.
+    fn stop_after(&mut self, duration: Duration) {
+        let (cancel, cancelled) = mpsc::channel::<()>();
+        let mdns = self.mdns.clone();
+        let service_name = self.service_name.clone();
+        let thread = thread::spawn(move || {
+            if cancelled.recv_timeout(duration) == Err(mpsc::RecvTimeoutError::Timeout) {
+                let _ignored = mdns.stop_browse(&service_name);
             }
         });
-        Self { mdns, handle, rx }
+        self.stop_timer = Some(StopTimer {
+            cancel: Some(cancel),
+            thread: Some(thread),
+        });
     }
 
     /// Get the next discovered device.
     ///
-    /// Note that this will not return unique devices, so you may receive the same device multiple times.
+    /// Streams of [`Client::discover`] return every announcement, so you may receive the same device multiple
+    /// times, streams of [`Client::discover_unique`] only return a device again when it changed.
     ///
     /// # Errors
     ///
     /// Will return `Error::Aborted` if the discovery was aborted.
     pub async fn next(&mut self) -> Result<DeviceInfo, Error> {
-        self.rx.recv().await.ok_or(Error::Aborted)
+        loop {
+            if let DiscoveryEvent::Resolved(device) = self.next_event().await? {
+                return Ok(device);
+            }
+        }
+    }
+
+    /// Get the next discovery event, including removed devices and the search lifecycle.
+    ///
+    /// Use this instead of [`ResultStream::next`] to maintain a live list of the devices.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Error::Aborted` if the discovery was aborted.
+    pub async fn next_event(&mut self) -> Result<DiscoveryEvent, Error> {
+        while let Ok(event) = self.receiver.recv_async().await {
+            if let Some(event) = self.handle_event(event) {
+                return Ok(event);
+            }
+        }
+        Err(Error::Aborted)
+    }
+
+    fn handle_event(&mut self, event: ServiceEvent) -> Option<DiscoveryEvent> {
+        match event {
+            ServiceEvent::ServiceResolved(info) => {
+                tracing::debug!("Discovered device: {info:?}");
+                let info = DeviceInfo::new(*info);
+                let (device, changed) = match self.devices.remove(info.fullname()) {
+                    Some(mut device) => {
+                        let previous = device.clone();
+                        device.merge(info);
+                        let changed = previous != device
+                            || previous.record.get_properties() != device.record.get_properties();
+                        (device, changed)
+                    }
+                    None => (info, true),
+                };
+                self.devices
+                    .insert(device.fullname().to_owned(), device.clone());
+                (changed || !self.unique).then_some(DiscoveryEvent::Resolved(device))
+            }
+            ServiceEvent::ServiceRemoved(_, fullname) => {
+                tracing::debug!("Device removed: {fullname}");
+                self.devices.remove(&fullname);
+                Some(DiscoveryEvent::Removed(fullname))
+            }
+            ServiceEvent::SearchStarted(service_name) => {
+                Some(DiscoveryEvent::SearchStarted(service_name))
+            }
+            ServiceEvent::SearchStopped(service_name) => {
+                Some(DiscoveryEvent::SearchStopped(service_name))
+            }
+            evt => {
+                tracing::debug!("Unhandled discovery event: {evt:?}");
+                None
+            }
+        }
     }
 
     /// Get the first discovered device.
@@ -214,26 +830,324 @@ impl ResultStream {
 
 impl Drop for ResultStream {
     fn drop(&mut self) {
-        self.handle.abort();
-        for _ in 0..5 {
-            if matches!(self.mdns.shutdown(), Err(mdns_error::Again)) {
-                // retry shutdown a few times
-            } else {
-                return;
+        self.stop_timer.take();
+        shutdown(&self.mdns);
+    }
+}
+
+/// Timer of [`ResultStream::stop_after`], cancelled when dropped.
+struct StopTimer {
+    cancel: Option<mpsc::Sender<()>>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl Drop for StopTimer {
+    fn drop(&mut self) {
+        // Disconnecting the channel wakes the thread, so joining doesn't wait for the duration
+        self.cancel.take();
+        if let Some(thread) = self.thread.take() {
+            let _ignored = thread.join();
+        }
+    }
+}
+
+/// Advertisement of an ESPHome API server with mDNS, so it's discovered like a device.
+///
+/// Needed for virtual devices, like the ones built with the [`server`](crate::server) module, which
+/// Home Assistant should discover. The service is announced until the [`Registration`] is dropped.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::discovery::Advertisement;
+///
+/// let registration = Advertisement::new("virtual-device", 6053)
+///     .with_friendly_name("Virtual Device")
+///     .with_mac("AA:BB:CC:DD:EE:FF")
+///     .with_encryption()
+///     .register()
+///     .expect("Registered");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Advertisement {
+    name: String,
+    port: u16,
+    hostname: Option<String>,
+    addresses: Vec<IpAddr>,
+    properties: HashMap<String, String>,
+    interface: Option<IfKind>,
+    service_name: Option<String>,
+}
+
+impl Advertisement {
+    /// Creates an advertisement of a device with the given name, listening on the given port.
+    ///
+    /// The service is announced with the `version` of the API, like ESPHome devices do.
+    #[must_use]
+    pub fn new(name: &str, port: u16) -> Self {
+        Self {
+            name: name.to_owned(),
+            port,
+            hostname: None,
+            addresses: Vec::new(),
+            properties: HashMap::from([(
+                "version".to_owned(),
+                format!("{}.{}", API_VERSION.0, API_VERSION.1),
+            )]),
+            interface: None,
+            service_name: None,
+        }
+    }
+
+    /// Set the hostname of the device.
+    /// If not set, it will default to the name of the device in the `.local` domain.
+    #[must_use]
+    pub fn with_hostname(mut self, hostname: &str) -> Self {
+        self.hostname = Some(hostname.to_owned());
+        self
+    }
+
+    /// Add an address the device is reachable on.
+    /// If no addresses are added, the addresses of the network interfaces are announced.
+    #[must_use]
+    pub fn with_address(mut self, addr: IpAddr) -> Self {
+        self.addresses.push(addr);
+        self
+    }
+
+    /// Set the interface name to announce the device on.
+    /// If not set, it will use the all interfaces.
+    #[must_use]
+    pub fn with_interface(mut self, interface: &str) -> Self {
+        self.interface = Some(IfKind::Name(interface.to_owned()));
+        self
+    }
+
+    /// Set the service name to announce the device with.
+    /// If not set, it will default to `_esphomelib._tcp.local`.
+    #[must_use]
+    pub fn with_service_name(mut self, service_name: impl Into<String>) -> Self {
+        self.service_name = Some(service_name.into());
+        self
+    }
+
+    /// Set an attribute of the TXT record, like `board` or `platform`.
+    #[must_use]
+    pub fn with_attribute(mut self, key: &str, value: &str) -> Self {
+        self.properties.insert(key.to_owned(), value.to_owned());
+        self
+    }
+
+    /// Set the `friendly_name` attribute, shown by Home Assistant for the discovered device.
+    #[must_use]
+    pub fn with_friendly_name(self, friendly_name: &str) -> Self {
+        self.with_attribute("friendly_name", friendly_name)
+    }
+
+    /// Set the `mac` attribute, in lowercase hexadecimal without separators like ESPHome devices.
+    #[must_use]
+    pub fn with_mac(self, mac: &str) -> Self {
+        let mac = mac.replace(':', "").to_ascii_lowercase();
+        self.with_attribute("mac", &mac)
+    }
+
+    /// Set the `api_encryption` attribute, which tells clients a key is required for communication.
+    #[must_use]
+    pub fn with_encryption(self) -> Self {
+        self.with_attribute("api_encryption", "Noise_NNpsk0_25519_ChaChaPoly_SHA256")
+    }
+
+    /// Start announcing the device.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Error` if the mDNS daemon can't be started, or the service can't be registered.
+    pub fn register(self) -> Result<Registration, Error> {
+        let service_info = self.service_info()?;
+        let mdns = ServiceDaemon::new().map_err(|e| Error::InitializationError {
+            reason: e.to_string(),
+        })?;
+        let registered = self
+            .interface
+            .map_or(Ok(()), |interface| mdns.enable_interface(interface))
+            .and_then(|()| mdns.register(service_info.clone()));
+        if let Err(e) = registered {
+            shutdown(&mdns);
+            return Err(Error::RegistrationError {
+                reason: e.to_string(),
+            });
+        }
+        tracing::debug!("Registered service: {}", service_info.get_fullname());
+        Ok(Registration {
+            mdns,
+            fullname: service_info.get_fullname().to_owned(),
+        })
+    }
+
+    fn service_info(&self) -> Result<ServiceInfo, Error> {
+        let hostname = self
+            .hostname
+            .clone()
+            .unwrap_or_else(|| format!("{}.local.", self.name));
+        let service_info = ServiceInfo::new(
+            self.service_name.as_deref().unwrap_or(SERVICE_NAME),
+            &self.name,
+            &hostname,
+            self.addresses.as_slice(),
+            self.port,
+            self.properties.clone(),
+        )
+        .map_err(|e| Error::RegistrationError {
+            reason: e.to_string(),
+        })?;
+        Ok(if self.addresses.is_empty() {
+            service_info.enable_addr_auto()
+        } else {
+            service_info
+        })
+    }
+}
+
+/// Registered advertisement of a device, the service is unregistered when it's dropped.
+pub struct Registration {
+    mdns: ServiceDaemon,
+    fullname: String,
+}
+
+impl fmt::Debug for Registration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Registration")
+            .field("mdns", &"ServiceDaemon {}")
+            .field("fullname", &self.fullname)
+            .finish()
+    }
+}
+
+impl Registration {
+    /// Gets the full service name of the advertised device.
+    #[must_use]
+    pub fn fullname(&self) -> &str {
+        &self.fullname
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        if let Err(e) = self.mdns.unregister(&self.fullname) {
+            tracing::error!("Failed to unregister service {}: {e}", self.fullname);
+        }
+        shutdown(&self.mdns);
+    }
+}
+
+/// Resolves the ip addresses of a single device by its hostname, without browsing the network.
+///
+/// The hostname is the name of the device in the `.local` domain, e.g. "kitchen.local", the domain is
+/// added to bare device names. The addresses are ordered by preference. Only the hostname is queried,
+/// so the API port is not known: devices listen on port 6053 unless configured otherwise, browsing
+/// with [`Client`] reports the announced port and attributes.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use std::{net::SocketAddr, time::Duration};
+/// use esphome_client::{EspHomeClient, discovery};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let addresses = discovery::resolve("kitchen.local", Duration::from_secs(3)).await?;
+/// let client = EspHomeClient::builder()
+///     .address(addresses.into_iter().map(|ip| SocketAddr::new(ip, 6053)).collect::<Vec<_>>())
+///     .connect()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Will return `Error::NotFound` if the device doesn't answer within the timeout, or `Error` if the
+/// query cannot be started.
+pub async fn resolve(hostname: &str, timeout: Duration) -> Result<Vec<IpAddr>, Error> {
+    let hostname = local_hostname(hostname);
+    let addresses = resolve_hostname(&hostname, timeout).await?;
+    if addresses.is_empty() {
+        return Err(Error::NotFound { hostname });
+    }
+    Ok(addresses)
+}
+
+/// Adds the `.local` domain to bare device names.
+fn local_hostname(hostname: &str) -> String {
+    let hostname = hostname.trim_end_matches('.');
+    if hostname.contains('.') {
+        hostname.to_owned()
+    } else {
+        format!("{hostname}.local")
+    }
+}
+
+/// Resolves the ip addresses of a `.local` hostname using mDNS.
+///
+/// Returns the addresses of the first answer ordered by preference, or no addresses if the host
+/// doesn't answer within the timeout.
+pub(crate) async fn resolve_hostname(
+    hostname: &str,
+    timeout: Duration,
+) -> Result<Vec<IpAddr>, Error> {
+    let hostname = if hostname.ends_with('.') {
+        hostname.to_owned()
+    } else {
+        format!("{hostname}.")
+    };
+    let mdns = ServiceDaemon::new().map_err(|e| Error::InitializationError {
+        reason: e.to_string(),
+    })?;
+    let result = match mdns.resolve_hostname(&hostname, u64::try_from(timeout.as_millis()).ok()) {
+        Ok(receiver) => Ok(first_answer(&receiver).await),
+        Err(e) => Err(Error::InitializationError {
+            reason: e.to_string(),
+        }),
+    };
+    shutdown(&mdns);
+    let mut addresses = result?;
+    let preference = AddressPreference::new();
+    addresses.sort_by_key(|a| preference.sort_key(a));
+    Ok(addresses.iter().map(DeviceAddress::ip).collect())
+}
+
+/// Waits for the first addresses found for a hostname, returns no addresses on timeout.
+async fn first_answer(receiver: &Receiver<HostnameResolutionEvent>) -> Vec<DeviceAddress> {
+    while let Ok(event) = receiver.recv_async().await {
+        match event {
+            HostnameResolutionEvent::AddressesFound(_, found) => {
+                return found.iter().map(DeviceAddress::from).collect();
             }
+            HostnameResolutionEvent::SearchTimeout(_)
+            | HostnameResolutionEvent::SearchStopped(_) => break,
+            evt => tracing::debug!("Unhandled hostname resolution event: {evt:?}"),
+        }
+    }
+    vec![]
+}
+
+fn shutdown(mdns: &ServiceDaemon) {
+    for _ in 0..5 {
+        if matches!(mdns.shutdown(), Err(mdns_error::Again)) {
+            // retry shutdown a few times
+        } else {
+            return;
         }
-        tracing::error!("Failed to shutdown mDNS daemon after multiple attempts");
     }
+    tracing::error!("Failed to shutdown mDNS daemon after multiple attempts");
 }
 
 #[cfg(test)]
 mod tests {
-    use mdns_sd::ServiceInfo;
+    use mdns_sd::{InterfaceId, ScopedIpV4};
 
     use super::*;
 
     use std::net::{IpAddr, Ipv4Addr};
-    use std::time::Duration;
+    use std::time::{Duration, Instant};
+    use tokio::time::timeout;
 
     #[test]
     fn test_device_info_hostname_and_attributes() {
@@ -252,7 +1166,7 @@ mod tests {
         .unwrap()
         .as_resolved_service();
 
-        let device = DeviceInfo { record: info };
+        let device = DeviceInfo::new(info);
 
         assert_eq!(device.hostname(), "test.local");
         let attrs = device.attributes();
@@ -260,6 +1174,37 @@ mod tests {
         assert!(device.has_encryption());
     }
 
+    #[test]
+    fn test_device_info_txt_record() {
+        let props = HashMap::from([
+            ("version".to_owned(), "2025.6.0".to_owned()),
+            ("mac".to_owned(), "abcdef012345".to_owned()),
+            ("platform".to_owned(), "ESP32".to_owned()),
+            ("network".to_owned(), "wifi".to_owned()),
+            ("friendly_name".to_owned(), "Kitchen".to_owned()),
+        ]);
+        let info = ServiceInfo::new(
+            "_esphomelib._tcp.local",
+            "test-device",
+            "test.local",
+            "127.0.0.1",
+            6053,
+            props,
+        )
+        .unwrap()
+        .as_resolved_service();
+
+        let txt = DeviceInfo::new(info).txt_record();
+        assert_eq!(txt.version(), Some("2025.6.0"));
+        assert_eq!(txt.mac(), Some("abcdef012345"));
+        assert_eq!(txt.platform(), Some("ESP32"));
+        assert_eq!(txt.board(), None);
+        assert_eq!(txt.network(), Some("wifi"));
+        assert_eq!(txt.friendly_name(), Some("Kitchen"));
+        assert_eq!(txt.project_name(), None);
+        assert_eq!(txt.api_encryption(), None);
+    }
+
     #[test]
     fn test_device_info_socket_address() {
         let info = ServiceInfo::new(
@@ -273,10 +1218,300 @@ mod tests {
         .unwrap()
         .as_resolved_service();
 
-        let device = DeviceInfo { record: info };
+        let device = DeviceInfo::new(info);
         let addr = device.socket_address().unwrap();
         assert_eq!(addr.ip(), IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10)));
         assert_eq!(addr.port(), 6053);
+        assert_eq!(device.socket_addresses().collect::<Vec<_>>(), vec![addr]);
+    }
+
+    fn resolved_service(addresses: HashSet<ScopedIp>) -> ResolvedService {
+        let mut info = ServiceInfo::new(
+            "_esphomelib._tcp.local",
+            "test-device",
+            "test.local",
+            "",
+            6053,
+            HashMap::<String, String>::new(),
+        )
+        .unwrap()
+        .as_resolved_service();
+        info.addresses = addresses;
+        info
+    }
+
+    fn scoped_v4(addr: [u8; 4], interface: &str) -> ScopedIp {
+        ScopedIp::V4(ScopedIpV4::new(
+            Ipv4Addr::from(addr),
+            InterfaceId {
+                name: interface.to_owned(),
+                index: 1,
+            },
+        ))
+    }
+
+    #[test]
+    fn test_device_info_prefers_routable_address() {
+        let info = ServiceInfo::new(
+            "_esphomelib._tcp.local",
+            "test-device",
+            "test.local",
+            "fe80::1,169.254.1.1,2001:db8::1,192.168.1.10",
+            6053,
+            HashMap::<String, String>::new(),
+        )
+        .unwrap()
+        .as_resolved_service();
+
+        let device = DeviceInfo::new(info);
+        let addr = device.socket_address().unwrap();
+        assert_eq!(addr.ip(), IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10)));
+        assert_eq!(device.addresses().len(), 4);
+        assert!(device.addresses()[1].ip().is_ipv6());
+        assert!(device.addresses()[1].is_routable());
+        assert!(!device.addresses()[3].is_routable());
+    }
+
+    #[test]
+    fn test_device_info_address_preference() {
+        let info = ServiceInfo::new(
+            "_esphomelib._tcp.local",
+            "test-device",
+            "test.local",
+            "fe80::1,169.254.1.1,2001:db8::1,192.168.1.10",
+            6053,
+            HashMap::<String, String>::new(),
+        )
+        .unwrap()
+        .as_resolved_service();
+        let device = DeviceInfo::new(info);
+        let ips = |preference| {
+            device
+                .socket_addresses_with(preference)
+                .iter()
+                .map(|addr| addr.ip().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            ips(AddressPreference::new()),
+            ["192.168.1.10", "2001:db8::1", "169.254.1.1", "fe80::1"]
+        );
+        assert_eq!(
+            ips(AddressPreference::new().with_ipv4_first(false)),
+            ["2001:db8::1", "192.168.1.10", "fe80::1", "169.254.1.1"]
+        );
+        assert_eq!(
+            ips(AddressPreference::new().with_routable_first(false)),
+            ["169.254.1.1", "192.168.1.10", "2001:db8::1", "fe80::1"]
+        );
+        assert_eq!(
+            ips(AddressPreference::new().without_link_local()),
+            ["192.168.1.10", "2001:db8::1"]
+        );
+        assert_eq!(
+            device.socket_address_with(
+                AddressPreference::new()
+                    .with_ipv4_first(false)
+                    .without_link_local()
+            ),
+            Some("[2001:db8::1]:6053".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_device_info_merge_announcements() {
+        let mut device = DeviceInfo::new(resolved_service(HashSet::from([scoped_v4(
+            [192, 168, 1, 10],
+            "eth0",
+        )])));
+        device.merge(DeviceInfo::new(resolved_service(HashSet::from([
+            scoped_v4([192, 168, 1, 10], "wlan0"),
+            scoped_v4([10, 0, 0, 10], "wlan0"),
+        ]))));
+
+        let addresses = device.addresses();
+        assert_eq!(addresses.len(), 2);
+        assert_eq!(addresses[0].ip(), IpAddr::V4(Ipv4Addr::new(10, 0, 0, 10)));
+        assert_eq!(addresses[0].interfaces(), ["wlan0"]);
+        assert_eq!(
+            addresses[1].ip(),
+            IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10))
+        );
+        assert_eq!(addresses[1].interfaces(), ["eth0", "wlan0"]);
+    }
+
+    #[test]
+    fn test_device_info_merge_replaces_addresses() {
+        let mut device = DeviceInfo::new(resolved_service(HashSet::from([
+            scoped_v4([192, 168, 1, 10], "eth0"),
+            scoped_v4([192, 168, 2, 10], "wlan0"),
+        ])));
+        // The device got a new DHCP lease on eth0
+        device.merge(DeviceInfo::new(resolved_service(HashSet::from([
+            scoped_v4([192, 168, 1, 20], "eth0"),
+        ]))));
+
+        assert_eq!(
+            device.socket_address(),
+            Some("192.168.1.20:6053".parse().unwrap())
+        );
+        let ips: Vec<IpAddr> = device.addresses().iter().map(DeviceAddress::ip).collect();
+        assert_eq!(
+            ips,
+            [
+                IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20)),
+                IpAddr::V4(Ipv4Addr::new(192, 168, 2, 10))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_result_stream_events() {
+        let mdns = ServiceDaemon::new().unwrap();
+        let receiver = mdns.browse(SERVICE_NAME).unwrap();
+        let mut stream = ResultStream::new(mdns, receiver, SERVICE_NAME.to_owned(), false);
+
+        let resolved = stream.handle_event(ServiceEvent::ServiceResolved(Box::new(
+            resolved_service(HashSet::from([scoped_v4([192, 168, 1, 10], "eth0")])),
+        )));
+        let Some(DiscoveryEvent::Resolved(device)) = resolved else {
+            panic!("Unexpected event: {resolved:?}");
+        };
+        assert!(stream.devices.contains_key(device.fullname()));
+
+        let removed = stream.handle_event(ServiceEvent::ServiceRemoved(
+            SERVICE_NAME.to_owned(),
+            device.fullname().to_owned(),
+        ));
+        assert_eq!(
+            removed,
+            Some(DiscoveryEvent::Removed(device.fullname().to_owned()))
+        );
+        assert!(stream.devices.is_empty());
+
+        assert_eq!(
+            stream.handle_event(ServiceEvent::SearchStarted(SERVICE_NAME.to_owned())),
+            Some(DiscoveryEvent::SearchStarted(SERVICE_NAME.to_owned()))
+        );
+        assert_eq!(
+            stream.handle_event(ServiceEvent::ServiceFound(
+                SERVICE_NAME.to_owned(),
+                device.fullname().to_owned()
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test_result_stream_unique_devices() {
+        let mdns = ServiceDaemon::new().unwrap();
+        let receiver = mdns.browse(SERVICE_NAME).unwrap();
+        let mut stream = ResultStream::new(mdns, receiver, SERVICE_NAME.to_owned(), true);
+        let announcement = |addr, interface| {
+            ServiceEvent::ServiceResolved(Box::new(resolved_service(HashSet::from([scoped_v4(
+                addr, interface,
+            )]))))
+        };
+
+        assert!(
+            stream
+                .handle_event(announcement([192, 168, 1, 10], "eth0"))
+                .is_some()
+        );
+        assert!(
+            stream
+                .handle_event(announcement([192, 168, 1, 10], "eth0"))
+                .is_none()
+        );
+        let changed = stream.handle_event(announcement([10, 0, 0, 10], "wlan0"));
+        let Some(DiscoveryEvent::Resolved(device)) = changed else {
+            panic!("Unexpected event: {changed:?}");
+        };
+        assert_eq!(device.addresses().len(), 2);
+
+        stream.handle_event(ServiceEvent::ServiceRemoved(
+            SERVICE_NAME.to_owned(),
+            device.fullname().to_owned(),
+        ));
+        assert!(
+            stream
+                .handle_event(announcement([10, 0, 0, 10], "wlan0"))
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn test_dropping_stream_cancels_stop_timer() {
+        let mut stream = Client::default()
+            .with_service_name("_esphome-test._tcp.local.")
+            .discover()
+            .unwrap();
+        stream.stop_after(Duration::from_secs(60));
+        let started = Instant::now();
+        drop(stream);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_discover_for_stops_after_duration() {
+        let devices = timeout(
+            Duration::from_secs(5),
+            Client::default()
+                .with_service_name("_esphome-test._tcp.local.")
+                .discover_for(Duration::from_millis(100)),
+        )
+        .await
+        .expect("Discovery didn't stop")
+        .unwrap();
+        assert!(devices.is_empty());
+    }
+
+    #[test]
+    fn test_discovery_cache() {
+        let service = |name: &str, ip: &str| {
+            let props = HashMap::from([
+                ("mac".to_owned(), "abcdef012345".to_owned()),
+                ("api_encryption".to_owned(), "Noise".to_owned()),
+            ]);
+            ServiceInfo::new(
+                "_esphomelib._tcp.local.",
+                name,
+                &format!("{name}.local."),
+                ip,
+                6053,
+                props,
+            )
+            .unwrap()
+            .as_resolved_service()
+        };
+        let mut cache = Cache::new();
+        cache.insert(&DeviceInfo::new(service("kitchen", "192.168.1.10")));
+        cache.insert(&DeviceInfo::new(service("garage", "192.168.1.11")));
+        cache.insert(&DeviceInfo::new(service("kitchen", "192.168.1.12")));
+
+        let names: Vec<&str> = cache.devices().iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, ["garage", "kitchen"]);
+        assert_eq!(
+            cache.get("kitchen"),
+            Some(&CachedDevice {
+                name: "kitchen".to_owned(),
+                hostname: "kitchen.local.".to_owned(),
+                mac: Some("abcdef012345".to_owned()),
+                addresses: vec!["192.168.1.12:6053".parse().unwrap()],
+                encryption: true,
+            })
+        );
+        assert!(cache.remove("garage").is_some());
+        assert!(cache.remove("garage").is_none());
+        assert_eq!(cache.devices().len(), 1);
+    }
+
+    #[test]
+    fn test_local_hostname() {
+        assert_eq!(local_hostname("kitchen"), "kitchen.local");
+        assert_eq!(local_hostname("kitchen.local"), "kitchen.local");
+        assert_eq!(local_hostname("kitchen.local."), "kitchen.local");
     }
 
     #[test]
@@ -292,6 +1527,50 @@ mod tests {
         assert_eq!(client.service_name.as_deref(), Some("_custom._tcp.local"));
     }
 
+    #[test]
+    fn test_advertisement_service_info() {
+        let info = Advertisement::new("virtual-device", 6053)
+            .with_address(IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10)))
+            .with_friendly_name("Virtual Device")
+            .with_mac("AA:BB:CC:DD:EE:FF")
+            .with_encryption()
+            .service_info()
+            .unwrap();
+        assert_eq!(
+            info.get_fullname(),
+            "virtual-device._esphomelib._tcp.local."
+        );
+        assert_eq!(info.get_hostname(), "virtual-device.local.");
+        assert!(!info.is_addr_auto());
+
+        let device = DeviceInfo::new(info.as_resolved_service());
+        assert_eq!(
+            device.socket_address(),
+            Some(SocketAddr::new(
+                IpAddr::V4(Ipv4Addr::new(192, 168, 1, 10)),
+                6053
+            ))
+        );
+        let attrs = device.attributes();
+        assert_eq!(
+            attrs.get("friendly_name").map(String::as_str),
+            Some("Virtual Device")
+        );
+        assert_eq!(attrs.get("mac").map(String::as_str), Some("aabbccddeeff"));
+        assert!(attrs.contains_key("version"));
+        assert!(device.has_encryption());
+    }
+
+    #[test]
+    fn test_advertisement_without_addresses() {
+        let info = Advertisement::new("virtual-device", 6053)
+            .with_hostname("virtual.local.")
+            .service_info()
+            .unwrap();
+        assert_eq!(info.get_hostname(), "virtual.local.");
+        assert!(info.is_addr_auto());
+    }
+
     #[test]
     fn test_error_display() {
         let init_err = Error::InitializationError {
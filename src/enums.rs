@@ -0,0 +1,95 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    error::ParseEnumError,
+    proto::{
+        ClimateAction, ClimateFanMode, ClimateMode, ClimatePreset, ClimateSwingMode, ColorMode,
+        CoverOperation, EntityCategory, FanDirection, LegacyCoverState, LogLevel,
+    },
+};
+
+/// Implements `Display` and `FromStr` using the names of the proto definition.
+///
+/// Values are formatted as their name in lower case, without the prefix, e.g. `LOG_LEVEL_VERY_VERBOSE`
+/// becomes `very_verbose`. Parsing is case insensitive, accepts dashes and spaces as separators, and
+/// accepts the name with or without the prefix.
+macro_rules! impl_names {
+    ($($enum:ident => $prefix:literal),* $(,)?) => {
+        $(
+            impl fmt::Display for $enum {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    let name = self.as_str_name();
+                    f.pad(&name.strip_prefix($prefix).unwrap_or(name).to_ascii_lowercase())
+                }
+            }
+
+            impl FromStr for $enum {
+                type Err = ParseEnumError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    parse_name(s, $prefix, Self::from_str_name).ok_or_else(|| ParseEnumError {
+                        enum_name: stringify!($enum),
+                        value: s.to_owned(),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_names! {
+    ClimateAction => "CLIMATE_ACTION_",
+    ClimateFanMode => "CLIMATE_FAN_",
+    ClimateMode => "CLIMATE_MODE_",
+    ClimatePreset => "CLIMATE_PRESET_",
+    ClimateSwingMode => "CLIMATE_SWING_",
+    ColorMode => "COLOR_MODE_",
+    CoverOperation => "COVER_OPERATION_",
+    EntityCategory => "ENTITY_CATEGORY_",
+    FanDirection => "FAN_DIRECTION_",
+    LegacyCoverState => "LEGACY_COVER_STATE_",
+    LogLevel => "LOG_LEVEL_",
+}
+
+fn parse_name<T>(s: &str, prefix: &str, from_str_name: fn(&str) -> Option<T>) -> Option<T> {
+    let name = s.trim().to_ascii_uppercase().replace(['-', ' '], "_");
+    from_str_name(&name).or_else(|| from_str_name(&format!("{prefix}{name}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(LogLevel::VeryVerbose.to_string(), "very_verbose");
+        assert_eq!(ClimateMode::HeatCool.to_string(), "heat_cool");
+        assert_eq!(ClimateFanMode::ClimateFanAuto.to_string(), "auto");
+        assert_eq!(EntityCategory::Diagnostic.to_string(), "diagnostic");
+        assert_eq!(format!("{:>6}", LogLevel::Warn), "  warn");
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("debug".parse::<LogLevel>().unwrap(), LogLevel::Debug);
+        assert_eq!(
+            "Very-Verbose".parse::<LogLevel>().unwrap(),
+            LogLevel::VeryVerbose
+        );
+        assert_eq!(
+            "LOG_LEVEL_WARN".parse::<LogLevel>().unwrap(),
+            LogLevel::Warn
+        );
+        assert_eq!(
+            "fan only".parse::<ClimateMode>().unwrap(),
+            ClimateMode::FanOnly
+        );
+        assert_eq!(
+            "quiet".parse::<ClimateFanMode>().unwrap(),
+            ClimateFanMode::ClimateFanQuiet
+        );
+
+        let error = "loud".parse::<LogLevel>().unwrap_err();
+        assert_eq!(error.to_string(), "Unknown LogLevel value: loud");
+    }
+}
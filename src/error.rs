@@ -3,6 +3,10 @@
     reason = "Error suffix is for readability"
 )]
 use std::io::Error as StdIoError;
+#[cfg(feature = "codec")]
+use std::io::ErrorKind;
+
+use crate::bluetooth::BluetoothAddress;
 
 /// Main error type for ESPHome client operations.
 #[derive(Debug, thiserror::Error)]
@@ -22,10 +26,22 @@ pub enum ClientError {
     #[error("Stream error: {0}")]
     Stream(#[from] StreamError),
 
+    /// The connection was closed by the device.
+    #[error("Connection closed by the device")]
+    Disconnected,
+
     /// Protocol parsing errors.
     #[error("Protocol error: {0}")]
     Protocol(#[from] ProtocolError),
 
+    /// Command rejected by validation against the entity metadata.
+    #[error("Invalid command: {0}")]
+    Command(#[from] CommandError),
+
+    /// Bluetooth proxy errors.
+    #[error("Bluetooth error: {0}")]
+    Bluetooth(#[from] BluetoothError),
+
     /// Timeout during operation.
     #[error("Operation timed out after {timeout_ms}ms")]
     Timeout {
@@ -33,6 +49,22 @@ pub enum ClientError {
         timeout_ms: u128,
     },
 
+    /// No message was received from the device within the idle timeout.
+    #[error("Connection idle for more than {timeout_ms}ms")]
+    IdleTimeout {
+        /// Idle duration in milliseconds after which the connection is considered stalled.
+        timeout_ms: u128,
+    },
+
+    /// The outgoing queue is full, see [`EspHomeClientBuilder::outgoing_queue`](crate::EspHomeClientBuilder::outgoing_queue).
+    ///
+    /// The message was not sent, and can be retried once the pending messages are written.
+    #[error("Outgoing queue is full, {capacity} messages are waiting to be written")]
+    Backpressure {
+        /// Maximum number of messages waiting to be written.
+        capacity: usize,
+    },
+
     /// Configuration error.
     #[error("Configuration error: {message}")]
     Configuration {
@@ -49,6 +81,29 @@ pub enum ClientError {
         actual: String,
     },
 
+    /// Connected to another device than expected, see [`EspHomeClientBuilder::expected_mac`](crate::EspHomeClientBuilder::expected_mac).
+    #[error("Device mismatch: expected MAC address {expected}, actual {actual}")]
+    DeviceMismatch {
+        /// Expected MAC address.
+        expected: String,
+        /// MAC address reported by the device.
+        actual: String,
+    },
+
+    /// The device does not expose the requested entity.
+    #[error("Entity not found: {name}")]
+    EntityNotFound {
+        /// Name or description of the entity that was looked for.
+        name: String,
+    },
+
+    /// The entity does not support the requested operation.
+    #[error("Unsupported operation: {reason}")]
+    UnsupportedOperation {
+        /// Description of the operation that isn't supported.
+        reason: String,
+    },
+
     /// Invalid internal state.
     #[error("Invalid internal state: {reason}")]
     InvalidInternalState {
@@ -57,6 +112,61 @@ pub enum ClientError {
     },
 }
 
+impl ClientError {
+    /// Whether the operation may succeed when retried, possibly after reconnecting.
+    ///
+    /// Lost connections, timeouts and failed connection attempts are recoverable. Errors caused by the
+    /// configuration, like a wrong key or connecting to another device, are not.
+    #[must_use]
+    pub const fn is_recoverable(&self) -> bool {
+        if self.is_connection_lost() || self.is_timeout() {
+            return true;
+        }
+        matches!(
+            self,
+            Self::Connection(
+                ConnectionError::TcpConnect { .. }
+                    | ConnectionError::NoiseHandshake { .. }
+                    | ConnectionError::Noise(
+                        NoiseError::Handshake { .. }
+                            | NoiseError::Transport { .. }
+                            | NoiseError::CryptoOperation { .. }
+                    )
+            )
+        )
+    }
+
+    /// Whether a request, or the connection, timed out.
+    #[must_use]
+    pub const fn is_timeout(&self) -> bool {
+        matches!(self, Self::Timeout { .. } | Self::IdleTimeout { .. })
+    }
+
+    /// Whether the connection to the device is closed or broken.
+    #[must_use]
+    pub const fn is_connection_lost(&self) -> bool {
+        matches!(
+            self,
+            Self::Disconnected | Self::Stream(_) | Self::IdleTimeout { .. }
+        )
+    }
+
+    /// Whether the device rejected the credentials, like a wrong password or encryption key.
+    #[must_use]
+    pub const fn is_auth_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::Authentication { .. }
+                | Self::Connection(
+                    ConnectionError::EncryptionRequired
+                        | ConnectionError::Noise(
+                            NoiseError::InvalidPsk | NoiseError::InvalidKey { .. }
+                        )
+                )
+        )
+    }
+}
+
 /// Connection-specific errors.
 #[derive(Debug, thiserror::Error)]
 pub enum ConnectionError {
@@ -70,12 +180,39 @@ pub enum ConnectionError {
         source: StdIoError,
     },
 
+    /// Failed to apply the socket options to the TCP connection.
+    #[error("Failed to configure socket: {source}")]
+    SocketOptions {
+        /// Source IO error.
+        #[source]
+        source: StdIoError,
+    },
+
     /// Noise protocol handshake failed.
     #[error("Noise handshake failed: {reason}")]
     NoiseHandshake {
         /// Reason for the handshake failure.
         reason: String,
     },
+
+    /// Noise protocol errors during the handshake or the encrypted transport.
+    #[error("Noise error: {0}")]
+    Noise(#[from] NoiseError),
+
+    /// The device requires an encrypted connection, but no key was provided.
+    ///
+    /// Detected while setting up a plain connection, when the device responds with a noise frame.
+    #[error("The device requires an encrypted connection, provide an encryption key")]
+    EncryptionRequired,
+
+    /// The device announced a different name than expected, see [`EspHomeClientBuilder::expected_server_name`](crate::EspHomeClientBuilder::expected_server_name).
+    #[error("Connected to {actual}, expected {expected}")]
+    ServerNameMismatch {
+        /// Expected name of the device.
+        expected: String,
+        /// Name announced by the device.
+        actual: String,
+    },
 }
 
 /// Stream-related errors.
@@ -147,6 +284,198 @@ pub enum ProtocolError {
         /// Reason for validation failure.
         reason: String,
     },
+
+    /// The message is not available in the API version of the device.
+    #[error("Message type {type_id} requires API version {required}, the device uses {actual}")]
+    UnsupportedMessage {
+        /// Message type id.
+        type_id: u16,
+        /// Oldest API version in which the message is available.
+        required: String,
+        /// API version reported by the device.
+        actual: String,
+    },
+}
+
+/// Command validation errors, raised before a command is sent to the device.
+#[derive(Debug, thiserror::Error)]
+pub enum CommandError {
+    /// The entity requires a code, but none was given.
+    #[error("Entity {entity} requires a code")]
+    CodeRequired {
+        /// Object id of the entity.
+        entity: String,
+    },
+
+    /// The value is outside of the range supported by the entity.
+    #[error("Value {value} of {entity} is outside of the range {min} - {max}")]
+    OutOfRange {
+        /// Object id of the entity.
+        entity: String,
+        /// Requested value.
+        value: f32,
+        /// Minimum value of the entity.
+        min: f32,
+        /// Maximum value of the entity.
+        max: f32,
+    },
+
+    /// The value is not a multiple of the step size of the entity.
+    #[error("Value {value} of {entity} doesn't match the step size {step}")]
+    InvalidStep {
+        /// Object id of the entity.
+        entity: String,
+        /// Requested value.
+        value: f32,
+        /// Step size of the entity.
+        step: f32,
+    },
+
+    /// The length of the text is outside of the range supported by the entity.
+    #[error("Length {length} of {entity} is outside of the range {min} - {max}")]
+    InvalidLength {
+        /// Object id of the entity.
+        entity: String,
+        /// Length of the requested text, in characters.
+        length: usize,
+        /// Minimum length of the entity.
+        min: u32,
+        /// Maximum length of the entity.
+        max: u32,
+    },
+
+    /// The timestamp can't be represented by the API, which uses unsigned 32 bit epoch seconds.
+    #[error("Timestamp {timestamp} is out of range")]
+    InvalidTimestamp {
+        /// Requested timestamp in seconds since the unix epoch.
+        timestamp: i64,
+    },
+
+    /// The option is not one of the options of the entity.
+    #[error("Unknown option '{option}' for {entity}")]
+    UnknownOption {
+        /// Object id of the entity.
+        entity: String,
+        /// Requested option.
+        option: String,
+    },
+}
+
+/// Errors reported by the Bluetooth proxy of the device.
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum BluetoothError {
+    /// Connecting to the Bluetooth device failed.
+    #[error("Connecting to {} failed with error {error}", BluetoothAddress::from(*address))]
+    ConnectionFailed {
+        /// Address of the Bluetooth device.
+        address: u64,
+        /// Error code reported by the proxy.
+        error: i32,
+    },
+
+    /// Pairing with the Bluetooth device failed.
+    #[error("Pairing with {} failed with error {error}", BluetoothAddress::from(*address))]
+    PairingFailed {
+        /// Address of the Bluetooth device.
+        address: u64,
+        /// Error code reported by the proxy.
+        error: i32,
+    },
+
+    /// Removing the pairing of the Bluetooth device failed.
+    #[error("Unpairing {} failed with error {error}", BluetoothAddress::from(*address))]
+    UnpairingFailed {
+        /// Address of the Bluetooth device.
+        address: u64,
+        /// Error code reported by the proxy.
+        error: i32,
+    },
+
+    /// Clearing the cached GATT services of the Bluetooth device failed.
+    #[error("Clearing the cache of {} failed with error {error}", BluetoothAddress::from(*address))]
+    ClearCacheFailed {
+        /// Address of the Bluetooth device.
+        address: u64,
+        /// Error code reported by the proxy.
+        error: i32,
+    },
+
+    /// The string is not a valid Bluetooth address, like `AA:BB:CC:DD:EE:FF`.
+    #[error("Invalid Bluetooth address: {address}")]
+    InvalidAddress {
+        /// The invalid address.
+        address: String,
+    },
+
+    /// The Bluetooth device disconnected while waiting for a response.
+    #[error("Device {} disconnected", BluetoothAddress::from(*address))]
+    Disconnected {
+        /// Address of the Bluetooth device.
+        address: u64,
+    },
+
+    /// A GATT operation failed, as reported by a `BluetoothGATTErrorResponse`.
+    #[error("GATT operation on handle {handle} of {} failed with error {error}", BluetoothAddress::from(*address))]
+    Gatt {
+        /// Address of the Bluetooth device.
+        address: u64,
+        /// Handle of the characteristic or descriptor, 0 for operations on the device.
+        handle: u32,
+        /// Error code reported by the proxy.
+        error: i32,
+    },
+}
+
+/// Bluetooth advertisement parsing errors.
+#[derive(Debug, thiserror::Error)]
+pub enum AdvertisementError {
+    /// An AD structure claims more bytes than are left in the payload.
+    #[error("AD structure at offset {offset} with length {length} exceeds the payload")]
+    Truncated {
+        /// Offset of the length byte of the AD structure.
+        offset: usize,
+        /// Length claimed by the AD structure.
+        length: usize,
+    },
+
+    /// The data of an AD structure doesn't match the format of its type.
+    #[error("Invalid AD structure of type {ad_type:#04x}: {reason}")]
+    InvalidStructure {
+        /// AD type of the structure.
+        ad_type: u8,
+        /// Reason why the structure is invalid.
+        reason: String,
+    },
+}
+
+/// BTHome decoding errors.
+#[cfg(feature = "bthome")]
+#[derive(Clone, Copy, Debug, thiserror::Error)]
+pub enum BtHomeError {
+    /// The data is encrypted, which isn't supported.
+    #[error("Encrypted BTHome data is not supported")]
+    Encrypted,
+
+    /// The data is of another BTHome version than v2.
+    #[error("Unsupported BTHome version {version}")]
+    UnsupportedVersion {
+        /// Version of the data.
+        version: u8,
+    },
+
+    /// The object id is unknown, so the remaining data can't be decoded.
+    #[error("Unknown BTHome object id {object_id:#04x}")]
+    UnknownObject {
+        /// The unknown object id.
+        object_id: u8,
+    },
+
+    /// The data ends before the end of an object.
+    #[error("BTHome data is truncated")]
+    Truncated {
+        /// Id of the truncated object, `None` if the data is empty.
+        object_id: Option<u8>,
+    },
 }
 
 /// Discovery-related errors.
@@ -162,6 +491,30 @@ pub enum DiscoveryError {
     /// Discovery was aborted, e.g., due to a shutdown signal.
     #[error("Discovery aborted")]
     Aborted,
+
+    /// No device answered for the hostname.
+    #[error("Device not found: {hostname}")]
+    NotFound {
+        /// Hostname which was resolved.
+        hostname: String,
+    },
+
+    /// Error registering a service advertisement.
+    #[error("Registration error: {reason}")]
+    RegistrationError {
+        /// Reason for the registration error.
+        reason: String,
+    },
+}
+
+/// Error parsing the name of an enum of the API.
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("Unknown {enum_name} value: {value}")]
+pub struct ParseEnumError {
+    /// Name of the enum which was parsed.
+    pub enum_name: &'static str,
+    /// The value which couldn't be parsed.
+    pub value: String,
 }
 
 /// Noise protocol specific errors.
@@ -188,6 +541,10 @@ pub enum NoiseError {
         reason: String,
     },
 
+    /// The device rejected the handshake, as the key doesn't match the key of the device.
+    #[error("Invalid pre-shared key: the encryption key doesn't match the key of the device")]
+    InvalidPsk,
+
     /// Noise encryption/decryption failed.
     #[error("Noise crypto operation failed: {reason}")]
     CryptoOperation {
@@ -216,12 +573,41 @@ impl From<snow::Error> for NoiseError {
 /// Convert `NoiseError` to `ClientError`.
 impl From<NoiseError> for ClientError {
     fn from(err: NoiseError) -> Self {
-        Self::Connection(ConnectionError::NoiseHandshake {
-            reason: err.to_string(),
+        Self::Connection(ConnectionError::Noise(err))
+    }
+}
+
+/// Error of the codecs in [`codec`](crate::codec), wrapping the [`ClientError`].
+///
+/// Unlike `ClientError`, it converts from the IO errors raised by the tokio-util framing.
+#[cfg(feature = "codec")]
+#[derive(Debug, thiserror::Error)]
+#[error(transparent)]
+pub struct CodecError(#[from] pub ClientError);
+
+/// Convert IO errors raised by the tokio-util codec framing to `CodecError`.
+///
+/// The codec does not tell reads and writes apart, other errors are reported as read errors.
+#[cfg(feature = "codec")]
+impl From<StdIoError> for CodecError {
+    fn from(err: StdIoError) -> Self {
+        Self(match err.kind() {
+            ErrorKind::UnexpectedEof | ErrorKind::ConnectionReset | ErrorKind::BrokenPipe => {
+                ClientError::Disconnected
+            }
+            _ => ClientError::Stream(StreamError::Read { source: err }),
         })
     }
 }
 
+/// Convert `CodecError` to the wrapped `ClientError`.
+#[cfg(feature = "codec")]
+impl From<CodecError> for ClientError {
+    fn from(err: CodecError) -> Self {
+        err.0
+    }
+}
+
 /// Convert `prost` errors to `ProtocolError`.
 impl From<prost::DecodeError> for ProtocolError {
     fn from(err: prost::DecodeError) -> Self {
@@ -234,3 +620,36 @@ impl From<prost::EncodeError> for ProtocolError {
         Self::ProtobufEncode { source: err }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_error_classification() {
+        let disconnected = ClientError::Disconnected;
+        assert!(disconnected.is_connection_lost());
+        assert!(disconnected.is_recoverable());
+        assert!(!disconnected.is_timeout());
+
+        let timeout = ClientError::Timeout { timeout_ms: 1 };
+        assert!(timeout.is_timeout());
+        assert!(timeout.is_recoverable());
+        assert!(!timeout.is_connection_lost());
+
+        let invalid_psk = ClientError::from(NoiseError::InvalidPsk);
+        assert!(invalid_psk.is_auth_failure());
+        assert!(!invalid_psk.is_recoverable());
+
+        let encryption_required = ClientError::from(ConnectionError::EncryptionRequired);
+        assert!(encryption_required.is_auth_failure());
+        assert!(!encryption_required.is_recoverable());
+
+        let mismatch = ClientError::DeviceMismatch {
+            expected: "aabbccddeeff".to_owned(),
+            actual: "abcdef012345".to_owned(),
+        };
+        assert!(!mismatch.is_recoverable());
+        assert!(!mismatch.is_auth_failure());
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::{Mutex, PoisonError};
+
+use crate::proto::{EspHomeMessage, HomeAssistantStateResponse};
+
+/// Home Assistant entity state, or attribute, requested by the device.
+///
+/// Devices request these for their `homeassistant` platform sensors, after a
+/// `SubscribeHomeAssistantStatesRequest` is sent, see [`EspHomeClient::subscribe_home_assistant_states`](crate::EspHomeClient::subscribe_home_assistant_states).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StateSubscription {
+    /// Id of the Home Assistant entity, e.g. `sensor.outside_temperature`.
+    pub entity_id: String,
+    /// Attribute of the entity, empty for the state of the entity itself.
+    pub attribute: String,
+    /// Whether the device only needs the state once, instead of on every change.
+    pub once: bool,
+}
+
+impl StateSubscription {
+    /// Creates the message providing the given state for this subscription.
+    #[must_use]
+    pub fn state(&self, state: impl Into<String>) -> HomeAssistantStateResponse {
+        HomeAssistantStateResponse {
+            entity_id: self.entity_id.clone(),
+            state: state.into(),
+            attribute: self.attribute.clone(),
+        }
+    }
+
+    fn matches(&self, entity_id: &str, attribute: &str) -> bool {
+        self.entity_id == entity_id && self.attribute == attribute
+    }
+}
+
+/// Registry of the Home Assistant states requested by the device.
+#[derive(Debug, Default)]
+pub(crate) struct StateSubscriptions {
+    requested: Mutex<Vec<StateSubscription>>,
+}
+
+impl StateSubscriptions {
+    /// Registers the subscription if the message is a state request of the device.
+    pub(crate) fn update(&self, message: &EspHomeMessage) {
+        let EspHomeMessage::SubscribeHomeAssistantStateResponse(request) = message else {
+            return;
+        };
+        let subscription = StateSubscription {
+            entity_id: request.entity_id.clone(),
+            attribute: request.attribute.clone(),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            once: request.once,
+            #[cfg(any(feature = "api-1-9", feature = "api-1-8"))]
+            once: false,
+        };
+        let mut requested = self
+            .requested
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if !requested.contains(&subscription) {
+            requested.push(subscription);
+        }
+    }
+
+    /// Returns the requested states, in the order they were requested.
+    pub(crate) fn requested(&self) -> Vec<StateSubscription> {
+        self.requested
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Marks the state as provided, removing it if it was only requested once.
+    pub(crate) fn provided(&self, entity_id: &str, attribute: &str) {
+        self.requested
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|subscription| {
+                !(subscription.once && subscription.matches(entity_id, attribute))
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::SubscribeHomeAssistantStateResponse;
+
+    #[test]
+    fn test_state_subscriptions() {
+        let subscriptions = StateSubscriptions::default();
+        let request = SubscribeHomeAssistantStateResponse {
+            entity_id: "sun.sun".to_owned(),
+            attribute: "elevation".to_owned(),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            once: false,
+        };
+        subscriptions.update(&request.clone().into());
+        subscriptions.update(&request.into());
+        let requested = subscriptions.requested();
+        assert_eq!(requested.len(), 1, "Duplicate requests are registered once");
+
+        let subscription = requested.first().unwrap();
+        assert_eq!(subscription.state("12.5").attribute, "elevation");
+        subscriptions.provided("sun.sun", "elevation");
+        assert_eq!(
+            subscriptions.requested().len(),
+            1,
+            "Subscriptions without once are kept"
+        );
+    }
+
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    #[test]
+    fn test_state_subscriptions_once() {
+        let subscriptions = StateSubscriptions::default();
+        subscriptions.update(
+            &SubscribeHomeAssistantStateResponse {
+                entity_id: "input_number.target".to_owned(),
+                attribute: String::new(),
+                once: true,
+            }
+            .into(),
+        );
+        subscriptions.provided("input_number.target", "");
+        assert!(
+            subscriptions.requested().is_empty(),
+            "Once subscriptions are removed"
+        );
+    }
+}
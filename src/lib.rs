@@ -140,19 +140,74 @@
     variant_size_differences
 )]
 
+/// Helpers for the Bluetooth proxy, like decoding of advertisements.
+pub mod bluetooth;
+/// Camera image reassembly, with optional decoding when the "image" feature is enabled.
+pub mod camera;
+/// Capturing of the API traffic to pcapng files.
+pub mod capture;
 mod client;
+#[cfg(feature = "codec")]
+/// Codecs for the plain and noise framing, to drive custom transports, only available with the "codec" feature.
+pub use client::codec;
+/// Builders for entity command requests.
+pub mod commands;
+/// High-level access to the entities of a device by name.
+pub mod device;
 #[cfg(feature = "discovery")]
 /// Module for discovering ESPHome devices on the local network, only available with the "discovery" feature.
 pub mod discovery;
+mod enums;
 /// Error types for the library.
 pub mod error;
+/// Providing Home Assistant states requested by the device.
+pub mod home_assistant;
+/// Parsing of the log lines sent by the device.
+pub mod logs;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 mod proto;
+/// Routing of incoming messages into separate channels per message class.
+pub mod router;
+#[cfg(feature = "runtime-tokio")]
+/// Server side of the API, to build virtual devices and proxies on top of this crate, only available with the "runtime-tokio" feature.
+pub use client::server;
+/// Unified entity state updates.
+pub mod states;
+#[cfg(feature = "test-util")]
+/// Mock ESPHome API server for testing applications without a device, only available with the "test-util" feature.
+pub use client::test_util;
+/// Conversions between the epoch seconds used by the API and [`std::time::SystemTime`], to answer
+/// time requests of the device.
+pub mod time;
+#[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+/// Typed voice assistant timer events, only available with API version 1.10 or newer.
+pub mod voice_assistant;
 
-pub use client::{EspHomeClient, EspHomeClientBuilder, EspHomeClientWriteStream};
+#[cfg(feature = "runtime-tokio")]
+pub use client::TokioTimer;
+pub use client::{
+    BackoffPolicy, BufferPool, ClientConfig, ClientStatistics, ConnectionEvent, Entities,
+    EntityInfo, EntityKind, EspHomeClient, EspHomeClientBuilder, EspHomeClientWriteStream,
+    EspHomeConnectionManager, KeepaliveConfig, LifecycleEvent, ReadBufferPolicy, ReconnectConfig,
+    ServerAddress, Sleep, Timer, Transport, WriteBatch,
+};
+#[cfg(feature = "supervisor")]
+pub use client::{DeviceHandle, DeviceId, Supervisor, SupervisorEvent};
 /// Re-export of types that can be used with the ESPHome API.
+///
+/// With the "api-versions" feature, the messages of each API version are available side by side in
+/// the `v1_8` to `v1_14` modules, to pick the message set matching a device at runtime.
+///
+/// With the "reflect" feature, [`types::descriptor_pool`] and `EspHomeMessage::to_dynamic` give
+/// access to the fields of messages by name, using the re-exported [`prost_reflect`] crate.
 pub mod types {
+    #[cfg(feature = "api-versions")]
+    pub use super::proto::versions::*;
     pub use super::proto::*;
 }
+#[cfg(feature = "reflect")]
+pub use prost_reflect;
 pub use proto::API_VERSION;
 
 /// This is a helper function to convert GATT UUIDs from the format used in ESPHome: [u64, u64] to a byte array.
@@ -0,0 +1,166 @@
+use std::borrow::Cow;
+
+use crate::proto::{LogLevel, SubscribeLogsResponse};
+
+/// Log line of the device, parsed from a `SubscribeLogsResponse`.
+///
+/// ESPHome formats log lines as `[I][component:123]: message`, wrapped in ANSI color codes. Lines
+/// which don't follow the format are kept as a whole in the message, with an empty tag.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{
+///     EspHomeClient,
+///     logs::LogEntry,
+///     types::{EspHomeMessage, LogLevel, SubscribeLogsRequest},
+/// };
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// client
+///     .try_write(SubscribeLogsRequest { level: LogLevel::Debug.into(), ..Default::default() })
+///     .await?;
+/// loop {
+///     if let EspHomeMessage::SubscribeLogsResponse(response) = client.try_read().await? {
+///         let entry = LogEntry::from(&response);
+///         println!("{:?} {}: {}", entry.level, entry.tag, entry.message);
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogEntry {
+    /// Level of the log line.
+    pub level: LogLevel,
+    /// Tag of the component which logged the line, e.g. `sensor`.
+    pub tag: String,
+    /// Line in the source of the component, if included in the log line.
+    pub line: Option<u32>,
+    /// The logged message, without color codes.
+    pub message: String,
+    /// The complete log line as sent by the device, including color codes.
+    pub raw: String,
+}
+
+impl LogEntry {
+    /// Parses a log line of the given level.
+    #[must_use]
+    pub fn parse(level: LogLevel, raw: &str) -> Self {
+        let stripped = strip_ansi_codes(raw);
+        let stripped = stripped.trim_end();
+        let (tag, line, message) = parse_line(stripped).unwrap_or(("", None, stripped));
+        Self {
+            level,
+            tag: tag.to_owned(),
+            line,
+            message: message.to_owned(),
+            raw: raw.to_owned(),
+        }
+    }
+}
+
+impl From<&SubscribeLogsResponse> for LogEntry {
+    fn from(response: &SubscribeLogsResponse) -> Self {
+        #[cfg(any(feature = "api-1-9", feature = "api-1-8"))]
+        let raw = Cow::Borrowed(response.message.as_str());
+        #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+        let raw = String::from_utf8_lossy(&response.message);
+        Self::parse(response.level(), &raw)
+    }
+}
+
+/// Splits `[I][tag:line]: message` into the tag, line and message.
+fn parse_line(text: &str) -> Option<(&str, Option<u32>, &str)> {
+    let rest = text.strip_prefix('[')?;
+    let (level, rest) = rest.split_once(']')?;
+    if level.chars().count() != 1 {
+        return None;
+    }
+    let (source, rest) = rest.strip_prefix('[')?.split_once(']')?;
+    let message = rest.strip_prefix(':').unwrap_or(rest).trim_start();
+    let numbered = source
+        .rsplit_once(':')
+        .and_then(|(tag, number)| Some((tag, number.parse().ok()?)));
+    Some(numbered.map_or((source, None, message), |(tag, number)| {
+        (tag, Some(number), message)
+    }))
+}
+
+/// Removes the ANSI escape sequences, like color codes, from the text.
+#[must_use]
+pub fn strip_ansi_codes(text: &str) -> Cow<'_, str> {
+    if !text.contains('\x1b') {
+        return Cow::Borrowed(text);
+    }
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            stripped.push(c);
+            continue;
+        }
+        // Control sequences are `ESC [`, parameters and a final byte in the range `@` to `~`
+        if chars.next() == Some('[') {
+            for param in chars.by_ref() {
+                if ('@'..='~').contains(&param) {
+                    break;
+                }
+            }
+        }
+    }
+    Cow::Owned(stripped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_line() {
+        let entry = LogEntry::parse(
+            LogLevel::Debug,
+            "\x1b[0;36m[D][sensor:093]: 'Temperature': Sending state 21.50000 °C\x1b[0m",
+        );
+        assert_eq!(entry.level, LogLevel::Debug);
+        assert_eq!(entry.tag, "sensor");
+        assert_eq!(entry.line, Some(93));
+        assert_eq!(entry.message, "'Temperature': Sending state 21.50000 °C");
+        assert!(entry.raw.starts_with("\x1b[0;36m"));
+    }
+
+    #[test]
+    fn test_parse_log_line_without_line_number() {
+        let entry = LogEntry::parse(LogLevel::Config, "[C][wifi]:   SSID: 'home'");
+        assert_eq!(entry.tag, "wifi");
+        assert_eq!(entry.line, None);
+        assert_eq!(entry.message, "SSID: 'home'");
+    }
+
+    #[test]
+    fn test_parse_unformatted_log_line() {
+        let entry = LogEntry::parse(LogLevel::Info, "\x1b[1;31mBoot loop detected\x1b[0m\n");
+        assert_eq!(entry.tag, "");
+        assert_eq!(entry.line, None);
+        assert_eq!(entry.message, "Boot loop detected");
+    }
+
+    #[test]
+    fn test_log_entry_from_response() {
+        let response = SubscribeLogsResponse {
+            level: LogLevel::Warn.into(),
+            message: "[W][component:123]: Component took a long time".into(),
+            #[cfg(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8"))]
+            send_failed: false,
+        };
+        let entry = LogEntry::from(&response);
+        assert_eq!(entry.level, LogLevel::Warn);
+        assert_eq!(entry.tag, "component");
+        assert_eq!(entry.message, "Component took a long time");
+    }
+
+    #[test]
+    fn test_strip_ansi_codes() {
+        assert_eq!(strip_ansi_codes("plain"), "plain");
+        assert_eq!(strip_ansi_codes("\x1b[0;33mwarn\x1b[0m"), "warn");
+    }
+}
@@ -0,0 +1,64 @@
+//! Metrics of the connections, emitted with the [`metrics`](https://docs.rs/metrics) facade.
+//!
+//! Metrics are only recorded when the application installs a recorder, like a Prometheus exporter.
+//! Message counters are labeled with the name of the message type in the `type` label.
+
+use std::time::Duration;
+
+use ::metrics::{counter, histogram};
+
+use crate::proto::EspHomeMessage;
+
+/// Counter of the messages sent to devices, labeled with the message type.
+pub const MESSAGES_SENT: &str = "esphome_client_messages_sent_total";
+/// Counter of the messages received from devices, labeled with the message type.
+pub const MESSAGES_RECEIVED: &str = "esphome_client_messages_received_total";
+/// Counter of the bytes written to the connections, including framing and encryption.
+pub const BYTES_SENT: &str = "esphome_client_bytes_sent_total";
+/// Counter of the bytes read from the connections, including framing and encryption.
+pub const BYTES_RECEIVED: &str = "esphome_client_bytes_received_total";
+/// Histogram of the time spent framing and encrypting outgoing messages, in seconds.
+pub const ENCODE_DURATION: &str = "esphome_client_encode_duration_seconds";
+/// Histogram of the time spent decoding received messages, in seconds.
+pub const DECODE_DURATION: &str = "esphome_client_decode_duration_seconds";
+/// Counter of the reconnection attempts of connection managers.
+pub const RECONNECT_ATTEMPTS: &str = "esphome_client_reconnect_attempts_total";
+/// Counter of the connections re-established by connection managers.
+pub const RECONNECTS: &str = "esphome_client_reconnects_total";
+
+/// Records a message sent to the device, from its payload with the type id in the first two bytes.
+pub(crate) fn message_sent(payload: &[u8]) {
+    let name = payload
+        .get(..2)
+        .and_then(|id| EspHomeMessage::message_name_of_type(u16::from_be_bytes([id[0], id[1]])))
+        .unwrap_or("Unknown");
+    counter!(MESSAGES_SENT, "type" => name).increment(1);
+}
+
+pub(crate) fn message_received(message: &EspHomeMessage) {
+    counter!(MESSAGES_RECEIVED, "type" => message.message_name()).increment(1);
+}
+
+pub(crate) fn bytes_sent(bytes: usize) {
+    counter!(BYTES_SENT).increment(u64::try_from(bytes).unwrap_or(u64::MAX));
+}
+
+pub(crate) fn bytes_received(bytes: usize) {
+    counter!(BYTES_RECEIVED).increment(u64::try_from(bytes).unwrap_or(u64::MAX));
+}
+
+pub(crate) fn encoded(duration: Duration) {
+    histogram!(ENCODE_DURATION).record(duration.as_secs_f64());
+}
+
+pub(crate) fn decoded(duration: Duration) {
+    histogram!(DECODE_DURATION).record(duration.as_secs_f64());
+}
+
+pub(crate) fn reconnect_attempt() {
+    counter!(RECONNECT_ATTEMPTS).increment(1);
+}
+
+pub(crate) fn reconnected() {
+    counter!(RECONNECTS).increment(1);
+}
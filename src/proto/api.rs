@@ -52,3 +52,32 @@ pub use api_1_9::*;
 mod api_1_8;
 #[cfg(all(feature = "api-1-8", not(any(feature = "api-1-14", feature = "api-1-13", feature = "api-1-12", feature = "api-1-10", feature = "api-1-9"))))]
 pub use api_1_8::*;
+
+// If feature "api-versions" is specified, include all versions as separate modules
+#[cfg(feature = "api-versions")]
+pub mod versions {
+    /// Messages of ESPHome API version 1.14.
+    pub mod v1_14 {
+        include!("api_1_14/mod.rs");
+    }
+    /// Messages of ESPHome API version 1.13.
+    pub mod v1_13 {
+        include!("api_1_13/mod.rs");
+    }
+    /// Messages of ESPHome API version 1.12.
+    pub mod v1_12 {
+        include!("api_1_12/mod.rs");
+    }
+    /// Messages of ESPHome API version 1.10.
+    pub mod v1_10 {
+        include!("api_1_10/mod.rs");
+    }
+    /// Messages of ESPHome API version 1.9.
+    pub mod v1_9 {
+        include!("api_1_9/mod.rs");
+    }
+    /// Messages of ESPHome API version 1.8.
+    pub mod v1_8 {
+        include!("api_1_8/mod.rs");
+    }
+}
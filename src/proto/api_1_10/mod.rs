@@ -3060,6 +3060,136 @@ impl UpdateCommand {
     }
 }
 pub const API_VERSION: (u32, u32) = (1u32, 10u32);
+/// Message type ids used on the wire with the name of the message type, for all messages of this API version.
+pub const MESSAGE_TYPES: &[(u16, &str)] = &[
+    (1u16, "HelloRequest"),
+    (2u16, "HelloResponse"),
+    (3u16, "ConnectRequest"),
+    (4u16, "ConnectResponse"),
+    (5u16, "DisconnectRequest"),
+    (6u16, "DisconnectResponse"),
+    (7u16, "PingRequest"),
+    (8u16, "PingResponse"),
+    (9u16, "DeviceInfoRequest"),
+    (10u16, "DeviceInfoResponse"),
+    (11u16, "ListEntitiesRequest"),
+    (19u16, "ListEntitiesDoneResponse"),
+    (20u16, "SubscribeStatesRequest"),
+    (12u16, "ListEntitiesBinarySensorResponse"),
+    (21u16, "BinarySensorStateResponse"),
+    (13u16, "ListEntitiesCoverResponse"),
+    (22u16, "CoverStateResponse"),
+    (30u16, "CoverCommandRequest"),
+    (14u16, "ListEntitiesFanResponse"),
+    (23u16, "FanStateResponse"),
+    (31u16, "FanCommandRequest"),
+    (15u16, "ListEntitiesLightResponse"),
+    (24u16, "LightStateResponse"),
+    (32u16, "LightCommandRequest"),
+    (16u16, "ListEntitiesSensorResponse"),
+    (25u16, "SensorStateResponse"),
+    (17u16, "ListEntitiesSwitchResponse"),
+    (26u16, "SwitchStateResponse"),
+    (33u16, "SwitchCommandRequest"),
+    (18u16, "ListEntitiesTextSensorResponse"),
+    (27u16, "TextSensorStateResponse"),
+    (28u16, "SubscribeLogsRequest"),
+    (29u16, "SubscribeLogsResponse"),
+    (124u16, "NoiseEncryptionSetKeyRequest"),
+    (125u16, "NoiseEncryptionSetKeyResponse"),
+    (34u16, "SubscribeHomeassistantServicesRequest"),
+    (35u16, "HomeassistantServiceResponse"),
+    (38u16, "SubscribeHomeAssistantStatesRequest"),
+    (39u16, "SubscribeHomeAssistantStateResponse"),
+    (40u16, "HomeAssistantStateResponse"),
+    (36u16, "GetTimeRequest"),
+    (37u16, "GetTimeResponse"),
+    (41u16, "ListEntitiesServicesResponse"),
+    (42u16, "ExecuteServiceRequest"),
+    (43u16, "ListEntitiesCameraResponse"),
+    (44u16, "CameraImageResponse"),
+    (45u16, "CameraImageRequest"),
+    (46u16, "ListEntitiesClimateResponse"),
+    (47u16, "ClimateStateResponse"),
+    (48u16, "ClimateCommandRequest"),
+    (49u16, "ListEntitiesNumberResponse"),
+    (50u16, "NumberStateResponse"),
+    (51u16, "NumberCommandRequest"),
+    (52u16, "ListEntitiesSelectResponse"),
+    (53u16, "SelectStateResponse"),
+    (54u16, "SelectCommandRequest"),
+    (55u16, "ListEntitiesSirenResponse"),
+    (56u16, "SirenStateResponse"),
+    (57u16, "SirenCommandRequest"),
+    (58u16, "ListEntitiesLockResponse"),
+    (59u16, "LockStateResponse"),
+    (60u16, "LockCommandRequest"),
+    (61u16, "ListEntitiesButtonResponse"),
+    (62u16, "ButtonCommandRequest"),
+    (63u16, "ListEntitiesMediaPlayerResponse"),
+    (64u16, "MediaPlayerStateResponse"),
+    (65u16, "MediaPlayerCommandRequest"),
+    (66u16, "SubscribeBluetoothLeAdvertisementsRequest"),
+    (67u16, "BluetoothLeAdvertisementResponse"),
+    (93u16, "BluetoothLeRawAdvertisementsResponse"),
+    (68u16, "BluetoothDeviceRequest"),
+    (69u16, "BluetoothDeviceConnectionResponse"),
+    (70u16, "BluetoothGattGetServicesRequest"),
+    (71u16, "BluetoothGattGetServicesResponse"),
+    (72u16, "BluetoothGattGetServicesDoneResponse"),
+    (73u16, "BluetoothGattReadRequest"),
+    (74u16, "BluetoothGattReadResponse"),
+    (75u16, "BluetoothGattWriteRequest"),
+    (76u16, "BluetoothGattReadDescriptorRequest"),
+    (77u16, "BluetoothGattWriteDescriptorRequest"),
+    (78u16, "BluetoothGattNotifyRequest"),
+    (79u16, "BluetoothGattNotifyDataResponse"),
+    (80u16, "SubscribeBluetoothConnectionsFreeRequest"),
+    (81u16, "BluetoothConnectionsFreeResponse"),
+    (82u16, "BluetoothGattErrorResponse"),
+    (83u16, "BluetoothGattWriteResponse"),
+    (84u16, "BluetoothGattNotifyResponse"),
+    (85u16, "BluetoothDevicePairingResponse"),
+    (86u16, "BluetoothDeviceUnpairingResponse"),
+    (87u16, "UnsubscribeBluetoothLeAdvertisementsRequest"),
+    (88u16, "BluetoothDeviceClearCacheResponse"),
+    (126u16, "BluetoothScannerStateResponse"),
+    (127u16, "BluetoothScannerSetModeRequest"),
+    (89u16, "SubscribeVoiceAssistantRequest"),
+    (90u16, "VoiceAssistantRequest"),
+    (91u16, "VoiceAssistantResponse"),
+    (92u16, "VoiceAssistantEventResponse"),
+    (106u16, "VoiceAssistantAudio"),
+    (115u16, "VoiceAssistantTimerEventResponse"),
+    (119u16, "VoiceAssistantAnnounceRequest"),
+    (120u16, "VoiceAssistantAnnounceFinished"),
+    (121u16, "VoiceAssistantConfigurationRequest"),
+    (122u16, "VoiceAssistantConfigurationResponse"),
+    (123u16, "VoiceAssistantSetConfiguration"),
+    (94u16, "ListEntitiesAlarmControlPanelResponse"),
+    (95u16, "AlarmControlPanelStateResponse"),
+    (96u16, "AlarmControlPanelCommandRequest"),
+    (97u16, "ListEntitiesTextResponse"),
+    (98u16, "TextStateResponse"),
+    (99u16, "TextCommandRequest"),
+    (100u16, "ListEntitiesDateResponse"),
+    (101u16, "DateStateResponse"),
+    (102u16, "DateCommandRequest"),
+    (103u16, "ListEntitiesTimeResponse"),
+    (104u16, "TimeStateResponse"),
+    (105u16, "TimeCommandRequest"),
+    (107u16, "ListEntitiesEventResponse"),
+    (108u16, "EventResponse"),
+    (109u16, "ListEntitiesValveResponse"),
+    (110u16, "ValveStateResponse"),
+    (111u16, "ValveCommandRequest"),
+    (112u16, "ListEntitiesDateTimeResponse"),
+    (113u16, "DateTimeStateResponse"),
+    (114u16, "DateTimeCommandRequest"),
+    (116u16, "ListEntitiesUpdateResponse"),
+    (117u16, "UpdateStateResponse"),
+    (118u16, "UpdateCommandRequest"),
+];
 #[derive(Clone, Debug, PartialEq)]
 pub enum EspHomeMessage {
     HelloRequest(HelloRequest),
@@ -3191,10 +3321,29 @@ pub enum EspHomeMessage {
     ListEntitiesUpdateResponse(ListEntitiesUpdateResponse),
     UpdateStateResponse(UpdateStateResponse),
     UpdateCommandRequest(UpdateCommandRequest),
+    /// Message with a type id unknown to this API version, e.g. sent by newer firmware.
+    Unknown {
+        /// Message type id used on the wire.
+        type_id: u16,
+        /// Encoded protobuf payload of the message.
+        payload: Vec<u8>,
+    },
+}
+/// Trait implemented by all messages that can be wrapped in an [`EspHomeMessage`].
+pub trait MessageType: Into<
+        EspHomeMessage,
+    > + TryFrom<EspHomeMessage, Error = EspHomeMessage> {
+    /// Message type id used on the wire.
+    const TYPE_ID: u16;
+    /// Oldest API version in which the message is available, as (major, minor).
+    const MIN_API_VERSION: (u32, u32);
+    /// Gets a reference to the message, if the wrapped message is of this type.
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self>;
 }
 impl EspHomeMessage {
+    /// Gets the message type id used on the wire.
     #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
-    const fn get_message_type(&self) -> u16 {
+    pub const fn get_message_type(&self) -> u16 {
         match self {
             Self::HelloRequest(_) => 1u16,
             Self::HelloResponse(_) => 2u16,
@@ -3323,165 +3472,701 @@ impl EspHomeMessage {
             Self::ListEntitiesUpdateResponse(_) => 116u16,
             Self::UpdateStateResponse(_) => 117u16,
             Self::UpdateCommandRequest(_) => 118u16,
+            Self::Unknown { type_id, .. } => *type_id,
         }
     }
-}
-impl From<EspHomeMessage> for Vec<u8> {
+    /// Gets the oldest API version in which the message is available, as (major, minor).
+    ///
+    /// Messages available in all known API versions, and unknown messages, report version 1.0.
     #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
-    fn from(val: EspHomeMessage) -> Self {
-        use prost::Message as _;
-        let type_id = val.get_message_type();
-        let payload = match val {
-            EspHomeMessage::HelloRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::HelloResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::ConnectRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ConnectResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::DisconnectRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::DisconnectResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::PingRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::PingResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::DeviceInfoRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::DeviceInfoResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesDoneResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::SubscribeStatesRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesBinarySensorResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BinarySensorStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesCoverResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::CoverStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::CoverCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesFanResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::FanStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::FanCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesLightResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::LightStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::LightCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesSensorResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::SensorStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesSwitchResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::SwitchStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::SwitchCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesTextSensorResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::TextSensorStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::SubscribeLogsRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::SubscribeLogsResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::NoiseEncryptionSetKeyRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::NoiseEncryptionSetKeyResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::SubscribeHomeassistantServicesRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::HomeassistantServiceResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::SubscribeHomeAssistantStatesRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::SubscribeHomeAssistantStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::HomeAssistantStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::GetTimeRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::GetTimeResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesServicesResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::ExecuteServiceRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesCameraResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::CameraImageResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::CameraImageRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesClimateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::ClimateStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::ClimateCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesNumberResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::NumberStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::NumberCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesSelectResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::SelectStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::SelectCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesSirenResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::SirenStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::SirenCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesLockResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::LockStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::LockCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesButtonResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::ButtonCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesMediaPlayerResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::MediaPlayerStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::MediaPlayerCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::SubscribeBluetoothLeAdvertisementsRequest(d) => {
-                d.encode_to_vec()
+    pub const fn min_api_version(&self) -> (u32, u32) {
+        match self {
+            Self::HelloRequest(_) => <HelloRequest as MessageType>::MIN_API_VERSION,
+            Self::HelloResponse(_) => <HelloResponse as MessageType>::MIN_API_VERSION,
+            Self::ConnectRequest(_) => <ConnectRequest as MessageType>::MIN_API_VERSION,
+            Self::ConnectResponse(_) => <ConnectResponse as MessageType>::MIN_API_VERSION,
+            Self::DisconnectRequest(_) => {
+                <DisconnectRequest as MessageType>::MIN_API_VERSION
             }
-            EspHomeMessage::BluetoothLeAdvertisementResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothLeRawAdvertisementsResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothDeviceRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothDeviceConnectionResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothGattGetServicesRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothGattGetServicesResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothGattGetServicesDoneResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothGattReadRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothGattReadResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothGattWriteRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothGattReadDescriptorRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothGattWriteDescriptorRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothGattNotifyRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothGattNotifyDataResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::SubscribeBluetoothConnectionsFreeRequest(d) => {
-                d.encode_to_vec()
+            Self::DisconnectResponse(_) => {
+                <DisconnectResponse as MessageType>::MIN_API_VERSION
             }
-            EspHomeMessage::BluetoothConnectionsFreeResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothGattErrorResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothGattWriteResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothGattNotifyResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothDevicePairingResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothDeviceUnpairingResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::UnsubscribeBluetoothLeAdvertisementsRequest(d) => {
-                d.encode_to_vec()
+            Self::PingRequest(_) => <PingRequest as MessageType>::MIN_API_VERSION,
+            Self::PingResponse(_) => <PingResponse as MessageType>::MIN_API_VERSION,
+            Self::DeviceInfoRequest(_) => {
+                <DeviceInfoRequest as MessageType>::MIN_API_VERSION
             }
-            EspHomeMessage::BluetoothDeviceClearCacheResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothScannerStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::BluetoothScannerSetModeRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::SubscribeVoiceAssistantRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::VoiceAssistantRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::VoiceAssistantResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::VoiceAssistantEventResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::VoiceAssistantAudio(d) => d.encode_to_vec(),
-            EspHomeMessage::VoiceAssistantTimerEventResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::VoiceAssistantAnnounceRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::VoiceAssistantAnnounceFinished(d) => d.encode_to_vec(),
-            EspHomeMessage::VoiceAssistantConfigurationRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::VoiceAssistantConfigurationResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::VoiceAssistantSetConfiguration(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesAlarmControlPanelResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::AlarmControlPanelStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::AlarmControlPanelCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesTextResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::TextStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::TextCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesDateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::DateStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::DateCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesTimeResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::TimeStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::TimeCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesEventResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::EventResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesValveResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::ValveStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::ValveCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesDateTimeResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::DateTimeStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::DateTimeCommandRequest(d) => d.encode_to_vec(),
-            EspHomeMessage::ListEntitiesUpdateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::UpdateStateResponse(d) => d.encode_to_vec(),
-            EspHomeMessage::UpdateCommandRequest(d) => d.encode_to_vec(),
-        };
-        let payload_len = u16::try_from(payload.len())
-            .expect("Payload length exceeds u16::MAX");
-        [type_id.to_be_bytes().to_vec(), payload_len.to_be_bytes().to_vec(), payload]
-            .concat()
+            Self::DeviceInfoResponse(_) => {
+                <DeviceInfoResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesRequest(_) => {
+                <ListEntitiesRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesDoneResponse(_) => {
+                <ListEntitiesDoneResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::SubscribeStatesRequest(_) => {
+                <SubscribeStatesRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesBinarySensorResponse(_) => {
+                <ListEntitiesBinarySensorResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BinarySensorStateResponse(_) => {
+                <BinarySensorStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesCoverResponse(_) => {
+                <ListEntitiesCoverResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::CoverStateResponse(_) => {
+                <CoverStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::CoverCommandRequest(_) => {
+                <CoverCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesFanResponse(_) => {
+                <ListEntitiesFanResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::FanStateResponse(_) => {
+                <FanStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::FanCommandRequest(_) => {
+                <FanCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesLightResponse(_) => {
+                <ListEntitiesLightResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::LightStateResponse(_) => {
+                <LightStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::LightCommandRequest(_) => {
+                <LightCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesSensorResponse(_) => {
+                <ListEntitiesSensorResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::SensorStateResponse(_) => {
+                <SensorStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesSwitchResponse(_) => {
+                <ListEntitiesSwitchResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::SwitchStateResponse(_) => {
+                <SwitchStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::SwitchCommandRequest(_) => {
+                <SwitchCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesTextSensorResponse(_) => {
+                <ListEntitiesTextSensorResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::TextSensorStateResponse(_) => {
+                <TextSensorStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::SubscribeLogsRequest(_) => {
+                <SubscribeLogsRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::SubscribeLogsResponse(_) => {
+                <SubscribeLogsResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::NoiseEncryptionSetKeyRequest(_) => {
+                <NoiseEncryptionSetKeyRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::NoiseEncryptionSetKeyResponse(_) => {
+                <NoiseEncryptionSetKeyResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::SubscribeHomeassistantServicesRequest(_) => {
+                <SubscribeHomeassistantServicesRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::HomeassistantServiceResponse(_) => {
+                <HomeassistantServiceResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::SubscribeHomeAssistantStatesRequest(_) => {
+                <SubscribeHomeAssistantStatesRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::SubscribeHomeAssistantStateResponse(_) => {
+                <SubscribeHomeAssistantStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::HomeAssistantStateResponse(_) => {
+                <HomeAssistantStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::GetTimeRequest(_) => <GetTimeRequest as MessageType>::MIN_API_VERSION,
+            Self::GetTimeResponse(_) => <GetTimeResponse as MessageType>::MIN_API_VERSION,
+            Self::ListEntitiesServicesResponse(_) => {
+                <ListEntitiesServicesResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::ExecuteServiceRequest(_) => {
+                <ExecuteServiceRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesCameraResponse(_) => {
+                <ListEntitiesCameraResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::CameraImageResponse(_) => {
+                <CameraImageResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::CameraImageRequest(_) => {
+                <CameraImageRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesClimateResponse(_) => {
+                <ListEntitiesClimateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::ClimateStateResponse(_) => {
+                <ClimateStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::ClimateCommandRequest(_) => {
+                <ClimateCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesNumberResponse(_) => {
+                <ListEntitiesNumberResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::NumberStateResponse(_) => {
+                <NumberStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::NumberCommandRequest(_) => {
+                <NumberCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesSelectResponse(_) => {
+                <ListEntitiesSelectResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::SelectStateResponse(_) => {
+                <SelectStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::SelectCommandRequest(_) => {
+                <SelectCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesSirenResponse(_) => {
+                <ListEntitiesSirenResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::SirenStateResponse(_) => {
+                <SirenStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::SirenCommandRequest(_) => {
+                <SirenCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesLockResponse(_) => {
+                <ListEntitiesLockResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::LockStateResponse(_) => {
+                <LockStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::LockCommandRequest(_) => {
+                <LockCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesButtonResponse(_) => {
+                <ListEntitiesButtonResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::ButtonCommandRequest(_) => {
+                <ButtonCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesMediaPlayerResponse(_) => {
+                <ListEntitiesMediaPlayerResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::MediaPlayerStateResponse(_) => {
+                <MediaPlayerStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::MediaPlayerCommandRequest(_) => {
+                <MediaPlayerCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::SubscribeBluetoothLeAdvertisementsRequest(_) => {
+                <SubscribeBluetoothLeAdvertisementsRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothLeAdvertisementResponse(_) => {
+                <BluetoothLeAdvertisementResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothLeRawAdvertisementsResponse(_) => {
+                <BluetoothLeRawAdvertisementsResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothDeviceRequest(_) => {
+                <BluetoothDeviceRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothDeviceConnectionResponse(_) => {
+                <BluetoothDeviceConnectionResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothGattGetServicesRequest(_) => {
+                <BluetoothGattGetServicesRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothGattGetServicesResponse(_) => {
+                <BluetoothGattGetServicesResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothGattGetServicesDoneResponse(_) => {
+                <BluetoothGattGetServicesDoneResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothGattReadRequest(_) => {
+                <BluetoothGattReadRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothGattReadResponse(_) => {
+                <BluetoothGattReadResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothGattWriteRequest(_) => {
+                <BluetoothGattWriteRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothGattReadDescriptorRequest(_) => {
+                <BluetoothGattReadDescriptorRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothGattWriteDescriptorRequest(_) => {
+                <BluetoothGattWriteDescriptorRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothGattNotifyRequest(_) => {
+                <BluetoothGattNotifyRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothGattNotifyDataResponse(_) => {
+                <BluetoothGattNotifyDataResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::SubscribeBluetoothConnectionsFreeRequest(_) => {
+                <SubscribeBluetoothConnectionsFreeRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothConnectionsFreeResponse(_) => {
+                <BluetoothConnectionsFreeResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothGattErrorResponse(_) => {
+                <BluetoothGattErrorResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothGattWriteResponse(_) => {
+                <BluetoothGattWriteResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothGattNotifyResponse(_) => {
+                <BluetoothGattNotifyResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothDevicePairingResponse(_) => {
+                <BluetoothDevicePairingResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothDeviceUnpairingResponse(_) => {
+                <BluetoothDeviceUnpairingResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::UnsubscribeBluetoothLeAdvertisementsRequest(_) => {
+                <UnsubscribeBluetoothLeAdvertisementsRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothDeviceClearCacheResponse(_) => {
+                <BluetoothDeviceClearCacheResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothScannerStateResponse(_) => {
+                <BluetoothScannerStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::BluetoothScannerSetModeRequest(_) => {
+                <BluetoothScannerSetModeRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::SubscribeVoiceAssistantRequest(_) => {
+                <SubscribeVoiceAssistantRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::VoiceAssistantRequest(_) => {
+                <VoiceAssistantRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::VoiceAssistantResponse(_) => {
+                <VoiceAssistantResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::VoiceAssistantEventResponse(_) => {
+                <VoiceAssistantEventResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::VoiceAssistantAudio(_) => {
+                <VoiceAssistantAudio as MessageType>::MIN_API_VERSION
+            }
+            Self::VoiceAssistantTimerEventResponse(_) => {
+                <VoiceAssistantTimerEventResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::VoiceAssistantAnnounceRequest(_) => {
+                <VoiceAssistantAnnounceRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::VoiceAssistantAnnounceFinished(_) => {
+                <VoiceAssistantAnnounceFinished as MessageType>::MIN_API_VERSION
+            }
+            Self::VoiceAssistantConfigurationRequest(_) => {
+                <VoiceAssistantConfigurationRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::VoiceAssistantConfigurationResponse(_) => {
+                <VoiceAssistantConfigurationResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::VoiceAssistantSetConfiguration(_) => {
+                <VoiceAssistantSetConfiguration as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesAlarmControlPanelResponse(_) => {
+                <ListEntitiesAlarmControlPanelResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::AlarmControlPanelStateResponse(_) => {
+                <AlarmControlPanelStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::AlarmControlPanelCommandRequest(_) => {
+                <AlarmControlPanelCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesTextResponse(_) => {
+                <ListEntitiesTextResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::TextStateResponse(_) => {
+                <TextStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::TextCommandRequest(_) => {
+                <TextCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesDateResponse(_) => {
+                <ListEntitiesDateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::DateStateResponse(_) => {
+                <DateStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::DateCommandRequest(_) => {
+                <DateCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesTimeResponse(_) => {
+                <ListEntitiesTimeResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::TimeStateResponse(_) => {
+                <TimeStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::TimeCommandRequest(_) => {
+                <TimeCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesEventResponse(_) => {
+                <ListEntitiesEventResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::EventResponse(_) => <EventResponse as MessageType>::MIN_API_VERSION,
+            Self::ListEntitiesValveResponse(_) => {
+                <ListEntitiesValveResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::ValveStateResponse(_) => {
+                <ValveStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::ValveCommandRequest(_) => {
+                <ValveCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesDateTimeResponse(_) => {
+                <ListEntitiesDateTimeResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::DateTimeStateResponse(_) => {
+                <DateTimeStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::DateTimeCommandRequest(_) => {
+                <DateTimeCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::ListEntitiesUpdateResponse(_) => {
+                <ListEntitiesUpdateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::UpdateStateResponse(_) => {
+                <UpdateStateResponse as MessageType>::MIN_API_VERSION
+            }
+            Self::UpdateCommandRequest(_) => {
+                <UpdateCommandRequest as MessageType>::MIN_API_VERSION
+            }
+            Self::Unknown { .. } => (1, 0),
+        }
     }
-}
-impl TryFrom<Vec<u8>> for EspHomeMessage {
-    type Error = String;
+    /// Gets the name of the message type.
     #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
-    fn try_from(msg: Vec<u8>) -> Result<Self, Self::Error> {
-        use prost::Message as _;
-        if msg.len() < 4 {
-            return Err("Message too short".to_owned());
+    pub const fn message_name(&self) -> &'static str {
+        match self {
+            Self::HelloRequest(_) => "HelloRequest",
+            Self::HelloResponse(_) => "HelloResponse",
+            Self::ConnectRequest(_) => "ConnectRequest",
+            Self::ConnectResponse(_) => "ConnectResponse",
+            Self::DisconnectRequest(_) => "DisconnectRequest",
+            Self::DisconnectResponse(_) => "DisconnectResponse",
+            Self::PingRequest(_) => "PingRequest",
+            Self::PingResponse(_) => "PingResponse",
+            Self::DeviceInfoRequest(_) => "DeviceInfoRequest",
+            Self::DeviceInfoResponse(_) => "DeviceInfoResponse",
+            Self::ListEntitiesRequest(_) => "ListEntitiesRequest",
+            Self::ListEntitiesDoneResponse(_) => "ListEntitiesDoneResponse",
+            Self::SubscribeStatesRequest(_) => "SubscribeStatesRequest",
+            Self::ListEntitiesBinarySensorResponse(_) => {
+                "ListEntitiesBinarySensorResponse"
+            }
+            Self::BinarySensorStateResponse(_) => "BinarySensorStateResponse",
+            Self::ListEntitiesCoverResponse(_) => "ListEntitiesCoverResponse",
+            Self::CoverStateResponse(_) => "CoverStateResponse",
+            Self::CoverCommandRequest(_) => "CoverCommandRequest",
+            Self::ListEntitiesFanResponse(_) => "ListEntitiesFanResponse",
+            Self::FanStateResponse(_) => "FanStateResponse",
+            Self::FanCommandRequest(_) => "FanCommandRequest",
+            Self::ListEntitiesLightResponse(_) => "ListEntitiesLightResponse",
+            Self::LightStateResponse(_) => "LightStateResponse",
+            Self::LightCommandRequest(_) => "LightCommandRequest",
+            Self::ListEntitiesSensorResponse(_) => "ListEntitiesSensorResponse",
+            Self::SensorStateResponse(_) => "SensorStateResponse",
+            Self::ListEntitiesSwitchResponse(_) => "ListEntitiesSwitchResponse",
+            Self::SwitchStateResponse(_) => "SwitchStateResponse",
+            Self::SwitchCommandRequest(_) => "SwitchCommandRequest",
+            Self::ListEntitiesTextSensorResponse(_) => "ListEntitiesTextSensorResponse",
+            Self::TextSensorStateResponse(_) => "TextSensorStateResponse",
+            Self::SubscribeLogsRequest(_) => "SubscribeLogsRequest",
+            Self::SubscribeLogsResponse(_) => "SubscribeLogsResponse",
+            Self::NoiseEncryptionSetKeyRequest(_) => "NoiseEncryptionSetKeyRequest",
+            Self::NoiseEncryptionSetKeyResponse(_) => "NoiseEncryptionSetKeyResponse",
+            Self::SubscribeHomeassistantServicesRequest(_) => {
+                "SubscribeHomeassistantServicesRequest"
+            }
+            Self::HomeassistantServiceResponse(_) => "HomeassistantServiceResponse",
+            Self::SubscribeHomeAssistantStatesRequest(_) => {
+                "SubscribeHomeAssistantStatesRequest"
+            }
+            Self::SubscribeHomeAssistantStateResponse(_) => {
+                "SubscribeHomeAssistantStateResponse"
+            }
+            Self::HomeAssistantStateResponse(_) => "HomeAssistantStateResponse",
+            Self::GetTimeRequest(_) => "GetTimeRequest",
+            Self::GetTimeResponse(_) => "GetTimeResponse",
+            Self::ListEntitiesServicesResponse(_) => "ListEntitiesServicesResponse",
+            Self::ExecuteServiceRequest(_) => "ExecuteServiceRequest",
+            Self::ListEntitiesCameraResponse(_) => "ListEntitiesCameraResponse",
+            Self::CameraImageResponse(_) => "CameraImageResponse",
+            Self::CameraImageRequest(_) => "CameraImageRequest",
+            Self::ListEntitiesClimateResponse(_) => "ListEntitiesClimateResponse",
+            Self::ClimateStateResponse(_) => "ClimateStateResponse",
+            Self::ClimateCommandRequest(_) => "ClimateCommandRequest",
+            Self::ListEntitiesNumberResponse(_) => "ListEntitiesNumberResponse",
+            Self::NumberStateResponse(_) => "NumberStateResponse",
+            Self::NumberCommandRequest(_) => "NumberCommandRequest",
+            Self::ListEntitiesSelectResponse(_) => "ListEntitiesSelectResponse",
+            Self::SelectStateResponse(_) => "SelectStateResponse",
+            Self::SelectCommandRequest(_) => "SelectCommandRequest",
+            Self::ListEntitiesSirenResponse(_) => "ListEntitiesSirenResponse",
+            Self::SirenStateResponse(_) => "SirenStateResponse",
+            Self::SirenCommandRequest(_) => "SirenCommandRequest",
+            Self::ListEntitiesLockResponse(_) => "ListEntitiesLockResponse",
+            Self::LockStateResponse(_) => "LockStateResponse",
+            Self::LockCommandRequest(_) => "LockCommandRequest",
+            Self::ListEntitiesButtonResponse(_) => "ListEntitiesButtonResponse",
+            Self::ButtonCommandRequest(_) => "ButtonCommandRequest",
+            Self::ListEntitiesMediaPlayerResponse(_) => "ListEntitiesMediaPlayerResponse",
+            Self::MediaPlayerStateResponse(_) => "MediaPlayerStateResponse",
+            Self::MediaPlayerCommandRequest(_) => "MediaPlayerCommandRequest",
+            Self::SubscribeBluetoothLeAdvertisementsRequest(_) => {
+                "SubscribeBluetoothLeAdvertisementsRequest"
+            }
+            Self::BluetoothLeAdvertisementResponse(_) => {
+                "BluetoothLeAdvertisementResponse"
+            }
+            Self::BluetoothLeRawAdvertisementsResponse(_) => {
+                "BluetoothLeRawAdvertisementsResponse"
+            }
+            Self::BluetoothDeviceRequest(_) => "BluetoothDeviceRequest",
+            Self::BluetoothDeviceConnectionResponse(_) => {
+                "BluetoothDeviceConnectionResponse"
+            }
+            Self::BluetoothGattGetServicesRequest(_) => "BluetoothGattGetServicesRequest",
+            Self::BluetoothGattGetServicesResponse(_) => {
+                "BluetoothGattGetServicesResponse"
+            }
+            Self::BluetoothGattGetServicesDoneResponse(_) => {
+                "BluetoothGattGetServicesDoneResponse"
+            }
+            Self::BluetoothGattReadRequest(_) => "BluetoothGattReadRequest",
+            Self::BluetoothGattReadResponse(_) => "BluetoothGattReadResponse",
+            Self::BluetoothGattWriteRequest(_) => "BluetoothGattWriteRequest",
+            Self::BluetoothGattReadDescriptorRequest(_) => {
+                "BluetoothGattReadDescriptorRequest"
+            }
+            Self::BluetoothGattWriteDescriptorRequest(_) => {
+                "BluetoothGattWriteDescriptorRequest"
+            }
+            Self::BluetoothGattNotifyRequest(_) => "BluetoothGattNotifyRequest",
+            Self::BluetoothGattNotifyDataResponse(_) => "BluetoothGattNotifyDataResponse",
+            Self::SubscribeBluetoothConnectionsFreeRequest(_) => {
+                "SubscribeBluetoothConnectionsFreeRequest"
+            }
+            Self::BluetoothConnectionsFreeResponse(_) => {
+                "BluetoothConnectionsFreeResponse"
+            }
+            Self::BluetoothGattErrorResponse(_) => "BluetoothGattErrorResponse",
+            Self::BluetoothGattWriteResponse(_) => "BluetoothGattWriteResponse",
+            Self::BluetoothGattNotifyResponse(_) => "BluetoothGattNotifyResponse",
+            Self::BluetoothDevicePairingResponse(_) => "BluetoothDevicePairingResponse",
+            Self::BluetoothDeviceUnpairingResponse(_) => {
+                "BluetoothDeviceUnpairingResponse"
+            }
+            Self::UnsubscribeBluetoothLeAdvertisementsRequest(_) => {
+                "UnsubscribeBluetoothLeAdvertisementsRequest"
+            }
+            Self::BluetoothDeviceClearCacheResponse(_) => {
+                "BluetoothDeviceClearCacheResponse"
+            }
+            Self::BluetoothScannerStateResponse(_) => "BluetoothScannerStateResponse",
+            Self::BluetoothScannerSetModeRequest(_) => "BluetoothScannerSetModeRequest",
+            Self::SubscribeVoiceAssistantRequest(_) => "SubscribeVoiceAssistantRequest",
+            Self::VoiceAssistantRequest(_) => "VoiceAssistantRequest",
+            Self::VoiceAssistantResponse(_) => "VoiceAssistantResponse",
+            Self::VoiceAssistantEventResponse(_) => "VoiceAssistantEventResponse",
+            Self::VoiceAssistantAudio(_) => "VoiceAssistantAudio",
+            Self::VoiceAssistantTimerEventResponse(_) => {
+                "VoiceAssistantTimerEventResponse"
+            }
+            Self::VoiceAssistantAnnounceRequest(_) => "VoiceAssistantAnnounceRequest",
+            Self::VoiceAssistantAnnounceFinished(_) => "VoiceAssistantAnnounceFinished",
+            Self::VoiceAssistantConfigurationRequest(_) => {
+                "VoiceAssistantConfigurationRequest"
+            }
+            Self::VoiceAssistantConfigurationResponse(_) => {
+                "VoiceAssistantConfigurationResponse"
+            }
+            Self::VoiceAssistantSetConfiguration(_) => "VoiceAssistantSetConfiguration",
+            Self::ListEntitiesAlarmControlPanelResponse(_) => {
+                "ListEntitiesAlarmControlPanelResponse"
+            }
+            Self::AlarmControlPanelStateResponse(_) => "AlarmControlPanelStateResponse",
+            Self::AlarmControlPanelCommandRequest(_) => "AlarmControlPanelCommandRequest",
+            Self::ListEntitiesTextResponse(_) => "ListEntitiesTextResponse",
+            Self::TextStateResponse(_) => "TextStateResponse",
+            Self::TextCommandRequest(_) => "TextCommandRequest",
+            Self::ListEntitiesDateResponse(_) => "ListEntitiesDateResponse",
+            Self::DateStateResponse(_) => "DateStateResponse",
+            Self::DateCommandRequest(_) => "DateCommandRequest",
+            Self::ListEntitiesTimeResponse(_) => "ListEntitiesTimeResponse",
+            Self::TimeStateResponse(_) => "TimeStateResponse",
+            Self::TimeCommandRequest(_) => "TimeCommandRequest",
+            Self::ListEntitiesEventResponse(_) => "ListEntitiesEventResponse",
+            Self::EventResponse(_) => "EventResponse",
+            Self::ListEntitiesValveResponse(_) => "ListEntitiesValveResponse",
+            Self::ValveStateResponse(_) => "ValveStateResponse",
+            Self::ValveCommandRequest(_) => "ValveCommandRequest",
+            Self::ListEntitiesDateTimeResponse(_) => "ListEntitiesDateTimeResponse",
+            Self::DateTimeStateResponse(_) => "DateTimeStateResponse",
+            Self::DateTimeCommandRequest(_) => "DateTimeCommandRequest",
+            Self::ListEntitiesUpdateResponse(_) => "ListEntitiesUpdateResponse",
+            Self::UpdateStateResponse(_) => "UpdateStateResponse",
+            Self::UpdateCommandRequest(_) => "UpdateCommandRequest",
+            Self::Unknown { .. } => "Unknown",
         }
-        let type_id = u16::from_be_bytes([msg[0], msg[1]]);
-        let payload = &msg[4..];
+    }
+    /// Gets the name of the message type with the given type id, if the type id is known.
+    #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
+    pub const fn message_name_of_type(type_id: u16) -> Option<&'static str> {
+        match type_id {
+            1u16 => Some("HelloRequest"),
+            2u16 => Some("HelloResponse"),
+            3u16 => Some("ConnectRequest"),
+            4u16 => Some("ConnectResponse"),
+            5u16 => Some("DisconnectRequest"),
+            6u16 => Some("DisconnectResponse"),
+            7u16 => Some("PingRequest"),
+            8u16 => Some("PingResponse"),
+            9u16 => Some("DeviceInfoRequest"),
+            10u16 => Some("DeviceInfoResponse"),
+            11u16 => Some("ListEntitiesRequest"),
+            19u16 => Some("ListEntitiesDoneResponse"),
+            20u16 => Some("SubscribeStatesRequest"),
+            12u16 => Some("ListEntitiesBinarySensorResponse"),
+            21u16 => Some("BinarySensorStateResponse"),
+            13u16 => Some("ListEntitiesCoverResponse"),
+            22u16 => Some("CoverStateResponse"),
+            30u16 => Some("CoverCommandRequest"),
+            14u16 => Some("ListEntitiesFanResponse"),
+            23u16 => Some("FanStateResponse"),
+            31u16 => Some("FanCommandRequest"),
+            15u16 => Some("ListEntitiesLightResponse"),
+            24u16 => Some("LightStateResponse"),
+            32u16 => Some("LightCommandRequest"),
+            16u16 => Some("ListEntitiesSensorResponse"),
+            25u16 => Some("SensorStateResponse"),
+            17u16 => Some("ListEntitiesSwitchResponse"),
+            26u16 => Some("SwitchStateResponse"),
+            33u16 => Some("SwitchCommandRequest"),
+            18u16 => Some("ListEntitiesTextSensorResponse"),
+            27u16 => Some("TextSensorStateResponse"),
+            28u16 => Some("SubscribeLogsRequest"),
+            29u16 => Some("SubscribeLogsResponse"),
+            124u16 => Some("NoiseEncryptionSetKeyRequest"),
+            125u16 => Some("NoiseEncryptionSetKeyResponse"),
+            34u16 => Some("SubscribeHomeassistantServicesRequest"),
+            35u16 => Some("HomeassistantServiceResponse"),
+            38u16 => Some("SubscribeHomeAssistantStatesRequest"),
+            39u16 => Some("SubscribeHomeAssistantStateResponse"),
+            40u16 => Some("HomeAssistantStateResponse"),
+            36u16 => Some("GetTimeRequest"),
+            37u16 => Some("GetTimeResponse"),
+            41u16 => Some("ListEntitiesServicesResponse"),
+            42u16 => Some("ExecuteServiceRequest"),
+            43u16 => Some("ListEntitiesCameraResponse"),
+            44u16 => Some("CameraImageResponse"),
+            45u16 => Some("CameraImageRequest"),
+            46u16 => Some("ListEntitiesClimateResponse"),
+            47u16 => Some("ClimateStateResponse"),
+            48u16 => Some("ClimateCommandRequest"),
+            49u16 => Some("ListEntitiesNumberResponse"),
+            50u16 => Some("NumberStateResponse"),
+            51u16 => Some("NumberCommandRequest"),
+            52u16 => Some("ListEntitiesSelectResponse"),
+            53u16 => Some("SelectStateResponse"),
+            54u16 => Some("SelectCommandRequest"),
+            55u16 => Some("ListEntitiesSirenResponse"),
+            56u16 => Some("SirenStateResponse"),
+            57u16 => Some("SirenCommandRequest"),
+            58u16 => Some("ListEntitiesLockResponse"),
+            59u16 => Some("LockStateResponse"),
+            60u16 => Some("LockCommandRequest"),
+            61u16 => Some("ListEntitiesButtonResponse"),
+            62u16 => Some("ButtonCommandRequest"),
+            63u16 => Some("ListEntitiesMediaPlayerResponse"),
+            64u16 => Some("MediaPlayerStateResponse"),
+            65u16 => Some("MediaPlayerCommandRequest"),
+            66u16 => Some("SubscribeBluetoothLeAdvertisementsRequest"),
+            67u16 => Some("BluetoothLeAdvertisementResponse"),
+            93u16 => Some("BluetoothLeRawAdvertisementsResponse"),
+            68u16 => Some("BluetoothDeviceRequest"),
+            69u16 => Some("BluetoothDeviceConnectionResponse"),
+            70u16 => Some("BluetoothGattGetServicesRequest"),
+            71u16 => Some("BluetoothGattGetServicesResponse"),
+            72u16 => Some("BluetoothGattGetServicesDoneResponse"),
+            73u16 => Some("BluetoothGattReadRequest"),
+            74u16 => Some("BluetoothGattReadResponse"),
+            75u16 => Some("BluetoothGattWriteRequest"),
+            76u16 => Some("BluetoothGattReadDescriptorRequest"),
+            77u16 => Some("BluetoothGattWriteDescriptorRequest"),
+            78u16 => Some("BluetoothGattNotifyRequest"),
+            79u16 => Some("BluetoothGattNotifyDataResponse"),
+            80u16 => Some("SubscribeBluetoothConnectionsFreeRequest"),
+            81u16 => Some("BluetoothConnectionsFreeResponse"),
+            82u16 => Some("BluetoothGattErrorResponse"),
+            83u16 => Some("BluetoothGattWriteResponse"),
+            84u16 => Some("BluetoothGattNotifyResponse"),
+            85u16 => Some("BluetoothDevicePairingResponse"),
+            86u16 => Some("BluetoothDeviceUnpairingResponse"),
+            87u16 => Some("UnsubscribeBluetoothLeAdvertisementsRequest"),
+            88u16 => Some("BluetoothDeviceClearCacheResponse"),
+            126u16 => Some("BluetoothScannerStateResponse"),
+            127u16 => Some("BluetoothScannerSetModeRequest"),
+            89u16 => Some("SubscribeVoiceAssistantRequest"),
+            90u16 => Some("VoiceAssistantRequest"),
+            91u16 => Some("VoiceAssistantResponse"),
+            92u16 => Some("VoiceAssistantEventResponse"),
+            106u16 => Some("VoiceAssistantAudio"),
+            115u16 => Some("VoiceAssistantTimerEventResponse"),
+            119u16 => Some("VoiceAssistantAnnounceRequest"),
+            120u16 => Some("VoiceAssistantAnnounceFinished"),
+            121u16 => Some("VoiceAssistantConfigurationRequest"),
+            122u16 => Some("VoiceAssistantConfigurationResponse"),
+            123u16 => Some("VoiceAssistantSetConfiguration"),
+            94u16 => Some("ListEntitiesAlarmControlPanelResponse"),
+            95u16 => Some("AlarmControlPanelStateResponse"),
+            96u16 => Some("AlarmControlPanelCommandRequest"),
+            97u16 => Some("ListEntitiesTextResponse"),
+            98u16 => Some("TextStateResponse"),
+            99u16 => Some("TextCommandRequest"),
+            100u16 => Some("ListEntitiesDateResponse"),
+            101u16 => Some("DateStateResponse"),
+            102u16 => Some("DateCommandRequest"),
+            103u16 => Some("ListEntitiesTimeResponse"),
+            104u16 => Some("TimeStateResponse"),
+            105u16 => Some("TimeCommandRequest"),
+            107u16 => Some("ListEntitiesEventResponse"),
+            108u16 => Some("EventResponse"),
+            109u16 => Some("ListEntitiesValveResponse"),
+            110u16 => Some("ValveStateResponse"),
+            111u16 => Some("ValveCommandRequest"),
+            112u16 => Some("ListEntitiesDateTimeResponse"),
+            113u16 => Some("DateTimeStateResponse"),
+            114u16 => Some("DateTimeCommandRequest"),
+            116u16 => Some("ListEntitiesUpdateResponse"),
+            117u16 => Some("UpdateStateResponse"),
+            118u16 => Some("UpdateCommandRequest"),
+            _ => None,
+        }
+    }
+    /// Decodes the protobuf payload of a frame with the given message type id, borrowing the payload.
+    ///
+    /// Payloads of unknown message types are returned as [`Self::Unknown`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if the payload can't be decoded as the message type.
+    #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
+    pub fn decode_frame(type_id: u16, payload: &[u8]) -> Result<Self, String> {
+        use prost::Message as _;
         match type_id {
             1u16 => HelloRequest::decode(payload).map(EspHomeMessage::HelloRequest),
             2u16 => HelloResponse::decode(payload).map(EspHomeMessage::HelloResponse),
@@ -3950,17 +4635,980 @@ impl TryFrom<Vec<u8>> for EspHomeMessage {
                 ListEntitiesUpdateResponse::decode(payload)
                     .map(EspHomeMessage::ListEntitiesUpdateResponse)
             }
-            117u16 => {
-                UpdateStateResponse::decode(payload)
-                    .map(EspHomeMessage::UpdateStateResponse)
+            117u16 => {
+                UpdateStateResponse::decode(payload)
+                    .map(EspHomeMessage::UpdateStateResponse)
+            }
+            118u16 => {
+                UpdateCommandRequest::decode(payload)
+                    .map(EspHomeMessage::UpdateCommandRequest)
+            }
+            _ => {
+                Ok(Self::Unknown {
+                    type_id,
+                    payload: payload.to_vec(),
+                })
+            }
+        }
+            .map_err(|e| format!("Failed to decode message: {e}"))
+    }
+    /// Converts the message to a dynamic message, giving access to its fields by name.
+    ///
+    /// Returns `None` for unknown messages, as their descriptor isn't available.
+    #[cfg(feature = "reflect")]
+    #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
+    pub fn to_dynamic(&self) -> Option<prost_reflect::DynamicMessage> {
+        use prost::Message as _;
+        let (name, payload) = match self {
+            Self::HelloRequest(msg) => ("HelloRequest", msg.encode_to_vec()),
+            Self::HelloResponse(msg) => ("HelloResponse", msg.encode_to_vec()),
+            Self::ConnectRequest(msg) => ("ConnectRequest", msg.encode_to_vec()),
+            Self::ConnectResponse(msg) => ("ConnectResponse", msg.encode_to_vec()),
+            Self::DisconnectRequest(msg) => ("DisconnectRequest", msg.encode_to_vec()),
+            Self::DisconnectResponse(msg) => ("DisconnectResponse", msg.encode_to_vec()),
+            Self::PingRequest(msg) => ("PingRequest", msg.encode_to_vec()),
+            Self::PingResponse(msg) => ("PingResponse", msg.encode_to_vec()),
+            Self::DeviceInfoRequest(msg) => ("DeviceInfoRequest", msg.encode_to_vec()),
+            Self::DeviceInfoResponse(msg) => ("DeviceInfoResponse", msg.encode_to_vec()),
+            Self::ListEntitiesRequest(msg) => {
+                ("ListEntitiesRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesDoneResponse(msg) => {
+                ("ListEntitiesDoneResponse", msg.encode_to_vec())
+            }
+            Self::SubscribeStatesRequest(msg) => {
+                ("SubscribeStatesRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesBinarySensorResponse(msg) => {
+                ("ListEntitiesBinarySensorResponse", msg.encode_to_vec())
+            }
+            Self::BinarySensorStateResponse(msg) => {
+                ("BinarySensorStateResponse", msg.encode_to_vec())
+            }
+            Self::ListEntitiesCoverResponse(msg) => {
+                ("ListEntitiesCoverResponse", msg.encode_to_vec())
+            }
+            Self::CoverStateResponse(msg) => ("CoverStateResponse", msg.encode_to_vec()),
+            Self::CoverCommandRequest(msg) => {
+                ("CoverCommandRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesFanResponse(msg) => {
+                ("ListEntitiesFanResponse", msg.encode_to_vec())
+            }
+            Self::FanStateResponse(msg) => ("FanStateResponse", msg.encode_to_vec()),
+            Self::FanCommandRequest(msg) => ("FanCommandRequest", msg.encode_to_vec()),
+            Self::ListEntitiesLightResponse(msg) => {
+                ("ListEntitiesLightResponse", msg.encode_to_vec())
+            }
+            Self::LightStateResponse(msg) => ("LightStateResponse", msg.encode_to_vec()),
+            Self::LightCommandRequest(msg) => {
+                ("LightCommandRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesSensorResponse(msg) => {
+                ("ListEntitiesSensorResponse", msg.encode_to_vec())
+            }
+            Self::SensorStateResponse(msg) => {
+                ("SensorStateResponse", msg.encode_to_vec())
+            }
+            Self::ListEntitiesSwitchResponse(msg) => {
+                ("ListEntitiesSwitchResponse", msg.encode_to_vec())
+            }
+            Self::SwitchStateResponse(msg) => {
+                ("SwitchStateResponse", msg.encode_to_vec())
+            }
+            Self::SwitchCommandRequest(msg) => {
+                ("SwitchCommandRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesTextSensorResponse(msg) => {
+                ("ListEntitiesTextSensorResponse", msg.encode_to_vec())
+            }
+            Self::TextSensorStateResponse(msg) => {
+                ("TextSensorStateResponse", msg.encode_to_vec())
+            }
+            Self::SubscribeLogsRequest(msg) => {
+                ("SubscribeLogsRequest", msg.encode_to_vec())
+            }
+            Self::SubscribeLogsResponse(msg) => {
+                ("SubscribeLogsResponse", msg.encode_to_vec())
+            }
+            Self::NoiseEncryptionSetKeyRequest(msg) => {
+                ("NoiseEncryptionSetKeyRequest", msg.encode_to_vec())
+            }
+            Self::NoiseEncryptionSetKeyResponse(msg) => {
+                ("NoiseEncryptionSetKeyResponse", msg.encode_to_vec())
+            }
+            Self::SubscribeHomeassistantServicesRequest(msg) => {
+                ("SubscribeHomeassistantServicesRequest", msg.encode_to_vec())
+            }
+            Self::HomeassistantServiceResponse(msg) => {
+                ("HomeassistantServiceResponse", msg.encode_to_vec())
+            }
+            Self::SubscribeHomeAssistantStatesRequest(msg) => {
+                ("SubscribeHomeAssistantStatesRequest", msg.encode_to_vec())
+            }
+            Self::SubscribeHomeAssistantStateResponse(msg) => {
+                ("SubscribeHomeAssistantStateResponse", msg.encode_to_vec())
+            }
+            Self::HomeAssistantStateResponse(msg) => {
+                ("HomeAssistantStateResponse", msg.encode_to_vec())
+            }
+            Self::GetTimeRequest(msg) => ("GetTimeRequest", msg.encode_to_vec()),
+            Self::GetTimeResponse(msg) => ("GetTimeResponse", msg.encode_to_vec()),
+            Self::ListEntitiesServicesResponse(msg) => {
+                ("ListEntitiesServicesResponse", msg.encode_to_vec())
+            }
+            Self::ExecuteServiceRequest(msg) => {
+                ("ExecuteServiceRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesCameraResponse(msg) => {
+                ("ListEntitiesCameraResponse", msg.encode_to_vec())
+            }
+            Self::CameraImageResponse(msg) => {
+                ("CameraImageResponse", msg.encode_to_vec())
+            }
+            Self::CameraImageRequest(msg) => ("CameraImageRequest", msg.encode_to_vec()),
+            Self::ListEntitiesClimateResponse(msg) => {
+                ("ListEntitiesClimateResponse", msg.encode_to_vec())
+            }
+            Self::ClimateStateResponse(msg) => {
+                ("ClimateStateResponse", msg.encode_to_vec())
+            }
+            Self::ClimateCommandRequest(msg) => {
+                ("ClimateCommandRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesNumberResponse(msg) => {
+                ("ListEntitiesNumberResponse", msg.encode_to_vec())
+            }
+            Self::NumberStateResponse(msg) => {
+                ("NumberStateResponse", msg.encode_to_vec())
+            }
+            Self::NumberCommandRequest(msg) => {
+                ("NumberCommandRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesSelectResponse(msg) => {
+                ("ListEntitiesSelectResponse", msg.encode_to_vec())
+            }
+            Self::SelectStateResponse(msg) => {
+                ("SelectStateResponse", msg.encode_to_vec())
+            }
+            Self::SelectCommandRequest(msg) => {
+                ("SelectCommandRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesSirenResponse(msg) => {
+                ("ListEntitiesSirenResponse", msg.encode_to_vec())
+            }
+            Self::SirenStateResponse(msg) => ("SirenStateResponse", msg.encode_to_vec()),
+            Self::SirenCommandRequest(msg) => {
+                ("SirenCommandRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesLockResponse(msg) => {
+                ("ListEntitiesLockResponse", msg.encode_to_vec())
+            }
+            Self::LockStateResponse(msg) => ("LockStateResponse", msg.encode_to_vec()),
+            Self::LockCommandRequest(msg) => ("LockCommandRequest", msg.encode_to_vec()),
+            Self::ListEntitiesButtonResponse(msg) => {
+                ("ListEntitiesButtonResponse", msg.encode_to_vec())
+            }
+            Self::ButtonCommandRequest(msg) => {
+                ("ButtonCommandRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesMediaPlayerResponse(msg) => {
+                ("ListEntitiesMediaPlayerResponse", msg.encode_to_vec())
+            }
+            Self::MediaPlayerStateResponse(msg) => {
+                ("MediaPlayerStateResponse", msg.encode_to_vec())
+            }
+            Self::MediaPlayerCommandRequest(msg) => {
+                ("MediaPlayerCommandRequest", msg.encode_to_vec())
+            }
+            Self::SubscribeBluetoothLeAdvertisementsRequest(msg) => {
+                ("SubscribeBluetoothLEAdvertisementsRequest", msg.encode_to_vec())
+            }
+            Self::BluetoothLeAdvertisementResponse(msg) => {
+                ("BluetoothLEAdvertisementResponse", msg.encode_to_vec())
+            }
+            Self::BluetoothLeRawAdvertisementsResponse(msg) => {
+                ("BluetoothLERawAdvertisementsResponse", msg.encode_to_vec())
+            }
+            Self::BluetoothDeviceRequest(msg) => {
+                ("BluetoothDeviceRequest", msg.encode_to_vec())
+            }
+            Self::BluetoothDeviceConnectionResponse(msg) => {
+                ("BluetoothDeviceConnectionResponse", msg.encode_to_vec())
+            }
+            Self::BluetoothGattGetServicesRequest(msg) => {
+                ("BluetoothGATTGetServicesRequest", msg.encode_to_vec())
+            }
+            Self::BluetoothGattGetServicesResponse(msg) => {
+                ("BluetoothGATTGetServicesResponse", msg.encode_to_vec())
+            }
+            Self::BluetoothGattGetServicesDoneResponse(msg) => {
+                ("BluetoothGATTGetServicesDoneResponse", msg.encode_to_vec())
+            }
+            Self::BluetoothGattReadRequest(msg) => {
+                ("BluetoothGATTReadRequest", msg.encode_to_vec())
+            }
+            Self::BluetoothGattReadResponse(msg) => {
+                ("BluetoothGATTReadResponse", msg.encode_to_vec())
+            }
+            Self::BluetoothGattWriteRequest(msg) => {
+                ("BluetoothGATTWriteRequest", msg.encode_to_vec())
+            }
+            Self::BluetoothGattReadDescriptorRequest(msg) => {
+                ("BluetoothGATTReadDescriptorRequest", msg.encode_to_vec())
+            }
+            Self::BluetoothGattWriteDescriptorRequest(msg) => {
+                ("BluetoothGATTWriteDescriptorRequest", msg.encode_to_vec())
+            }
+            Self::BluetoothGattNotifyRequest(msg) => {
+                ("BluetoothGATTNotifyRequest", msg.encode_to_vec())
+            }
+            Self::BluetoothGattNotifyDataResponse(msg) => {
+                ("BluetoothGATTNotifyDataResponse", msg.encode_to_vec())
+            }
+            Self::SubscribeBluetoothConnectionsFreeRequest(msg) => {
+                ("SubscribeBluetoothConnectionsFreeRequest", msg.encode_to_vec())
+            }
+            Self::BluetoothConnectionsFreeResponse(msg) => {
+                ("BluetoothConnectionsFreeResponse", msg.encode_to_vec())
+            }
+            Self::BluetoothGattErrorResponse(msg) => {
+                ("BluetoothGATTErrorResponse", msg.encode_to_vec())
+            }
+            Self::BluetoothGattWriteResponse(msg) => {
+                ("BluetoothGATTWriteResponse", msg.encode_to_vec())
+            }
+            Self::BluetoothGattNotifyResponse(msg) => {
+                ("BluetoothGATTNotifyResponse", msg.encode_to_vec())
+            }
+            Self::BluetoothDevicePairingResponse(msg) => {
+                ("BluetoothDevicePairingResponse", msg.encode_to_vec())
+            }
+            Self::BluetoothDeviceUnpairingResponse(msg) => {
+                ("BluetoothDeviceUnpairingResponse", msg.encode_to_vec())
+            }
+            Self::UnsubscribeBluetoothLeAdvertisementsRequest(msg) => {
+                ("UnsubscribeBluetoothLEAdvertisementsRequest", msg.encode_to_vec())
+            }
+            Self::BluetoothDeviceClearCacheResponse(msg) => {
+                ("BluetoothDeviceClearCacheResponse", msg.encode_to_vec())
+            }
+            Self::BluetoothScannerStateResponse(msg) => {
+                ("BluetoothScannerStateResponse", msg.encode_to_vec())
+            }
+            Self::BluetoothScannerSetModeRequest(msg) => {
+                ("BluetoothScannerSetModeRequest", msg.encode_to_vec())
+            }
+            Self::SubscribeVoiceAssistantRequest(msg) => {
+                ("SubscribeVoiceAssistantRequest", msg.encode_to_vec())
+            }
+            Self::VoiceAssistantRequest(msg) => {
+                ("VoiceAssistantRequest", msg.encode_to_vec())
+            }
+            Self::VoiceAssistantResponse(msg) => {
+                ("VoiceAssistantResponse", msg.encode_to_vec())
+            }
+            Self::VoiceAssistantEventResponse(msg) => {
+                ("VoiceAssistantEventResponse", msg.encode_to_vec())
+            }
+            Self::VoiceAssistantAudio(msg) => {
+                ("VoiceAssistantAudio", msg.encode_to_vec())
+            }
+            Self::VoiceAssistantTimerEventResponse(msg) => {
+                ("VoiceAssistantTimerEventResponse", msg.encode_to_vec())
+            }
+            Self::VoiceAssistantAnnounceRequest(msg) => {
+                ("VoiceAssistantAnnounceRequest", msg.encode_to_vec())
+            }
+            Self::VoiceAssistantAnnounceFinished(msg) => {
+                ("VoiceAssistantAnnounceFinished", msg.encode_to_vec())
+            }
+            Self::VoiceAssistantConfigurationRequest(msg) => {
+                ("VoiceAssistantConfigurationRequest", msg.encode_to_vec())
+            }
+            Self::VoiceAssistantConfigurationResponse(msg) => {
+                ("VoiceAssistantConfigurationResponse", msg.encode_to_vec())
+            }
+            Self::VoiceAssistantSetConfiguration(msg) => {
+                ("VoiceAssistantSetConfiguration", msg.encode_to_vec())
+            }
+            Self::ListEntitiesAlarmControlPanelResponse(msg) => {
+                ("ListEntitiesAlarmControlPanelResponse", msg.encode_to_vec())
+            }
+            Self::AlarmControlPanelStateResponse(msg) => {
+                ("AlarmControlPanelStateResponse", msg.encode_to_vec())
+            }
+            Self::AlarmControlPanelCommandRequest(msg) => {
+                ("AlarmControlPanelCommandRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesTextResponse(msg) => {
+                ("ListEntitiesTextResponse", msg.encode_to_vec())
+            }
+            Self::TextStateResponse(msg) => ("TextStateResponse", msg.encode_to_vec()),
+            Self::TextCommandRequest(msg) => ("TextCommandRequest", msg.encode_to_vec()),
+            Self::ListEntitiesDateResponse(msg) => {
+                ("ListEntitiesDateResponse", msg.encode_to_vec())
+            }
+            Self::DateStateResponse(msg) => ("DateStateResponse", msg.encode_to_vec()),
+            Self::DateCommandRequest(msg) => ("DateCommandRequest", msg.encode_to_vec()),
+            Self::ListEntitiesTimeResponse(msg) => {
+                ("ListEntitiesTimeResponse", msg.encode_to_vec())
+            }
+            Self::TimeStateResponse(msg) => ("TimeStateResponse", msg.encode_to_vec()),
+            Self::TimeCommandRequest(msg) => ("TimeCommandRequest", msg.encode_to_vec()),
+            Self::ListEntitiesEventResponse(msg) => {
+                ("ListEntitiesEventResponse", msg.encode_to_vec())
+            }
+            Self::EventResponse(msg) => ("EventResponse", msg.encode_to_vec()),
+            Self::ListEntitiesValveResponse(msg) => {
+                ("ListEntitiesValveResponse", msg.encode_to_vec())
+            }
+            Self::ValveStateResponse(msg) => ("ValveStateResponse", msg.encode_to_vec()),
+            Self::ValveCommandRequest(msg) => {
+                ("ValveCommandRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesDateTimeResponse(msg) => {
+                ("ListEntitiesDateTimeResponse", msg.encode_to_vec())
+            }
+            Self::DateTimeStateResponse(msg) => {
+                ("DateTimeStateResponse", msg.encode_to_vec())
+            }
+            Self::DateTimeCommandRequest(msg) => {
+                ("DateTimeCommandRequest", msg.encode_to_vec())
+            }
+            Self::ListEntitiesUpdateResponse(msg) => {
+                ("ListEntitiesUpdateResponse", msg.encode_to_vec())
+            }
+            Self::UpdateStateResponse(msg) => {
+                ("UpdateStateResponse", msg.encode_to_vec())
+            }
+            Self::UpdateCommandRequest(msg) => {
+                ("UpdateCommandRequest", msg.encode_to_vec())
+            }
+            Self::Unknown { .. } => return None,
+        };
+        let descriptor = descriptor_pool().get_message_by_name(name)?;
+        prost_reflect::DynamicMessage::decode(descriptor, payload.as_slice()).ok()
+    }
+}
+/// Concise representation of the message, with the message name and the key fields
+/// identifying the entity or device. Use [`Debug`] for the full message.
+impl std::fmt::Display for EspHomeMessage {
+    #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HelloRequest(_) => f.write_str("HelloRequest"),
+            Self::HelloResponse(_) => f.write_str("HelloResponse"),
+            Self::ConnectRequest(_) => f.write_str("ConnectRequest"),
+            Self::ConnectResponse(_) => f.write_str("ConnectResponse"),
+            Self::DisconnectRequest(_) => f.write_str("DisconnectRequest"),
+            Self::DisconnectResponse(_) => f.write_str("DisconnectResponse"),
+            Self::PingRequest(_) => f.write_str("PingRequest"),
+            Self::PingResponse(_) => f.write_str("PingResponse"),
+            Self::DeviceInfoRequest(_) => f.write_str("DeviceInfoRequest"),
+            Self::DeviceInfoResponse(_) => f.write_str("DeviceInfoResponse"),
+            Self::ListEntitiesRequest(_) => f.write_str("ListEntitiesRequest"),
+            Self::ListEntitiesDoneResponse(_) => f.write_str("ListEntitiesDoneResponse"),
+            Self::SubscribeStatesRequest(_) => f.write_str("SubscribeStatesRequest"),
+            Self::ListEntitiesBinarySensorResponse(msg) => {
+                write!(
+                    f, "ListEntitiesBinarySensorResponse(key={}, object_id=\"{}\")", msg
+                    .key, msg.object_id
+                )
+            }
+            Self::BinarySensorStateResponse(msg) => {
+                write!(f, "BinarySensorStateResponse(key={})", msg.key)
+            }
+            Self::ListEntitiesCoverResponse(msg) => {
+                write!(
+                    f, "ListEntitiesCoverResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::CoverStateResponse(msg) => {
+                write!(f, "CoverStateResponse(key={})", msg.key)
+            }
+            Self::CoverCommandRequest(msg) => {
+                write!(f, "CoverCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesFanResponse(msg) => {
+                write!(
+                    f, "ListEntitiesFanResponse(key={}, object_id=\"{}\")", msg.key, msg
+                    .object_id
+                )
+            }
+            Self::FanStateResponse(msg) => write!(f, "FanStateResponse(key={})", msg.key),
+            Self::FanCommandRequest(msg) => {
+                write!(f, "FanCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesLightResponse(msg) => {
+                write!(
+                    f, "ListEntitiesLightResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::LightStateResponse(msg) => {
+                write!(f, "LightStateResponse(key={})", msg.key)
+            }
+            Self::LightCommandRequest(msg) => {
+                write!(f, "LightCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesSensorResponse(msg) => {
+                write!(
+                    f, "ListEntitiesSensorResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::SensorStateResponse(msg) => {
+                write!(f, "SensorStateResponse(key={})", msg.key)
+            }
+            Self::ListEntitiesSwitchResponse(msg) => {
+                write!(
+                    f, "ListEntitiesSwitchResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::SwitchStateResponse(msg) => {
+                write!(f, "SwitchStateResponse(key={})", msg.key)
+            }
+            Self::SwitchCommandRequest(msg) => {
+                write!(f, "SwitchCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesTextSensorResponse(msg) => {
+                write!(
+                    f, "ListEntitiesTextSensorResponse(key={}, object_id=\"{}\")", msg
+                    .key, msg.object_id
+                )
+            }
+            Self::TextSensorStateResponse(msg) => {
+                write!(f, "TextSensorStateResponse(key={})", msg.key)
+            }
+            Self::SubscribeLogsRequest(_) => f.write_str("SubscribeLogsRequest"),
+            Self::SubscribeLogsResponse(_) => f.write_str("SubscribeLogsResponse"),
+            Self::NoiseEncryptionSetKeyRequest(_) => {
+                f.write_str("NoiseEncryptionSetKeyRequest")
+            }
+            Self::NoiseEncryptionSetKeyResponse(_) => {
+                f.write_str("NoiseEncryptionSetKeyResponse")
+            }
+            Self::SubscribeHomeassistantServicesRequest(_) => {
+                f.write_str("SubscribeHomeassistantServicesRequest")
+            }
+            Self::HomeassistantServiceResponse(_) => {
+                f.write_str("HomeassistantServiceResponse")
+            }
+            Self::SubscribeHomeAssistantStatesRequest(_) => {
+                f.write_str("SubscribeHomeAssistantStatesRequest")
+            }
+            Self::SubscribeHomeAssistantStateResponse(_) => {
+                f.write_str("SubscribeHomeAssistantStateResponse")
+            }
+            Self::HomeAssistantStateResponse(_) => {
+                f.write_str("HomeAssistantStateResponse")
+            }
+            Self::GetTimeRequest(_) => f.write_str("GetTimeRequest"),
+            Self::GetTimeResponse(_) => f.write_str("GetTimeResponse"),
+            Self::ListEntitiesServicesResponse(msg) => {
+                write!(f, "ListEntitiesServicesResponse(key={})", msg.key)
+            }
+            Self::ExecuteServiceRequest(msg) => {
+                write!(f, "ExecuteServiceRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesCameraResponse(msg) => {
+                write!(
+                    f, "ListEntitiesCameraResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::CameraImageResponse(msg) => {
+                write!(f, "CameraImageResponse(key={})", msg.key)
+            }
+            Self::CameraImageRequest(_) => f.write_str("CameraImageRequest"),
+            Self::ListEntitiesClimateResponse(msg) => {
+                write!(
+                    f, "ListEntitiesClimateResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::ClimateStateResponse(msg) => {
+                write!(f, "ClimateStateResponse(key={})", msg.key)
+            }
+            Self::ClimateCommandRequest(msg) => {
+                write!(f, "ClimateCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesNumberResponse(msg) => {
+                write!(
+                    f, "ListEntitiesNumberResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::NumberStateResponse(msg) => {
+                write!(f, "NumberStateResponse(key={})", msg.key)
+            }
+            Self::NumberCommandRequest(msg) => {
+                write!(f, "NumberCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesSelectResponse(msg) => {
+                write!(
+                    f, "ListEntitiesSelectResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::SelectStateResponse(msg) => {
+                write!(f, "SelectStateResponse(key={})", msg.key)
+            }
+            Self::SelectCommandRequest(msg) => {
+                write!(f, "SelectCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesSirenResponse(msg) => {
+                write!(
+                    f, "ListEntitiesSirenResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::SirenStateResponse(msg) => {
+                write!(f, "SirenStateResponse(key={})", msg.key)
+            }
+            Self::SirenCommandRequest(msg) => {
+                write!(f, "SirenCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesLockResponse(msg) => {
+                write!(
+                    f, "ListEntitiesLockResponse(key={}, object_id=\"{}\")", msg.key, msg
+                    .object_id
+                )
+            }
+            Self::LockStateResponse(msg) => {
+                write!(f, "LockStateResponse(key={})", msg.key)
+            }
+            Self::LockCommandRequest(msg) => {
+                write!(f, "LockCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesButtonResponse(msg) => {
+                write!(
+                    f, "ListEntitiesButtonResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::ButtonCommandRequest(msg) => {
+                write!(f, "ButtonCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesMediaPlayerResponse(msg) => {
+                write!(
+                    f, "ListEntitiesMediaPlayerResponse(key={}, object_id=\"{}\")", msg
+                    .key, msg.object_id
+                )
+            }
+            Self::MediaPlayerStateResponse(msg) => {
+                write!(f, "MediaPlayerStateResponse(key={})", msg.key)
+            }
+            Self::MediaPlayerCommandRequest(msg) => {
+                write!(f, "MediaPlayerCommandRequest(key={})", msg.key)
+            }
+            Self::SubscribeBluetoothLeAdvertisementsRequest(_) => {
+                f.write_str("SubscribeBluetoothLeAdvertisementsRequest")
+            }
+            Self::BluetoothLeAdvertisementResponse(msg) => {
+                write!(
+                    f, "BluetoothLeAdvertisementResponse(address={:012X})", msg.address
+                )
+            }
+            Self::BluetoothLeRawAdvertisementsResponse(_) => {
+                f.write_str("BluetoothLeRawAdvertisementsResponse")
+            }
+            Self::BluetoothDeviceRequest(msg) => {
+                write!(f, "BluetoothDeviceRequest(address={:012X})", msg.address)
+            }
+            Self::BluetoothDeviceConnectionResponse(msg) => {
+                write!(
+                    f, "BluetoothDeviceConnectionResponse(address={:012X})", msg.address
+                )
+            }
+            Self::BluetoothGattGetServicesRequest(msg) => {
+                write!(
+                    f, "BluetoothGattGetServicesRequest(address={:012X})", msg.address
+                )
+            }
+            Self::BluetoothGattGetServicesResponse(msg) => {
+                write!(
+                    f, "BluetoothGattGetServicesResponse(address={:012X})", msg.address
+                )
+            }
+            Self::BluetoothGattGetServicesDoneResponse(msg) => {
+                write!(
+                    f, "BluetoothGattGetServicesDoneResponse(address={:012X})", msg
+                    .address
+                )
+            }
+            Self::BluetoothGattReadRequest(msg) => {
+                write!(f, "BluetoothGattReadRequest(address={:012X})", msg.address)
+            }
+            Self::BluetoothGattReadResponse(msg) => {
+                write!(f, "BluetoothGattReadResponse(address={:012X})", msg.address)
+            }
+            Self::BluetoothGattWriteRequest(msg) => {
+                write!(f, "BluetoothGattWriteRequest(address={:012X})", msg.address)
+            }
+            Self::BluetoothGattReadDescriptorRequest(msg) => {
+                write!(
+                    f, "BluetoothGattReadDescriptorRequest(address={:012X})", msg.address
+                )
+            }
+            Self::BluetoothGattWriteDescriptorRequest(msg) => {
+                write!(
+                    f, "BluetoothGattWriteDescriptorRequest(address={:012X})", msg
+                    .address
+                )
+            }
+            Self::BluetoothGattNotifyRequest(msg) => {
+                write!(f, "BluetoothGattNotifyRequest(address={:012X})", msg.address)
+            }
+            Self::BluetoothGattNotifyDataResponse(msg) => {
+                write!(
+                    f, "BluetoothGattNotifyDataResponse(address={:012X})", msg.address
+                )
+            }
+            Self::SubscribeBluetoothConnectionsFreeRequest(_) => {
+                f.write_str("SubscribeBluetoothConnectionsFreeRequest")
+            }
+            Self::BluetoothConnectionsFreeResponse(_) => {
+                f.write_str("BluetoothConnectionsFreeResponse")
+            }
+            Self::BluetoothGattErrorResponse(msg) => {
+                write!(f, "BluetoothGattErrorResponse(address={:012X})", msg.address)
+            }
+            Self::BluetoothGattWriteResponse(msg) => {
+                write!(f, "BluetoothGattWriteResponse(address={:012X})", msg.address)
+            }
+            Self::BluetoothGattNotifyResponse(msg) => {
+                write!(f, "BluetoothGattNotifyResponse(address={:012X})", msg.address)
+            }
+            Self::BluetoothDevicePairingResponse(msg) => {
+                write!(f, "BluetoothDevicePairingResponse(address={:012X})", msg.address)
+            }
+            Self::BluetoothDeviceUnpairingResponse(msg) => {
+                write!(
+                    f, "BluetoothDeviceUnpairingResponse(address={:012X})", msg.address
+                )
+            }
+            Self::UnsubscribeBluetoothLeAdvertisementsRequest(_) => {
+                f.write_str("UnsubscribeBluetoothLeAdvertisementsRequest")
+            }
+            Self::BluetoothDeviceClearCacheResponse(msg) => {
+                write!(
+                    f, "BluetoothDeviceClearCacheResponse(address={:012X})", msg.address
+                )
+            }
+            Self::BluetoothScannerStateResponse(_) => {
+                f.write_str("BluetoothScannerStateResponse")
+            }
+            Self::BluetoothScannerSetModeRequest(_) => {
+                f.write_str("BluetoothScannerSetModeRequest")
+            }
+            Self::SubscribeVoiceAssistantRequest(_) => {
+                f.write_str("SubscribeVoiceAssistantRequest")
+            }
+            Self::VoiceAssistantRequest(_) => f.write_str("VoiceAssistantRequest"),
+            Self::VoiceAssistantResponse(_) => f.write_str("VoiceAssistantResponse"),
+            Self::VoiceAssistantEventResponse(_) => {
+                f.write_str("VoiceAssistantEventResponse")
+            }
+            Self::VoiceAssistantAudio(_) => f.write_str("VoiceAssistantAudio"),
+            Self::VoiceAssistantTimerEventResponse(_) => {
+                f.write_str("VoiceAssistantTimerEventResponse")
+            }
+            Self::VoiceAssistantAnnounceRequest(_) => {
+                f.write_str("VoiceAssistantAnnounceRequest")
+            }
+            Self::VoiceAssistantAnnounceFinished(_) => {
+                f.write_str("VoiceAssistantAnnounceFinished")
+            }
+            Self::VoiceAssistantConfigurationRequest(_) => {
+                f.write_str("VoiceAssistantConfigurationRequest")
+            }
+            Self::VoiceAssistantConfigurationResponse(_) => {
+                f.write_str("VoiceAssistantConfigurationResponse")
+            }
+            Self::VoiceAssistantSetConfiguration(_) => {
+                f.write_str("VoiceAssistantSetConfiguration")
+            }
+            Self::ListEntitiesAlarmControlPanelResponse(msg) => {
+                write!(
+                    f, "ListEntitiesAlarmControlPanelResponse(key={}, object_id=\"{}\")",
+                    msg.key, msg.object_id
+                )
+            }
+            Self::AlarmControlPanelStateResponse(msg) => {
+                write!(f, "AlarmControlPanelStateResponse(key={})", msg.key)
+            }
+            Self::AlarmControlPanelCommandRequest(msg) => {
+                write!(f, "AlarmControlPanelCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesTextResponse(msg) => {
+                write!(
+                    f, "ListEntitiesTextResponse(key={}, object_id=\"{}\")", msg.key, msg
+                    .object_id
+                )
+            }
+            Self::TextStateResponse(msg) => {
+                write!(f, "TextStateResponse(key={})", msg.key)
+            }
+            Self::TextCommandRequest(msg) => {
+                write!(f, "TextCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesDateResponse(msg) => {
+                write!(
+                    f, "ListEntitiesDateResponse(key={}, object_id=\"{}\")", msg.key, msg
+                    .object_id
+                )
+            }
+            Self::DateStateResponse(msg) => {
+                write!(f, "DateStateResponse(key={})", msg.key)
+            }
+            Self::DateCommandRequest(msg) => {
+                write!(f, "DateCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesTimeResponse(msg) => {
+                write!(
+                    f, "ListEntitiesTimeResponse(key={}, object_id=\"{}\")", msg.key, msg
+                    .object_id
+                )
+            }
+            Self::TimeStateResponse(msg) => {
+                write!(f, "TimeStateResponse(key={})", msg.key)
+            }
+            Self::TimeCommandRequest(msg) => {
+                write!(f, "TimeCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesEventResponse(msg) => {
+                write!(
+                    f, "ListEntitiesEventResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::EventResponse(msg) => write!(f, "EventResponse(key={})", msg.key),
+            Self::ListEntitiesValveResponse(msg) => {
+                write!(
+                    f, "ListEntitiesValveResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::ValveStateResponse(msg) => {
+                write!(f, "ValveStateResponse(key={})", msg.key)
+            }
+            Self::ValveCommandRequest(msg) => {
+                write!(f, "ValveCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesDateTimeResponse(msg) => {
+                write!(
+                    f, "ListEntitiesDateTimeResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::DateTimeStateResponse(msg) => {
+                write!(f, "DateTimeStateResponse(key={})", msg.key)
+            }
+            Self::DateTimeCommandRequest(msg) => {
+                write!(f, "DateTimeCommandRequest(key={})", msg.key)
+            }
+            Self::ListEntitiesUpdateResponse(msg) => {
+                write!(
+                    f, "ListEntitiesUpdateResponse(key={}, object_id=\"{}\")", msg.key,
+                    msg.object_id
+                )
+            }
+            Self::UpdateStateResponse(msg) => {
+                write!(f, "UpdateStateResponse(key={})", msg.key)
+            }
+            Self::UpdateCommandRequest(msg) => {
+                write!(f, "UpdateCommandRequest(key={})", msg.key)
+            }
+            Self::Unknown { type_id, payload } => {
+                write!(f, "Unknown(type_id={type_id}, {} bytes)", payload.len())
+            }
+        }
+    }
+}
+/// Gets the descriptors of all messages of this API version.
+///
+/// # Panics
+///
+/// Will panic if the descriptor set shipped with the crate can't be decoded.
+#[cfg(feature = "reflect")]
+pub fn descriptor_pool() -> &'static prost_reflect::DescriptorPool {
+    static POOL: std::sync::OnceLock<prost_reflect::DescriptorPool> = std::sync::OnceLock::new();
+    POOL.get_or_init(|| {
+        prost_reflect::DescriptorPool::decode(
+                include_bytes!(
+                    concat!(env!("CARGO_MANIFEST_DIR"),
+                    "/src/proto/api_1_10/descriptor.bin")
+                )
+                    .as_slice(),
+            )
+            .expect("Descriptor set should be valid")
+    })
+}
+impl From<EspHomeMessage> for Vec<u8> {
+    #[allow(clippy::too_many_lines, reason = "Generated code for all messages")]
+    fn from(val: EspHomeMessage) -> Self {
+        use prost::Message as _;
+        let type_id = val.get_message_type();
+        let payload = match val {
+            EspHomeMessage::HelloRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::HelloResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::ConnectRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ConnectResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::DisconnectRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::DisconnectResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::PingRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::PingResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::DeviceInfoRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::DeviceInfoResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesDoneResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::SubscribeStatesRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesBinarySensorResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BinarySensorStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesCoverResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::CoverStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::CoverCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesFanResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::FanStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::FanCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesLightResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::LightStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::LightCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesSensorResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::SensorStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesSwitchResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::SwitchStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::SwitchCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesTextSensorResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::TextSensorStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::SubscribeLogsRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::SubscribeLogsResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::NoiseEncryptionSetKeyRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::NoiseEncryptionSetKeyResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::SubscribeHomeassistantServicesRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::HomeassistantServiceResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::SubscribeHomeAssistantStatesRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::SubscribeHomeAssistantStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::HomeAssistantStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::GetTimeRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::GetTimeResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesServicesResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::ExecuteServiceRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesCameraResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::CameraImageResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::CameraImageRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesClimateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::ClimateStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::ClimateCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesNumberResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::NumberStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::NumberCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesSelectResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::SelectStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::SelectCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesSirenResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::SirenStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::SirenCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesLockResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::LockStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::LockCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesButtonResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::ButtonCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesMediaPlayerResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::MediaPlayerStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::MediaPlayerCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::SubscribeBluetoothLeAdvertisementsRequest(d) => {
+                d.encode_to_vec()
+            }
+            EspHomeMessage::BluetoothLeAdvertisementResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothLeRawAdvertisementsResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothDeviceRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothDeviceConnectionResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothGattGetServicesRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothGattGetServicesResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothGattGetServicesDoneResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothGattReadRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothGattReadResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothGattWriteRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothGattReadDescriptorRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothGattWriteDescriptorRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothGattNotifyRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothGattNotifyDataResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::SubscribeBluetoothConnectionsFreeRequest(d) => {
+                d.encode_to_vec()
             }
-            118u16 => {
-                UpdateCommandRequest::decode(payload)
-                    .map(EspHomeMessage::UpdateCommandRequest)
+            EspHomeMessage::BluetoothConnectionsFreeResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothGattErrorResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothGattWriteResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothGattNotifyResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothDevicePairingResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothDeviceUnpairingResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::UnsubscribeBluetoothLeAdvertisementsRequest(d) => {
+                d.encode_to_vec()
             }
-            _ => return Err(format!("Unknown message type: {type_id}")),
+            EspHomeMessage::BluetoothDeviceClearCacheResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothScannerStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::BluetoothScannerSetModeRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::SubscribeVoiceAssistantRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::VoiceAssistantRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::VoiceAssistantResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::VoiceAssistantEventResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::VoiceAssistantAudio(d) => d.encode_to_vec(),
+            EspHomeMessage::VoiceAssistantTimerEventResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::VoiceAssistantAnnounceRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::VoiceAssistantAnnounceFinished(d) => d.encode_to_vec(),
+            EspHomeMessage::VoiceAssistantConfigurationRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::VoiceAssistantConfigurationResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::VoiceAssistantSetConfiguration(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesAlarmControlPanelResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::AlarmControlPanelStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::AlarmControlPanelCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesTextResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::TextStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::TextCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesDateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::DateStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::DateCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesTimeResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::TimeStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::TimeCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesEventResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::EventResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesValveResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::ValveStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::ValveCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesDateTimeResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::DateTimeStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::DateTimeCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::ListEntitiesUpdateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::UpdateStateResponse(d) => d.encode_to_vec(),
+            EspHomeMessage::UpdateCommandRequest(d) => d.encode_to_vec(),
+            EspHomeMessage::Unknown { payload, .. } => payload,
+        };
+        let payload_len = u16::try_from(payload.len())
+            .expect("Payload length exceeds u16::MAX");
+        [type_id.to_be_bytes().to_vec(), payload_len.to_be_bytes().to_vec(), payload]
+            .concat()
+    }
+}
+impl TryFrom<Vec<u8>> for EspHomeMessage {
+    type Error = String;
+    fn try_from(msg: Vec<u8>) -> Result<Self, Self::Error> {
+        Self::try_from(msg.as_slice())
+    }
+}
+impl TryFrom<&[u8]> for EspHomeMessage {
+    type Error = String;
+    fn try_from(msg: &[u8]) -> Result<Self, Self::Error> {
+        if msg.len() < 4 {
+            return Err("Message too short".to_owned());
         }
-            .map_err(|e| format!("Failed to decode message: {e}"))
+        let type_id = u16::from_be_bytes([msg[0], msg[1]]);
+        Self::decode_frame(type_id, &msg[4..])
     }
 }
 impl From<HelloRequest> for EspHomeMessage {
@@ -3968,89 +5616,431 @@ impl From<HelloRequest> for EspHomeMessage {
         Self::HelloRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for HelloRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::HelloRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for HelloRequest {
+    const TYPE_ID: u16 = 1u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::HelloRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<HelloResponse> for EspHomeMessage {
     fn from(msg: HelloResponse) -> Self {
         Self::HelloResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for HelloResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::HelloResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for HelloResponse {
+    const TYPE_ID: u16 = 2u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::HelloResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ConnectRequest> for EspHomeMessage {
     fn from(msg: ConnectRequest) -> Self {
         Self::ConnectRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ConnectRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ConnectRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ConnectRequest {
+    const TYPE_ID: u16 = 3u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ConnectRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ConnectResponse> for EspHomeMessage {
     fn from(msg: ConnectResponse) -> Self {
         Self::ConnectResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ConnectResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ConnectResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ConnectResponse {
+    const TYPE_ID: u16 = 4u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ConnectResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<DisconnectRequest> for EspHomeMessage {
     fn from(msg: DisconnectRequest) -> Self {
         Self::DisconnectRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for DisconnectRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::DisconnectRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for DisconnectRequest {
+    const TYPE_ID: u16 = 5u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::DisconnectRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<DisconnectResponse> for EspHomeMessage {
     fn from(msg: DisconnectResponse) -> Self {
         Self::DisconnectResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for DisconnectResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::DisconnectResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for DisconnectResponse {
+    const TYPE_ID: u16 = 6u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::DisconnectResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<PingRequest> for EspHomeMessage {
     fn from(msg: PingRequest) -> Self {
         Self::PingRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for PingRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::PingRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for PingRequest {
+    const TYPE_ID: u16 = 7u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::PingRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<PingResponse> for EspHomeMessage {
     fn from(msg: PingResponse) -> Self {
         Self::PingResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for PingResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::PingResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for PingResponse {
+    const TYPE_ID: u16 = 8u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::PingResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<DeviceInfoRequest> for EspHomeMessage {
     fn from(msg: DeviceInfoRequest) -> Self {
         Self::DeviceInfoRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for DeviceInfoRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::DeviceInfoRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for DeviceInfoRequest {
+    const TYPE_ID: u16 = 9u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::DeviceInfoRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<DeviceInfoResponse> for EspHomeMessage {
     fn from(msg: DeviceInfoResponse) -> Self {
         Self::DeviceInfoResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for DeviceInfoResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::DeviceInfoResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for DeviceInfoResponse {
+    const TYPE_ID: u16 = 10u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::DeviceInfoResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesRequest> for EspHomeMessage {
     fn from(msg: ListEntitiesRequest) -> Self {
         Self::ListEntitiesRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesRequest {
+    const TYPE_ID: u16 = 11u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesDoneResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesDoneResponse) -> Self {
         Self::ListEntitiesDoneResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesDoneResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesDoneResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesDoneResponse {
+    const TYPE_ID: u16 = 19u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesDoneResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SubscribeStatesRequest> for EspHomeMessage {
     fn from(msg: SubscribeStatesRequest) -> Self {
         Self::SubscribeStatesRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SubscribeStatesRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SubscribeStatesRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SubscribeStatesRequest {
+    const TYPE_ID: u16 = 20u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SubscribeStatesRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesBinarySensorResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesBinarySensorResponse) -> Self {
         Self::ListEntitiesBinarySensorResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesBinarySensorResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesBinarySensorResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesBinarySensorResponse {
+    const TYPE_ID: u16 = 12u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesBinarySensorResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BinarySensorStateResponse> for EspHomeMessage {
     fn from(msg: BinarySensorStateResponse) -> Self {
         Self::BinarySensorStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BinarySensorStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BinarySensorStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BinarySensorStateResponse {
+    const TYPE_ID: u16 = 21u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BinarySensorStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesCoverResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesCoverResponse) -> Self {
         Self::ListEntitiesCoverResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesCoverResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesCoverResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesCoverResponse {
+    const TYPE_ID: u16 = 13u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesCoverResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<CoverStateResponse> for EspHomeMessage {
     fn from(msg: CoverStateResponse) -> Self {
         Self::CoverStateResponse(msg)
     }
 }
-impl From<CoverCommandRequest> for EspHomeMessage {
-    fn from(msg: CoverCommandRequest) -> Self {
-        Self::CoverCommandRequest(msg)
+impl TryFrom<EspHomeMessage> for CoverStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::CoverStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for CoverStateResponse {
+    const TYPE_ID: u16 = 22u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::CoverStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
+impl From<CoverCommandRequest> for EspHomeMessage {
+    fn from(msg: CoverCommandRequest) -> Self {
+        Self::CoverCommandRequest(msg)
+    }
+}
+impl TryFrom<EspHomeMessage> for CoverCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::CoverCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for CoverCommandRequest {
+    const TYPE_ID: u16 = 30u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::CoverCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
     }
 }
 impl From<ListEntitiesFanResponse> for EspHomeMessage {
@@ -4058,543 +6048,2614 @@ impl From<ListEntitiesFanResponse> for EspHomeMessage {
         Self::ListEntitiesFanResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesFanResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesFanResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesFanResponse {
+    const TYPE_ID: u16 = 14u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesFanResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<FanStateResponse> for EspHomeMessage {
     fn from(msg: FanStateResponse) -> Self {
         Self::FanStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for FanStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::FanStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for FanStateResponse {
+    const TYPE_ID: u16 = 23u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::FanStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<FanCommandRequest> for EspHomeMessage {
     fn from(msg: FanCommandRequest) -> Self {
         Self::FanCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for FanCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::FanCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for FanCommandRequest {
+    const TYPE_ID: u16 = 31u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::FanCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesLightResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesLightResponse) -> Self {
         Self::ListEntitiesLightResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesLightResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesLightResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesLightResponse {
+    const TYPE_ID: u16 = 15u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesLightResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<LightStateResponse> for EspHomeMessage {
     fn from(msg: LightStateResponse) -> Self {
         Self::LightStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for LightStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::LightStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for LightStateResponse {
+    const TYPE_ID: u16 = 24u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::LightStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<LightCommandRequest> for EspHomeMessage {
     fn from(msg: LightCommandRequest) -> Self {
         Self::LightCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for LightCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::LightCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for LightCommandRequest {
+    const TYPE_ID: u16 = 32u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::LightCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesSensorResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesSensorResponse) -> Self {
         Self::ListEntitiesSensorResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesSensorResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesSensorResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesSensorResponse {
+    const TYPE_ID: u16 = 16u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesSensorResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SensorStateResponse> for EspHomeMessage {
     fn from(msg: SensorStateResponse) -> Self {
         Self::SensorStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SensorStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SensorStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SensorStateResponse {
+    const TYPE_ID: u16 = 25u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SensorStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesSwitchResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesSwitchResponse) -> Self {
         Self::ListEntitiesSwitchResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesSwitchResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesSwitchResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesSwitchResponse {
+    const TYPE_ID: u16 = 17u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesSwitchResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SwitchStateResponse> for EspHomeMessage {
     fn from(msg: SwitchStateResponse) -> Self {
         Self::SwitchStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SwitchStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SwitchStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SwitchStateResponse {
+    const TYPE_ID: u16 = 26u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SwitchStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SwitchCommandRequest> for EspHomeMessage {
     fn from(msg: SwitchCommandRequest) -> Self {
         Self::SwitchCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SwitchCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SwitchCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SwitchCommandRequest {
+    const TYPE_ID: u16 = 33u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SwitchCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesTextSensorResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesTextSensorResponse) -> Self {
         Self::ListEntitiesTextSensorResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesTextSensorResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesTextSensorResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesTextSensorResponse {
+    const TYPE_ID: u16 = 18u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesTextSensorResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<TextSensorStateResponse> for EspHomeMessage {
     fn from(msg: TextSensorStateResponse) -> Self {
         Self::TextSensorStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for TextSensorStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::TextSensorStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for TextSensorStateResponse {
+    const TYPE_ID: u16 = 27u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::TextSensorStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SubscribeLogsRequest> for EspHomeMessage {
     fn from(msg: SubscribeLogsRequest) -> Self {
         Self::SubscribeLogsRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SubscribeLogsRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SubscribeLogsRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SubscribeLogsRequest {
+    const TYPE_ID: u16 = 28u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SubscribeLogsRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SubscribeLogsResponse> for EspHomeMessage {
     fn from(msg: SubscribeLogsResponse) -> Self {
         Self::SubscribeLogsResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SubscribeLogsResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SubscribeLogsResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SubscribeLogsResponse {
+    const TYPE_ID: u16 = 29u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SubscribeLogsResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<NoiseEncryptionSetKeyRequest> for EspHomeMessage {
     fn from(msg: NoiseEncryptionSetKeyRequest) -> Self {
         Self::NoiseEncryptionSetKeyRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for NoiseEncryptionSetKeyRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::NoiseEncryptionSetKeyRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for NoiseEncryptionSetKeyRequest {
+    const TYPE_ID: u16 = 124u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::NoiseEncryptionSetKeyRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<NoiseEncryptionSetKeyResponse> for EspHomeMessage {
     fn from(msg: NoiseEncryptionSetKeyResponse) -> Self {
         Self::NoiseEncryptionSetKeyResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for NoiseEncryptionSetKeyResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::NoiseEncryptionSetKeyResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for NoiseEncryptionSetKeyResponse {
+    const TYPE_ID: u16 = 125u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::NoiseEncryptionSetKeyResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SubscribeHomeassistantServicesRequest> for EspHomeMessage {
     fn from(msg: SubscribeHomeassistantServicesRequest) -> Self {
         Self::SubscribeHomeassistantServicesRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SubscribeHomeassistantServicesRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SubscribeHomeassistantServicesRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SubscribeHomeassistantServicesRequest {
+    const TYPE_ID: u16 = 34u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SubscribeHomeassistantServicesRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<HomeassistantServiceResponse> for EspHomeMessage {
     fn from(msg: HomeassistantServiceResponse) -> Self {
         Self::HomeassistantServiceResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for HomeassistantServiceResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::HomeassistantServiceResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for HomeassistantServiceResponse {
+    const TYPE_ID: u16 = 35u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::HomeassistantServiceResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SubscribeHomeAssistantStatesRequest> for EspHomeMessage {
     fn from(msg: SubscribeHomeAssistantStatesRequest) -> Self {
         Self::SubscribeHomeAssistantStatesRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SubscribeHomeAssistantStatesRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SubscribeHomeAssistantStatesRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SubscribeHomeAssistantStatesRequest {
+    const TYPE_ID: u16 = 38u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SubscribeHomeAssistantStatesRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SubscribeHomeAssistantStateResponse> for EspHomeMessage {
     fn from(msg: SubscribeHomeAssistantStateResponse) -> Self {
         Self::SubscribeHomeAssistantStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SubscribeHomeAssistantStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SubscribeHomeAssistantStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SubscribeHomeAssistantStateResponse {
+    const TYPE_ID: u16 = 39u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SubscribeHomeAssistantStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<HomeAssistantStateResponse> for EspHomeMessage {
     fn from(msg: HomeAssistantStateResponse) -> Self {
         Self::HomeAssistantStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for HomeAssistantStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::HomeAssistantStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for HomeAssistantStateResponse {
+    const TYPE_ID: u16 = 40u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::HomeAssistantStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<GetTimeRequest> for EspHomeMessage {
     fn from(msg: GetTimeRequest) -> Self {
         Self::GetTimeRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for GetTimeRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::GetTimeRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for GetTimeRequest {
+    const TYPE_ID: u16 = 36u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::GetTimeRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<GetTimeResponse> for EspHomeMessage {
     fn from(msg: GetTimeResponse) -> Self {
         Self::GetTimeResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for GetTimeResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::GetTimeResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for GetTimeResponse {
+    const TYPE_ID: u16 = 37u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::GetTimeResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesServicesResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesServicesResponse) -> Self {
         Self::ListEntitiesServicesResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesServicesResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesServicesResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesServicesResponse {
+    const TYPE_ID: u16 = 41u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesServicesResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ExecuteServiceRequest> for EspHomeMessage {
     fn from(msg: ExecuteServiceRequest) -> Self {
         Self::ExecuteServiceRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ExecuteServiceRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ExecuteServiceRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ExecuteServiceRequest {
+    const TYPE_ID: u16 = 42u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ExecuteServiceRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesCameraResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesCameraResponse) -> Self {
         Self::ListEntitiesCameraResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesCameraResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesCameraResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesCameraResponse {
+    const TYPE_ID: u16 = 43u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesCameraResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<CameraImageResponse> for EspHomeMessage {
     fn from(msg: CameraImageResponse) -> Self {
         Self::CameraImageResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for CameraImageResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::CameraImageResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for CameraImageResponse {
+    const TYPE_ID: u16 = 44u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::CameraImageResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<CameraImageRequest> for EspHomeMessage {
     fn from(msg: CameraImageRequest) -> Self {
         Self::CameraImageRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for CameraImageRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::CameraImageRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for CameraImageRequest {
+    const TYPE_ID: u16 = 45u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::CameraImageRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesClimateResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesClimateResponse) -> Self {
         Self::ListEntitiesClimateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesClimateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesClimateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesClimateResponse {
+    const TYPE_ID: u16 = 46u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesClimateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ClimateStateResponse> for EspHomeMessage {
     fn from(msg: ClimateStateResponse) -> Self {
         Self::ClimateStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ClimateStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ClimateStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ClimateStateResponse {
+    const TYPE_ID: u16 = 47u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ClimateStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ClimateCommandRequest> for EspHomeMessage {
     fn from(msg: ClimateCommandRequest) -> Self {
         Self::ClimateCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ClimateCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ClimateCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ClimateCommandRequest {
+    const TYPE_ID: u16 = 48u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ClimateCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesNumberResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesNumberResponse) -> Self {
         Self::ListEntitiesNumberResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesNumberResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesNumberResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesNumberResponse {
+    const TYPE_ID: u16 = 49u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesNumberResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<NumberStateResponse> for EspHomeMessage {
     fn from(msg: NumberStateResponse) -> Self {
         Self::NumberStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for NumberStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::NumberStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for NumberStateResponse {
+    const TYPE_ID: u16 = 50u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::NumberStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<NumberCommandRequest> for EspHomeMessage {
     fn from(msg: NumberCommandRequest) -> Self {
         Self::NumberCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for NumberCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::NumberCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for NumberCommandRequest {
+    const TYPE_ID: u16 = 51u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::NumberCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesSelectResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesSelectResponse) -> Self {
         Self::ListEntitiesSelectResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesSelectResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesSelectResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesSelectResponse {
+    const TYPE_ID: u16 = 52u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesSelectResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SelectStateResponse> for EspHomeMessage {
     fn from(msg: SelectStateResponse) -> Self {
         Self::SelectStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SelectStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SelectStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SelectStateResponse {
+    const TYPE_ID: u16 = 53u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SelectStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SelectCommandRequest> for EspHomeMessage {
     fn from(msg: SelectCommandRequest) -> Self {
         Self::SelectCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SelectCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SelectCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SelectCommandRequest {
+    const TYPE_ID: u16 = 54u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SelectCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesSirenResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesSirenResponse) -> Self {
         Self::ListEntitiesSirenResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesSirenResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesSirenResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesSirenResponse {
+    const TYPE_ID: u16 = 55u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesSirenResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SirenStateResponse> for EspHomeMessage {
     fn from(msg: SirenStateResponse) -> Self {
         Self::SirenStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SirenStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SirenStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SirenStateResponse {
+    const TYPE_ID: u16 = 56u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SirenStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SirenCommandRequest> for EspHomeMessage {
     fn from(msg: SirenCommandRequest) -> Self {
         Self::SirenCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SirenCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SirenCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SirenCommandRequest {
+    const TYPE_ID: u16 = 57u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SirenCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesLockResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesLockResponse) -> Self {
         Self::ListEntitiesLockResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesLockResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesLockResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesLockResponse {
+    const TYPE_ID: u16 = 58u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesLockResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<LockStateResponse> for EspHomeMessage {
     fn from(msg: LockStateResponse) -> Self {
         Self::LockStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for LockStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::LockStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for LockStateResponse {
+    const TYPE_ID: u16 = 59u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::LockStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<LockCommandRequest> for EspHomeMessage {
     fn from(msg: LockCommandRequest) -> Self {
         Self::LockCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for LockCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::LockCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for LockCommandRequest {
+    const TYPE_ID: u16 = 60u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::LockCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesButtonResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesButtonResponse) -> Self {
         Self::ListEntitiesButtonResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesButtonResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesButtonResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesButtonResponse {
+    const TYPE_ID: u16 = 61u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesButtonResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ButtonCommandRequest> for EspHomeMessage {
     fn from(msg: ButtonCommandRequest) -> Self {
         Self::ButtonCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ButtonCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ButtonCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ButtonCommandRequest {
+    const TYPE_ID: u16 = 62u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ButtonCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesMediaPlayerResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesMediaPlayerResponse) -> Self {
         Self::ListEntitiesMediaPlayerResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesMediaPlayerResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesMediaPlayerResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesMediaPlayerResponse {
+    const TYPE_ID: u16 = 63u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesMediaPlayerResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<MediaPlayerStateResponse> for EspHomeMessage {
     fn from(msg: MediaPlayerStateResponse) -> Self {
         Self::MediaPlayerStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for MediaPlayerStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::MediaPlayerStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for MediaPlayerStateResponse {
+    const TYPE_ID: u16 = 64u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::MediaPlayerStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<MediaPlayerCommandRequest> for EspHomeMessage {
     fn from(msg: MediaPlayerCommandRequest) -> Self {
         Self::MediaPlayerCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for MediaPlayerCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::MediaPlayerCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for MediaPlayerCommandRequest {
+    const TYPE_ID: u16 = 65u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::MediaPlayerCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SubscribeBluetoothLeAdvertisementsRequest> for EspHomeMessage {
     fn from(msg: SubscribeBluetoothLeAdvertisementsRequest) -> Self {
         Self::SubscribeBluetoothLeAdvertisementsRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SubscribeBluetoothLeAdvertisementsRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SubscribeBluetoothLeAdvertisementsRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SubscribeBluetoothLeAdvertisementsRequest {
+    const TYPE_ID: u16 = 66u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SubscribeBluetoothLeAdvertisementsRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothLeAdvertisementResponse> for EspHomeMessage {
     fn from(msg: BluetoothLeAdvertisementResponse) -> Self {
         Self::BluetoothLeAdvertisementResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothLeAdvertisementResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothLeAdvertisementResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothLeAdvertisementResponse {
+    const TYPE_ID: u16 = 67u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothLeAdvertisementResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothLeRawAdvertisementsResponse> for EspHomeMessage {
     fn from(msg: BluetoothLeRawAdvertisementsResponse) -> Self {
         Self::BluetoothLeRawAdvertisementsResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothLeRawAdvertisementsResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothLeRawAdvertisementsResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothLeRawAdvertisementsResponse {
+    const TYPE_ID: u16 = 93u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothLeRawAdvertisementsResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothDeviceRequest> for EspHomeMessage {
     fn from(msg: BluetoothDeviceRequest) -> Self {
         Self::BluetoothDeviceRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothDeviceRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothDeviceRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothDeviceRequest {
+    const TYPE_ID: u16 = 68u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothDeviceRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothDeviceConnectionResponse> for EspHomeMessage {
     fn from(msg: BluetoothDeviceConnectionResponse) -> Self {
         Self::BluetoothDeviceConnectionResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothDeviceConnectionResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothDeviceConnectionResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothDeviceConnectionResponse {
+    const TYPE_ID: u16 = 69u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothDeviceConnectionResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothGattGetServicesRequest> for EspHomeMessage {
     fn from(msg: BluetoothGattGetServicesRequest) -> Self {
         Self::BluetoothGattGetServicesRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothGattGetServicesRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothGattGetServicesRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothGattGetServicesRequest {
+    const TYPE_ID: u16 = 70u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothGattGetServicesRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothGattGetServicesResponse> for EspHomeMessage {
     fn from(msg: BluetoothGattGetServicesResponse) -> Self {
         Self::BluetoothGattGetServicesResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothGattGetServicesResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothGattGetServicesResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothGattGetServicesResponse {
+    const TYPE_ID: u16 = 71u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothGattGetServicesResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothGattGetServicesDoneResponse> for EspHomeMessage {
     fn from(msg: BluetoothGattGetServicesDoneResponse) -> Self {
         Self::BluetoothGattGetServicesDoneResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothGattGetServicesDoneResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothGattGetServicesDoneResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothGattGetServicesDoneResponse {
+    const TYPE_ID: u16 = 72u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothGattGetServicesDoneResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothGattReadRequest> for EspHomeMessage {
     fn from(msg: BluetoothGattReadRequest) -> Self {
         Self::BluetoothGattReadRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothGattReadRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothGattReadRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothGattReadRequest {
+    const TYPE_ID: u16 = 73u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothGattReadRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothGattReadResponse> for EspHomeMessage {
     fn from(msg: BluetoothGattReadResponse) -> Self {
         Self::BluetoothGattReadResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothGattReadResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothGattReadResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothGattReadResponse {
+    const TYPE_ID: u16 = 74u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothGattReadResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothGattWriteRequest> for EspHomeMessage {
     fn from(msg: BluetoothGattWriteRequest) -> Self {
         Self::BluetoothGattWriteRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothGattWriteRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothGattWriteRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothGattWriteRequest {
+    const TYPE_ID: u16 = 75u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothGattWriteRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothGattReadDescriptorRequest> for EspHomeMessage {
     fn from(msg: BluetoothGattReadDescriptorRequest) -> Self {
         Self::BluetoothGattReadDescriptorRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothGattReadDescriptorRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothGattReadDescriptorRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothGattReadDescriptorRequest {
+    const TYPE_ID: u16 = 76u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothGattReadDescriptorRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothGattWriteDescriptorRequest> for EspHomeMessage {
     fn from(msg: BluetoothGattWriteDescriptorRequest) -> Self {
         Self::BluetoothGattWriteDescriptorRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothGattWriteDescriptorRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothGattWriteDescriptorRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothGattWriteDescriptorRequest {
+    const TYPE_ID: u16 = 77u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothGattWriteDescriptorRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothGattNotifyRequest> for EspHomeMessage {
     fn from(msg: BluetoothGattNotifyRequest) -> Self {
         Self::BluetoothGattNotifyRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothGattNotifyRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothGattNotifyRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothGattNotifyRequest {
+    const TYPE_ID: u16 = 78u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothGattNotifyRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothGattNotifyDataResponse> for EspHomeMessage {
     fn from(msg: BluetoothGattNotifyDataResponse) -> Self {
         Self::BluetoothGattNotifyDataResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothGattNotifyDataResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothGattNotifyDataResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothGattNotifyDataResponse {
+    const TYPE_ID: u16 = 79u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothGattNotifyDataResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SubscribeBluetoothConnectionsFreeRequest> for EspHomeMessage {
     fn from(msg: SubscribeBluetoothConnectionsFreeRequest) -> Self {
         Self::SubscribeBluetoothConnectionsFreeRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SubscribeBluetoothConnectionsFreeRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SubscribeBluetoothConnectionsFreeRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SubscribeBluetoothConnectionsFreeRequest {
+    const TYPE_ID: u16 = 80u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SubscribeBluetoothConnectionsFreeRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothConnectionsFreeResponse> for EspHomeMessage {
     fn from(msg: BluetoothConnectionsFreeResponse) -> Self {
         Self::BluetoothConnectionsFreeResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothConnectionsFreeResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothConnectionsFreeResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothConnectionsFreeResponse {
+    const TYPE_ID: u16 = 81u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothConnectionsFreeResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothGattErrorResponse> for EspHomeMessage {
     fn from(msg: BluetoothGattErrorResponse) -> Self {
         Self::BluetoothGattErrorResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothGattErrorResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothGattErrorResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothGattErrorResponse {
+    const TYPE_ID: u16 = 82u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothGattErrorResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothGattWriteResponse> for EspHomeMessage {
     fn from(msg: BluetoothGattWriteResponse) -> Self {
         Self::BluetoothGattWriteResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothGattWriteResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothGattWriteResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothGattWriteResponse {
+    const TYPE_ID: u16 = 83u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothGattWriteResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothGattNotifyResponse> for EspHomeMessage {
     fn from(msg: BluetoothGattNotifyResponse) -> Self {
         Self::BluetoothGattNotifyResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothGattNotifyResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothGattNotifyResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothGattNotifyResponse {
+    const TYPE_ID: u16 = 84u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothGattNotifyResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothDevicePairingResponse> for EspHomeMessage {
     fn from(msg: BluetoothDevicePairingResponse) -> Self {
         Self::BluetoothDevicePairingResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothDevicePairingResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothDevicePairingResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothDevicePairingResponse {
+    const TYPE_ID: u16 = 85u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothDevicePairingResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothDeviceUnpairingResponse> for EspHomeMessage {
     fn from(msg: BluetoothDeviceUnpairingResponse) -> Self {
         Self::BluetoothDeviceUnpairingResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothDeviceUnpairingResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothDeviceUnpairingResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothDeviceUnpairingResponse {
+    const TYPE_ID: u16 = 86u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothDeviceUnpairingResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<UnsubscribeBluetoothLeAdvertisementsRequest> for EspHomeMessage {
     fn from(msg: UnsubscribeBluetoothLeAdvertisementsRequest) -> Self {
         Self::UnsubscribeBluetoothLeAdvertisementsRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for UnsubscribeBluetoothLeAdvertisementsRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::UnsubscribeBluetoothLeAdvertisementsRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for UnsubscribeBluetoothLeAdvertisementsRequest {
+    const TYPE_ID: u16 = 87u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::UnsubscribeBluetoothLeAdvertisementsRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothDeviceClearCacheResponse> for EspHomeMessage {
     fn from(msg: BluetoothDeviceClearCacheResponse) -> Self {
         Self::BluetoothDeviceClearCacheResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothDeviceClearCacheResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothDeviceClearCacheResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothDeviceClearCacheResponse {
+    const TYPE_ID: u16 = 88u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothDeviceClearCacheResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothScannerStateResponse> for EspHomeMessage {
     fn from(msg: BluetoothScannerStateResponse) -> Self {
         Self::BluetoothScannerStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothScannerStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothScannerStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothScannerStateResponse {
+    const TYPE_ID: u16 = 126u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothScannerStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<BluetoothScannerSetModeRequest> for EspHomeMessage {
     fn from(msg: BluetoothScannerSetModeRequest) -> Self {
         Self::BluetoothScannerSetModeRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for BluetoothScannerSetModeRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::BluetoothScannerSetModeRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for BluetoothScannerSetModeRequest {
+    const TYPE_ID: u16 = 127u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::BluetoothScannerSetModeRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<SubscribeVoiceAssistantRequest> for EspHomeMessage {
     fn from(msg: SubscribeVoiceAssistantRequest) -> Self {
         Self::SubscribeVoiceAssistantRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for SubscribeVoiceAssistantRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::SubscribeVoiceAssistantRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for SubscribeVoiceAssistantRequest {
+    const TYPE_ID: u16 = 89u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::SubscribeVoiceAssistantRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<VoiceAssistantRequest> for EspHomeMessage {
     fn from(msg: VoiceAssistantRequest) -> Self {
         Self::VoiceAssistantRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for VoiceAssistantRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::VoiceAssistantRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for VoiceAssistantRequest {
+    const TYPE_ID: u16 = 90u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::VoiceAssistantRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<VoiceAssistantResponse> for EspHomeMessage {
     fn from(msg: VoiceAssistantResponse) -> Self {
         Self::VoiceAssistantResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for VoiceAssistantResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::VoiceAssistantResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for VoiceAssistantResponse {
+    const TYPE_ID: u16 = 91u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::VoiceAssistantResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<VoiceAssistantEventResponse> for EspHomeMessage {
     fn from(msg: VoiceAssistantEventResponse) -> Self {
         Self::VoiceAssistantEventResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for VoiceAssistantEventResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::VoiceAssistantEventResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for VoiceAssistantEventResponse {
+    const TYPE_ID: u16 = 92u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 0u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::VoiceAssistantEventResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<VoiceAssistantAudio> for EspHomeMessage {
     fn from(msg: VoiceAssistantAudio) -> Self {
         Self::VoiceAssistantAudio(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for VoiceAssistantAudio {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::VoiceAssistantAudio(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for VoiceAssistantAudio {
+    const TYPE_ID: u16 = 106u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::VoiceAssistantAudio(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<VoiceAssistantTimerEventResponse> for EspHomeMessage {
     fn from(msg: VoiceAssistantTimerEventResponse) -> Self {
         Self::VoiceAssistantTimerEventResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for VoiceAssistantTimerEventResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::VoiceAssistantTimerEventResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for VoiceAssistantTimerEventResponse {
+    const TYPE_ID: u16 = 115u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::VoiceAssistantTimerEventResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<VoiceAssistantAnnounceRequest> for EspHomeMessage {
     fn from(msg: VoiceAssistantAnnounceRequest) -> Self {
         Self::VoiceAssistantAnnounceRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for VoiceAssistantAnnounceRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::VoiceAssistantAnnounceRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for VoiceAssistantAnnounceRequest {
+    const TYPE_ID: u16 = 119u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::VoiceAssistantAnnounceRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<VoiceAssistantAnnounceFinished> for EspHomeMessage {
     fn from(msg: VoiceAssistantAnnounceFinished) -> Self {
         Self::VoiceAssistantAnnounceFinished(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for VoiceAssistantAnnounceFinished {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::VoiceAssistantAnnounceFinished(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for VoiceAssistantAnnounceFinished {
+    const TYPE_ID: u16 = 120u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::VoiceAssistantAnnounceFinished(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<VoiceAssistantConfigurationRequest> for EspHomeMessage {
     fn from(msg: VoiceAssistantConfigurationRequest) -> Self {
         Self::VoiceAssistantConfigurationRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for VoiceAssistantConfigurationRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::VoiceAssistantConfigurationRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for VoiceAssistantConfigurationRequest {
+    const TYPE_ID: u16 = 121u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::VoiceAssistantConfigurationRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<VoiceAssistantConfigurationResponse> for EspHomeMessage {
     fn from(msg: VoiceAssistantConfigurationResponse) -> Self {
         Self::VoiceAssistantConfigurationResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for VoiceAssistantConfigurationResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::VoiceAssistantConfigurationResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for VoiceAssistantConfigurationResponse {
+    const TYPE_ID: u16 = 122u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::VoiceAssistantConfigurationResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<VoiceAssistantSetConfiguration> for EspHomeMessage {
     fn from(msg: VoiceAssistantSetConfiguration) -> Self {
         Self::VoiceAssistantSetConfiguration(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for VoiceAssistantSetConfiguration {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::VoiceAssistantSetConfiguration(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for VoiceAssistantSetConfiguration {
+    const TYPE_ID: u16 = 123u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::VoiceAssistantSetConfiguration(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesAlarmControlPanelResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesAlarmControlPanelResponse) -> Self {
         Self::ListEntitiesAlarmControlPanelResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesAlarmControlPanelResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesAlarmControlPanelResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesAlarmControlPanelResponse {
+    const TYPE_ID: u16 = 94u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesAlarmControlPanelResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<AlarmControlPanelStateResponse> for EspHomeMessage {
     fn from(msg: AlarmControlPanelStateResponse) -> Self {
         Self::AlarmControlPanelStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for AlarmControlPanelStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::AlarmControlPanelStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for AlarmControlPanelStateResponse {
+    const TYPE_ID: u16 = 95u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::AlarmControlPanelStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<AlarmControlPanelCommandRequest> for EspHomeMessage {
     fn from(msg: AlarmControlPanelCommandRequest) -> Self {
         Self::AlarmControlPanelCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for AlarmControlPanelCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::AlarmControlPanelCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for AlarmControlPanelCommandRequest {
+    const TYPE_ID: u16 = 96u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::AlarmControlPanelCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesTextResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesTextResponse) -> Self {
         Self::ListEntitiesTextResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesTextResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesTextResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesTextResponse {
+    const TYPE_ID: u16 = 97u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesTextResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<TextStateResponse> for EspHomeMessage {
     fn from(msg: TextStateResponse) -> Self {
         Self::TextStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for TextStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::TextStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for TextStateResponse {
+    const TYPE_ID: u16 = 98u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::TextStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<TextCommandRequest> for EspHomeMessage {
     fn from(msg: TextCommandRequest) -> Self {
         Self::TextCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for TextCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::TextCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for TextCommandRequest {
+    const TYPE_ID: u16 = 99u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::TextCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesDateResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesDateResponse) -> Self {
         Self::ListEntitiesDateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesDateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesDateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesDateResponse {
+    const TYPE_ID: u16 = 100u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesDateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<DateStateResponse> for EspHomeMessage {
     fn from(msg: DateStateResponse) -> Self {
         Self::DateStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for DateStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::DateStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for DateStateResponse {
+    const TYPE_ID: u16 = 101u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::DateStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<DateCommandRequest> for EspHomeMessage {
     fn from(msg: DateCommandRequest) -> Self {
         Self::DateCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for DateCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::DateCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for DateCommandRequest {
+    const TYPE_ID: u16 = 102u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::DateCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesTimeResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesTimeResponse) -> Self {
         Self::ListEntitiesTimeResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesTimeResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesTimeResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesTimeResponse {
+    const TYPE_ID: u16 = 103u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesTimeResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<TimeStateResponse> for EspHomeMessage {
     fn from(msg: TimeStateResponse) -> Self {
         Self::TimeStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for TimeStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::TimeStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for TimeStateResponse {
+    const TYPE_ID: u16 = 104u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::TimeStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<TimeCommandRequest> for EspHomeMessage {
     fn from(msg: TimeCommandRequest) -> Self {
         Self::TimeCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for TimeCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::TimeCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for TimeCommandRequest {
+    const TYPE_ID: u16 = 105u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 9u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::TimeCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesEventResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesEventResponse) -> Self {
         Self::ListEntitiesEventResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesEventResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesEventResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesEventResponse {
+    const TYPE_ID: u16 = 107u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesEventResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<EventResponse> for EspHomeMessage {
     fn from(msg: EventResponse) -> Self {
         Self::EventResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for EventResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::EventResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for EventResponse {
+    const TYPE_ID: u16 = 108u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::EventResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesValveResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesValveResponse) -> Self {
         Self::ListEntitiesValveResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesValveResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesValveResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesValveResponse {
+    const TYPE_ID: u16 = 109u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesValveResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ValveStateResponse> for EspHomeMessage {
     fn from(msg: ValveStateResponse) -> Self {
         Self::ValveStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ValveStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ValveStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ValveStateResponse {
+    const TYPE_ID: u16 = 110u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ValveStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ValveCommandRequest> for EspHomeMessage {
     fn from(msg: ValveCommandRequest) -> Self {
         Self::ValveCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ValveCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ValveCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ValveCommandRequest {
+    const TYPE_ID: u16 = 111u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ValveCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesDateTimeResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesDateTimeResponse) -> Self {
         Self::ListEntitiesDateTimeResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesDateTimeResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesDateTimeResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesDateTimeResponse {
+    const TYPE_ID: u16 = 112u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesDateTimeResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<DateTimeStateResponse> for EspHomeMessage {
     fn from(msg: DateTimeStateResponse) -> Self {
         Self::DateTimeStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for DateTimeStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::DateTimeStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for DateTimeStateResponse {
+    const TYPE_ID: u16 = 113u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::DateTimeStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<DateTimeCommandRequest> for EspHomeMessage {
     fn from(msg: DateTimeCommandRequest) -> Self {
         Self::DateTimeCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for DateTimeCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::DateTimeCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for DateTimeCommandRequest {
+    const TYPE_ID: u16 = 114u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::DateTimeCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<ListEntitiesUpdateResponse> for EspHomeMessage {
     fn from(msg: ListEntitiesUpdateResponse) -> Self {
         Self::ListEntitiesUpdateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for ListEntitiesUpdateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::ListEntitiesUpdateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for ListEntitiesUpdateResponse {
+    const TYPE_ID: u16 = 116u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::ListEntitiesUpdateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<UpdateStateResponse> for EspHomeMessage {
     fn from(msg: UpdateStateResponse) -> Self {
         Self::UpdateStateResponse(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for UpdateStateResponse {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::UpdateStateResponse(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for UpdateStateResponse {
+    const TYPE_ID: u16 = 117u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::UpdateStateResponse(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
 impl From<UpdateCommandRequest> for EspHomeMessage {
     fn from(msg: UpdateCommandRequest) -> Self {
         Self::UpdateCommandRequest(msg)
     }
 }
+impl TryFrom<EspHomeMessage> for UpdateCommandRequest {
+    type Error = EspHomeMessage;
+    fn try_from(msg: EspHomeMessage) -> Result<Self, Self::Error> {
+        match msg {
+            EspHomeMessage::UpdateCommandRequest(msg) => Ok(msg),
+            other => Err(other),
+        }
+    }
+}
+impl MessageType for UpdateCommandRequest {
+    const TYPE_ID: u16 = 118u16;
+    const MIN_API_VERSION: (u32, u32) = (1u32, 10u32);
+    fn from_message(msg: &EspHomeMessage) -> Option<&Self> {
+        match msg {
+            EspHomeMessage::UpdateCommandRequest(msg) => Some(msg),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+use crate::{EspHomeClient, error::ClientError, proto::EspHomeMessage};
+
+/// Category of an incoming message, used by the [`Router`] to pick a channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageClass {
+    /// Entity state updates, e.g. `SensorStateResponse` or `EventResponse`.
+    State,
+    /// Log lines, `SubscribeLogsResponse`.
+    Log,
+    /// Bluetooth LE advertisements forwarded by a bluetooth proxy.
+    BluetoothAdvertisement,
+    /// Voice assistant requests, audio and events.
+    VoiceAssistant,
+    /// Home Assistant service (action) calls requested by the device.
+    HomeAssistantService,
+    /// Any message not covered by the other classes.
+    Other,
+}
+
+impl MessageClass {
+    /// Determines the class of the given message.
+    #[must_use]
+    pub const fn of(message: &EspHomeMessage) -> Self {
+        match message {
+            EspHomeMessage::BinarySensorStateResponse(_)
+            | EspHomeMessage::ClimateStateResponse(_)
+            | EspHomeMessage::CoverStateResponse(_)
+            | EspHomeMessage::FanStateResponse(_)
+            | EspHomeMessage::LightStateResponse(_)
+            | EspHomeMessage::LockStateResponse(_)
+            | EspHomeMessage::MediaPlayerStateResponse(_)
+            | EspHomeMessage::NumberStateResponse(_)
+            | EspHomeMessage::SelectStateResponse(_)
+            | EspHomeMessage::SensorStateResponse(_)
+            | EspHomeMessage::SwitchStateResponse(_)
+            | EspHomeMessage::TextSensorStateResponse(_) => Self::State,
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::AlarmControlPanelStateResponse(_)
+            | EspHomeMessage::DateStateResponse(_)
+            | EspHomeMessage::TextStateResponse(_)
+            | EspHomeMessage::TimeStateResponse(_) => Self::State,
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::DateTimeStateResponse(_)
+            | EspHomeMessage::EventResponse(_)
+            | EspHomeMessage::SirenStateResponse(_)
+            | EspHomeMessage::UpdateStateResponse(_)
+            | EspHomeMessage::ValveStateResponse(_) => Self::State,
+            #[cfg(not(any(
+                feature = "api-1-13",
+                feature = "api-1-12",
+                feature = "api-1-10",
+                feature = "api-1-9",
+                feature = "api-1-8"
+            )))]
+            EspHomeMessage::WaterHeaterStateResponse(_) => Self::State,
+
+            EspHomeMessage::SubscribeLogsResponse(_) => Self::Log,
+
+            EspHomeMessage::BluetoothLeAdvertisementResponse(_) => Self::BluetoothAdvertisement,
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::BluetoothLeRawAdvertisementsResponse(_) => Self::BluetoothAdvertisement,
+
+            EspHomeMessage::VoiceAssistantRequest(_)
+            | EspHomeMessage::VoiceAssistantResponse(_)
+            | EspHomeMessage::VoiceAssistantEventResponse(_) => Self::VoiceAssistant,
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::VoiceAssistantAudio(_) => Self::VoiceAssistant,
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::VoiceAssistantAnnounceRequest(_)
+            | EspHomeMessage::VoiceAssistantAnnounceFinished(_)
+            | EspHomeMessage::VoiceAssistantConfigurationRequest(_)
+            | EspHomeMessage::VoiceAssistantConfigurationResponse(_)
+            | EspHomeMessage::VoiceAssistantSetConfiguration(_)
+            | EspHomeMessage::VoiceAssistantTimerEventResponse(_) => Self::VoiceAssistant,
+
+            #[cfg(any(
+                feature = "api-1-12",
+                feature = "api-1-10",
+                feature = "api-1-9",
+                feature = "api-1-8"
+            ))]
+            EspHomeMessage::HomeassistantServiceResponse(_) => Self::HomeAssistantService,
+            #[cfg(not(any(
+                feature = "api-1-12",
+                feature = "api-1-10",
+                feature = "api-1-9",
+                feature = "api-1-8"
+            )))]
+            EspHomeMessage::HomeassistantActionRequest(_) => Self::HomeAssistantService,
+
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Splits the incoming message stream into separate, independently bounded channels per [`MessageClass`].
+///
+/// Messages are forwarded without waiting on the receivers: when a channel is full the message is dropped
+/// (and logged), so a slow consumer of one class can't stall the processing of the others.
+/// Messages of a class without a registered channel are discarded.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use esphome_client::{EspHomeClient, router::{MessageClass, Router}};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// let mut router = Router::new();
+/// let mut states = router.channel(MessageClass::State, 64);
+/// let mut logs = router.channel(MessageClass::Log, 1024);
+/// tokio::spawn(async move { router.run(&client).await });
+///
+/// while let Some(state) = states.recv().await {
+///     println!("State: {state:?}");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct Router {
+    senders: HashMap<MessageClass, mpsc::Sender<EspHomeMessage>>,
+}
+
+impl Router {
+    /// Creates a router without any channels.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a channel for the given class, holding at most `capacity` messages.
+    ///
+    /// Registering a class twice replaces the previous channel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is 0.
+    pub fn channel(
+        &mut self,
+        class: MessageClass,
+        capacity: usize,
+    ) -> mpsc::Receiver<EspHomeMessage> {
+        let (tx, rx) = mpsc::channel(capacity);
+        self.senders.insert(class, tx);
+        rx
+    }
+
+    /// Forwards a single message to the channel of its class.
+    ///
+    /// Returns `true` if the message was delivered to a channel.
+    pub fn route(&mut self, message: EspHomeMessage) -> bool {
+        let class = MessageClass::of(&message);
+        let Some(sender) = self.senders.get(&class) else {
+            tracing::trace!("No channel for {class:?}, discarding message");
+            return false;
+        };
+        match sender.try_send(message) {
+            Ok(()) => true,
+            Err(TrySendError::Full(message)) => {
+                tracing::warn!("Channel for {class:?} is full, dropping message: {message:?}");
+                false
+            }
+            Err(TrySendError::Closed(_)) => {
+                tracing::debug!("Channel for {class:?} closed, removing it");
+                self.senders.remove(&class);
+                false
+            }
+        }
+    }
+
+    /// Reads messages from the client and routes them until all channels are closed.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if reading from the client fails, for example due to a disconnected stream.
+    pub async fn run(mut self, client: &EspHomeClient) -> Result<(), ClientError> {
+        while !self.senders.is_empty() {
+            let message = client.try_read().await?;
+            self.route(message);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::{PingRequest, SensorStateResponse, SubscribeLogsResponse};
+
+    fn sensor_state(key: u32) -> EspHomeMessage {
+        SensorStateResponse {
+            key,
+            ..Default::default()
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_message_class_of() {
+        assert_eq!(MessageClass::of(&sensor_state(1)), MessageClass::State);
+        assert_eq!(
+            MessageClass::of(&SubscribeLogsResponse::default().into()),
+            MessageClass::Log
+        );
+        assert_eq!(
+            MessageClass::of(&PingRequest {}.into()),
+            MessageClass::Other
+        );
+    }
+
+    #[test]
+    fn test_route_to_channel() {
+        let mut router = Router::new();
+        let mut states = router.channel(MessageClass::State, 2);
+        assert!(router.route(sensor_state(1)));
+        assert!(!router.route(SubscribeLogsResponse::default().into()));
+        assert_eq!(states.try_recv().unwrap(), sensor_state(1));
+        states.try_recv().unwrap_err();
+    }
+
+    #[test]
+    fn test_route_drops_when_full() {
+        let mut router = Router::new();
+        let mut states = router.channel(MessageClass::State, 1);
+        let mut logs = router.channel(MessageClass::Log, 1);
+        assert!(router.route(sensor_state(1)));
+        assert!(!router.route(sensor_state(2)));
+        // A full state channel does not block other classes
+        assert!(router.route(SubscribeLogsResponse::default().into()));
+        assert_eq!(states.try_recv().unwrap(), sensor_state(1));
+        logs.try_recv().unwrap();
+    }
+
+    #[test]
+    fn test_route_removes_closed_channel() {
+        let mut router = Router::new();
+        drop(router.channel(MessageClass::State, 1));
+        assert!(!router.route(sensor_state(1)));
+        assert!(router.senders.is_empty());
+    }
+}
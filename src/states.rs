@@ -0,0 +1,508 @@
+#[cfg(not(any(
+    feature = "api-1-13",
+    feature = "api-1-12",
+    feature = "api-1-10",
+    feature = "api-1-9",
+    feature = "api-1-8"
+)))]
+use crate::proto::WaterHeaterStateResponse;
+#[cfg(not(any(feature = "api-1-8")))]
+use crate::proto::{
+    AlarmControlPanelStateResponse, DateStateResponse, TextStateResponse, TimeStateResponse,
+};
+#[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+use crate::proto::{
+    DateTimeStateResponse, EventResponse, SirenStateResponse, UpdateStateResponse,
+    ValveStateResponse,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Mutex, PoisonError},
+};
+
+use tokio::sync::watch;
+
+use crate::{
+    EspHomeClient,
+    error::ClientError,
+    proto::{
+        BinarySensorStateResponse, ClimateStateResponse, CoverStateResponse, EspHomeMessage,
+        FanStateResponse, LightStateResponse, LockStateResponse, MediaPlayerStateResponse,
+        NumberStateResponse, SelectStateResponse, SensorStateResponse, SwitchStateResponse,
+        TextSensorStateResponse,
+    },
+    router::MessageClass,
+};
+
+/// Gets the device ID and key of the entity of a message, the device ID is 0 for API versions without
+/// sub devices.
+macro_rules! entity_id {
+    ($entity:expr) => {{
+        #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+        let device_id = $entity.device_id;
+        #[cfg(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8"))]
+        let device_id = 0;
+        (device_id, $entity.key)
+    }};
+}
+pub(crate) use entity_id;
+
+/// Device ID and key of an entity, see [`EntityState::id`].
+type EntityId = (u32, u32);
+
+/// State update of a single entity, unifying the `*StateResponse` messages.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntityState {
+    /// State of a binary sensor.
+    BinarySensor(BinarySensorStateResponse),
+    /// State of a cover.
+    Cover(CoverStateResponse),
+    /// State of a fan.
+    Fan(FanStateResponse),
+    /// State of a light.
+    Light(LightStateResponse),
+    /// State of a sensor.
+    Sensor(SensorStateResponse),
+    /// State of a switch.
+    Switch(SwitchStateResponse),
+    /// State of a text sensor.
+    TextSensor(TextSensorStateResponse),
+    /// State of a climate.
+    Climate(ClimateStateResponse),
+    /// State of a number.
+    Number(NumberStateResponse),
+    /// State of a select.
+    Select(SelectStateResponse),
+    /// State of a lock.
+    Lock(LockStateResponse),
+    /// State of a media player.
+    MediaPlayer(MediaPlayerStateResponse),
+    #[cfg(not(any(feature = "api-1-8")))]
+    /// State of a alarm control panel.
+    AlarmControlPanel(AlarmControlPanelStateResponse),
+    #[cfg(not(any(feature = "api-1-8")))]
+    /// State of a text.
+    Text(TextStateResponse),
+    #[cfg(not(any(feature = "api-1-8")))]
+    /// State of a date.
+    Date(DateStateResponse),
+    #[cfg(not(any(feature = "api-1-8")))]
+    /// State of a time.
+    Time(TimeStateResponse),
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// State of a siren.
+    Siren(SirenStateResponse),
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// State of a event.
+    Event(EventResponse),
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// State of a valve.
+    Valve(ValveStateResponse),
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// State of a date time.
+    DateTime(DateTimeStateResponse),
+    #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+    /// State of a update.
+    Update(UpdateStateResponse),
+    #[cfg(not(any(
+        feature = "api-1-13",
+        feature = "api-1-12",
+        feature = "api-1-10",
+        feature = "api-1-9",
+        feature = "api-1-8"
+    )))]
+    /// State of a water heater.
+    WaterHeater(WaterHeaterStateResponse),
+}
+
+impl EntityState {
+    /// Gets the ID of the device and the key of the entity the state belongs to.
+    ///
+    /// Keys are only unique per device, the device ID is 0 for the main device and for API versions
+    /// without sub devices.
+    #[must_use]
+    pub const fn id(&self) -> (u32, u32) {
+        match self {
+            Self::BinarySensor(state) => entity_id!(state),
+            Self::Cover(state) => entity_id!(state),
+            Self::Fan(state) => entity_id!(state),
+            Self::Light(state) => entity_id!(state),
+            Self::Sensor(state) => entity_id!(state),
+            Self::Switch(state) => entity_id!(state),
+            Self::TextSensor(state) => entity_id!(state),
+            Self::Climate(state) => entity_id!(state),
+            Self::Number(state) => entity_id!(state),
+            Self::Select(state) => entity_id!(state),
+            Self::Lock(state) => entity_id!(state),
+            Self::MediaPlayer(state) => entity_id!(state),
+            #[cfg(not(any(feature = "api-1-8")))]
+            Self::AlarmControlPanel(state) => entity_id!(state),
+            #[cfg(not(any(feature = "api-1-8")))]
+            Self::Text(state) => entity_id!(state),
+            #[cfg(not(any(feature = "api-1-8")))]
+            Self::Date(state) => entity_id!(state),
+            #[cfg(not(any(feature = "api-1-8")))]
+            Self::Time(state) => entity_id!(state),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            Self::Siren(state) => entity_id!(state),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            Self::Event(state) => entity_id!(state),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            Self::Valve(state) => entity_id!(state),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            Self::DateTime(state) => entity_id!(state),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            Self::Update(state) => entity_id!(state),
+            #[cfg(not(any(
+                feature = "api-1-13",
+                feature = "api-1-12",
+                feature = "api-1-10",
+                feature = "api-1-9",
+                feature = "api-1-8"
+            )))]
+            Self::WaterHeater(state) => entity_id!(state),
+        }
+    }
+
+    /// Gets the key of the entity the state belongs to.
+    #[must_use]
+    pub const fn key(&self) -> u32 {
+        self.id().1
+    }
+
+    /// Gets the ID of the device the entity belongs to, see [`EntityState::id`].
+    #[must_use]
+    pub const fn device_id(&self) -> u32 {
+        self.id().0
+    }
+}
+
+impl TryFrom<EspHomeMessage> for EntityState {
+    type Error = EspHomeMessage;
+
+    fn try_from(message: EspHomeMessage) -> Result<Self, Self::Error> {
+        match message {
+            EspHomeMessage::BinarySensorStateResponse(state) => Ok(Self::BinarySensor(state)),
+            EspHomeMessage::CoverStateResponse(state) => Ok(Self::Cover(state)),
+            EspHomeMessage::FanStateResponse(state) => Ok(Self::Fan(state)),
+            EspHomeMessage::LightStateResponse(state) => Ok(Self::Light(state)),
+            EspHomeMessage::SensorStateResponse(state) => Ok(Self::Sensor(state)),
+            EspHomeMessage::SwitchStateResponse(state) => Ok(Self::Switch(state)),
+            EspHomeMessage::TextSensorStateResponse(state) => Ok(Self::TextSensor(state)),
+            EspHomeMessage::ClimateStateResponse(state) => Ok(Self::Climate(state)),
+            EspHomeMessage::NumberStateResponse(state) => Ok(Self::Number(state)),
+            EspHomeMessage::SelectStateResponse(state) => Ok(Self::Select(state)),
+            EspHomeMessage::LockStateResponse(state) => Ok(Self::Lock(state)),
+            EspHomeMessage::MediaPlayerStateResponse(state) => Ok(Self::MediaPlayer(state)),
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::AlarmControlPanelStateResponse(state) => {
+                Ok(Self::AlarmControlPanel(state))
+            }
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::TextStateResponse(state) => Ok(Self::Text(state)),
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::DateStateResponse(state) => Ok(Self::Date(state)),
+            #[cfg(not(any(feature = "api-1-8")))]
+            EspHomeMessage::TimeStateResponse(state) => Ok(Self::Time(state)),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::SirenStateResponse(state) => Ok(Self::Siren(state)),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::EventResponse(state) => Ok(Self::Event(state)),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::ValveStateResponse(state) => Ok(Self::Valve(state)),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::DateTimeStateResponse(state) => Ok(Self::DateTime(state)),
+            #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+            EspHomeMessage::UpdateStateResponse(state) => Ok(Self::Update(state)),
+            #[cfg(not(any(
+                feature = "api-1-13",
+                feature = "api-1-12",
+                feature = "api-1-10",
+                feature = "api-1-9",
+                feature = "api-1-8"
+            )))]
+            EspHomeMessage::WaterHeaterStateResponse(state) => Ok(Self::WaterHeater(state)),
+            other => Err(other),
+        }
+    }
+}
+
+/// Stream of entity state updates read from a client, created by [`EspHomeClient::subscribe_states`].
+///
+/// Other messages read from the client are skipped, but still delivered to subscribers of
+/// [`EspHomeClient::subscribe`].
+#[derive(Debug)]
+pub struct StateStream<'a> {
+    client: &'a EspHomeClient,
+    entities: Option<HashSet<EntityId>>,
+    changes: Option<ChangeFilter>,
+}
+
+impl<'a> StateStream<'a> {
+    /// Creates a stream of the state updates read from the given client.
+    ///
+    /// The device only sends state updates after a `SubscribeStatesRequest`.
+    #[must_use]
+    pub const fn new(client: &'a EspHomeClient) -> Self {
+        Self {
+            client,
+            entities: None,
+            changes: None,
+        }
+    }
+
+    /// Only yields the state updates of the entities with the given device IDs and keys, see
+    /// [`EntityState::id`].
+    #[must_use]
+    pub fn filter_keys(mut self, entities: &[(u32, u32)]) -> Self {
+        self.entities = Some(entities.iter().copied().collect());
+        self
+    }
+
+    /// Only yields state updates which differ from the previous state of the entity.
+    ///
+    /// The values of sensors and numbers are considered unchanged if they differ by at most the epsilon,
+    /// other states have to be equal. Events are always yielded, as every event is a new occurrence.
+    ///
+    /// # Usage:
+    /// ```rust,no_run
+    /// use esphome_client::EspHomeClient;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+    /// let mut states = client.subscribe_states().await?.changes_only(0.1);
+    /// loop {
+    ///     println!("{:?}", states.next().await?);
+    /// }
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn changes_only(mut self, epsilon: f32) -> Self {
+        self.changes = Some(ChangeFilter {
+            epsilon,
+            previous: HashMap::new(),
+        });
+        self
+    }
+
+    /// Waits for the next state update.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if reading from the client fails, for example due to a disconnected stream.
+    pub async fn next(&mut self) -> Result<EntityState, ClientError> {
+        loop {
+            let Ok(state) = EntityState::try_from(self.client.try_read().await?) else {
+                continue;
+            };
+            if self
+                .entities
+                .as_ref()
+                .is_some_and(|entities| !entities.contains(&state.id()))
+            {
+                continue;
+            }
+            if self
+                .changes
+                .as_mut()
+                .is_none_or(|changes| changes.is_changed(&state))
+            {
+                return Ok(state);
+            }
+        }
+    }
+}
+
+/// Previous state per entity, to suppress unchanged state updates.
+#[derive(Debug)]
+struct ChangeFilter {
+    epsilon: f32,
+    previous: HashMap<EntityId, EntityState>,
+}
+
+impl ChangeFilter {
+    /// Checks if the state differs from the previous state of the entity, and remembers it.
+    fn is_changed(&mut self, state: &EntityState) -> bool {
+        let changed = self
+            .previous
+            .get(&state.id())
+            .is_none_or(|previous| !is_same_state(previous, state, self.epsilon));
+        if changed {
+            self.previous.insert(state.id(), state.clone());
+        }
+        changed
+    }
+}
+
+/// Compares two states of an entity, with an epsilon for the values of sensors and numbers.
+fn is_same_state(previous: &EntityState, state: &EntityState, epsilon: f32) -> bool {
+    match (previous, state) {
+        (EntityState::Sensor(previous), EntityState::Sensor(state)) => {
+            is_same_value(previous.state, state.state, epsilon)
+                && SensorStateResponse {
+                    state: 0.0,
+                    ..*previous
+                } == SensorStateResponse {
+                    state: 0.0,
+                    ..*state
+                }
+        }
+        (EntityState::Number(previous), EntityState::Number(state)) => {
+            is_same_value(previous.state, state.state, epsilon)
+                && NumberStateResponse {
+                    state: 0.0,
+                    ..*previous
+                } == NumberStateResponse {
+                    state: 0.0,
+                    ..*state
+                }
+        }
+        #[cfg(not(any(feature = "api-1-9", feature = "api-1-8")))]
+        (_, EntityState::Event(_)) => false,
+        (previous, state) => previous == state,
+    }
+}
+
+fn is_same_value(previous: f32, value: f32, epsilon: f32) -> bool {
+    (previous.is_nan() && value.is_nan()) || (previous - value).abs() <= epsilon
+}
+
+/// Cache of the latest state per entity, enabled with [`EspHomeClientBuilder::with_state_cache`](crate::EspHomeClientBuilder::with_state_cache).
+#[derive(Debug, Default)]
+pub(crate) struct StateCache {
+    entities: Mutex<HashMap<EntityId, watch::Sender<Option<EntityState>>>>,
+}
+
+impl StateCache {
+    /// Updates the cache if the message is a state update.
+    pub(crate) fn update(&self, message: &EspHomeMessage) {
+        if MessageClass::of(message) != MessageClass::State {
+            return;
+        }
+        let Ok(state) = EntityState::try_from(message.clone()) else {
+            return;
+        };
+        self.entities
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(state.id())
+            .or_insert_with(|| watch::channel(None).0)
+            .send_replace(Some(state));
+    }
+
+    /// Gets a receiver for the state of the entity, which is `None` until the first state is received.
+    pub(crate) fn watch(&self, device_id: u32, key: u32) -> watch::Receiver<Option<EntityState>> {
+        self.entities
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry((device_id, key))
+            .or_insert_with(|| watch::channel(None).0)
+            .subscribe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::PingRequest;
+
+    #[test]
+    fn test_entity_state_from_message() {
+        let state = EntityState::try_from(EspHomeMessage::from(SensorStateResponse {
+            key: 7,
+            state: 21.5,
+            ..Default::default()
+        }))
+        .unwrap();
+        assert_eq!(state.key(), 7);
+        assert!(matches!(state, EntityState::Sensor(s) if s.state > 21.0));
+    }
+
+    #[test]
+    fn test_state_cache_watch() {
+        let cache = StateCache::default();
+        let mut before = cache.watch(0, 7);
+        assert!(before.borrow().is_none());
+
+        cache.update(&PingRequest {}.into());
+        cache.update(
+            &SensorStateResponse {
+                key: 7,
+                state: 1.0,
+                ..Default::default()
+            }
+            .into(),
+        );
+        assert!(before.has_changed().unwrap());
+        assert_eq!(before.borrow_and_update().as_ref().unwrap().key(), 7);
+
+        // Receivers created after an update see the latest state
+        let after = cache.watch(0, 7);
+        assert!(matches!(*after.borrow(), Some(EntityState::Sensor(_))));
+        assert!(cache.watch(0, 8).borrow().is_none());
+    }
+
+    #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+    #[test]
+    fn test_state_cache_sub_devices() {
+        let cache = StateCache::default();
+        for device_id in [1, 2] {
+            cache.update(
+                &SensorStateResponse {
+                    key: 7,
+                    state: f32::from(u8::try_from(device_id).unwrap()),
+                    device_id,
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+        // Entities of sub devices may share a key
+        let state = cache.watch(1, 7).borrow().clone().unwrap();
+        assert_eq!(state.id(), (1, 7));
+        assert!(matches!(state, EntityState::Sensor(s) if s.state < 1.5));
+        assert_eq!(cache.watch(2, 7).borrow().as_ref().unwrap().device_id(), 2);
+        assert!(cache.watch(0, 7).borrow().is_none());
+    }
+
+    #[test]
+    fn test_change_filter() {
+        let sensor = |state| {
+            EntityState::Sensor(SensorStateResponse {
+                key: 7,
+                state,
+                ..Default::default()
+            })
+        };
+        let mut changes = ChangeFilter {
+            epsilon: 0.1,
+            previous: HashMap::new(),
+        };
+        assert!(changes.is_changed(&sensor(20.0)));
+        assert!(!changes.is_changed(&sensor(20.0)));
+        assert!(!changes.is_changed(&sensor(20.05)));
+        assert!(changes.is_changed(&sensor(20.2)));
+        // Small steps are compared to the last yielded state
+        assert!(!changes.is_changed(&sensor(20.25)));
+        assert!(changes.is_changed(&sensor(f32::NAN)));
+        assert!(!changes.is_changed(&sensor(f32::NAN)));
+
+        let binary_sensor = |key, state| {
+            EntityState::BinarySensor(BinarySensorStateResponse {
+                key,
+                state,
+                ..Default::default()
+            })
+        };
+        assert!(changes.is_changed(&binary_sensor(8, true)));
+        assert!(!changes.is_changed(&binary_sensor(8, true)));
+        assert!(changes.is_changed(&binary_sensor(8, false)));
+        assert!(changes.is_changed(&binary_sensor(9, false)));
+    }
+
+    #[test]
+    fn test_entity_state_from_other_message() {
+        let message = EntityState::try_from(EspHomeMessage::from(PingRequest {})).unwrap_err();
+        assert_eq!(message, PingRequest {}.into());
+    }
+}
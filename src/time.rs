@@ -0,0 +1,190 @@
+#![allow(
+    clippy::module_name_repetitions,
+    reason = "Names refer to the time messages of the API"
+)]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::{
+    error::{ClientError, CommandError},
+    proto::GetTimeResponse,
+};
+
+/// Converts epoch seconds, as used by the API, to a [`SystemTime`].
+#[must_use]
+pub fn from_epoch_seconds(epoch_seconds: u32) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(u64::from(epoch_seconds))
+}
+
+/// Converts a [`SystemTime`] to epoch seconds, as used by the API, fractional seconds are dropped.
+///
+/// # Errors
+///
+/// Will return [`CommandError::InvalidTimestamp`] if the moment is before 1970 or after 2106.
+pub fn to_epoch_seconds(time: SystemTime) -> Result<u32, ClientError> {
+    let timestamp = match time.duration_since(UNIX_EPOCH) {
+        Ok(since_epoch) => i64::try_from(since_epoch.as_secs()).unwrap_or(i64::MAX),
+        Err(e) => i64::try_from(e.duration().as_secs())
+            .map_or(i64::MIN, |seconds| -seconds)
+            .min(-1),
+    };
+    Ok(u32::try_from(timestamp).map_err(|_err| CommandError::InvalidTimestamp { timestamp })?)
+}
+
+/// Gets the moment of a [`GetTimeResponse`], which devices send when their time is synchronized.
+#[must_use]
+pub fn response_time(response: &GetTimeResponse) -> SystemTime {
+    from_epoch_seconds(response.epoch_seconds)
+}
+
+/// Creates a [`GetTimeResponse`] with the given moment, to answer a `GetTimeRequest` of the device.
+///
+/// # Usage:
+/// ```rust,no_run
+/// use std::time::SystemTime;
+/// use esphome_client::{EspHomeClient, time::get_time_response, types::EspHomeMessage};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = EspHomeClient::builder().address("192.168.0.2:6053").connect().await?;
+/// loop {
+///     if let EspHomeMessage::GetTimeRequest(_) = client.try_read().await? {
+///         client.try_write(get_time_response(SystemTime::now())?).await?;
+///     }
+/// }
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Will return [`CommandError::InvalidTimestamp`] if the moment is before 1970 or after 2106.
+pub fn get_time_response(time: SystemTime) -> Result<GetTimeResponse, ClientError> {
+    Ok(response_at(to_epoch_seconds(time)?))
+}
+
+const fn response_at(epoch_seconds: u32) -> GetTimeResponse {
+    GetTimeResponse {
+        epoch_seconds,
+        #[cfg(not(any(
+            feature = "api-1-12",
+            feature = "api-1-10",
+            feature = "api-1-9",
+            feature = "api-1-8"
+        )))]
+        timezone: String::new(),
+        #[cfg(not(any(
+            feature = "api-1-13",
+            feature = "api-1-12",
+            feature = "api-1-10",
+            feature = "api-1-9",
+            feature = "api-1-8"
+        )))]
+        parsed_timezone: None,
+    }
+}
+
+#[cfg(not(any(
+    feature = "api-1-12",
+    feature = "api-1-10",
+    feature = "api-1-9",
+    feature = "api-1-8"
+)))]
+/// Creates a [`GetTimeResponse`] with the given moment and timezone.
+///
+/// The timezone is a POSIX TZ string, e.g. `CET-1CEST,M3.5.0,M10.5.0/3`, which the device uses for
+/// its local time. Only available with API version 1.13 or newer.
+///
+/// # Errors
+///
+/// Will return [`CommandError::InvalidTimestamp`] if the moment is before 1970 or after 2106.
+pub fn get_time_response_in_timezone(
+    time: SystemTime,
+    timezone: &str,
+) -> Result<GetTimeResponse, ClientError> {
+    Ok(GetTimeResponse {
+        timezone: timezone.to_owned(),
+        ..get_time_response(time)?
+    })
+}
+
+#[cfg(feature = "chrono")]
+/// Gets the moment of a [`GetTimeResponse`] as a [`DateTime`].
+#[must_use]
+pub fn response_date_time(response: &GetTimeResponse) -> DateTime<Utc> {
+    DateTime::from_timestamp(i64::from(response.epoch_seconds), 0).unwrap_or_default()
+}
+
+#[cfg(feature = "chrono")]
+/// Creates a [`GetTimeResponse`] with the given moment, fractional seconds are dropped.
+///
+/// # Errors
+///
+/// Will return [`CommandError::InvalidTimestamp`] if the moment is before 1970 or after 2106.
+pub fn date_time_response<Tz: TimeZone>(
+    date_time: &DateTime<Tz>,
+) -> Result<GetTimeResponse, ClientError> {
+    let timestamp = date_time.timestamp();
+    let epoch_seconds =
+        u32::try_from(timestamp).map_err(|_err| CommandError::InvalidTimestamp { timestamp })?;
+    Ok(response_at(epoch_seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_epoch_seconds_round_trip() {
+        let time = from_epoch_seconds(1_700_000_000);
+        assert_eq!(to_epoch_seconds(time).unwrap(), 1_700_000_000);
+        assert_eq!(
+            to_epoch_seconds(time + Duration::from_millis(999)).unwrap(),
+            1_700_000_000
+        );
+        assert_eq!(
+            response_time(&get_time_response(time).unwrap()),
+            from_epoch_seconds(1_700_000_000)
+        );
+    }
+
+    #[test]
+    fn test_to_epoch_seconds_out_of_range() {
+        let before_epoch = UNIX_EPOCH - Duration::from_millis(500);
+        assert!(matches!(
+            to_epoch_seconds(before_epoch),
+            Err(ClientError::Command(CommandError::InvalidTimestamp {
+                timestamp: -1
+            }))
+        ));
+        let after_range = UNIX_EPOCH + Duration::from_secs(u64::from(u32::MAX) + 1);
+        assert!(matches!(
+            to_epoch_seconds(after_range),
+            Err(ClientError::Command(CommandError::InvalidTimestamp { .. }))
+        ));
+    }
+
+    #[cfg(not(any(
+        feature = "api-1-12",
+        feature = "api-1-10",
+        feature = "api-1-9",
+        feature = "api-1-8"
+    )))]
+    #[test]
+    fn test_get_time_response_in_timezone() {
+        let response =
+            get_time_response_in_timezone(from_epoch_seconds(60), "CET-1CEST,M3.5.0,M10.5.0/3")
+                .unwrap();
+        assert_eq!(response.epoch_seconds, 60);
+        assert_eq!(response.timezone, "CET-1CEST,M3.5.0,M10.5.0/3");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_date_time_response() {
+        let date_time = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+        let response = date_time_response(&date_time).unwrap();
+        assert_eq!(response.epoch_seconds, 1_700_000_000);
+        assert_eq!(response_date_time(&response), date_time);
+    }
+}
@@ -0,0 +1,202 @@
+use std::time::Duration;
+
+use crate::{
+    EspHomeClient,
+    error::ClientError,
+    proto::{EspHomeMessage, VoiceAssistantTimerEvent, VoiceAssistantTimerEventResponse},
+};
+
+/// A voice assistant timer, as reported in a [`TimerEvent`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timer {
+    /// Identifier of the timer, stable across its events.
+    pub id: String,
+    /// Name given to the timer, may be empty.
+    pub name: String,
+    /// Total duration the timer was set to.
+    pub total: Duration,
+    /// Time left before the timer finishes.
+    pub remaining: Duration,
+    /// Whether the timer is running, `false` while paused.
+    pub is_active: bool,
+}
+
+/// Voice assistant timer event, typed from a `VoiceAssistantTimerEventResponse`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TimerEvent {
+    /// A timer was started.
+    Started(Timer),
+    /// A timer was updated, e.g. paused, resumed or its duration changed.
+    Updated(Timer),
+    /// A timer was cancelled before finishing.
+    Cancelled(Timer),
+    /// A timer finished.
+    Finished(Timer),
+}
+
+impl TimerEvent {
+    /// Returns the timer the event applies to.
+    #[must_use]
+    pub const fn timer(&self) -> &Timer {
+        match self {
+            Self::Started(timer)
+            | Self::Updated(timer)
+            | Self::Cancelled(timer)
+            | Self::Finished(timer) => timer,
+        }
+    }
+
+    /// Returns the protocol event type of this event.
+    #[must_use]
+    pub const fn event_type(&self) -> VoiceAssistantTimerEvent {
+        match self {
+            Self::Started(_) => VoiceAssistantTimerEvent::VoiceAssistantTimerStarted,
+            Self::Updated(_) => VoiceAssistantTimerEvent::VoiceAssistantTimerUpdated,
+            Self::Cancelled(_) => VoiceAssistantTimerEvent::VoiceAssistantTimerCancelled,
+            Self::Finished(_) => VoiceAssistantTimerEvent::VoiceAssistantTimerFinished,
+        }
+    }
+}
+
+impl TryFrom<VoiceAssistantTimerEventResponse> for TimerEvent {
+    type Error = VoiceAssistantTimerEventResponse;
+
+    /// Fails with the original response if its event type is unknown.
+    fn try_from(response: VoiceAssistantTimerEventResponse) -> Result<Self, Self::Error> {
+        let Ok(event_type) = VoiceAssistantTimerEvent::try_from(response.event_type) else {
+            return Err(response);
+        };
+        let timer = Timer {
+            id: response.timer_id,
+            name: response.name,
+            total: Duration::from_secs(response.total_seconds.into()),
+            remaining: Duration::from_secs(response.seconds_left.into()),
+            is_active: response.is_active,
+        };
+        Ok(match event_type {
+            VoiceAssistantTimerEvent::VoiceAssistantTimerStarted => Self::Started(timer),
+            VoiceAssistantTimerEvent::VoiceAssistantTimerUpdated => Self::Updated(timer),
+            VoiceAssistantTimerEvent::VoiceAssistantTimerCancelled => Self::Cancelled(timer),
+            VoiceAssistantTimerEvent::VoiceAssistantTimerFinished => Self::Finished(timer),
+        })
+    }
+}
+
+impl TryFrom<EspHomeMessage> for TimerEvent {
+    type Error = EspHomeMessage;
+
+    fn try_from(message: EspHomeMessage) -> Result<Self, Self::Error> {
+        match message {
+            EspHomeMessage::VoiceAssistantTimerEventResponse(response) => {
+                Self::try_from(response).map_err(EspHomeMessage::from)
+            }
+            other => Err(other),
+        }
+    }
+}
+
+impl From<TimerEvent> for VoiceAssistantTimerEventResponse {
+    /// Durations are truncated to whole seconds, saturating at `u32::MAX`.
+    fn from(event: TimerEvent) -> Self {
+        let event_type = event.event_type();
+        let (TimerEvent::Started(timer)
+        | TimerEvent::Updated(timer)
+        | TimerEvent::Cancelled(timer)
+        | TimerEvent::Finished(timer)) = event;
+        Self {
+            event_type: event_type.into(),
+            timer_id: timer.id,
+            name: timer.name,
+            total_seconds: whole_seconds(timer.total),
+            seconds_left: whole_seconds(timer.remaining),
+            is_active: timer.is_active,
+        }
+    }
+}
+
+impl From<TimerEvent> for EspHomeMessage {
+    fn from(event: TimerEvent) -> Self {
+        VoiceAssistantTimerEventResponse::from(event).into()
+    }
+}
+
+fn whole_seconds(duration: Duration) -> u32 {
+    u32::try_from(duration.as_secs()).unwrap_or(u32::MAX)
+}
+
+/// Stream of voice assistant timer events read from a client.
+///
+/// Other messages read from the client are skipped, but still delivered to subscribers of
+/// [`EspHomeClient::subscribe`].
+#[derive(Debug)]
+pub struct TimerEventStream<'a> {
+    client: &'a EspHomeClient,
+}
+
+impl<'a> TimerEventStream<'a> {
+    /// Creates a stream of the timer events read from the given client.
+    #[must_use]
+    pub const fn new(client: &'a EspHomeClient) -> Self {
+        Self { client }
+    }
+
+    /// Waits for the next timer event.
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if reading from the client fails, for example due to a disconnected stream.
+    pub async fn next(&mut self) -> Result<TimerEvent, ClientError> {
+        loop {
+            if let Ok(event) = TimerEvent::try_from(self.client.try_read().await?) {
+                return Ok(event);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::PingRequest;
+
+    #[test]
+    fn test_timer_event_from_message() {
+        let event = TimerEvent::try_from(EspHomeMessage::from(VoiceAssistantTimerEventResponse {
+            event_type: VoiceAssistantTimerEvent::VoiceAssistantTimerUpdated.into(),
+            timer_id: "t1".to_owned(),
+            name: "pasta".to_owned(),
+            total_seconds: 600,
+            seconds_left: 420,
+            is_active: false,
+        }))
+        .unwrap();
+        assert_eq!(
+            event,
+            TimerEvent::Updated(Timer {
+                id: "t1".to_owned(),
+                name: "pasta".to_owned(),
+                total: Duration::from_secs(600),
+                remaining: Duration::from_secs(420),
+                is_active: false,
+            })
+        );
+
+        let response = VoiceAssistantTimerEventResponse::from(event);
+        assert_eq!(response.seconds_left, 420);
+        assert_eq!(
+            response.event_type,
+            i32::from(VoiceAssistantTimerEvent::VoiceAssistantTimerUpdated)
+        );
+    }
+
+    #[test]
+    fn test_timer_event_rejects_other_messages() {
+        let unknown = VoiceAssistantTimerEventResponse {
+            event_type: 42,
+            ..Default::default()
+        };
+        assert_eq!(TimerEvent::try_from(unknown.clone()).unwrap_err(), unknown);
+        let other = TimerEvent::try_from(EspHomeMessage::from(PingRequest {})).unwrap_err();
+        assert!(matches!(other, EspHomeMessage::PingRequest(_)));
+    }
+}
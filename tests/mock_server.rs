@@ -0,0 +1,295 @@
+#![cfg(feature = "test-util")]
+
+use esphome_client::{
+    API_VERSION, BufferPool, EntityKind, EspHomeClient, LifecycleEvent,
+    error::{ClientError, ConnectionError, NoiseError},
+    test_util::MockEspHomeServer,
+    types::{
+        EspHomeMessage, ListEntitiesSwitchResponse, PingRequest, SensorStateResponse,
+        SubscribeStatesRequest, SwitchCommandRequest, SwitchStateResponse,
+    },
+};
+use std::sync::{Arc, Mutex};
+use tokio::time::Duration;
+
+const KEY: &str = "px7tsbK3C7bpXHr2OevEV2ZMg/FrNBw2+O2pNPbedtA=";
+
+#[tokio::test]
+async fn test_mock_server_plain_entities() {
+    let server = MockEspHomeServer::builder()
+        .name("kitchen")
+        .entity(ListEntitiesSwitchResponse {
+            key: 1,
+            object_id: "light".to_owned(),
+            name: "Light".to_owned(),
+            ..Default::default()
+        })
+        .start()
+        .await
+        .expect("Failed to start mock server");
+
+    let client = EspHomeClient::builder()
+        .address(server.address())
+        .expected_server_name("kitchen")
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await
+        .expect("Failed to connect to mock server");
+    assert_eq!(client.api_version(), Some(API_VERSION));
+
+    let entities = client
+        .list_entities()
+        .await
+        .expect("Failed to list entities");
+    assert_eq!(entities.switches.len(), 1);
+    assert_eq!(entities.switches[0].object_id, "light");
+    client.ping().await.expect("Failed to ping mock server");
+
+    // Hello, list entities and ping requests, and their responses
+    let statistics = client.statistics();
+    assert!(statistics.messages_sent >= 3);
+    assert!(statistics.messages_received >= 4);
+    assert!(statistics.bytes_received > 0);
+    assert_eq!(statistics.decode_errors, 0);
+    assert!(statistics.last_received.is_some());
+}
+
+#[tokio::test]
+async fn test_mock_server_entity_registry() {
+    let server = MockEspHomeServer::builder()
+        .entity(ListEntitiesSwitchResponse {
+            key: 1,
+            object_id: "light".to_owned(),
+            name: "Light".to_owned(),
+            ..Default::default()
+        })
+        .start()
+        .await
+        .expect("Failed to start mock server");
+
+    let client = EspHomeClient::builder()
+        .address(server.address())
+        .with_entity_registry()
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await
+        .expect("Failed to connect to mock server");
+    assert_eq!(client.entity(0, 1), None);
+
+    client
+        .list_entities()
+        .await
+        .expect("Failed to list entities");
+    let entity = client.entity(0, 1).expect("Entity not recorded");
+    assert_eq!(entity.name, "Light");
+    assert_eq!(entity.kind, EntityKind::Switch);
+    assert_eq!(client.entity_by_object_id("light"), Some(entity));
+}
+
+#[tokio::test]
+async fn test_mock_server_lifecycle_events() {
+    let server = MockEspHomeServer::builder()
+        .start()
+        .await
+        .expect("Failed to start mock server");
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&events);
+    let client = EspHomeClient::builder()
+        .address(server.address())
+        .on_lifecycle(move |event| recorded.lock().unwrap().push(event.clone()))
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await
+        .expect("Failed to connect to mock server");
+    assert_eq!(
+        *events.lock().unwrap(),
+        [LifecycleEvent::Connected, LifecycleEvent::SetupComplete]
+    );
+
+    server.close();
+    assert!(client.try_read().await.unwrap_err().is_connection_lost());
+    assert!(client.try_read().await.is_err());
+    assert_eq!(
+        events.lock().unwrap().last(),
+        Some(&LifecycleEvent::Disconnected {
+            reason: ClientError::Disconnected.to_string()
+        })
+    );
+    assert_eq!(events.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_mock_server_filtered_states() {
+    let mut server = MockEspHomeServer::builder();
+    for key in 1..=3 {
+        server = server.respond_to::<SubscribeStatesRequest>(SensorStateResponse {
+            key,
+            ..Default::default()
+        });
+    }
+    let server = server.start().await.expect("Failed to start mock server");
+
+    let client = EspHomeClient::builder()
+        .address(server.address())
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await
+        .expect("Failed to connect to mock server");
+    let mut states = client
+        .subscribe_states_filtered(&[(0, 2)])
+        .await
+        .expect("Failed to subscribe to states");
+    let state = states.next().await.expect("Failed to read state");
+    assert_eq!(state.key(), 2);
+}
+
+#[tokio::test]
+async fn test_mock_server_scripted_response() {
+    let server = MockEspHomeServer::builder()
+        .respond_to::<SwitchCommandRequest>(SwitchStateResponse {
+            key: 1,
+            ..Default::default()
+        })
+        .start()
+        .await
+        .expect("Failed to start mock server");
+
+    let client = EspHomeClient::builder()
+        .address(server.address())
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await
+        .expect("Failed to connect to mock server");
+    client
+        .try_write(SwitchCommandRequest {
+            key: 1,
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to send command");
+    let response = client.try_read().await.expect("Failed to read response");
+    assert!(
+        matches!(&response, EspHomeMessage::SwitchStateResponse(state) if state.key == 1),
+        "Unexpected response: {response:?}"
+    );
+
+    client.try_write(PingRequest {}).await.unwrap();
+    assert!(matches!(
+        client.try_read().await.unwrap(),
+        EspHomeMessage::PingResponse(_)
+    ));
+    let received = server.received();
+    assert!(matches!(received[0], EspHomeMessage::HelloRequest(_)));
+    assert!(received.iter().any(
+        |message| matches!(message, EspHomeMessage::SwitchCommandRequest(command) if command.key == 1)
+    ));
+}
+
+#[tokio::test]
+async fn test_mock_server_noise() {
+    let server = MockEspHomeServer::builder()
+        .key(KEY)
+        .name("garage")
+        .start()
+        .await
+        .expect("Failed to start mock server");
+
+    let client = EspHomeClient::builder()
+        .address(server.address())
+        .key(KEY)
+        .expected_mac("ab:cd:ef:01:23:45")
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await
+        .expect("Failed to connect to mock server");
+    assert_eq!(client.server_name(), Some("garage"));
+    assert_eq!(client.server_mac(), Some("abcdef012345"));
+    client.ping().await.expect("Failed to ping mock server");
+}
+
+#[tokio::test]
+async fn test_mock_server_shared_buffer_pool() {
+    let plain = MockEspHomeServer::builder()
+        .start()
+        .await
+        .expect("Failed to start mock server");
+    let noise = MockEspHomeServer::builder()
+        .key(KEY)
+        .start()
+        .await
+        .expect("Failed to start mock server");
+
+    let pool = BufferPool::new().capacity(1024).max_idle(4);
+    let plain_client = EspHomeClient::builder()
+        .address(plain.address())
+        .buffer_pool(pool.clone())
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await
+        .expect("Failed to connect to mock server");
+    let noise_client = EspHomeClient::builder()
+        .address(noise.address())
+        .key(KEY)
+        .buffer_pool(pool.clone())
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await
+        .expect("Failed to connect to mock server");
+    plain_client
+        .ping()
+        .await
+        .expect("Failed to ping mock server");
+    noise_client
+        .ping()
+        .await
+        .expect("Failed to ping mock server");
+
+    // Both connections are idle, so neither holds on to a buffer
+    assert_eq!(pool.idle(), 2);
+}
+
+#[tokio::test]
+async fn test_mock_server_noise_invalid_key() {
+    let server = MockEspHomeServer::builder()
+        .key(KEY)
+        .start()
+        .await
+        .expect("Failed to start mock server");
+
+    let result = EspHomeClient::builder()
+        .address(server.address())
+        .key("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=")
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await;
+    assert!(
+        matches!(
+            result,
+            Err(ClientError::Connection(ConnectionError::Noise(
+                NoiseError::InvalidPsk
+            )))
+        ),
+        "Unexpected result: {result:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_mock_server_invalid_password() {
+    let server = MockEspHomeServer::builder()
+        .password("secret")
+        .start()
+        .await
+        .expect("Failed to start mock server");
+
+    let result = EspHomeClient::builder()
+        .address(server.address())
+        .password("wrong")
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await;
+    assert!(
+        matches!(result, Err(ClientError::Authentication { .. })),
+        "Unexpected result: {result:?}"
+    );
+}
@@ -1,5 +1,8 @@
+#![cfg(feature = "runtime-tokio")]
+
 use esphome_client::{
     EspHomeClient,
+    error::{ClientError, ConnectionError},
     types::{EspHomeMessage, HelloRequest, HelloResponse},
 };
 use prost::Message;
@@ -21,14 +24,17 @@ async fn test_noise_connection_hello() {
     tokio::time::sleep(Duration::from_millis(100)).await;
 
     // Build a noise connection
-    let mut stream = EspHomeClient::builder()
+    let stream = EspHomeClient::builder()
         .address(addr)
         .timeout(Duration::from_secs(2))
         .key(KEY)
+        .expected_mac("AB:CD:EF:01:23:45")
         .without_connection_setup()
         .connect()
         .await
         .expect("Failed to connect in noise mode");
+    assert_eq!(stream.server_name(), Some("ServerName"));
+    assert_eq!(stream.server_mac(), Some("abcdef012345"));
 
     // Send a HelloRequest
     let hello = HelloRequest {
@@ -57,6 +63,78 @@ async fn test_noise_connection_hello() {
     mock_server.close();
 }
 
+#[tokio::test]
+async fn test_noise_connection_server_name_mismatch() {
+    let addr = "127.0.0.1:16066";
+    let _mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let error = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .key(KEY)
+        .expected_server_name("livingroom")
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect_err("Connection to another device should fail");
+    assert!(
+        matches!(
+            &error,
+            ClientError::Connection(ConnectionError::ServerNameMismatch { expected, actual })
+                if expected == "livingroom" && actual == "ServerName"
+        ),
+        "Unexpected error: {error}"
+    );
+}
+
+#[tokio::test]
+async fn test_noise_connection_mac_mismatch() {
+    let addr = "127.0.0.1:16067";
+    let _mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let error = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .key(KEY)
+        .expected_mac("aa:bb:cc:dd:ee:ff")
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect_err("Connection to another device should fail");
+    assert!(
+        matches!(
+            &error,
+            ClientError::DeviceMismatch { expected, actual }
+                if expected == "aabbccddeeff" && actual == "abcdef012345"
+        ),
+        "Unexpected error: {error}"
+    );
+}
+
+#[tokio::test]
+async fn test_noise_connection_key_bytes() {
+    let addr = "127.0.0.1:16068";
+    let _mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let key: [u8; 32] = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x10, 0x11, 0x12, 0x13, 0x14,
+        0x15, 0x16, 0x17, 0x18, 0x19, 0x20, 0x21, 0x22, 0x23, 0x24, 0x25, 0x26, 0x27, 0x28, 0x29,
+        0x30, 0x31,
+    ];
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .key_bytes(key)
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect with raw key");
+    assert_eq!(client.server_name(), Some("ServerName"));
+}
+
 struct MockServer {
     handle: tokio::task::JoinHandle<()>,
 }
@@ -1,8 +1,27 @@
+#![cfg(feature = "runtime-tokio")]
+
 use esphome_client::{
-    EspHomeClient,
-    types::{EspHomeMessage, HelloRequest, HelloResponse},
+    BackoffPolicy, ConnectionEvent, EspHomeClient, LifecycleEvent, Sleep, Timer,
+    error::BluetoothError,
+    error::ClientError,
+    error::ConnectionError,
+    types::{
+        BluetoothDeviceConnectionResponse, BluetoothDeviceRequest, BluetoothDeviceRequestType,
+        BluetoothGattErrorResponse, BluetoothGattReadRequest, BluetoothGattReadResponse,
+        CameraImageRequest, CameraImageResponse, DeviceInfoRequest, DeviceInfoResponse,
+        EspHomeMessage, HelloRequest, HelloResponse, LightCommandRequest, MESSAGE_TYPES,
+        MessageType, NumberCommandRequest, PingRequest, PingResponse, SensorStateResponse,
+        SwitchCommandRequest,
+    },
 };
 use prost::Message;
+use std::{
+    net::SocketAddr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     net::TcpListener,
@@ -19,7 +38,7 @@ async fn test_plain_connection_hello() {
     tokio::time::sleep(Duration::from_millis(100)).await;
 
     // Build a plain connection (no key)
-    let mut stream = EspHomeClient::builder()
+    let stream = EspHomeClient::builder()
         .address(addr)
         .timeout(Duration::from_secs(2))
         .without_connection_setup()
@@ -54,6 +73,1651 @@ async fn test_plain_connection_hello() {
     mock_server.close();
 }
 
+#[tokio::test]
+async fn test_plain_connection_shared_client() {
+    let addr = "127.0.0.1:16055";
+    let mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = Arc::new(
+        EspHomeClient::builder()
+            .address(addr)
+            .timeout(Duration::from_secs(2))
+            .without_connection_setup()
+            .connect()
+            .await
+            .expect("Failed to connect in plain mode"),
+    );
+
+    // Read from a separate task while writing from this one
+    let reader = tokio::spawn({
+        let client = Arc::clone(&client);
+        async move { client.try_read().await }
+    });
+    client
+        .try_write(HelloRequest {
+            client_info: "integration-test".to_string(),
+            api_version_major: 1,
+            api_version_minor: 10,
+        })
+        .await
+        .expect("Failed to send HelloRequest");
+
+    let response = timeout(Duration::from_secs(2), reader)
+        .await
+        .expect("Timeout waiting for HelloResponse")
+        .expect("Reader task panicked")
+        .expect("Failed to read HelloResponse");
+    assert!(
+        matches!(response, EspHomeMessage::HelloResponse(_)),
+        "Expected HelloResponse, got {response:?}"
+    );
+
+    mock_server.close();
+}
+
+#[tokio::test]
+async fn test_plain_connection_subscribe() {
+    let addr = "127.0.0.1:16058";
+    let mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+    let mut first = client.subscribe();
+    let mut second = client.subscribe();
+
+    client
+        .try_write(HelloRequest {
+            client_info: "integration-test".to_string(),
+            api_version_major: 1,
+            api_version_minor: 10,
+        })
+        .await
+        .expect("Failed to send HelloRequest");
+    let response = timeout(Duration::from_secs(2), client.try_read())
+        .await
+        .expect("Timeout waiting for HelloResponse")
+        .expect("Failed to read HelloResponse");
+
+    // Every subscriber receives a copy of the message returned by try_read
+    assert_eq!(
+        first.try_recv().expect("No message for subscriber"),
+        response
+    );
+    assert_eq!(
+        second.try_recv().expect("No message for subscriber"),
+        response
+    );
+
+    mock_server.close();
+}
+
+#[tokio::test]
+async fn test_plain_connection_send_and_wait() {
+    let addr = "127.0.0.1:16059";
+    let mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .request_timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+
+    let response = client
+        .send_and_wait::<HelloResponse>(HelloRequest {
+            client_info: "integration-test".to_string(),
+            api_version_major: 1,
+            api_version_minor: 10,
+        })
+        .await
+        .expect("Failed to receive HelloResponse");
+    assert_eq!(response.name, "mock-server");
+
+    mock_server.close();
+}
+
+#[tokio::test]
+async fn test_plain_connection_send_and_wait_buffers_messages() {
+    let addr = "127.0.0.1:16060";
+    let mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .request_timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+
+    // The mock server closes the connection without sending a DeviceInfoResponse
+    let error = client
+        .send_and_wait::<DeviceInfoResponse>(HelloRequest {
+            client_info: "integration-test".to_string(),
+            api_version_major: 1,
+            api_version_minor: 10,
+        })
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ClientError::Disconnected),
+        "Unexpected error: {error}"
+    );
+
+    // The unrelated HelloResponse was buffered
+    let response = timeout(Duration::from_secs(2), client.try_read())
+        .await
+        .expect("Timeout waiting for HelloResponse")
+        .expect("Failed to read HelloResponse");
+    assert!(matches!(response, EspHomeMessage::HelloResponse(_)));
+
+    mock_server.close();
+}
+
+#[tokio::test]
+async fn test_plain_connection_send_and_wait_limits_buffer() {
+    let addr = "127.0.0.1:16090";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("Failed to accept");
+        let mut frames = Vec::new();
+        for key in 0..1030 {
+            let state = SensorStateResponse {
+                key,
+                ..Default::default()
+            }
+            .encode_to_vec();
+            frames.extend_from_slice(&[0x00, u8::try_from(state.len()).unwrap()]);
+            frames.push(u8::try_from(<SensorStateResponse as MessageType>::TYPE_ID).unwrap());
+            frames.extend_from_slice(&state);
+        }
+        frames.extend_from_slice(&[0x00, 0x00, 8]);
+        socket.write_all(&frames).await.expect("Send messages");
+        // Keep the connection open until the client is done
+        let mut buf = [0u8; 64];
+        while socket.read(&mut buf).await.is_ok_and(|read| read > 0) {}
+    });
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+    client
+        .send_and_wait::<PingResponse>(PingRequest {})
+        .await
+        .expect("Failed to receive PingResponse");
+
+    // The oldest of the buffered state messages were dropped
+    let message = client.try_read().await.expect("Failed to read state");
+    assert!(
+        matches!(message, EspHomeMessage::SensorStateResponse(ref state) if state.key == 6),
+        "Unexpected message: {message:?}"
+    );
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_plain_connection_auto_reconnect() {
+    let addr = "127.0.0.1:16056";
+    let mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let manager = Arc::new(
+        EspHomeClient::builder()
+            .address(addr)
+            .timeout(Duration::from_secs(2))
+            .without_connection_setup()
+            .connect_with_auto_reconnect(
+                BackoffPolicy::new()
+                    .initial_delay(Duration::from_millis(50))
+                    .max_attempts(3),
+            )
+            .await
+            .expect("Failed to connect in plain mode"),
+    );
+    let mut events = manager.events();
+    let hello = HelloRequest {
+        client_info: "integration-test".to_string(),
+        api_version_major: 1,
+        api_version_minor: 10,
+    };
+
+    // The mock server closes the connection after responding to the HelloRequest
+    manager
+        .try_write(hello.clone())
+        .await
+        .expect("Failed to send HelloRequest");
+    let response = timeout(Duration::from_secs(2), manager.try_read())
+        .await
+        .expect("Timeout waiting for HelloResponse")
+        .expect("Failed to read HelloResponse");
+    assert!(matches!(response, EspHomeMessage::HelloResponse(_)));
+
+    // Reading continues on a new connection once the closed connection is detected
+    let reader = tokio::spawn({
+        let manager = Arc::clone(&manager);
+        async move { manager.try_read().await }
+    });
+    timeout(Duration::from_secs(2), async {
+        while events.recv().await.expect("Event channel closed") != ConnectionEvent::Reconnected {}
+    })
+    .await
+    .expect("Timeout waiting for reconnect");
+
+    manager
+        .try_write(hello)
+        .await
+        .expect("Failed to send HelloRequest after reconnect");
+    let response = timeout(Duration::from_secs(2), reader)
+        .await
+        .expect("Timeout waiting for HelloResponse")
+        .expect("Reader task panicked")
+        .expect("Failed to read HelloResponse after reconnect");
+    assert!(matches!(response, EspHomeMessage::HelloResponse(_)));
+
+    mock_server.close();
+}
+
+#[tokio::test]
+async fn test_plain_connection_auto_reconnect_gives_up() {
+    // The device is replaced by another one, which fails the server name verification
+    let addr = "127.0.0.1:16089";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        for name in ["kitchen", "garage"] {
+            let (mut socket, _) = listener.accept().await.expect("Failed to accept");
+            let mut header = [0u8; 3];
+            socket.read_exact(&mut header).await.expect("Frame header");
+            let mut buf = vec![0u8; usize::from(header[1])];
+            socket.read_exact(&mut buf).await.expect("HelloRequest");
+            let response = HelloResponse {
+                name: name.to_owned(),
+                api_version_major: 1,
+                ..Default::default()
+            }
+            .encode_to_vec();
+            let mut frame = vec![0x00, u8::try_from(response.len()).unwrap(), 2];
+            frame.extend_from_slice(&response);
+            socket.write_all(&frame).await.expect("HelloResponse");
+        }
+    });
+
+    let manager = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .expected_server_name("kitchen")
+        .connect_with_auto_reconnect(BackoffPolicy::new().initial_delay(Duration::from_millis(10)))
+        .await
+        .expect("Failed to connect in plain mode");
+    let mut events = manager.events();
+
+    let error = timeout(Duration::from_secs(2), async {
+        loop {
+            if let Err(e) = manager.try_read().await {
+                return e;
+            }
+        }
+    })
+    .await
+    .expect("Reconnecting should give up right away");
+    assert!(
+        matches!(
+            error,
+            ClientError::Connection(ConnectionError::ServerNameMismatch { .. })
+        ),
+        "Unexpected error: {error}"
+    );
+    let mut received = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        received.push(event);
+    }
+    assert_eq!(received.last(), Some(&ConnectionEvent::GaveUp));
+    server.abort();
+}
+
+#[cfg(feature = "sink")]
+#[tokio::test]
+async fn test_plain_connection_sink() {
+    use futures_util::SinkExt;
+
+    let addr = "127.0.0.1:16057";
+    let mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+
+    let mut sink = client.write_stream();
+    sink.send(
+        HelloRequest {
+            client_info: "integration-test".to_string(),
+            api_version_major: 1,
+            api_version_minor: 10,
+        }
+        .into(),
+    )
+    .await
+    .expect("Failed to send HelloRequest");
+
+    let response = timeout(Duration::from_secs(2), client.try_read())
+        .await
+        .expect("Timeout waiting for HelloResponse")
+        .expect("Failed to read HelloResponse");
+    assert!(matches!(response, EspHomeMessage::HelloResponse(_)));
+
+    mock_server.close();
+}
+
+#[tokio::test]
+async fn test_plain_connection_ping() {
+    let addr = "127.0.0.1:16061";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("Failed to accept");
+        // PingRequest has an empty payload: preamble, length 0, type 7
+        let mut request = [0u8; 3];
+        socket
+            .read_exact(&mut request)
+            .await
+            .expect("Read PingRequest");
+        assert_eq!(request, [0, 0, 7]);
+        socket
+            .write_all(&[0, 0, 8])
+            .await
+            .expect("Send PingResponse");
+        socket
+    });
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .request_timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+
+    let rtt = client.ping().await.expect("Failed to ping");
+    assert!(rtt < Duration::from_secs(2));
+    server.await.expect("Mock server failed");
+}
+
+#[tokio::test]
+async fn test_plain_connection_idle_timeout() {
+    let addr = "127.0.0.1:16062";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    // Accept the connection, but never send anything
+    let server = tokio::spawn(async move { listener.accept().await });
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .idle_timeout(Duration::from_millis(200))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+
+    let error = timeout(Duration::from_secs(2), client.try_read())
+        .await
+        .expect("Idle timeout did not trigger")
+        .unwrap_err();
+    assert!(
+        matches!(error, ClientError::IdleTimeout { timeout_ms: 200 }),
+        "Unexpected error: {error}"
+    );
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_plain_connection_capture_image() {
+    let addr = "127.0.0.1:16063";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("Failed to accept");
+        let request = read_camera_request(&mut socket).await;
+        assert!(request.single && !request.stream);
+
+        // Chunks of another camera are interleaved with the requested one
+        write_camera_chunk(&mut socket, 1, &[1, 2], false).await;
+        write_camera_chunk(&mut socket, 2, &[9], true).await;
+        write_camera_chunk(&mut socket, 1, &[3], true).await;
+        socket
+    });
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .request_timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+
+    let image = client
+        .capture_image(1)
+        .await
+        .expect("Failed to capture image");
+    assert_eq!(image, vec![1, 2, 3]);
+    // The chunk of the other camera is still available
+    let other = client.try_read().await.expect("Failed to read");
+    assert!(matches!(other, EspHomeMessage::CameraImageResponse(chunk) if chunk.key == 2));
+    server.await.expect("Mock server failed");
+}
+
+#[cfg(feature = "stream")]
+#[tokio::test]
+async fn test_plain_connection_camera_stream() {
+    use esphome_client::camera::CameraStream;
+    use futures_util::StreamExt;
+
+    let addr = "127.0.0.1:16064";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("Failed to accept");
+        let request = read_camera_request(&mut socket).await;
+        assert!(request.stream && !request.single);
+        write_camera_chunk(&mut socket, 1, &[1], false).await;
+        write_camera_chunk(&mut socket, 1, &[2], true).await;
+
+        // The stream request is repeated while streaming
+        let request = read_camera_request(&mut socket).await;
+        assert!(request.stream);
+        write_camera_chunk(&mut socket, 1, &[3], true).await;
+        socket
+    });
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+
+    let mut images = CameraStream::with_refresh_interval(&client, 1, Duration::from_millis(100));
+    let first = timeout(Duration::from_secs(2), images.next())
+        .await
+        .expect("Timeout waiting for first image");
+    assert_eq!(first, Some(vec![1, 2]));
+    let second = timeout(Duration::from_secs(2), images.next())
+        .await
+        .expect("Timeout waiting for second image");
+    assert_eq!(second, Some(vec![3]));
+    server.await.expect("Mock server failed");
+}
+
+#[tokio::test]
+async fn test_plain_connection_gatt_read() {
+    let addr = "127.0.0.1:16065";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("Failed to accept");
+        let mut header = [0u8; 3];
+        socket
+            .read_exact(&mut header)
+            .await
+            .expect("Read BluetoothGattReadRequest header");
+        assert_eq!(header[2], 73); // Message type ID for BluetoothGattReadRequest
+        let mut buf = vec![0u8; header[1] as usize];
+        socket
+            .read_exact(&mut buf)
+            .await
+            .expect("Read BluetoothGattReadRequest");
+        let request = BluetoothGattReadRequest::decode(buf.as_slice())
+            .expect("Decode BluetoothGattReadRequest");
+
+        // A read of another handle is answered first
+        for handle in [request.handle + 1, request.handle] {
+            let response = BluetoothGattReadResponse {
+                address: request.address,
+                handle,
+                data: vec![u8::try_from(handle).expect("Small handle")],
+            }
+            .encode_to_vec();
+            socket
+                .write_all(&[vec![0, response.len() as u8, 74], response].concat())
+                .await
+                .expect("Send BluetoothGattReadResponse");
+        }
+
+        socket
+            .read_exact(&mut header)
+            .await
+            .expect("Read second BluetoothGattReadRequest header");
+        let mut buf = vec![0u8; header[1] as usize];
+        socket
+            .read_exact(&mut buf)
+            .await
+            .expect("Read second BluetoothGattReadRequest");
+        let error = BluetoothGattErrorResponse {
+            address: request.address,
+            handle: request.handle,
+            error: 5,
+        }
+        .encode_to_vec();
+        socket
+            .write_all(&[vec![0, error.len() as u8, 82], error].concat())
+            .await
+            .expect("Send BluetoothGattErrorResponse");
+        socket
+    });
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .request_timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+
+    let value = client.gatt_read(0xAABB, 12).await.expect("Failed to read");
+    assert_eq!(value, vec![12]);
+    let error = client.gatt_read(0xAABB, 12).await.unwrap_err();
+    assert!(
+        matches!(
+            error,
+            ClientError::Bluetooth(BluetoothError::Gatt {
+                address: 0xAABB,
+                handle: 12,
+                error: 5
+            })
+        ),
+        "Unexpected error: {error}"
+    );
+    // The read of the other handle is still available
+    let other = client.try_read().await.expect("Failed to read");
+    assert!(
+        matches!(other, EspHomeMessage::BluetoothGattReadResponse(response) if response.handle == 13)
+    );
+    server.await.expect("Mock server failed");
+}
+
+#[tokio::test]
+async fn test_plain_connection_ble_connect() {
+    let addr = "127.0.0.1:16091";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("Failed to accept");
+        let mut header = [0u8; 3];
+        socket
+            .read_exact(&mut header)
+            .await
+            .expect("Read BluetoothDeviceRequest header");
+        assert_eq!(header[2], 68); // Message type ID for BluetoothDeviceRequest
+        let mut buf = vec![0u8; usize::from(header[1])];
+        socket
+            .read_exact(&mut buf)
+            .await
+            .expect("Read BluetoothDeviceRequest");
+        let request =
+            BluetoothDeviceRequest::decode(buf.as_slice()).expect("Decode BluetoothDeviceRequest");
+        let response = BluetoothDeviceConnectionResponse {
+            address: request.address,
+            connected: true,
+            mtu: 247,
+            error: 0,
+        }
+        .encode_to_vec();
+        socket
+            .write_all(&[vec![0, u8::try_from(response.len()).unwrap(), 69], response].concat())
+            .await
+            .expect("Send BluetoothDeviceConnectionResponse");
+        (request, socket)
+    });
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .request_timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+
+    let connection = client
+        .ble_connect(0xAABB, 1, false)
+        .await
+        .expect("Failed to connect");
+    assert_eq!(connection.mtu, 247);
+    let (request, _socket) = server.await.expect("Mock server failed");
+    assert_eq!(
+        request.request_type,
+        i32::from(BluetoothDeviceRequestType::ConnectV3WithoutCache)
+    );
+    assert!(request.has_address_type);
+    assert_eq!(request.address_type, 1);
+}
+
+#[tokio::test]
+async fn test_plain_connection_encryption_required() {
+    // Mock a device that requires encryption, which rejects plain connections with a noise frame
+    let addr = "127.0.0.1:16069";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("Failed to accept");
+        let reason = b"\x01Bad indicator byte";
+        let mut frame = vec![0x01, 0x00, u8::try_from(reason.len()).unwrap()];
+        frame.extend_from_slice(reason);
+        socket.write_all(&frame).await.expect("Failed to reject");
+    });
+
+    let error = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await
+        .expect_err("Plain connection should be rejected");
+    assert!(
+        matches!(
+            error,
+            ClientError::Connection(ConnectionError::EncryptionRequired)
+        ),
+        "Unexpected error: {error}"
+    );
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_plain_connection_closed_during_setup() {
+    // A device closing the connection without a noise frame may be rebooting, which is recoverable
+    let addr = "127.0.0.1:16087";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.expect("Failed to accept");
+        drop(socket);
+    });
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&events);
+    let error = EspHomeClient::builder()
+        .address(addr)
+        .on_lifecycle(move |event| recorded.lock().unwrap().push(event.clone()))
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await
+        .expect_err("Connection should fail");
+    assert!(
+        matches!(error, ClientError::Disconnected),
+        "Unexpected error: {error}"
+    );
+    assert!(error.is_recoverable());
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            LifecycleEvent::Connected,
+            LifecycleEvent::Disconnected {
+                reason: ClientError::Disconnected.to_string()
+            }
+        ]
+    );
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_plain_connection_concurrent_writes() {
+    let addr = "127.0.0.1:16070";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("Failed to accept");
+        let mut received = Vec::new();
+        for _ in 0..32 {
+            let mut header = [0u8; 3];
+            socket.read_exact(&mut header).await.expect("Frame header");
+            assert_eq!(header[0], 0, "Frames are not interleaved");
+            assert_eq!(header[2], 1, "Message type ID for HelloRequest");
+            let mut buf = vec![0u8; usize::from(header[1])];
+            socket.read_exact(&mut buf).await.expect("Frame payload");
+            let request = HelloRequest::decode(buf.as_slice()).expect("Valid HelloRequest");
+            received.push(request.client_info);
+        }
+        received
+    });
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+    let writes = (0..32u8).map(|i| {
+        let writer = client.write_stream();
+        tokio::spawn(async move {
+            writer
+                .try_write(HelloRequest {
+                    client_info: char::from(b'A' + i % 26).to_string().repeat(100),
+                    api_version_major: 1,
+                    api_version_minor: 10,
+                })
+                .await
+        })
+    });
+    for write in writes.collect::<Vec<_>>() {
+        write.await.unwrap().expect("Failed to write");
+    }
+
+    let received = timeout(Duration::from_secs(2), server)
+        .await
+        .expect("Timeout waiting for frames")
+        .unwrap();
+    for client_info in received {
+        let first = client_info.chars().next().unwrap();
+        assert!(client_info.chars().all(|c| c == first));
+    }
+}
+
+#[tokio::test]
+async fn test_plain_connection_write_batch() {
+    let addr = "127.0.0.1:16071";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("Failed to accept");
+        let mut received = Vec::new();
+        for _ in 0..3 {
+            let mut header = [0u8; 3];
+            socket.read_exact(&mut header).await.expect("Frame header");
+            let mut buf = vec![0u8; usize::from(header[1])];
+            socket.read_exact(&mut buf).await.expect("Frame payload");
+            received.push(header[2]);
+        }
+        received
+    });
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+    let mut batch = client.begin_batch();
+    batch
+        .push(HelloRequest::default())
+        .push(PingRequest {})
+        .push(DeviceInfoRequest {});
+    assert_eq!(batch.len(), 3);
+    batch.flush().await.expect("Failed to write batch");
+
+    let received = timeout(Duration::from_secs(2), server)
+        .await
+        .expect("Timeout waiting for frames")
+        .unwrap();
+    assert_eq!(received, [1, 7, 9], "Messages are written in order");
+}
+
+#[tokio::test]
+async fn test_plain_connection_write_batch_coalesced() {
+    let addr = "127.0.0.1:16086";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("Failed to accept");
+        let mut received = Vec::new();
+        for _ in 0..5 {
+            let mut header = [0u8; 3];
+            socket.read_exact(&mut header).await.expect("Frame header");
+            let mut buf = vec![0u8; usize::from(header[1])];
+            socket.read_exact(&mut buf).await.expect("Frame payload");
+            received.push((header[2], buf));
+        }
+        received
+    });
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+    let mut batch = client.begin_batch().coalesce_commands();
+    // Light commands only carry the changed fields, so none of them is dropped
+    batch
+        .push(LightCommandRequest {
+            key: 1,
+            has_state: true,
+            state: true,
+            ..Default::default()
+        })
+        .push(LightCommandRequest {
+            key: 1,
+            has_brightness: true,
+            brightness: 0.5,
+            ..Default::default()
+        });
+    for value in [0.2, 0.5, 0.8] {
+        let mut number = NumberCommandRequest {
+            key: 2,
+            ..Default::default()
+        };
+        number.state = value;
+        batch.push(number);
+        let mut switch = SwitchCommandRequest {
+            key: 3,
+            ..Default::default()
+        };
+        switch.state = value > 0.5;
+        batch.push(switch);
+    }
+    batch.push(PingRequest {});
+    assert_eq!(batch.len(), 5);
+    batch.flush().await.expect("Failed to write batch");
+
+    let received = timeout(Duration::from_secs(2), server)
+        .await
+        .expect("Timeout waiting for frames")
+        .unwrap();
+    let types: Vec<u8> = received
+        .iter()
+        .map(|(message_type, _)| *message_type)
+        .collect();
+    assert_eq!(
+        types,
+        [32, 32, 51, 33, 7],
+        "Only the last full-state command per entity is written"
+    );
+    let light = LightCommandRequest::decode(received[0].1.as_slice()).expect("Light command");
+    assert!(light.has_state && light.state);
+    let number = NumberCommandRequest::decode(received[2].1.as_slice()).expect("Number command");
+    assert!((number.state - 0.8).abs() < f32::EPSILON);
+    let switch = SwitchCommandRequest::decode(received[3].1.as_slice()).expect("Switch command");
+    assert!(switch.state);
+}
+
+#[tokio::test]
+async fn test_plain_connection_write_many() {
+    let addr = "127.0.0.1:16085";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("Failed to accept");
+        let mut received = Vec::new();
+        for _ in 0..3 {
+            let mut header = [0u8; 3];
+            socket.read_exact(&mut header).await.expect("Frame header");
+            let mut buf = vec![0u8; usize::from(header[1])];
+            socket.read_exact(&mut buf).await.expect("Frame payload");
+            received.push(header[2]);
+        }
+        received
+    });
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+    client
+        .try_write_many(Vec::<EspHomeMessage>::new())
+        .await
+        .expect("Failed to write no messages");
+    client
+        .try_write_many([
+            EspHomeMessage::from(HelloRequest::default()),
+            PingRequest {}.into(),
+            DeviceInfoRequest {}.into(),
+        ])
+        .await
+        .expect("Failed to write messages");
+
+    let received = timeout(Duration::from_secs(2), server)
+        .await
+        .expect("Timeout waiting for frames")
+        .unwrap();
+    assert_eq!(received, [1, 7, 9], "Messages are written in order");
+}
+
+#[tokio::test]
+async fn test_plain_connection_with_stream() {
+    let addr = "127.0.0.1:16072";
+    let mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let tcp_stream = tokio::net::TcpStream::connect(addr)
+        .await
+        .expect("Failed to connect to mock server");
+    let client = EspHomeClient::builder()
+        .with_stream(tcp_stream)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect with stream");
+    client
+        .try_write(HelloRequest::default())
+        .await
+        .expect("Failed to send HelloRequest");
+    let response = timeout(Duration::from_secs(2), client.try_read())
+        .await
+        .expect("Timeout waiting for HelloResponse")
+        .expect("Failed to read HelloResponse");
+    assert!(
+        matches!(response, EspHomeMessage::HelloResponse(_)),
+        "Unexpected response: {response:?}"
+    );
+    mock_server.close();
+}
+
+/// Timer counting the started sleeps, to check the client uses the timer of the builder.
+#[derive(Debug)]
+struct CountingTimer(Arc<AtomicUsize>);
+
+impl Timer for CountingTimer {
+    fn sleep(&self, duration: Duration) -> Sleep {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+#[tokio::test]
+async fn test_plain_connection_with_transport() {
+    let addr = "127.0.0.1:16088";
+    let mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let tcp_stream = tokio::net::TcpStream::connect(addr)
+        .await
+        .expect("Failed to connect to mock server");
+    let sleeps = Arc::new(AtomicUsize::new(0));
+    let client = EspHomeClient::builder()
+        .with_transport(tcp_stream)
+        .timer(CountingTimer(Arc::clone(&sleeps)))
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect with transport");
+    let connect_sleeps = sleeps.load(Ordering::SeqCst);
+    assert!(connect_sleeps > 0, "Connect timeout doesn't use the timer");
+    client
+        .try_write(HelloRequest::default())
+        .await
+        .expect("Failed to send HelloRequest");
+    let response = client
+        .try_read_timeout(Duration::from_secs(2))
+        .await
+        .expect("Failed to read HelloResponse");
+    assert!(
+        matches!(response, EspHomeMessage::HelloResponse(_)),
+        "Unexpected response: {response:?}"
+    );
+    assert!(
+        sleeps.load(Ordering::SeqCst) > connect_sleeps,
+        "Read timeout doesn't use the timer"
+    );
+    mock_server.close();
+}
+
+#[tokio::test]
+async fn test_plain_connection_address_fallback() {
+    let addr = "127.0.0.1:16073";
+    let _mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Nothing listens on the first address, the connection falls back to the second address
+    let addrs: Vec<SocketAddr> = vec!["127.0.0.1:16074".parse().unwrap(), addr.parse().unwrap()];
+    let client = EspHomeClient::builder()
+        .address(addrs)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect to the second address");
+    client
+        .try_write(HelloRequest::default())
+        .await
+        .expect("Failed to send HelloRequest");
+
+    let result = EspHomeClient::builder()
+        .address(vec!["127.0.0.1:16074".parse::<SocketAddr>().unwrap()])
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await;
+    assert!(
+        matches!(
+            result,
+            Err(ClientError::Connection(ConnectionError::TcpConnect { .. }))
+        ),
+        "Expected a connection error"
+    );
+}
+
+#[tokio::test]
+async fn test_plain_connection_local_address() {
+    let addr = "127.0.0.1:16075";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (_socket, peer) = listener.accept().await.expect("Failed to accept");
+        peer
+    });
+
+    let local_ip: std::net::IpAddr = "127.0.0.2".parse().unwrap();
+    let _client = EspHomeClient::builder()
+        .address(addr)
+        .local_address(local_ip)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect from local address");
+    let peer = timeout(Duration::from_secs(2), server)
+        .await
+        .expect("Timeout waiting for connection")
+        .unwrap();
+    assert_eq!(
+        peer.ip(),
+        local_ip,
+        "Connection is bound to the local address"
+    );
+}
+
+#[tokio::test]
+async fn test_plain_connection_socket_options() {
+    let addr = "127.0.0.1:16076";
+    let _mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .tcp_nodelay(false)
+        .tcp_keepalive(Duration::from_secs(10), 3)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect with socket options");
+    client
+        .try_write(HelloRequest::default())
+        .await
+        .expect("Failed to send HelloRequest");
+    let response = timeout(Duration::from_secs(2), client.try_read())
+        .await
+        .expect("Timeout waiting for HelloResponse")
+        .expect("Failed to read HelloResponse");
+    assert!(
+        matches!(response, EspHomeMessage::HelloResponse(_)),
+        "Unexpected response: {response:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_plain_connection_setup_timeout() {
+    let addr = "127.0.0.1:16077";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    // Accept the connection, but never answer the HelloRequest
+    let server = tokio::spawn(async move {
+        let (socket, _) = listener.accept().await.expect("Failed to accept");
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        drop(socket);
+    });
+
+    let result = timeout(
+        Duration::from_secs(2),
+        EspHomeClient::builder()
+            .address(addr)
+            .setup_timeout(Duration::from_millis(200))
+            .connect(),
+    )
+    .await
+    .expect("Connection setup is not limited by the setup timeout");
+    assert!(
+        matches!(result, Err(ClientError::Timeout { timeout_ms: 200 })),
+        "Expected a timeout error"
+    );
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_plain_connection_read_timeout() {
+    let addr = "127.0.0.1:16078";
+    let _mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+    let result = client.try_read_timeout(Duration::from_millis(100)).await;
+    assert!(
+        matches!(result, Err(ClientError::Timeout { timeout_ms: 100 })),
+        "Expected a timeout without a message"
+    );
+
+    client
+        .try_write(HelloRequest::default())
+        .await
+        .expect("Failed to send HelloRequest");
+    let response = client
+        .try_read_timeout(Duration::from_secs(2))
+        .await
+        .expect("Failed to read HelloResponse");
+    assert!(
+        matches!(response, EspHomeMessage::HelloResponse(_)),
+        "Unexpected response: {response:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_plain_connection_read_now() {
+    let addr = "127.0.0.1:16079";
+    let _mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+    assert!(
+        client
+            .try_read_now()
+            .await
+            .expect("Failed to read")
+            .is_none(),
+        "No message is received yet"
+    );
+
+    client
+        .try_write(HelloRequest::default())
+        .await
+        .expect("Failed to send HelloRequest");
+    let response = timeout(Duration::from_secs(2), async {
+        loop {
+            if let Some(message) = client.try_read_now().await.expect("Failed to read") {
+                return message;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("Timeout waiting for HelloResponse");
+    assert!(
+        matches!(response, EspHomeMessage::HelloResponse(_)),
+        "Unexpected response: {response:?}"
+    );
+}
+
+#[tokio::test]
+async fn test_plain_connection_cancelled_reads() {
+    let addr = "127.0.0.1:16084";
+    let listener = TcpListener::bind(addr)
+        .await
+        .expect("Failed to bind mock server");
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.expect("Failed to accept");
+        // PingRequest has an empty payload: preamble, length 0, type 7
+        socket
+            .write_all(&[0, 0, 7])
+            .await
+            .expect("Send PingRequest");
+        let mut response = [0u8; 3];
+        socket
+            .read_exact(&mut response)
+            .await
+            .expect("Read PingResponse");
+        assert_eq!(response, [0, 0, 8]);
+
+        // DeviceInfoResponse (type 10), sent in pieces while the client cancels its reads
+        let mut payload = vec![];
+        DeviceInfoResponse {
+            name: "kitchen".to_owned(),
+            ..Default::default()
+        }
+        .encode(&mut payload)
+        .expect("Encoding DeviceInfoResponse failed");
+        let frame = [
+            [0].to_vec(),
+            convert_to_leb128(payload.len() as u16),
+            [10].to_vec(),
+            payload,
+        ]
+        .concat();
+        for chunk in frame.chunks(4) {
+            socket.write_all(chunk).await.expect("Send chunk");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        socket
+    });
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .without_connection_setup()
+        .connect()
+        .await
+        .expect("Failed to connect in plain mode");
+
+    let message = timeout(Duration::from_secs(2), async {
+        loop {
+            tokio::select! {
+                message = client.try_read() => return message.expect("Failed to read"),
+                () = tokio::time::sleep(Duration::from_millis(3)) => {}
+            }
+        }
+    })
+    .await
+    .expect("Timeout waiting for DeviceInfoResponse");
+    match message {
+        EspHomeMessage::DeviceInfoResponse(info) => assert_eq!(info.name, "kitchen"),
+        other => panic!("Expected DeviceInfoResponse, got {other:?}"),
+    }
+    server.await.expect("Mock server failed");
+}
+
+#[tokio::test]
+async fn test_plain_connection_hello_response() {
+    let addr = "127.0.0.1:16080";
+    let _mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await
+        .expect("Failed to connect with connection setup");
+    assert_eq!(client.api_version(), Some((1, 10)));
+    assert_eq!(client.server_info(), Some("mock-server"));
+    assert_eq!(
+        client.hello_response().map(|hello| hello.name.as_str()),
+        Some("mock-server")
+    );
+}
+
+#[test]
+fn test_plain_connection_tracing_span() {
+    #[derive(Clone, Default)]
+    struct LogBuffer(Arc<std::sync::Mutex<Vec<u8>>>);
+    impl std::io::Write for LogBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let logs = LogBuffer::default();
+    let writer = logs.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to build runtime");
+    tracing::subscriber::with_default(subscriber, || {
+        runtime.block_on(async {
+            let addr = "127.0.0.1:16083";
+            let _mock_server = MockServer::start(addr.into());
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let client = EspHomeClient::builder()
+                .address(addr)
+                .timeout(Duration::from_secs(2))
+                .connect()
+                .await
+                .expect("Failed to connect with connection setup");
+            client.close().await.expect("Failed to close the client");
+        });
+    });
+
+    let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+    assert!(
+        logs.lines().any(|line| line.contains(
+            "esphome{address=127.0.0.1:16083}: esphome_client::client: Send: HelloRequest"
+        )),
+        "Missing address in logs: {logs}"
+    );
+    assert!(
+        logs.lines().any(|line| line.contains(
+            "esphome{address=127.0.0.1:16083 name=\"mock-server\"}: esphome_client::client: Send: DisconnectRequest"
+        )),
+        "Missing device name in logs: {logs}"
+    );
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn test_plain_connection_metrics() {
+    use esphome_client::metrics::{BYTES_RECEIVED, BYTES_SENT, MESSAGES_RECEIVED, MESSAGES_SENT};
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("Failed to build runtime");
+    metrics::with_local_recorder(&recorder, || {
+        runtime.block_on(async {
+            let addr = "127.0.0.1:16082";
+            let _mock_server = MockServer::start(addr.into());
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            let client = EspHomeClient::builder()
+                .address(addr)
+                .timeout(Duration::from_secs(2))
+                .connect()
+                .await
+                .expect("Failed to connect with connection setup");
+            client.close().await.expect("Failed to close the client");
+        });
+    });
+
+    let counters: Vec<(String, Vec<String>, u64)> = snapshotter
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .filter_map(|(key, _, _, value)| match value {
+            DebugValue::Counter(count) => Some((
+                key.key().name().to_owned(),
+                key.key()
+                    .labels()
+                    .map(|label| label.value().to_owned())
+                    .collect(),
+                count,
+            )),
+            _ => None,
+        })
+        .collect();
+    assert!(
+        counters.contains(&(MESSAGES_SENT.to_owned(), vec!["HelloRequest".to_owned()], 1)),
+        "Unexpected counters: {counters:?}"
+    );
+    assert!(
+        counters.contains(&(
+            MESSAGES_RECEIVED.to_owned(),
+            vec!["HelloResponse".to_owned()],
+            1
+        )),
+        "Unexpected counters: {counters:?}"
+    );
+    for name in [BYTES_SENT, BYTES_RECEIVED] {
+        assert!(
+            counters
+                .iter()
+                .any(|(counter, _, count)| counter == name && *count > 0),
+            "No bytes counted for {name}: {counters:?}"
+        );
+    }
+}
+
+#[cfg(not(any(
+    feature = "api-1-12",
+    feature = "api-1-10",
+    feature = "api-1-9",
+    feature = "api-1-8"
+)))]
+#[tokio::test]
+async fn test_plain_connection_negotiate_api_version() {
+    use esphome_client::{error::ProtocolError, types::ZWaveProxyRequest};
+
+    let addr = "127.0.0.1:16081";
+    let _mock_server = MockServer::start(addr.into());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let client = EspHomeClient::builder()
+        .address(addr)
+        .timeout(Duration::from_secs(2))
+        .negotiate_api_version()
+        .connect()
+        .await
+        .expect("Failed to connect with connection setup");
+    // The mock server reports API version 1.10, which doesn't know about Z-Wave proxies
+    let result = client.try_write(ZWaveProxyRequest::default()).await;
+    assert!(
+        matches!(
+            result,
+            Err(ClientError::Protocol(
+                ProtocolError::UnsupportedMessage { .. }
+            ))
+        ),
+        "Expected an unsupported message error"
+    );
+    // Write streams and batches check the messages like the client
+    let result = client
+        .write_stream()
+        .try_write(ZWaveProxyRequest::default())
+        .await;
+    assert!(
+        matches!(
+            result,
+            Err(ClientError::Protocol(
+                ProtocolError::UnsupportedMessage { .. }
+            ))
+        ),
+        "Expected an unsupported message error from the write stream"
+    );
+    let mut batch = client.begin_batch();
+    batch
+        .push(PingRequest {})
+        .push(ZWaveProxyRequest::default());
+    assert!(
+        matches!(
+            batch.flush().await,
+            Err(ClientError::Protocol(
+                ProtocolError::UnsupportedMessage { .. }
+            ))
+        ),
+        "Expected an unsupported message error from the batch"
+    );
+    client
+        .try_write(PingRequest {})
+        .await
+        .expect("Messages known by the device are sent");
+}
+
+#[cfg(feature = "api-versions")]
+#[test]
+fn test_parallel_api_versions() {
+    use esphome_client::types::{v1_8, v1_14};
+
+    assert_eq!(v1_8::API_VERSION, (1, 8));
+    assert_eq!(v1_14::API_VERSION, (1, 14));
+    let payload: Vec<u8> = v1_8::EspHomeMessage::from(v1_8::HelloRequest {
+        client_info: "versions".to_owned(),
+        api_version_major: 1,
+        api_version_minor: 8,
+    })
+    .into();
+    let message = v1_14::EspHomeMessage::try_from(payload).expect("Failed to decode message");
+    assert!(
+        matches!(&message, v1_14::EspHomeMessage::HelloRequest(hello) if hello.client_info == "versions"),
+        "Unexpected message: {message:?}"
+    );
+}
+
+#[test]
+fn test_unknown_message_type() {
+    let payload = vec![0x03, 0xE7, 0x00, 0x02, 0x08, 0x01];
+    let message = EspHomeMessage::try_from(payload.clone()).expect("Unknown messages are decoded");
+    assert_eq!(
+        message,
+        EspHomeMessage::Unknown {
+            type_id: 999,
+            payload: vec![0x08, 0x01],
+        }
+    );
+    assert_eq!(message.get_message_type(), 999);
+    assert_eq!(
+        EspHomeMessage::decode_frame(999, &payload[4..]),
+        Ok(message.clone())
+    );
+    assert_eq!(Vec::<u8>::from(message), payload, "Payload is kept as is");
+}
+
+#[test]
+fn test_message_name_and_display() {
+    let message: EspHomeMessage = SwitchCommandRequest {
+        key: 42,
+        ..Default::default()
+    }
+    .into();
+    assert_eq!(message.message_name(), "SwitchCommandRequest");
+    assert_eq!(message.to_string(), "SwitchCommandRequest(key=42)");
+
+    let message: EspHomeMessage = HelloRequest::default().into();
+    assert_eq!(message.to_string(), "HelloRequest");
+
+    let message = EspHomeMessage::Unknown {
+        type_id: 999,
+        payload: vec![0x08, 0x01],
+    };
+    assert_eq!(message.message_name(), "Unknown");
+    assert_eq!(message.to_string(), "Unknown(type_id=999, 2 bytes)");
+}
+
+#[test]
+fn test_message_type_ids() {
+    assert_eq!(<HelloRequest as MessageType>::TYPE_ID, 1);
+    assert_eq!(
+        EspHomeMessage::from(PingRequest::default()).get_message_type(),
+        <PingRequest as MessageType>::TYPE_ID
+    );
+    assert_eq!(
+        EspHomeMessage::message_name_of_type(2),
+        Some("HelloResponse")
+    );
+    assert_eq!(EspHomeMessage::message_name_of_type(999), None);
+    assert!(MESSAGE_TYPES.contains(&(7, "PingRequest")));
+}
+
+#[cfg(feature = "reflect")]
+#[test]
+fn test_dynamic_message() {
+    use esphome_client::prost_reflect::{ReflectMessage as _, Value};
+
+    let message: EspHomeMessage = HelloRequest {
+        client_info: "reflect".to_owned(),
+        api_version_major: 1,
+        api_version_minor: 10,
+    }
+    .into();
+    let dynamic = message
+        .to_dynamic()
+        .expect("Known messages have a descriptor");
+    assert_eq!(dynamic.descriptor().name(), "HelloRequest");
+    assert_eq!(
+        dynamic.get_field_by_name("client_info").as_deref(),
+        Some(&Value::String("reflect".to_owned()))
+    );
+    assert_eq!(
+        dynamic.get_field_by_name("api_version_minor").as_deref(),
+        Some(&Value::U32(10))
+    );
+
+    let unknown = EspHomeMessage::Unknown {
+        type_id: 999,
+        payload: vec![],
+    };
+    assert!(unknown.to_dynamic().is_none());
+}
+
+#[tokio::test]
+async fn test_plain_connection_without_address() {
+    let result = EspHomeClient::builder()
+        .without_connection_setup()
+        .connect()
+        .await;
+    assert!(
+        matches!(result, Err(ClientError::Configuration { .. })),
+        "Expected a configuration error"
+    );
+}
+
+async fn read_camera_request(socket: &mut tokio::net::TcpStream) -> CameraImageRequest {
+    let mut header = [0u8; 3];
+    socket
+        .read_exact(&mut header)
+        .await
+        .expect("Read CameraImageRequest header");
+    assert_eq!(header[2], 45); // Message type ID for CameraImageRequest
+    let mut buf = vec![0u8; header[1] as usize];
+    socket
+        .read_exact(&mut buf)
+        .await
+        .expect("Read CameraImageRequest");
+    CameraImageRequest::decode(buf.as_slice()).expect("Decode CameraImageRequest")
+}
+
+async fn write_camera_chunk(socket: &mut tokio::net::TcpStream, key: u32, data: &[u8], done: bool) {
+    let chunk = CameraImageResponse {
+        key,
+        data: data.to_vec(),
+        done,
+        #[cfg(not(any(feature = "api-1-10", feature = "api-1-9", feature = "api-1-8")))]
+        device_id: 0,
+    }
+    .encode_to_vec();
+    socket
+        .write_all(&[vec![0, chunk.len() as u8, 44], chunk].concat())
+        .await
+        .expect("Send CameraImageResponse");
+}
+
 struct MockServer {
     handle: tokio::task::JoinHandle<()>,
 }
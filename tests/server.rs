@@ -0,0 +1,121 @@
+#![cfg(feature = "runtime-tokio")]
+
+use esphome_client::{
+    EspHomeClient,
+    error::{ClientError, ConnectionError, NoiseError},
+    server::EspHomeServer,
+    types::{DeviceInfoRequest, DeviceInfoResponse, EspHomeMessage},
+};
+use tokio::time::Duration;
+
+const KEY: &str = "px7tsbK3C7bpXHr2OevEV2ZMg/FrNBw2+O2pNPbedtA=";
+
+#[tokio::test]
+async fn test_server_plain_connection() {
+    let server = EspHomeServer::builder()
+        .name("virtual")
+        .bind("127.0.0.1:0")
+        .await
+        .expect("Failed to start server");
+    let address = server.local_addr().unwrap();
+
+    let device = tokio::spawn(async move {
+        let mut connection = server.accept().await.expect("Failed to accept client");
+        let hello = connection.read().await.expect("Failed to read hello");
+        assert!(matches!(hello, EspHomeMessage::HelloRequest(_)));
+        assert_eq!(connection.client_info(), Some("server-test"));
+        connection
+            .serve(|request| match request {
+                EspHomeMessage::DeviceInfoRequest(_) => vec![
+                    DeviceInfoResponse {
+                        name: "virtual".to_owned(),
+                        ..Default::default()
+                    }
+                    .into(),
+                ],
+                _ => Vec::new(),
+            })
+            .await
+    });
+
+    let client = EspHomeClient::builder()
+        .address(address)
+        .client_info("server-test")
+        .expected_server_name("virtual")
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await
+        .expect("Failed to connect to server");
+    client.ping().await.expect("Failed to ping server");
+    client.try_write(DeviceInfoRequest {}).await.unwrap();
+    let response = client.try_read().await.unwrap();
+    assert!(
+        matches!(&response, EspHomeMessage::DeviceInfoResponse(info) if info.name == "virtual"),
+        "Unexpected response: {response:?}"
+    );
+    client.close().await.expect("Failed to close connection");
+    device.await.unwrap().expect("Server connection failed");
+}
+
+#[tokio::test]
+async fn test_server_noise_connection() {
+    let server = EspHomeServer::builder()
+        .key(KEY)
+        .name("virtual")
+        .mac("AA:BB:CC:DD:EE:FF")
+        .bind("127.0.0.1:0")
+        .await
+        .expect("Failed to start server");
+    let address = server.local_addr().unwrap();
+
+    let device = tokio::spawn(async move {
+        let connection = server.accept().await.expect("Failed to accept client");
+        connection.serve(|_| Vec::new()).await
+    });
+
+    let client = EspHomeClient::builder()
+        .address(address)
+        .key(KEY)
+        .expected_mac("AA:BB:CC:DD:EE:FF")
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await
+        .expect("Failed to connect to server");
+    assert_eq!(client.server_name(), Some("virtual"));
+    client.ping().await.expect("Failed to ping server");
+    client.close().await.expect("Failed to close connection");
+    device.await.unwrap().expect("Server connection failed");
+}
+
+#[tokio::test]
+async fn test_server_noise_invalid_key() {
+    let server = EspHomeServer::builder()
+        .key(KEY)
+        .bind("127.0.0.1:0")
+        .await
+        .expect("Failed to start server");
+    let address = server.local_addr().unwrap();
+    let device = tokio::spawn(async move { server.accept().await.map(|_| ()) });
+
+    let result = EspHomeClient::builder()
+        .address(address)
+        .key("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=")
+        .timeout(Duration::from_secs(2))
+        .connect()
+        .await;
+    assert!(
+        matches!(
+            result,
+            Err(ClientError::Connection(ConnectionError::Noise(
+                NoiseError::InvalidPsk
+            )))
+        ),
+        "Unexpected result: {result:?}"
+    );
+    assert!(matches!(
+        device.await.unwrap(),
+        Err(ClientError::Connection(ConnectionError::Noise(
+            NoiseError::InvalidPsk
+        )))
+    ));
+}
@@ -0,0 +1,136 @@
+#![cfg(all(feature = "supervisor", feature = "test-util"))]
+
+use esphome_client::{
+    BackoffPolicy, ConnectionEvent, DeviceId, EspHomeClient, Supervisor, SupervisorEvent,
+    test_util::MockEspHomeServer,
+    types::{EspHomeMessage, SwitchCommandRequest, SwitchStateResponse},
+};
+use tokio::time::{Duration, timeout};
+
+async fn next_event(supervisor: &mut Supervisor) -> SupervisorEvent {
+    timeout(Duration::from_secs(5), supervisor.next())
+        .await
+        .expect("No supervisor event received")
+}
+
+#[tokio::test]
+async fn test_supervisor_merges_device_messages() {
+    let kitchen = MockEspHomeServer::builder()
+        .respond_to::<SwitchCommandRequest>(SwitchStateResponse {
+            key: 1,
+            ..Default::default()
+        })
+        .start()
+        .await
+        .expect("Failed to start mock server");
+    let garage = MockEspHomeServer::builder()
+        .respond_to::<SwitchCommandRequest>(SwitchStateResponse {
+            key: 2,
+            ..Default::default()
+        })
+        .start()
+        .await
+        .expect("Failed to start mock server");
+
+    let mut supervisor = Supervisor::new();
+    for (id, server) in [("kitchen", &kitchen), ("garage", &garage)] {
+        supervisor.add(
+            id,
+            EspHomeClient::builder().address(server.address()),
+            BackoffPolicy::new(),
+        );
+    }
+    let mut connected = Vec::new();
+    while connected.len() < 2 {
+        if let SupervisorEvent::Connected { device } = next_event(&mut supervisor).await {
+            connected.push(device);
+        }
+    }
+    connected.sort();
+    assert_eq!(
+        connected,
+        [DeviceId::from("garage"), DeviceId::from("kitchen")]
+    );
+
+    let handle = supervisor.device(&"garage".into()).unwrap();
+    assert!(handle.is_connected());
+    handle
+        .try_write(SwitchCommandRequest {
+            key: 2,
+            ..Default::default()
+        })
+        .await
+        .expect("Failed to send command");
+    let event = next_event(&mut supervisor).await;
+    assert!(
+        matches!(
+            &event,
+            SupervisorEvent::Message {
+                device,
+                message: EspHomeMessage::SwitchStateResponse(state),
+            } if device.as_str() == "garage" && state.key == 2
+        ),
+        "Unexpected event: {event:?}"
+    );
+
+    assert!(supervisor.remove(&"garage".into()));
+    assert!(!supervisor.remove(&"garage".into()));
+    assert_eq!(
+        supervisor.devices().collect::<Vec<_>>(),
+        [&DeviceId::from("kitchen")]
+    );
+}
+
+#[tokio::test]
+async fn test_supervisor_reports_disconnect() {
+    let server = MockEspHomeServer::builder()
+        .start()
+        .await
+        .expect("Failed to start mock server");
+    let address = server.address();
+
+    let mut supervisor = Supervisor::new();
+    let handle = supervisor.add(
+        "kitchen",
+        EspHomeClient::builder().address(address),
+        BackoffPolicy::new()
+            .initial_delay(Duration::from_millis(10))
+            .max_attempts(1),
+    );
+    assert!(matches!(
+        next_event(&mut supervisor).await,
+        SupervisorEvent::Connected { .. }
+    ));
+
+    server.close();
+    let event = next_event(&mut supervisor).await;
+    assert!(
+        matches!(
+            event,
+            SupervisorEvent::Connection {
+                event: ConnectionEvent::Disconnected { .. },
+                ..
+            }
+        ),
+        "Unexpected event: {event:?}"
+    );
+    assert!(!handle.is_connected());
+    assert!(matches!(
+        next_event(&mut supervisor).await,
+        SupervisorEvent::Connection {
+            event: ConnectionEvent::Reconnecting { attempt: 1, .. },
+            ..
+        }
+    ));
+    assert!(matches!(
+        next_event(&mut supervisor).await,
+        SupervisorEvent::Connection {
+            event: ConnectionEvent::GaveUp,
+            ..
+        }
+    ));
+    assert!(matches!(
+        next_event(&mut supervisor).await,
+        SupervisorEvent::Failed { .. }
+    ));
+}